@@ -0,0 +1,58 @@
+//! Latency of the public key operations and of the password hashing with small parameters
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use cryptoxide::kdf::argon2;
+use cryptoxide::scrypt::ScryptParams;
+use cryptoxide::{ed25519, x25519};
+
+fn signatures(c: &mut Criterion) {
+    let message = [1u8; 64];
+    let (keypair, public_key) = ed25519::keypair(&[2u8; 32]);
+    let signature = ed25519::signature(&message, &keypair);
+
+    c.bench_function("ed25519/sign", |b| {
+        b.iter(|| ed25519::signature(black_box(&message), &keypair))
+    });
+    c.bench_function("ed25519/verify", |b| {
+        b.iter(|| ed25519::verify(black_box(&message), &public_key, &signature))
+    });
+}
+
+fn key_exchange(c: &mut Criterion) {
+    let secret = x25519::SecretKey::from([3u8; 32]);
+    let public = x25519::SecretKey::from([4u8; 32]).public_key();
+
+    c.bench_function("x25519/diffie_hellman", |b| {
+        b.iter(|| secret.diffie_hellman(black_box(&public)))
+    });
+}
+
+fn password_hashing(c: &mut Criterion) {
+    let mut group = c.benchmark_group("password");
+    group.sample_size(20);
+
+    // 1 iteration over 4 MiB
+    let params = argon2::Builder::new()
+        .memory_kb(4096)
+        .iterations(1)
+        .parallelism(1)
+        .build()
+        .unwrap();
+    group.bench_function("argon2id-m4096-t1-p1", |b| {
+        b.iter(|| argon2::argon2::<32>(&params, b"password", b"saltsalt", &[], &[]))
+    });
+
+    // N=2^12, r=8 (4 MiB) and p=1
+    let params = ScryptParams::new(12, 8, 1);
+    group.bench_function("scrypt-n4096-r8-p1", |b| {
+        b.iter(|| {
+            let mut output = [0u8; 32];
+            params.run(b"password", b"saltsalt", &mut output);
+            output
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, signatures, key_exchange, password_hashing);
+criterion_main!(benches);
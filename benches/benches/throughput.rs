@@ -0,0 +1,79 @@
+//! Throughput of the hashing, MAC and stream cipher algorithms on various input sizes
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use cryptoxide::chacha20::ChaCha20;
+use cryptoxide::hashing::{blake2b, blake2s, sha2, sha3};
+use cryptoxide::hmac::Hmac;
+use cryptoxide::mac::Mac;
+use cryptoxide::poly1305::Poly1305;
+
+const SIZES: [(&str, usize); 4] = [
+    ("1B", 1),
+    ("1KB", 1024),
+    ("64KB", 64 * 1024),
+    ("1MB", 1024 * 1024),
+];
+
+// benchmark `f` processing an input of each size
+fn throughput(c: &mut Criterion, name: &str, f: fn(&[u8])) {
+    let mut group = c.benchmark_group(name);
+    for (label, size) in SIZES {
+        let input = vec![1u8; size];
+        group.throughput(Throughput::Bytes(size as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(label), &input, |b, input| {
+            b.iter(|| f(input))
+        });
+    }
+    group.finish();
+}
+
+fn hashing(c: &mut Criterion) {
+    throughput(c, "sha256", |input| {
+        criterion::black_box(sha2::Sha256::new().update(input).finalize());
+    });
+    throughput(c, "sha512", |input| {
+        criterion::black_box(sha2::Sha512::new().update(input).finalize());
+    });
+    throughput(c, "sha3-256", |input| {
+        criterion::black_box(sha3::Sha3_256::new().update(input).finalize());
+    });
+    throughput(c, "blake2b-256", |input| {
+        criterion::black_box(blake2b::Blake2b::<256>::new().update(input).finalize());
+    });
+    throughput(c, "blake2s-256", |input| {
+        criterion::black_box(blake2s::Blake2s::<256>::new().update(input).finalize());
+    });
+}
+
+fn ciphers(c: &mut Criterion) {
+    let mut group = c.benchmark_group("chacha20");
+    for (label, size) in SIZES {
+        let input = vec![1u8; size];
+        let mut output = vec![0u8; size];
+        group.throughput(Throughput::Bytes(size as u64));
+        group.bench_function(BenchmarkId::from_parameter(label), |b| {
+            b.iter(|| ChaCha20::new(&[0; 32], &[0; 12]).process(&input, &mut output))
+        });
+    }
+    group.finish();
+}
+
+fn macs(c: &mut Criterion) {
+    throughput(c, "poly1305", |input| {
+        let mut tag = [0u8; 16];
+        let mut mac = Poly1305::new(&[1; 32]);
+        mac.input(input);
+        mac.raw_result(&mut tag);
+        criterion::black_box(tag);
+    });
+    throughput(c, "hmac-sha256", |input| {
+        let mut tag = [0u8; 32];
+        let mut mac = Hmac::new(cryptoxide::sha2::Sha256::new(), &[1; 32]);
+        mac.input(input);
+        mac.raw_result(&mut tag);
+        criterion::black_box(tag);
+    });
+}
+
+criterion_group!(benches, hashing, ciphers, macs);
+criterion_main!(benches);
@@ -0,0 +1,26 @@
+#![no_main]
+
+use cryptoxide::ed25519;
+use libfuzzer_sys::fuzz_target;
+
+// Sign a message with an arbitrary extended secret key, and check that the
+// signature is valid for the public key associated with the extended secret.
+//
+// The key is first used clamped, as done by the Ed25519 key generation, then
+// as is: a non clamped extended key doesn't respect the ed25519 invariants
+// but must not make signing panic, and the signature must stay consistent
+// with `extended_to_public`.
+fuzz_target!(|input: ([u8; 64], &[u8])| {
+    let (key, message) = input;
+
+    let mut clamped = key;
+    clamped[0] &= 0b1111_1000;
+    clamped[31] &= 0b0011_1111;
+    clamped[31] |= 0b0100_0000;
+
+    for extended in [clamped, key] {
+        let public = ed25519::extended_to_public(&extended);
+        let signature = ed25519::signature_extended(message, &extended);
+        assert!(ed25519::verify(message, &public, &signature));
+    }
+});
@@ -0,0 +1,24 @@
+#![no_main]
+
+use cryptoxide::curve25519::Fe;
+use libfuzzer_sys::fuzz_target;
+
+// Check the field algebraic identities on 2 arbitrary field elements,
+// the third element of the associativity and distributivity checks
+// is derived from the 2 others.
+fuzz_target!(|data: [u8; 64]| {
+    let a = Fe::from_bytes(data[0..32].try_into().unwrap());
+    let b = Fe::from_bytes(data[32..64].try_into().unwrap());
+    let c = &(&a * &b) + &Fe::ONE;
+
+    assert!(&a + &b == &b + &a);
+    assert!(&a * &b == &b * &a);
+    assert!(&(&a * &b) * &c == &a * &(&b * &c));
+    assert!(&a * &(&b + &c) == &(&a * &b) + &(&a * &c));
+    assert!(&a * &a == a.square());
+    assert!(&a + &(-&a) == Fe::ZERO);
+    assert!(&(&a - &b) + &b == a);
+    if a.is_nonzero() {
+        assert!(&a * &a.invert() == Fe::ONE);
+    }
+});
@@ -0,0 +1,401 @@
+//! AEGIS-128L authenticated encryption
+//!
+//! AEGIS-128L is an AEAD built on the AES round function, with a state of eight
+//! 128 bits blocks, a 128 bits key, a 128 bits nonce and a 128 bits tag, as specified
+//! in the [CFRG draft][1] (and originally in the CAESAR competition, where it was part
+//! of the final portfolio).
+//!
+//! It is very fast when the AES round is available in hardware (i.e. when compiled
+//! with the `aes` target feature), and otherwise inherits the cache timing caveats of the
+//! software AES implementation, see [`crate::cipher::aes`].
+//!
+//! A nonce must never be reused with the same key: doing so reveals the XOR of the
+//! plaintexts and allows forgeries.
+//!
+//! # Examples
+//!
+//! ```
+//! use cryptoxide::aead::aegis128l::Aegis128L;
+//!
+//! let key = [1u8; 16];
+//! let nonce = [2u8; 16];
+//! let input = b"hello world!";
+//!
+//! let mut encrypted = [0u8; 12];
+//! let mut tag = [0u8; 16];
+//! Aegis128L::new(&key, &nonce, b"header").encrypt(input, &mut encrypted, &mut tag);
+//!
+//! let mut decrypted = [0u8; 12];
+//! assert!(Aegis128L::new(&key, &nonce, b"header").decrypt(&encrypted, &mut decrypted, &tag));
+//! assert_eq!(&decrypted, input);
+//! ```
+//!
+//! [1]: <https://datatracker.ietf.org/doc/draft-irtf-cfrg-aegis-aead/>
+
+use crate::cipher::aes;
+use crate::constant_time::CtEqual;
+use crate::secure_zero::Zeroize;
+
+type Block = [u8; 16];
+
+// 32 bytes rate: the state absorbs 2 blocks per update
+const RATE: usize = 32;
+
+const C0: Block = [
+    0x00, 0x01, 0x01, 0x02, 0x03, 0x05, 0x08, 0x0d, 0x15, 0x22, 0x37, 0x59, 0x90, 0xe9, 0x79, 0x62,
+];
+const C1: Block = [
+    0xdb, 0x3d, 0x18, 0x55, 0x6d, 0xc2, 0x2f, 0xf1, 0x20, 0x11, 0x31, 0x42, 0x73, 0xb5, 0x28, 0xdd,
+];
+
+fn xor(a: &Block, b: &Block) -> Block {
+    let mut r = [0u8; 16];
+    for i in 0..16 {
+        r[i] = a[i] ^ b[i];
+    }
+    r
+}
+
+fn and(a: &Block, b: &Block) -> Block {
+    let mut r = [0u8; 16];
+    for i in 0..16 {
+        r[i] = a[i] & b[i];
+    }
+    r
+}
+
+fn split(data: &[u8; RATE]) -> (Block, Block) {
+    let mut m0 = [0u8; 16];
+    let mut m1 = [0u8; 16];
+    m0.copy_from_slice(&data[0..16]);
+    m1.copy_from_slice(&data[16..32]);
+    (m0, m1)
+}
+
+#[derive(Clone)]
+struct State([Block; 8]);
+
+impl Drop for State {
+    fn drop(&mut self) {
+        for b in self.0.iter_mut() {
+            b.zeroize();
+        }
+    }
+}
+
+impl State {
+    fn new(key: &Block, nonce: &Block) -> Self {
+        let kn = xor(key, nonce);
+        let mut state = State([
+            kn,
+            C1,
+            C0,
+            C1,
+            kn,
+            xor(key, &C0),
+            xor(key, &C1),
+            xor(key, &C0),
+        ]);
+        for _ in 0..10 {
+            state.update(nonce, key);
+        }
+        state
+    }
+
+    fn update(&mut self, m0: &Block, m1: &Block) {
+        let s = &mut self.0;
+        let last = s[7];
+        for i in (1..8).rev() {
+            let mut b = s[i - 1];
+            let key = if i == 4 { xor(&s[4], m1) } else { s[i] };
+            aes::round(&mut b, &key);
+            s[i] = b;
+        }
+        let mut b = last;
+        aes::round(&mut b, &xor(&s[0], m0));
+        s[0] = b;
+    }
+
+    fn keystream(&self) -> [u8; RATE] {
+        let s = &self.0;
+        let z0 = xor(&xor(&s[6], &s[1]), &and(&s[2], &s[3]));
+        let z1 = xor(&xor(&s[2], &s[5]), &and(&s[6], &s[7]));
+        let mut z = [0u8; RATE];
+        z[0..16].copy_from_slice(&z0);
+        z[16..32].copy_from_slice(&z1);
+        z
+    }
+
+    fn absorb(&mut self, data: &[u8; RATE]) {
+        let (m0, m1) = split(data);
+        self.update(&m0, &m1);
+    }
+
+    fn encrypt(&mut self, input: &[u8], output: &mut [u8]) {
+        let mut block = [0u8; RATE];
+        block[..input.len()].copy_from_slice(input);
+        let z = self.keystream();
+        self.absorb(&block);
+        for (o, (i, z)) in output.iter_mut().zip(block.iter().zip(z.iter())) {
+            *o = i ^ z;
+        }
+    }
+
+    fn decrypt(&mut self, input: &[u8], output: &mut [u8]) {
+        let z = self.keystream();
+        let mut block = [0u8; RATE];
+        for (b, (i, z)) in block.iter_mut().zip(input.iter().zip(z.iter())) {
+            *b = i ^ z;
+        }
+        output.copy_from_slice(&block[..input.len()]);
+        self.absorb(&block);
+    }
+
+    fn finalize(mut self, ad_len: usize, msg_len: usize) -> Block {
+        let mut lengths = [0u8; 16];
+        lengths[0..8].copy_from_slice(&((ad_len as u64) * 8).to_le_bytes());
+        lengths[8..16].copy_from_slice(&((msg_len as u64) * 8).to_le_bytes());
+        let t = xor(&self.0[2], &lengths);
+        for _ in 0..7 {
+            self.update(&t, &t);
+        }
+        let mut tag = self.0[0];
+        for b in self.0[1..7].iter() {
+            tag = xor(&tag, b);
+        }
+        tag
+    }
+}
+
+/// AEGIS-128L one-shot context, initialized with the key, the nonce and the associated data
+#[derive(Clone)]
+pub struct Aegis128L {
+    state: State,
+    ad_len: usize,
+}
+
+impl Aegis128L {
+    /// Size of the key in bytes
+    pub const KEY_SIZE: usize = 16;
+    /// Size of the nonce in bytes
+    pub const NONCE_SIZE: usize = 16;
+    /// Size of the authentication tag in bytes
+    pub const TAG_SIZE: usize = 16;
+
+    /// Create a new context from the key, the nonce and the associated data
+    pub fn new(key: &[u8; 16], nonce: &[u8; 16], aad: &[u8]) -> Self {
+        let mut state = State::new(key, nonce);
+        let mut chunks = aad.chunks_exact(RATE);
+        for chunk in &mut chunks {
+            state.absorb(chunk.try_into().unwrap());
+        }
+        let rem = chunks.remainder();
+        if !rem.is_empty() {
+            let mut block = [0u8; RATE];
+            block[..rem.len()].copy_from_slice(rem);
+            state.absorb(&block);
+        }
+        Aegis128L {
+            state,
+            ad_len: aad.len(),
+        }
+    }
+
+    /// Encrypt the input into the output buffer, and write the authentication tag to out_tag
+    ///
+    /// The output buffer needs to be the same size as the input buffer.
+    pub fn encrypt(mut self, input: &[u8], output: &mut [u8], out_tag: &mut [u8; 16]) {
+        assert_eq!(input.len(), output.len());
+        for (i, o) in input.chunks(RATE).zip(output.chunks_mut(RATE)) {
+            self.state.encrypt(i, o);
+        }
+        *out_tag = self.state.clone().finalize(self.ad_len, input.len());
+    }
+
+    /// Decrypt the input into the output buffer, and check the authentication tag
+    ///
+    /// The output buffer needs to be the same size as the input buffer. If the tag
+    /// doesn't match, false is returned and the output buffer is zeroed.
+    pub fn decrypt(mut self, input: &[u8], output: &mut [u8], tag: &[u8; 16]) -> bool {
+        assert_eq!(input.len(), output.len());
+        for (i, o) in input.chunks(RATE).zip(output.chunks_mut(RATE)) {
+            self.state.decrypt(i, o);
+        }
+        let expected = self.state.clone().finalize(self.ad_len, input.len());
+        if expected.ct_eq(tag).is_true() {
+            true
+        } else {
+            output.zeroize();
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::decode_hex;
+    use alloc::vec;
+
+    struct Test {
+        key: &'static str,
+        nonce: &'static str,
+        ad: &'static str,
+        msg: &'static str,
+        ct: &'static str,
+        tag: &'static str,
+    }
+
+    const KEY: &str = "10010000000000000000000000000000";
+    const NONCE: &str = "10000200000000000000000000000000";
+
+    // draft-irtf-cfrg-aegis-aead, AEGIS-128L test vectors 1 to 4, and one additional
+    // vector with multiple and partial blocks for both the associated data and the message
+    const TESTS: [Test; 5] = [
+        Test {
+            key: KEY,
+            nonce: NONCE,
+            ad: "",
+            msg: "00000000000000000000000000000000",
+            ct: "c1c0e58bd913006feba00f4b3cc3594e",
+            tag: "abe0ece80c24868a226a35d16bdae37a",
+        },
+        Test {
+            key: KEY,
+            nonce: NONCE,
+            ad: "",
+            msg: "",
+            ct: "",
+            tag: "c2b879a67def9d74e6c14f708bbcc9b4",
+        },
+        Test {
+            key: KEY,
+            nonce: NONCE,
+            ad: "0001020304050607",
+            msg: "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f",
+            ct: "79d94593d8c2119d7e8fd9b8fc77845c5c077a05b2528b6ac54b563aed8efe84",
+            tag: "cc6f3372f6aa1bb82388d695c3962d9a",
+        },
+        Test {
+            key: KEY,
+            nonce: NONCE,
+            ad: "0001020304050607",
+            msg: "000102030405060708090a0b0c0d",
+            ct: "79d94593d8c2119d7e8fd9b8fc77",
+            tag: "5c04b3dba849b2701effbe32c7f0fab7",
+        },
+        Test {
+            key: KEY,
+            nonce: NONCE,
+            ad: "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f2021222324252627",
+            msg: "101112131415161718191a1b1c1d1e1f202122232425262728292a2b2c2d2e2f303132333435363738393a3b3c3d3e3f404142434445464748494a4b4c",
+            ct: "b31052ad1cca4e291abcf2df3502e6bdb1bfd6db36798be3607b1f94d34478aa7ede7f7aa7d20ff36007d0768ca65f77a5bf916cf351f6e857fe490a50",
+            tag: "5e67a1f33434ad7e2e269c32ed61129f",
+        },
+    ];
+
+    fn array16(s: &str) -> [u8; 16] {
+        decode_hex(s.as_bytes()).unwrap().try_into().unwrap()
+    }
+
+    #[test]
+    fn vectors() {
+        for (i, t) in TESTS.iter().enumerate() {
+            let key = array16(t.key);
+            let nonce = array16(t.nonce);
+            let ad = decode_hex(t.ad.as_bytes()).unwrap();
+            let msg = decode_hex(t.msg.as_bytes()).unwrap();
+            let tag = array16(t.tag);
+
+            let mut ct = vec![0u8; msg.len()];
+            let mut out_tag = [0u8; 16];
+            Aegis128L::new(&key, &nonce, &ad).encrypt(&msg, &mut ct, &mut out_tag);
+            assert_eq!(ct, decode_hex(t.ct.as_bytes()).unwrap(), "ciphertext {}", i);
+            assert_eq!(out_tag, tag, "tag {}", i);
+
+            let mut decrypted = vec![0u8; msg.len()];
+            assert!(
+                Aegis128L::new(&key, &nonce, &ad).decrypt(&ct, &mut decrypted, &tag),
+                "decrypt {}",
+                i
+            );
+            assert_eq!(decrypted, msg, "plaintext {}", i);
+        }
+    }
+
+    #[test]
+    fn invalid() {
+        let t = &TESTS[2];
+        let key = array16(t.key);
+        let nonce = array16(t.nonce);
+        let ad = decode_hex(t.ad.as_bytes()).unwrap();
+        let ct = decode_hex(t.ct.as_bytes()).unwrap();
+        let tag = array16(t.tag);
+        let mut out = vec![0u8; ct.len()];
+
+        let mut bad_key = key;
+        bad_key[0] ^= 1;
+        assert!(!Aegis128L::new(&bad_key, &nonce, &ad).decrypt(&ct, &mut out, &tag));
+
+        let mut bad_ct = ct.clone();
+        bad_ct[0] ^= 1;
+        assert!(!Aegis128L::new(&key, &nonce, &ad).decrypt(&bad_ct, &mut out, &tag));
+        assert_eq!(out, vec![0u8; ct.len()]);
+
+        assert!(!Aegis128L::new(&key, &nonce, &ad[1..]).decrypt(&ct, &mut out, &tag));
+
+        let mut bad_tag = tag;
+        bad_tag[15] ^= 0x80;
+        assert!(!Aegis128L::new(&key, &nonce, &ad).decrypt(&ct, &mut out, &bad_tag));
+    }
+}
+
+#[cfg(all(test, feature = "with-bench"))]
+mod bench {
+    use super::Aegis128L;
+    use test::Bencher;
+
+    // same shape as the ChaCha20Poly1305 benches, for comparison
+
+    #[bench]
+    pub fn aegis128l_10(bh: &mut Bencher) {
+        let input = [1u8; 10];
+        let aad = [3u8; 10];
+        bh.iter(|| {
+            let mut output = [0u8; 10];
+            let mut tag = [0u8; 16];
+            let mut output2 = [0u8; 10];
+            Aegis128L::new(&[0; 16], &[0; 16], &aad).encrypt(&input, &mut output, &mut tag);
+            Aegis128L::new(&[0; 16], &[0; 16], &aad).decrypt(&output, &mut output2, &tag);
+        });
+        bh.bytes = 10u64;
+    }
+
+    #[bench]
+    pub fn aegis128l_1k(bh: &mut Bencher) {
+        let input = [1u8; 1024];
+        let aad = [3u8; 1024];
+        bh.iter(|| {
+            let mut output = [0u8; 1024];
+            let mut tag = [0u8; 16];
+            let mut output2 = [0u8; 1024];
+            Aegis128L::new(&[0; 16], &[0; 16], &aad).encrypt(&input, &mut output, &mut tag);
+            Aegis128L::new(&[0; 16], &[0; 16], &aad).decrypt(&output, &mut output2, &tag);
+        });
+        bh.bytes = 1024u64;
+    }
+
+    #[bench]
+    pub fn aegis128l_64k(bh: &mut Bencher) {
+        let input = [1u8; 65536];
+        let aad = [3u8; 65536];
+        bh.iter(|| {
+            let mut output = [0u8; 65536];
+            let mut tag = [0u8; 16];
+            let mut output2 = [0u8; 65536];
+            Aegis128L::new(&[0; 16], &[0; 16], &aad).encrypt(&input, &mut output, &mut tag);
+            Aegis128L::new(&[0; 16], &[0; 16], &aad).decrypt(&output, &mut output2, &tag);
+        });
+        bh.bytes = 65536u64;
+    }
+}
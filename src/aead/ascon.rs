@@ -0,0 +1,336 @@
+//! Ascon-128 lightweight authenticated encryption
+//!
+//! Ascon-128 (version 1.2, as submitted to the NIST Lightweight Cryptography
+//! competition which it won) is an AEAD built on a 320 bits permutation, with a
+//! 128 bits key, a 128 bits nonce and a 128 bits tag, see the [specification][1].
+//!
+//! The state is 5 words of 64 bits, and the permutation only uses bitwise operations
+//! and rotations on those words: there is no table lookup, which makes the
+//! implementation constant time, and the code small: compiled for Cortex-M4
+//! (`thumbv7em-none-eabi`) with `opt-level = "s"`, the functions of this module are
+//! about 1.6KB of code (and about 4KB with `opt-level = 3`, which inlines the permutation),
+//! plus the 96 bytes of round constants.
+//!
+//! Note that NIST SP 800-232 standardizes a slightly different variant (Ascon-AEAD128),
+//! which is not compatible with this one.
+//!
+//! A nonce must never be reused with the same key.
+//!
+//! # Examples
+//!
+//! ```
+//! use cryptoxide::aead::ascon::Ascon128;
+//!
+//! let key = [1u8; 16];
+//! let nonce = [2u8; 16];
+//! let input = b"hello world!";
+//!
+//! let mut encrypted = [0u8; 12];
+//! let mut tag = [0u8; 16];
+//! Ascon128::new(&key, &nonce, b"header").encrypt(input, &mut encrypted, &mut tag);
+//!
+//! let mut decrypted = [0u8; 12];
+//! assert!(Ascon128::new(&key, &nonce, b"header").decrypt(&encrypted, &mut decrypted, &tag));
+//! assert_eq!(&decrypted, input);
+//! ```
+//!
+//! [1]: <https://ascon.iaik.tugraz.at/files/asconv12-nist.pdf>
+
+use crate::constant_time::CtEqual;
+use crate::secure_zero::Zeroize;
+
+// k = 128, r = 64, a = 12, b = 6 as the first bytes of the state
+const IV: u64 = 0x80400c0600000000;
+
+const RATE: usize = 8;
+
+const ROUND_CONSTANTS: [u64; 12] = [
+    0xf0, 0xe1, 0xd2, 0xc3, 0xb4, 0xa5, 0x96, 0x87, 0x78, 0x69, 0x5a, 0x4b,
+];
+
+fn load(bytes: &[u8]) -> u64 {
+    let mut b = [0u8; 8];
+    b.copy_from_slice(bytes);
+    u64::from_be_bytes(b)
+}
+
+// a partial block followed by the 0x80 padding byte
+fn load_padded(bytes: &[u8]) -> u64 {
+    let mut b = [0u8; 8];
+    b[..bytes.len()].copy_from_slice(bytes);
+    b[bytes.len()] = 0x80;
+    u64::from_be_bytes(b)
+}
+
+#[derive(Clone)]
+struct State([u64; 5]);
+
+impl Drop for State {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl State {
+    // Ascon-p with the last `rounds` round constants
+    fn permute(&mut self, rounds: usize) {
+        let [mut x0, mut x1, mut x2, mut x3, mut x4] = self.0;
+        for c in &ROUND_CONSTANTS[12 - rounds..] {
+            // addition of constants
+            x2 ^= c;
+
+            // substitution layer
+            x0 ^= x4;
+            x4 ^= x3;
+            x2 ^= x1;
+            let t0 = !x0 & x1;
+            let t1 = !x1 & x2;
+            let t2 = !x2 & x3;
+            let t3 = !x3 & x4;
+            let t4 = !x4 & x0;
+            x0 ^= t1;
+            x1 ^= t2;
+            x2 ^= t3;
+            x3 ^= t4;
+            x4 ^= t0;
+            x1 ^= x0;
+            x0 ^= x4;
+            x3 ^= x2;
+            x2 = !x2;
+
+            // linear diffusion layer
+            x0 ^= x0.rotate_right(19) ^ x0.rotate_right(28);
+            x1 ^= x1.rotate_right(61) ^ x1.rotate_right(39);
+            x2 ^= x2.rotate_right(1) ^ x2.rotate_right(6);
+            x3 ^= x3.rotate_right(10) ^ x3.rotate_right(17);
+            x4 ^= x4.rotate_right(7) ^ x4.rotate_right(41);
+        }
+        self.0 = [x0, x1, x2, x3, x4];
+    }
+}
+
+/// Ascon-128 one-shot context, initialized with the key, the nonce and the associated data
+#[derive(Clone)]
+pub struct Ascon128 {
+    state: State,
+    key: [u64; 2],
+}
+
+impl Drop for Ascon128 {
+    fn drop(&mut self) {
+        self.key.zeroize();
+    }
+}
+
+impl Ascon128 {
+    /// Size of the key in bytes
+    pub const KEY_SIZE: usize = 16;
+    /// Size of the nonce in bytes
+    pub const NONCE_SIZE: usize = 16;
+    /// Size of the authentication tag in bytes
+    pub const TAG_SIZE: usize = 16;
+
+    /// Create a new context from the key, the nonce and the associated data
+    pub fn new(key: &[u8; 16], nonce: &[u8; 16], aad: &[u8]) -> Self {
+        let key = [load(&key[0..8]), load(&key[8..16])];
+        let mut state = State([IV, key[0], key[1], load(&nonce[0..8]), load(&nonce[8..16])]);
+        state.permute(12);
+        state.0[3] ^= key[0];
+        state.0[4] ^= key[1];
+
+        if !aad.is_empty() {
+            let mut chunks = aad.chunks_exact(RATE);
+            for chunk in &mut chunks {
+                state.0[0] ^= load(chunk);
+                state.permute(6);
+            }
+            state.0[0] ^= load_padded(chunks.remainder());
+            state.permute(6);
+        }
+        // domain separation
+        state.0[4] ^= 1;
+
+        Ascon128 { state, key }
+    }
+
+    fn finalize(mut self) -> [u8; 16] {
+        let s = &mut self.state;
+        s.0[1] ^= self.key[0];
+        s.0[2] ^= self.key[1];
+        s.permute(12);
+        let mut tag = [0u8; 16];
+        tag[0..8].copy_from_slice(&(s.0[3] ^ self.key[0]).to_be_bytes());
+        tag[8..16].copy_from_slice(&(s.0[4] ^ self.key[1]).to_be_bytes());
+        tag
+    }
+
+    /// Encrypt the input into the output buffer, and write the authentication tag to out_tag
+    ///
+    /// The output buffer needs to be the same size as the input buffer.
+    pub fn encrypt(mut self, input: &[u8], output: &mut [u8], out_tag: &mut [u8; 16]) {
+        assert_eq!(input.len(), output.len());
+        let s = &mut self.state;
+        let mut inputs = input.chunks_exact(RATE);
+        let mut outputs = output.chunks_exact_mut(RATE);
+        for (i, o) in (&mut inputs).zip(&mut outputs) {
+            s.0[0] ^= load(i);
+            o.copy_from_slice(&s.0[0].to_be_bytes());
+            s.permute(6);
+        }
+        let rem = inputs.remainder();
+        s.0[0] ^= load_padded(rem);
+        outputs
+            .into_remainder()
+            .copy_from_slice(&s.0[0].to_be_bytes()[..rem.len()]);
+        *out_tag = self.finalize();
+    }
+
+    /// Decrypt the input into the output buffer, and check the authentication tag
+    ///
+    /// The output buffer needs to be the same size as the input buffer. If the tag
+    /// doesn't match, false is returned and the output buffer is zeroed.
+    pub fn decrypt(mut self, input: &[u8], output: &mut [u8], tag: &[u8; 16]) -> bool {
+        assert_eq!(input.len(), output.len());
+        let s = &mut self.state;
+        let mut inputs = input.chunks_exact(RATE);
+        let mut outputs = output.chunks_exact_mut(RATE);
+        for (i, o) in (&mut inputs).zip(&mut outputs) {
+            let c = load(i);
+            o.copy_from_slice(&(s.0[0] ^ c).to_be_bytes());
+            s.0[0] = c;
+            s.permute(6);
+        }
+        let rem = inputs.remainder();
+        let out = outputs.into_remainder();
+        let keystream = s.0[0].to_be_bytes();
+        for (o, (i, k)) in out.iter_mut().zip(rem.iter().zip(keystream.iter())) {
+            *o = i ^ k;
+        }
+        s.0[0] ^= load_padded(out);
+
+        let expected = self.finalize();
+        if expected.ct_eq(tag).is_true() {
+            true
+        } else {
+            output.zeroize();
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::decode_hex;
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    // entries of the NIST LWC KAT file (LWC_AEAD_KAT_128_128.txt), where the key,
+    // the nonce, the plaintext and the associated data are the bytes 00 01 02 ...
+    struct Test {
+        count: usize,
+        pt_len: usize,
+        ad_len: usize,
+        ct: &'static str,
+    }
+
+    const TESTS: [Test; 7] = [
+        Test {
+            count: 1,
+            pt_len: 0,
+            ad_len: 0,
+            ct: "E355159F292911F794CB1432A0103A8A",
+        },
+        Test {
+            count: 2,
+            pt_len: 0,
+            ad_len: 1,
+            ct: "944DF887CD4901614C5DEDBC42FC0DA0",
+        },
+        Test {
+            count: 34,
+            pt_len: 1,
+            ad_len: 0,
+            ct: "BC18C3F4E39ECA7222490D967C79BFFC92",
+        },
+        Test {
+            count: 35,
+            pt_len: 1,
+            ad_len: 1,
+            ct: "BD4102B707775C3C155AE497B43BF834E5",
+        },
+        Test {
+            count: 273,
+            pt_len: 8,
+            ad_len: 8,
+            ct: "69FFEE6F5505A489E897E5F141B2E4A2DAD326085A79408A",
+        },
+        Test {
+            count: 513,
+            pt_len: 15,
+            ad_len: 17,
+            ct: "8684539A9FCFF9F68A7A496010F129DC0722BC4625170CC8FBBABCE67AC6D0",
+        },
+        Test {
+            count: 1089,
+            pt_len: 32,
+            ad_len: 32,
+            ct: "B96C78651B6246B0C3B1A5D373B0D5168DCA4A96734CF0DDF5F92F8D15E30270279BF6A6CC3F2FC9350B915C292BDB8D",
+        },
+    ];
+
+    fn sequence(len: usize) -> Vec<u8> {
+        (0..len as u8).collect()
+    }
+
+    #[test]
+    fn kat() {
+        let key: [u8; 16] = sequence(16).try_into().unwrap();
+        for t in TESTS.iter() {
+            let pt = sequence(t.pt_len);
+            let ad = sequence(t.ad_len);
+            let expected = decode_hex(t.ct.to_ascii_lowercase().as_bytes()).unwrap();
+
+            let mut ct = vec![0u8; t.pt_len];
+            let mut tag = [0u8; 16];
+            Ascon128::new(&key, &key, &ad).encrypt(&pt, &mut ct, &mut tag);
+            assert_eq!(ct, expected[..t.pt_len], "ciphertext {}", t.count);
+            assert_eq!(tag, expected[t.pt_len..], "tag {}", t.count);
+
+            let mut decrypted = vec![0u8; t.pt_len];
+            assert!(
+                Ascon128::new(&key, &key, &ad).decrypt(&ct, &mut decrypted, &tag),
+                "decrypt {}",
+                t.count
+            );
+            assert_eq!(decrypted, pt, "plaintext {}", t.count);
+        }
+    }
+
+    #[test]
+    fn invalid() {
+        let key: [u8; 16] = sequence(16).try_into().unwrap();
+        let pt = sequence(15);
+        let ad = sequence(17);
+        let mut ct = vec![0u8; 15];
+        let mut tag = [0u8; 16];
+        Ascon128::new(&key, &key, &ad).encrypt(&pt, &mut ct, &mut tag);
+
+        let mut out = vec![0u8; 15];
+        let mut bad_ct = ct.clone();
+        bad_ct[14] ^= 1;
+        assert!(!Ascon128::new(&key, &key, &ad).decrypt(&bad_ct, &mut out, &tag));
+        assert_eq!(out, vec![0u8; 15]);
+
+        let mut bad_nonce = key;
+        bad_nonce[15] ^= 1;
+        assert!(!Ascon128::new(&key, &bad_nonce, &ad).decrypt(&ct, &mut out, &tag));
+
+        // missing associated data
+        assert!(!Ascon128::new(&key, &key, &[]).decrypt(&ct, &mut out, &tag));
+
+        let mut bad_tag = tag;
+        bad_tag[0] ^= 0x80;
+        assert!(!Ascon128::new(&key, &key, &ad).decrypt(&ct, &mut out, &bad_tag));
+    }
+}
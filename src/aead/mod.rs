@@ -0,0 +1,24 @@
+//! Authenticated Encryption with Associated Data (AEAD)
+//!
+//! The ChaCha20Poly1305 AEAD is available at the top level in [`crate::chacha20poly1305`].
+
+#[cfg(feature = "aegis")]
+pub mod aegis128l;
+
+#[cfg(feature = "ascon")]
+pub mod ascon;
+
+/// AEAD errors
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AeadError {
+    /// The tag doesn't match the data, which has been corrupted or tampered with
+    TagMismatch,
+}
+
+impl core::fmt::Display for AeadError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            AeadError::TagMismatch => write!(f, "tag mismatch"),
+        }
+    }
+}
@@ -0,0 +1,86 @@
+// AVX2 implementation generating 8 consecutive blocks at once
+//
+// Each 256 bits register holds the same row of 2 blocks (one block per 128 bits lane),
+// and 4 sets of registers are interleaved to compute 8 blocks. Since the row shuffles
+// of the diagonal rounds are done per 128 bits lane, the rounds are the same as the
+// SSE2 implementation on 2 blocks.
+
+#[cfg(target_arch = "x86")]
+use core::arch::x86::*;
+
+#[cfg(target_arch = "x86_64")]
+use core::arch::x86_64::*;
+
+use super::sse2::State;
+
+macro_rules! swizzle {
+    ($b: expr, $c: expr, $d: expr) => {
+        $b = _mm256_shuffle_epi32($b, 0b00111001); // <<< 8
+        $c = _mm256_shuffle_epi32($c, 0b01001110); // <<< 16
+        $d = _mm256_shuffle_epi32($d, 0b10010011); // <<< 24
+    };
+}
+
+macro_rules! add_rotate_xor {
+    ($a: expr, $b: expr, $c: expr, $d: literal) => {
+        // a += b; c ^= a; c <<<= d;
+        $a = _mm256_add_epi32($a, $b);
+        $c = _mm256_xor_si256($c, $a);
+        $c = _mm256_or_si256(_mm256_slli_epi32($c, $d), _mm256_srli_epi32($c, 32 - $d));
+    };
+}
+
+macro_rules! round {
+    ($a: expr, $b: expr, $c: expr, $d: expr) => {
+        add_rotate_xor!($a, $b, $d, 16);
+        add_rotate_xor!($c, $d, $b, 12);
+        add_rotate_xor!($a, $b, $d, 8);
+        add_rotate_xor!($c, $d, $b, 7);
+    };
+}
+
+/// Output the keystream of the 8 blocks starting at the counter of the state
+///
+/// The counter is incremented in the same way as `State::increment`, and the state is not modified.
+pub(super) fn keystream8<const ROUNDS: usize>(state: &State<ROUNDS>, output: &mut [u8; 512]) {
+    unsafe {
+        let a = _mm256_broadcastsi128_si256(state.a);
+        let b = _mm256_broadcastsi128_si256(state.b);
+        let c = _mm256_broadcastsi128_si256(state.c);
+        let d = _mm256_broadcastsi128_si256(state.d);
+
+        // block 2k in the low lane and block 2k+1 in the high lane
+        let mut init_d = [d; 4];
+        for (k, dk) in init_d.iter_mut().enumerate() {
+            let k = k as i32;
+            *dk = _mm256_add_epi32(d, _mm256_set_epi32(0, 0, 0, 2 * k + 1, 0, 0, 0, 2 * k));
+        }
+
+        let mut xa = [a; 4];
+        let mut xb = [b; 4];
+        let mut xc = [c; 4];
+        let mut xd = init_d;
+
+        for _ in 0..(ROUNDS / 2) {
+            for k in 0..4 {
+                round!(xa[k], xb[k], xc[k], xd[k]);
+                swizzle!(xb[k], xc[k], xd[k]);
+                round!(xa[k], xb[k], xc[k], xd[k]);
+                swizzle!(xd[k], xc[k], xb[k]);
+            }
+        }
+
+        #[allow(clippy::cast_ptr_alignment)]
+        let o = output.as_mut_ptr() as *mut __m256i;
+        for k in 0..4 {
+            let a = _mm256_add_epi32(xa[k], a);
+            let b = _mm256_add_epi32(xb[k], b);
+            let c = _mm256_add_epi32(xc[k], c);
+            let d = _mm256_add_epi32(xd[k], init_d[k]);
+            _mm256_storeu_si256(o.add(4 * k), _mm256_permute2x128_si256(a, b, 0x20));
+            _mm256_storeu_si256(o.add(4 * k + 1), _mm256_permute2x128_si256(c, d, 0x20));
+            _mm256_storeu_si256(o.add(4 * k + 2), _mm256_permute2x128_si256(a, b, 0x31));
+            _mm256_storeu_si256(o.add(4 * k + 3), _mm256_permute2x128_si256(c, d, 0x31));
+        }
+    }
+}
@@ -16,10 +16,14 @@
 //! cipher operation encrypt and decrypt.
 //!
 
-#[cfg(not(all(
-    any(target_arch = "x86", target_arch = "x86_64"),
-    any(target_feature = "sse2", target_feature = "avx2")
-)))]
+// also built for tests, so the SIMD backends can be checked against it
+#[cfg(any(
+    test,
+    not(all(
+        any(target_arch = "x86", target_arch = "x86_64"),
+        any(target_feature = "sse2", target_feature = "avx2")
+    ))
+))]
 mod reference;
 
 #[cfg(not(all(
@@ -39,3 +43,89 @@ mod sse2;
     target_feature = "sse2",
 ))]
 pub(crate) type ChaChaEngine<const R: usize> = sse2::State<R>;
+
+#[cfg(all(
+    any(target_arch = "x86", target_arch = "x86_64"),
+    target_feature = "avx2",
+))]
+mod avx2;
+
+#[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+mod neon;
+
+#[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+mod wasm_simd;
+
+/// Output the keystream of the 8 blocks starting at the counter of the state,
+/// and move the counter to the following block, as with 8 calls to `increment`
+pub(crate) fn keystream8<const R: usize>(state: &mut ChaChaEngine<R>, output: &mut [u8; 512]) {
+    #[cfg(all(
+        any(target_arch = "x86", target_arch = "x86_64"),
+        target_feature = "avx2",
+    ))]
+    {
+        avx2::keystream8(state, output);
+        for _ in 0..8 {
+            state.increment();
+        }
+        return;
+    }
+
+    #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+    {
+        for blocks in output.chunks_mut(256) {
+            neon::keystream4(state, blocks);
+            for _ in 0..4 {
+                state.increment();
+            }
+        }
+        return;
+    }
+
+    #[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+    {
+        for blocks in output.chunks_mut(256) {
+            wasm_simd::keystream4(state, blocks);
+            for _ in 0..4 {
+                state.increment();
+            }
+        }
+        return;
+    }
+
+    #[allow(unreachable_code)]
+    keystream8_blocks(state, output)
+}
+
+// generic version of keystream8, computing the blocks one by one
+fn keystream8_blocks<const R: usize>(state: &mut ChaChaEngine<R>, output: &mut [u8; 512]) {
+    for block in output.chunks_mut(64) {
+        let mut s = state.clone();
+        s.rounds();
+        s.add_back(state);
+        s.output_bytes(block);
+        state.increment();
+    }
+}
+
+#[cfg(all(test, feature = "with-bench"))]
+mod bench {
+    use super::{keystream8, keystream8_blocks, ChaChaEngine};
+    use test::Bencher;
+
+    #[bench]
+    pub fn keystream8_simd(bh: &mut Bencher) {
+        let mut state = ChaChaEngine::<20>::init(&[0u8; 32], &[0u8; 12]);
+        let mut output = [0u8; 512];
+        bh.iter(|| keystream8(&mut state, &mut output));
+        bh.bytes = 512;
+    }
+
+    #[bench]
+    pub fn keystream8_single_blocks(bh: &mut Bencher) {
+        let mut state = ChaChaEngine::<20>::init(&[0u8; 32], &[0u8; 12]);
+        let mut output = [0u8; 512];
+        bh.iter(|| keystream8_blocks(&mut state, &mut output));
+        bh.bytes = 512;
+    }
+}
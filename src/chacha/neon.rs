@@ -0,0 +1,76 @@
+// NEON implementation for AArch64 generating 4 consecutive blocks at once
+//
+// The 4 states are interleaved in 16 registers, each register holding the same
+// word of the 4 blocks, so that the quarter rounds operate on the 4 blocks with
+// no shuffling. The words are transposed back into blocks when writing the output.
+
+use super::reference::State;
+use core::arch::aarch64::*;
+
+macro_rules! add_rotate_xor {
+    ($a: expr, $b: expr, $c: expr, $d: literal) => {
+        // a += b; c ^= a; c <<<= d;
+        $a = vaddq_u32($a, $b);
+        $c = veorq_u32($c, $a);
+        $c = vsriq_n_u32::<{ 32 - $d }>(vshlq_n_u32::<$d>($c), $c);
+    };
+}
+
+macro_rules! QR {
+    ($x: ident, $a: literal, $b: literal, $c: literal, $d: literal) => {
+        add_rotate_xor!($x[$a], $x[$b], $x[$d], 16);
+        add_rotate_xor!($x[$c], $x[$d], $x[$b], 12);
+        add_rotate_xor!($x[$a], $x[$b], $x[$d], 8);
+        add_rotate_xor!($x[$c], $x[$d], $x[$b], 7);
+    };
+}
+
+/// Output the keystream of the 4 blocks starting at the counter of the state
+///
+/// The counter is incremented in the same way as `State::increment`, and the state is not modified.
+pub(super) fn keystream4<const ROUNDS: usize>(state: &State<ROUNDS>, output: &mut [u8]) {
+    assert_eq!(output.len(), 256);
+    unsafe {
+        let mut init = [vdupq_n_u32(0); 16];
+        for (v, w) in init.iter_mut().zip(state.state.iter()) {
+            *v = vdupq_n_u32(*w);
+        }
+        let counters = [0u32, 1, 2, 3];
+        init[12] = vaddq_u32(init[12], vld1q_u32(counters.as_ptr()));
+
+        let mut x = init;
+        for _ in 0..(ROUNDS / 2) {
+            QR!(x, 0, 4, 8, 12);
+            QR!(x, 1, 5, 9, 13);
+            QR!(x, 2, 6, 10, 14);
+            QR!(x, 3, 7, 11, 15);
+
+            QR!(x, 0, 5, 10, 15);
+            QR!(x, 1, 6, 11, 12);
+            QR!(x, 2, 7, 8, 13);
+            QR!(x, 3, 4, 9, 14);
+        }
+
+        for (v, i) in x.iter_mut().zip(init.iter()) {
+            *v = vaddq_u32(*v, *i);
+        }
+
+        // transpose each group of 4 words (4 words x 4 blocks) into 16 bytes of each block
+        let o = output.as_mut_ptr();
+        for g in 0..4 {
+            let t0 = vreinterpretq_u64_u32(vtrn1q_u32(x[4 * g], x[4 * g + 1]));
+            let t1 = vreinterpretq_u64_u32(vtrn2q_u32(x[4 * g], x[4 * g + 1]));
+            let t2 = vreinterpretq_u64_u32(vtrn1q_u32(x[4 * g + 2], x[4 * g + 3]));
+            let t3 = vreinterpretq_u64_u32(vtrn2q_u32(x[4 * g + 2], x[4 * g + 3]));
+            let blocks = [
+                vtrn1q_u64(t0, t2),
+                vtrn1q_u64(t1, t3),
+                vtrn2q_u64(t0, t2),
+                vtrn2q_u64(t1, t3),
+            ];
+            for (j, b) in blocks.iter().enumerate() {
+                vst1q_u8(o.add(64 * j + 16 * g), vreinterpretq_u8_u64(*b));
+            }
+        }
+    }
+}
@@ -1,8 +1,9 @@
 use crate::cryptoutil::{read_u32_le, write_u32v_le};
+use crate::secure_zero::Zeroize;
 
 #[derive(Clone)]
 pub(crate) struct State<const ROUNDS: usize> {
-    state: [u32; 16],
+    pub(super) state: [u32; 16],
 }
 
 macro_rules! QR {
@@ -18,6 +19,12 @@ macro_rules! QR {
     };
 }
 
+impl<const ROUNDS: usize> Drop for State<ROUNDS> {
+    fn drop(&mut self) {
+        self.state.zeroize()
+    }
+}
+
 impl<const ROUNDS: usize> State<ROUNDS> {
     // state initialization constant le-32bit array of b"expand 16-byte k"
     const CST16: [u32; 4] = [0x61707865, 0x3120646e, 0x79622d36, 0x6b206574];
@@ -39,6 +46,14 @@ impl<const ROUNDS: usize> State<ROUNDS> {
                 state[1] = Self::CST16[1];
                 state[2] = Self::CST16[2];
                 state[3] = Self::CST16[3];
+                state[4] = read_u32_le(&key[0..4]);
+                state[5] = read_u32_le(&key[4..8]);
+                state[6] = read_u32_le(&key[8..12]);
+                state[7] = read_u32_le(&key[12..16]);
+                state[8] = state[4];
+                state[9] = state[5];
+                state[10] = state[6];
+                state[11] = state[7];
             }
             32 => {
                 state[0] = Self::CST32[0];
@@ -101,6 +116,12 @@ impl<const ROUNDS: usize> State<ROUNDS> {
         self.state[12] = counter;
     }
 
+    #[inline]
+    pub(crate) fn set_counter64(&mut self, counter: u64) {
+        self.state[12] = counter as u32;
+        self.state[13] = (counter >> 32) as u32;
+    }
+
     #[inline]
     pub(crate) fn increment(&mut self) {
         self.state[12] = self.state[12].wrapping_add(1);
@@ -133,3 +154,76 @@ impl<const ROUNDS: usize> State<ROUNDS> {
         write_u32v_le(&mut output[16..32], &self.state[12..16]);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::State;
+
+    // RFC 8439 section 2.1.1
+    #[test]
+    fn quarter_round() {
+        let mut a = 0x11111111u32;
+        let mut b = 0x01020304u32;
+        let mut c = 0x9b8d6f43u32;
+        let mut d = 0x01234567u32;
+        QR!(a, b, c, d);
+        assert_eq!(
+            [a, b, c, d],
+            [0xea2a92f4, 0xcb1cf8ce, 0x4581472e, 0x5881c4bb]
+        );
+    }
+
+    // RFC 8439 section 2.2.1
+    #[test]
+    fn quarter_round_on_state() {
+        let mut x = [
+            0x879531e0u32,
+            0xc5ecf37d,
+            0x516461b1,
+            0xc9a62f8a,
+            0x44c20ef3,
+            0x3390af7f,
+            0xd9fc690b,
+            0x2a5f714c,
+            0x53372767,
+            0xb00a5631,
+            0x974c541a,
+            0x359e9963,
+            0x5c971061,
+            0x3d631689,
+            0x2098d9d6,
+            0x91dbd320,
+        ];
+        let [mut x2, mut x7, mut x8, mut x13] = [x[2], x[7], x[8], x[13]];
+        QR!(x2, x7, x8, x13);
+        x[2] = x2;
+        x[7] = x7;
+        x[8] = x8;
+        x[13] = x13;
+        assert_eq!(
+            x,
+            [
+                0x879531e0, 0xc5ecf37d, 0xbdb886dc, 0xc9a62f8a, 0x44c20ef3, 0x3390af7f, 0xd9fc690b,
+                0xcfacafd2, 0xe46bea80, 0xb00a5631, 0x974c541a, 0x359e9963, 0x5c971061, 0xccc07c79,
+                0x2098d9d6, 0x91dbd320,
+            ]
+        );
+    }
+
+    // RFC 8439 section 2.3.2, initial state setup
+    #[test]
+    fn init_state() {
+        let key: [u8; 32] = core::array::from_fn(|i| i as u8);
+        let nonce = [0, 0, 0, 0x09, 0, 0, 0, 0x4a, 0, 0, 0, 0];
+        let mut state = State::<20>::init(&key, &nonce);
+        state.set_counter(1);
+        assert_eq!(
+            state.state,
+            [
+                0x61707865, 0x3320646e, 0x79622d32, 0x6b206574, 0x03020100, 0x07060504, 0x0b0a0908,
+                0x0f0e0d0c, 0x13121110, 0x17161514, 0x1b1a1918, 0x1f1e1d1c, 0x00000001, 0x09000000,
+                0x4a000000, 0x00000000,
+            ]
+        );
+    }
+}
@@ -7,13 +7,15 @@ use core::arch::x86::*;
 use core::arch::x86_64::*;
 
 use core::convert::TryInto;
+use core::ptr;
+use core::sync::atomic::{compiler_fence, Ordering};
 
 #[derive(Clone)]
 pub(crate) struct State<const ROUNDS: usize> {
-    a: __m128i,
-    b: __m128i,
-    c: __m128i,
-    d: __m128i,
+    pub(super) a: __m128i,
+    pub(super) b: __m128i,
+    pub(super) c: __m128i,
+    pub(super) d: __m128i,
 }
 
 #[repr(align(16))]
@@ -61,6 +63,18 @@ macro_rules! round {
     };
 }
 
+impl<const ROUNDS: usize> Drop for State<ROUNDS> {
+    fn drop(&mut self) {
+        unsafe {
+            ptr::write_volatile(&mut self.a, _mm_setzero_si128());
+            ptr::write_volatile(&mut self.b, _mm_setzero_si128());
+            ptr::write_volatile(&mut self.c, _mm_setzero_si128());
+            ptr::write_volatile(&mut self.d, _mm_setzero_si128());
+        }
+        compiler_fence(Ordering::SeqCst);
+    }
+}
+
 impl<const ROUNDS: usize> State<ROUNDS> {
     // state initialization constant le-32bit array of b"expand 16-byte k"
     const CST16: [u32; 4] = [0x61707865, 0x3120646e, 0x79622d36, 0x6b206574];
@@ -154,6 +168,15 @@ impl<const ROUNDS: usize> State<ROUNDS> {
         self.d = align.to_m128i();
     }
 
+    #[inline]
+    pub(crate) fn set_counter64(&mut self, counter: u64) {
+        let mut align = Align128::zero();
+        align.from_m128i(self.d);
+        align.0[0] = counter as u32;
+        align.0[1] = (counter >> 32) as u32;
+        self.d = align.to_m128i();
+    }
+
     #[inline]
     pub(crate) fn increment(&mut self) {
         let mut align = Align128::zero();
@@ -207,3 +230,85 @@ impl<const ROUNDS: usize> State<ROUNDS> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::super::reference;
+    use super::State;
+
+    // 4 consecutive blocks with the given backend, as done by keystream8_blocks
+    macro_rules! blocks4 {
+        ($state:expr, $increment:ident) => {{
+            let mut state = $state;
+            let mut output = [0u8; 256];
+            for block in output.chunks_mut(64) {
+                let mut s = state.clone();
+                s.rounds();
+                s.add_back(&state);
+                s.output_bytes(block);
+                state.$increment();
+            }
+            output
+        }};
+    }
+
+    fn check<const R: usize>(key: &[u8], nonce: &[u8], counter: u64) {
+        let mut sse2 = State::<R>::init(key, nonce);
+        let mut refr = reference::State::<R>::init(key, nonce);
+        if nonce.len() == 8 {
+            sse2.set_counter64(counter);
+            refr.set_counter64(counter);
+            assert_eq!(blocks4!(sse2, increment64), blocks4!(refr, increment64));
+        } else {
+            sse2.set_counter(counter as u32);
+            refr.set_counter(counter as u32);
+            assert_eq!(blocks4!(sse2, increment), blocks4!(refr, increment));
+        }
+
+        let mut sse2 = State::<R>::init(key, nonce);
+        let mut refr = reference::State::<R>::init(key, nonce);
+        sse2.rounds();
+        refr.rounds();
+        let (mut out_sse2, mut out_refr) = ([0u8; 32], [0u8; 32]);
+        sse2.output_ad_bytes(&mut out_sse2);
+        refr.output_ad_bytes(&mut out_refr);
+        assert_eq!(out_sse2, out_refr);
+    }
+
+    #[test]
+    fn sse2_matches_reference() {
+        let key: [u8; 32] = core::array::from_fn(|i| (i * 7 + 3) as u8);
+        let nonce: [u8; 16] = core::array::from_fn(|i| (i * 11 + 5) as u8);
+
+        // counters crossing the 32 bits boundary check the carry of the 8 bytes nonce mode
+        for counter in [0, 1, 0xffff_fffe, 0x1_ffff_fffd] {
+            for key in [&key[..], &key[..16]] {
+                for nonce in [&nonce[..8], &nonce[..12], &nonce[..]] {
+                    check::<8>(key, nonce, counter);
+                    check::<12>(key, nonce, counter);
+                    check::<20>(key, nonce, counter);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn keystream8_matches_reference() {
+        let key: [u8; 32] = core::array::from_fn(|i| i as u8);
+        let nonce = [0, 0, 0, 0x09, 0, 0, 0, 0x4a, 0, 0, 0, 0];
+
+        let mut state = State::<20>::init(&key, &nonce);
+        state.set_counter(0xffff_fffc);
+        let mut output = [0u8; 512];
+        super::super::keystream8(&mut state, &mut output);
+
+        let mut refr = reference::State::<20>::init(&key, &nonce);
+        refr.set_counter(0xffff_fffc);
+        let expected = blocks4!(refr.clone(), increment);
+        for _ in 0..4 {
+            refr.increment();
+        }
+        assert_eq!(output[..256], expected);
+        assert_eq!(output[256..], blocks4!(refr, increment));
+    }
+}
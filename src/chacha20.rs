@@ -3,10 +3,16 @@
 //! Implementation of [ChaCha spec](https://www.rfc-editor.org/info/rfc7539)
 //! which is a fast and lean stream cipher.
 //!
-//! The maximum amount of data to be processed by a single instance of a ChaCha
-//! Context, is 256Gb (due to the 32 bits counter). Note that this is not
-//! enforced by the context, and using a context to process more than 256Gb of
-//! data would be insecure.
+//! # Limits
+//!
+//! The maximum amount of data to be processed by a single instance of the IETF ChaCha
+//! context ([`ChaCha`]) is 256 GiB (2^32 blocks of 64 bytes, due to the 32 bits counter),
+//! as going further would reuse the keystream. This is enforced by the context:
+//! [`ChaCha::process`] and [`ChaCha::process_mut`] panic if the input would
+//! overflow the counter, and [`ChaCha::try_process`] and [`ChaCha::try_process_mut`]
+//! return a [`ChaChaError`] instead, without processing anything.
+//!
+//! The original ChaCha and XChaCha use a 64 bits counter and have no practical limit.
 //!
 //! Along with the standard IETF ChaCha, there is support for the
 //! original ChaCha (with 64 bits counter) and
@@ -43,8 +49,27 @@
 
 use core::cmp;
 
-use crate::chacha::ChaChaEngine as ChaChaState;
+use crate::chacha::{keystream8, ChaChaEngine as ChaChaState};
 use crate::cryptoutil::xor_keystream_mut;
+use crate::secure_zero::Zeroize;
+
+/// ChaCha errors
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChaChaError {
+    /// Processing the input would overflow the 32 bits block counter (256 GiB of keystream)
+    CounterOverflow,
+}
+
+impl core::fmt::Display for ChaChaError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ChaChaError::CounterOverflow => write!(f, "chacha block counter overflow"),
+        }
+    }
+}
+
+// number of blocks of the IETF variant with 32 bits counter
+const MAX_BLOCKS: u64 = 1 << 32;
 
 /// ChaCha Context (IETF Variant - RFC7539)
 ///
@@ -53,16 +78,26 @@ use crate::cryptoutil::xor_keystream_mut;
 /// a runtime assertion.
 ///
 /// If you don't know what rounds values to use, use 20 or the `Chacha20` type directly.
+///
+/// A context can produce at most 256 GiB of keystream, see the [module documentation](self#limits).
 #[derive(Clone)]
 pub struct ChaCha<const ROUNDS: usize> {
     state: ChaChaState<ROUNDS>,
     output: [u8; 64],
     offset: usize,
+    // counter of the next block to generate, up to MAX_BLOCKS when exhausted
+    block: u64,
 }
 
 /// Alias to usual ChaCha context with 20 rounds
 pub type ChaCha20 = ChaCha<20>;
 
+impl<const ROUNDS: usize> Drop for ChaCha<ROUNDS> {
+    fn drop(&mut self) {
+        self.output.zeroize()
+    }
+}
+
 impl<const ROUNDS: usize> ChaCha<ROUNDS> {
     /// Create a new ChaCha20 context.
     ///
@@ -78,6 +113,7 @@ impl<const ROUNDS: usize> ChaCha<ROUNDS> {
             state: ChaChaState::init(key, nonce),
             output: [0u8; 64],
             offset: 64,
+            block: 0,
         }
     }
 
@@ -85,6 +121,12 @@ impl<const ROUNDS: usize> ChaCha<ROUNDS> {
     pub fn seek(&mut self, position: u32) {
         self.state.set_counter(position);
         self.offset = 64;
+        self.block = position as u64;
+    }
+
+    /// Number of bytes of keystream that can still be processed by this context
+    pub fn remaining(&self) -> u64 {
+        (MAX_BLOCKS - self.block) * 64 + (64 - self.offset) as u64
     }
 
     // put the the next 64 keystream bytes into self.output
@@ -96,6 +138,7 @@ impl<const ROUNDS: usize> ChaCha<ROUNDS> {
         state.output_bytes(&mut self.output);
 
         self.state.increment();
+        self.block += 1;
         self.offset = 0;
     }
 
@@ -103,13 +146,39 @@ impl<const ROUNDS: usize> ChaCha<ROUNDS> {
     ///
     /// To get only the stream of this cipher, one can just pass the zero
     /// buffer (X xor 0 = X)
+    ///
+    /// # Panics
+    ///
+    /// Panics if the input is longer than the [remaining](Self::remaining) keystream
     pub fn process_mut(&mut self, data: &mut [u8]) {
+        if let Err(e) = self.try_process_mut(data) {
+            panic!("{}", e)
+        }
+    }
+
+    /// Process the input in place through the cipher xoring, or return an error
+    /// without processing anything if the input is longer than the
+    /// [remaining](Self::remaining) keystream
+    pub fn try_process_mut(&mut self, data: &mut [u8]) -> Result<(), ChaChaError> {
+        if data.len() as u64 > self.remaining() {
+            return Err(ChaChaError::CounterOverflow);
+        }
         let len = data.len();
         let mut i = 0;
         while i < len {
             // If there is no keystream available in the output buffer,
-            // generate the next block.
+            // generate the next block, or directly the next 8 blocks if
+            // there is enough input left.
             if self.offset == 64 {
+                if len - i >= 512 {
+                    let mut keystream = [0u8; 512];
+                    keystream8(&mut self.state, &mut keystream);
+                    xor_keystream_mut(&mut data[i..i + 512], &keystream);
+                    keystream.zeroize();
+                    self.block += 8;
+                    i += 512;
+                    continue;
+                }
                 self.update();
             }
 
@@ -119,23 +188,62 @@ impl<const ROUNDS: usize> ChaCha<ROUNDS> {
             i += count;
             self.offset += count;
         }
+        Ok(())
     }
 
     /// Process the input through the cipher, xoring the byte one-by-one
     ///
     /// the output need to be the same size as the input otherwise
     /// this function will panic.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the input is longer than the [remaining](Self::remaining) keystream
     pub fn process(&mut self, input: &[u8], output: &mut [u8]) {
+        if let Err(e) = self.try_process(input, output) {
+            panic!("{}", e)
+        }
+    }
+
+    /// Process the input through the cipher, or return an error without processing
+    /// anything if the input is longer than the [remaining](Self::remaining) keystream
+    ///
+    /// the output need to be the same size as the input otherwise
+    /// this function will panic.
+    pub fn try_process(&mut self, input: &[u8], output: &mut [u8]) -> Result<(), ChaChaError> {
         assert_eq!(
             input.len(),
             output.len(),
             "chacha::process need to have input and output of the same size"
         );
+        if input.len() as u64 > self.remaining() {
+            return Err(ChaChaError::CounterOverflow);
+        }
         output.copy_from_slice(input);
-        self.process_mut(output);
+        self.try_process_mut(output)
     }
 }
 
+/// The ChaCha20 block function (RFC 8439 section 2.3)
+///
+/// Return the 64 bytes block of the key, counter and nonce, i.e. the keystream
+/// block at position `counter` of the ChaCha20 stream with this key and nonce.
+///
+/// This is a building block for protocol designers that use the block function directly
+/// as a PRF (e.g. key derivation, custom generators), and not an encryption function:
+/// to encrypt data, use [`ChaCha20`] or an AEAD like
+/// [`ChaCha20Poly1305`](crate::chacha20poly1305::ChaCha20Poly1305).
+pub fn chacha20_block(key: &[u8; 32], counter: u32, nonce: &[u8; 12]) -> [u8; 64] {
+    let mut initial = ChaChaState::<20>::init(key, nonce);
+    initial.set_counter(counter);
+    let mut state = initial.clone();
+    state.rounds();
+    state.add_back(&initial);
+    let mut output = [0u8; 64];
+    state.output_bytes(&mut output);
+    output
+}
+
 /// XChaCha Context
 #[derive(Clone)]
 pub struct XChaCha<const ROUNDS: usize> {
@@ -194,8 +302,17 @@ impl<const ROUNDS: usize> XChaCha<ROUNDS> {
         let mut i = 0;
         while i < len {
             // If there is no keystream available in the output buffer,
-            // generate the next block.
+            // generate the next block, or directly the next 8 blocks if
+            // there is enough input left.
             if self.offset == 64 {
+                if len - i >= 512 {
+                    let mut keystream = [0u8; 512];
+                    keystream8(&mut self.state, &mut keystream);
+                    xor_keystream_mut(&mut data[i..i + 512], &keystream);
+                    keystream.zeroize();
+                    i += 512;
+                    continue;
+                }
                 self.update();
             }
 
@@ -224,7 +341,8 @@ impl<const ROUNDS: usize> XChaCha<ROUNDS> {
 
 /// ChaCha Context (Original version - Bernstein & co)
 ///
-/// This variant has an 8 bytes nonce initializer, and an 8 bytes counter
+/// This variant has an 8 bytes nonce initializer, and an 8 bytes counter,
+/// and is compatible with libsodium's `crypto_stream_chacha20`.
 ///
 /// Note that the number of rounds is exposed here, and only the
 /// value of 8, 12 and 20 are supported. any other values triggers
@@ -232,6 +350,7 @@ impl<const ROUNDS: usize> XChaCha<ROUNDS> {
 ///
 /// If you don't know what round values to use, use 20
 #[derive(Clone)]
+#[doc(alias = "ChaChaLegacy")]
 pub struct ChaChaOriginal<const ROUNDS: usize> {
     state: ChaChaState<ROUNDS>,
     output: [u8; 64],
@@ -256,6 +375,12 @@ impl<const ROUNDS: usize> ChaChaOriginal<ROUNDS> {
         }
     }
 
+    /// Seek the stream to a specific (64-bytes) block number
+    pub fn seek(&mut self, position: u64) {
+        self.state.set_counter64(position);
+        self.offset = 64;
+    }
+
     // put the the next 64 keystream bytes into self.output
     fn update(&mut self) {
         let mut state = self.state.clone();
@@ -312,8 +437,10 @@ mod test {
     use core::iter::repeat;
 
     use super::ChaCha20;
+    use super::ChaChaError;
     use super::ChaChaOriginal;
     use super::XChaCha;
+    use crate::encoding::encode_hex;
 
     #[test]
     fn test_chacha20_256_tls_vectors() {
@@ -425,6 +552,31 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_chacha20_original_libsodium() {
+        // crypto_stream_chacha20_xor_ic of libsodium, with the initial counters 0
+        // and 2^32-1, the second block carrying the counter into the second word.
+        let key: [u8; 32] = core::array::from_fn(|i| i as u8);
+        let nonce = [0, 1, 2, 3, 4, 5, 6, 7];
+        let tests: [(u64, &str); 2] = [
+            (
+                0,
+                "f798a189f195e66982105ffb640bb7757f579da31602fc93ec01ac56f85ac3c134a4547b733b46413042c9440049176905d3be59ea1c53f15916155c2be8241a38008b9a26bc35941e2444177c8ade6689de95264986d95889fb60e84629c9bd9a5acb1cc118be563eb9b3a4a472f82e09a7e778492b562ef7130e88dfe031c79db9d4f7c7a899151b9a475032b63fc385245fe054e3dd5a97a5f576fe064025",
+            ),
+            (
+                0xffff_ffff,
+                "a2b8d04b13877b4a7013cb9031e4b70836e9705a9691bd18f8fca48502eacdcae0b8faaeef6c5dfee436afd8268aa6385dabb2855761127a3946b50d649f9a4b2fcab2c09a960545c6f57e9269ebc22b4ed12782e66dc4cb612536f5cdbed4bcba16af8a92140bf4ded4808af8eee82bd0f18fbb64f073c2a547bc2372528f36",
+            ),
+        ];
+        for (counter, expected) in tests {
+            let mut c = ChaChaOriginal::<20>::new(&key, &nonce);
+            c.seek(counter);
+            let mut stream = alloc::vec![0u8; expected.len() / 2];
+            c.process_mut(&mut stream);
+            assert_eq!(encode_hex(&stream), expected, "counter {}", counter);
+        }
+    }
+
     #[test]
     fn test_xchacha20_basic() {
         // There aren't any convenient test vectors for XChaCha/20,
@@ -578,6 +730,267 @@ mod test {
             assert_eq!(output, tv.keystream);
         }
     }
+
+    #[test]
+    fn test_chacha20_rfc8439_vectors() {
+        struct TestVector {
+            key: [u8; 32],
+            nonce: [u8; 12],
+            counter: u32,
+            plaintext: &'static [u8],
+            ciphertext: &'static [u8],
+        }
+        // taken from https://www.rfc-editor.org/rfc/rfc8439, the A.2 test vector #1 is
+        // the same as the A.1 test vector #1.
+        let test_vectors = [
+            // 2.3.2 block function (as keystream)
+            TestVector {
+                key: [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f, 0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17, 0x18, 0x19, 0x1a, 0x1b, 0x1c, 0x1d, 0x1e, 0x1f],
+                nonce: [0x00, 0x00, 0x00, 0x09, 0x00, 0x00, 0x00, 0x4a, 0x00, 0x00, 0x00, 0x00],
+                counter: 1,
+                plaintext: &[0u8; 64],
+                ciphertext: &[0x10, 0xf1, 0xe7, 0xe4, 0xd1, 0x3b, 0x59, 0x15, 0x50, 0x0f, 0xdd, 0x1f, 0xa3, 0x20, 0x71, 0xc4, 0xc7, 0xd1, 0xf4, 0xc7, 0x33, 0xc0, 0x68, 0x03, 0x04, 0x22, 0xaa, 0x9a, 0xc3, 0xd4, 0x6c, 0x4e, 0xd2, 0x82, 0x64, 0x46, 0x07, 0x9f, 0xaa, 0x09, 0x14, 0xc2, 0xd7, 0x05, 0xd9, 0x8b, 0x02, 0xa2, 0xb5, 0x12, 0x9c, 0xd1, 0xde, 0x16, 0x4e, 0xb9, 0xcb, 0xd0, 0x83, 0xe8, 0xa2, 0x50, 0x3c, 0x4e],
+            },
+            // 2.4.2 encryption
+            TestVector {
+                key: [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f, 0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17, 0x18, 0x19, 0x1a, 0x1b, 0x1c, 0x1d, 0x1e, 0x1f],
+                nonce: [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x4a, 0x00, 0x00, 0x00, 0x00],
+                counter: 1,
+                plaintext: b"Ladies and Gentlemen of the class of '99: If I could offer you only one tip for the future, sunscreen would be it.",
+                ciphertext: &[0x6e, 0x2e, 0x35, 0x9a, 0x25, 0x68, 0xf9, 0x80, 0x41, 0xba, 0x07, 0x28, 0xdd, 0x0d, 0x69, 0x81, 0xe9, 0x7e, 0x7a, 0xec, 0x1d, 0x43, 0x60, 0xc2, 0x0a, 0x27, 0xaf, 0xcc, 0xfd, 0x9f, 0xae, 0x0b, 0xf9, 0x1b, 0x65, 0xc5, 0x52, 0x47, 0x33, 0xab, 0x8f, 0x59, 0x3d, 0xab, 0xcd, 0x62, 0xb3, 0x57, 0x16, 0x39, 0xd6, 0x24, 0xe6, 0x51, 0x52, 0xab, 0x8f, 0x53, 0x0c, 0x35, 0x9f, 0x08, 0x61, 0xd8, 0x07, 0xca, 0x0d, 0xbf, 0x50, 0x0d, 0x6a, 0x61, 0x56, 0xa3, 0x8e, 0x08, 0x8a, 0x22, 0xb6, 0x5e, 0x52, 0xbc, 0x51, 0x4d, 0x16, 0xcc, 0xf8, 0x06, 0x81, 0x8c, 0xe9, 0x1a, 0xb7, 0x79, 0x37, 0x36, 0x5a, 0xf9, 0x0b, 0xbf, 0x74, 0xa3, 0x5b, 0xe6, 0xb4, 0x0b, 0x8e, 0xed, 0xf2, 0x78, 0x5e, 0x42, 0x87, 0x4d],
+            },
+            // A.1 test vector #1
+            TestVector {
+                key: [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+                nonce: [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+                counter: 0,
+                plaintext: &[0u8; 64],
+                ciphertext: &[0x76, 0xb8, 0xe0, 0xad, 0xa0, 0xf1, 0x3d, 0x90, 0x40, 0x5d, 0x6a, 0xe5, 0x53, 0x86, 0xbd, 0x28, 0xbd, 0xd2, 0x19, 0xb8, 0xa0, 0x8d, 0xed, 0x1a, 0xa8, 0x36, 0xef, 0xcc, 0x8b, 0x77, 0x0d, 0xc7, 0xda, 0x41, 0x59, 0x7c, 0x51, 0x57, 0x48, 0x8d, 0x77, 0x24, 0xe0, 0x3f, 0xb8, 0xd8, 0x4a, 0x37, 0x6a, 0x43, 0xb8, 0xf4, 0x15, 0x18, 0xa1, 0x1c, 0xc3, 0x87, 0xb6, 0x69, 0xb2, 0xee, 0x65, 0x86],
+            },
+            // A.1 test vector #2
+            TestVector {
+                key: [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+                nonce: [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+                counter: 1,
+                plaintext: &[0u8; 64],
+                ciphertext: &[0x9f, 0x07, 0xe7, 0xbe, 0x55, 0x51, 0x38, 0x7a, 0x98, 0xba, 0x97, 0x7c, 0x73, 0x2d, 0x08, 0x0d, 0xcb, 0x0f, 0x29, 0xa0, 0x48, 0xe3, 0x65, 0x69, 0x12, 0xc6, 0x53, 0x3e, 0x32, 0xee, 0x7a, 0xed, 0x29, 0xb7, 0x21, 0x76, 0x9c, 0xe6, 0x4e, 0x43, 0xd5, 0x71, 0x33, 0xb0, 0x74, 0xd8, 0x39, 0xd5, 0x31, 0xed, 0x1f, 0x28, 0x51, 0x0a, 0xfb, 0x45, 0xac, 0xe1, 0x0a, 0x1f, 0x4b, 0x79, 0x4d, 0x6f],
+            },
+            // A.1 test vector #3
+            TestVector {
+                key: [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01],
+                nonce: [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+                counter: 1,
+                plaintext: &[0u8; 64],
+                ciphertext: &[0x3a, 0xeb, 0x52, 0x24, 0xec, 0xf8, 0x49, 0x92, 0x9b, 0x9d, 0x82, 0x8d, 0xb1, 0xce, 0xd4, 0xdd, 0x83, 0x20, 0x25, 0xe8, 0x01, 0x8b, 0x81, 0x60, 0xb8, 0x22, 0x84, 0xf3, 0xc9, 0x49, 0xaa, 0x5a, 0x8e, 0xca, 0x00, 0xbb, 0xb4, 0xa7, 0x3b, 0xda, 0xd1, 0x92, 0xb5, 0xc4, 0x2f, 0x73, 0xf2, 0xfd, 0x4e, 0x27, 0x36, 0x44, 0xc8, 0xb3, 0x61, 0x25, 0xa6, 0x4a, 0xdd, 0xeb, 0x00, 0x6c, 0x13, 0xa0],
+            },
+            // A.1 test vector #4
+            TestVector {
+                key: [0x00, 0xff, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+                nonce: [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+                counter: 2,
+                plaintext: &[0u8; 64],
+                ciphertext: &[0x72, 0xd5, 0x4d, 0xfb, 0xf1, 0x2e, 0xc4, 0x4b, 0x36, 0x26, 0x92, 0xdf, 0x94, 0x13, 0x7f, 0x32, 0x8f, 0xea, 0x8d, 0xa7, 0x39, 0x90, 0x26, 0x5e, 0xc1, 0xbb, 0xbe, 0xa1, 0xae, 0x9a, 0xf0, 0xca, 0x13, 0xb2, 0x5a, 0xa2, 0x6c, 0xb4, 0xa6, 0x48, 0xcb, 0x9b, 0x9d, 0x1b, 0xe6, 0x5b, 0x2c, 0x09, 0x24, 0xa6, 0x6c, 0x54, 0xd5, 0x45, 0xec, 0x1b, 0x73, 0x74, 0xf4, 0x87, 0x2e, 0x99, 0xf0, 0x96],
+            },
+            // A.1 test vector #5
+            TestVector {
+                key: [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+                nonce: [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02],
+                counter: 0,
+                plaintext: &[0u8; 64],
+                ciphertext: &[0xc2, 0xc6, 0x4d, 0x37, 0x8c, 0xd5, 0x36, 0x37, 0x4a, 0xe2, 0x04, 0xb9, 0xef, 0x93, 0x3f, 0xcd, 0x1a, 0x8b, 0x22, 0x88, 0xb3, 0xdf, 0xa4, 0x96, 0x72, 0xab, 0x76, 0x5b, 0x54, 0xee, 0x27, 0xc7, 0x8a, 0x97, 0x0e, 0x0e, 0x95, 0x5c, 0x14, 0xf3, 0xa8, 0x8e, 0x74, 0x1b, 0x97, 0xc2, 0x86, 0xf7, 0x5f, 0x8f, 0xc2, 0x99, 0xe8, 0x14, 0x83, 0x62, 0xfa, 0x19, 0x8a, 0x39, 0x53, 0x1b, 0xed, 0x6d],
+            },
+            // A.2 test vector #2
+            TestVector {
+                key: [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01],
+                nonce: [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02],
+                counter: 1,
+                plaintext: b"Any submission to the IETF intended by the Contributor for publication as all or part of an IETF Internet-Draft or RFC and any statement made within the context of an IETF activity is considered an \"IETF Contribution\". Such statements include oral statements in IETF sessions, as well as written and electronic communications made at any time or place, which are addressed to",
+                ciphertext: &[0xa3, 0xfb, 0xf0, 0x7d, 0xf3, 0xfa, 0x2f, 0xde, 0x4f, 0x37, 0x6c, 0xa2, 0x3e, 0x82, 0x73, 0x70, 0x41, 0x60, 0x5d, 0x9f, 0x4f, 0x4f, 0x57, 0xbd, 0x8c, 0xff, 0x2c, 0x1d, 0x4b, 0x79, 0x55, 0xec, 0x2a, 0x97, 0x94, 0x8b, 0xd3, 0x72, 0x29, 0x15, 0xc8, 0xf3, 0xd3, 0x37, 0xf7, 0xd3, 0x70, 0x05, 0x0e, 0x9e, 0x96, 0xd6, 0x47, 0xb7, 0xc3, 0x9f, 0x56, 0xe0, 0x31, 0xca, 0x5e, 0xb6, 0x25, 0x0d, 0x40, 0x42, 0xe0, 0x27, 0x85, 0xec, 0xec, 0xfa, 0x4b, 0x4b, 0xb5, 0xe8, 0xea, 0xd0, 0x44, 0x0e, 0x20, 0xb6, 0xe8, 0xdb, 0x09, 0xd8, 0x81, 0xa7, 0xc6, 0x13, 0x2f, 0x42, 0x0e, 0x52, 0x79, 0x50, 0x42, 0xbd, 0xfa, 0x77, 0x73, 0xd8, 0xa9, 0x05, 0x14, 0x47, 0xb3, 0x29, 0x1c, 0xe1, 0x41, 0x1c, 0x68, 0x04, 0x65, 0x55, 0x2a, 0xa6, 0xc4, 0x05, 0xb7, 0x76, 0x4d, 0x5e, 0x87, 0xbe, 0xa8, 0x5a, 0xd0, 0x0f, 0x84, 0x49, 0xed, 0x8f, 0x72, 0xd0, 0xd6, 0x62, 0xab, 0x05, 0x26, 0x91, 0xca, 0x66, 0x42, 0x4b, 0xc8, 0x6d, 0x2d, 0xf8, 0x0e, 0xa4, 0x1f, 0x43, 0xab, 0xf9, 0x37, 0xd3, 0x25, 0x9d, 0xc4, 0xb2, 0xd0, 0xdf, 0xb4, 0x8a, 0x6c, 0x91, 0x39, 0xdd, 0xd7, 0xf7, 0x69, 0x66, 0xe9, 0x28, 0xe6, 0x35, 0x55, 0x3b, 0xa7, 0x6c, 0x5c, 0x87, 0x9d, 0x7b, 0x35, 0xd4, 0x9e, 0xb2, 0xe6, 0x2b, 0x08, 0x71, 0xcd, 0xac, 0x63, 0x89, 0x39, 0xe2, 0x5e, 0x8a, 0x1e, 0x0e, 0xf9, 0xd5, 0x28, 0x0f, 0xa8, 0xca, 0x32, 0x8b, 0x35, 0x1c, 0x3c, 0x76, 0x59, 0x89, 0xcb, 0xcf, 0x3d, 0xaa, 0x8b, 0x6c, 0xcc, 0x3a, 0xaf, 0x9f, 0x39, 0x79, 0xc9, 0x2b, 0x37, 0x20, 0xfc, 0x88, 0xdc, 0x95, 0xed, 0x84, 0xa1, 0xbe, 0x05, 0x9c, 0x64, 0x99, 0xb9, 0xfd, 0xa2, 0x36, 0xe7, 0xe8, 0x18, 0xb0, 0x4b, 0x0b, 0xc3, 0x9c, 0x1e, 0x87, 0x6b, 0x19, 0x3b, 0xfe, 0x55, 0x69, 0x75, 0x3f, 0x88, 0x12, 0x8c, 0xc0, 0x8a, 0xaa, 0x9b, 0x63, 0xd1, 0xa1, 0x6f, 0x80, 0xef, 0x25, 0x54, 0xd7, 0x18, 0x9c, 0x41, 0x1f, 0x58, 0x69, 0xca, 0x52, 0xc5, 0xb8, 0x3f, 0xa3, 0x6f, 0xf2, 0x16, 0xb9, 0xc1, 0xd3, 0x00, 0x62, 0xbe, 0xbc, 0xfd, 0x2d, 0xc5, 0xbc, 0xe0, 0x91, 0x19, 0x34, 0xfd, 0xa7, 0x9a, 0x86, 0xf6, 0xe6, 0x98, 0xce, 0xd7, 0x59, 0xc3, 0xff, 0x9b, 0x64, 0x77, 0x33, 0x8f, 0x3d, 0xa4, 0xf9, 0xcd, 0x85, 0x14, 0xea, 0x99, 0x82, 0xcc, 0xaf, 0xb3, 0x41, 0xb2, 0x38, 0x4d, 0xd9, 0x02, 0xf3, 0xd1, 0xab, 0x7a, 0xc6, 0x1d, 0xd2, 0x9c, 0x6f, 0x21, 0xba, 0x5b, 0x86, 0x2f, 0x37, 0x30, 0xe3, 0x7c, 0xfd, 0xc4, 0xfd, 0x80, 0x6c, 0x22, 0xf2, 0x21],
+            },
+            // A.2 test vector #3
+            TestVector {
+                key: [0x1c, 0x92, 0x40, 0xa5, 0xeb, 0x55, 0xd3, 0x8a, 0xf3, 0x33, 0x88, 0x86, 0x04, 0xf6, 0xb5, 0xf0, 0x47, 0x39, 0x17, 0xc1, 0x40, 0x2b, 0x80, 0x09, 0x9d, 0xca, 0x5c, 0xbc, 0x20, 0x70, 0x75, 0xc0],
+                nonce: [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02],
+                counter: 42,
+                plaintext: b"'Twas brillig, and the slithy toves\nDid gyre and gimble in the wabe:\nAll mimsy were the borogoves,\nAnd the mome raths outgrabe.",
+                ciphertext: &[0x62, 0xe6, 0x34, 0x7f, 0x95, 0xed, 0x87, 0xa4, 0x5f, 0xfa, 0xe7, 0x42, 0x6f, 0x27, 0xa1, 0xdf, 0x5f, 0xb6, 0x91, 0x10, 0x04, 0x4c, 0x0d, 0x73, 0x11, 0x8e, 0xff, 0xa9, 0x5b, 0x01, 0xe5, 0xcf, 0x16, 0x6d, 0x3d, 0xf2, 0xd7, 0x21, 0xca, 0xf9, 0xb2, 0x1e, 0x5f, 0xb1, 0x4c, 0x61, 0x68, 0x71, 0xfd, 0x84, 0xc5, 0x4f, 0x9d, 0x65, 0xb2, 0x83, 0x19, 0x6c, 0x7f, 0xe4, 0xf6, 0x05, 0x53, 0xeb, 0xf3, 0x9c, 0x64, 0x02, 0xc4, 0x22, 0x34, 0xe3, 0x2a, 0x35, 0x6b, 0x3e, 0x76, 0x43, 0x12, 0xa6, 0x1a, 0x55, 0x32, 0x05, 0x57, 0x16, 0xea, 0xd6, 0x96, 0x25, 0x68, 0xf8, 0x7d, 0x3f, 0x3f, 0x77, 0x04, 0xc6, 0xa8, 0xd1, 0xbc, 0xd1, 0xbf, 0x4d, 0x50, 0xd6, 0x15, 0x4b, 0x6d, 0xa7, 0x31, 0xb1, 0x87, 0xb5, 0x8d, 0xfd, 0x72, 0x8a, 0xfa, 0x36, 0x75, 0x7a, 0x79, 0x7a, 0xc1, 0x88, 0xd1],
+            },
+        ];
+
+        for tv in test_vectors.iter() {
+            let mut c = ChaCha20::new(&tv.key, &tv.nonce);
+            c.seek(tv.counter);
+            let mut output: Vec<u8> = repeat(0).take(tv.plaintext.len()).collect();
+            c.process(tv.plaintext, &mut output[..]);
+            assert_eq!(output, tv.ciphertext);
+
+            // decryption is the same operation
+            let mut c = ChaCha20::new(&tv.key, &tv.nonce);
+            c.seek(tv.counter);
+            c.process_mut(&mut output);
+            assert_eq!(output, tv.plaintext);
+        }
+    }
+
+    #[test]
+    fn test_chacha20_multi_blocks() {
+        // processing large inputs use the 8 blocks keystream, which need
+        // to match the keystream generated one byte at a time
+        let key = [0x42u8; 32];
+        let nonce = [0x24u8; 12];
+        for (seek, skip) in [(0, 0), (0, 3), (u32::MAX - 2, 0), (u32::MAX - 9, 64)] {
+            // the IETF counter cannot wrap, so stop at the last block for ChaCha20,
+            // while XChaCha carries into the next counter word
+            let chacha_seek = seek.min(u32::MAX - 23);
+            let mut expected = [0u8; 1500];
+            let mut c = ChaCha20::new(&key, &nonce);
+            c.seek(chacha_seek);
+            for b in expected.iter_mut() {
+                c.process_mut(core::slice::from_mut(b));
+            }
+
+            let mut output = [0u8; 1500];
+            let mut c = ChaCha20::new(&key, &nonce);
+            c.seek(chacha_seek);
+            c.process_mut(&mut output[..skip]);
+            c.process_mut(&mut output[skip..]);
+            assert_eq!(&output[..], &expected[..], "seek {} skip {}", seek, skip);
+
+            let mut output = [0u8; 1500];
+            let mut c = XChaCha::<20>::new(&key, &[0x24u8; 24]);
+            c.seek(seek);
+            c.process_mut(&mut output[..skip]);
+            c.process_mut(&mut output[skip..]);
+            let mut c = XChaCha::<20>::new(&key, &[0x24u8; 24]);
+            c.seek(seek);
+            for (i, b) in expected.iter_mut().enumerate() {
+                *b = 0;
+                c.process_mut(core::slice::from_mut(b));
+                assert_eq!(*b, output[i], "xchacha seek {} skip {}", seek, skip);
+            }
+        }
+    }
+
+    #[test]
+    fn block_function() {
+        let tests = [
+            // RFC 8439 section 2.3.2
+            (
+                [
+                    0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c,
+                    0x0d, 0x0e, 0x0f, 0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17, 0x18, 0x19,
+                    0x1a, 0x1b, 0x1c, 0x1d, 0x1e, 0x1f,
+                ],
+                1,
+                [0x00, 0x00, 0x00, 0x09, 0x00, 0x00, 0x00, 0x4a, 0x00, 0x00, 0x00, 0x00],
+                "10f1e7e4d13b5915500fdd1fa32071c4c7d1f4c733c068030422aa9ac3d46c4ed2826446079faa0914c2d705d98b02a2b5129cd1de164eb9cbd083e8a2503c4e",
+            ),
+            // RFC 8439 appendix A.1, test vectors #1 and #2
+            (
+                [0; 32],
+                0,
+                [0; 12],
+                "76b8e0ada0f13d90405d6ae55386bd28bdd219b8a08ded1aa836efcc8b770dc7da41597c5157488d7724e03fb8d84a376a43b8f41518a11cc387b669b2ee6586",
+            ),
+            (
+                [0; 32],
+                1,
+                [0; 12],
+                "9f07e7be5551387a98ba977c732d080dcb0f29a048e3656912c6533e32ee7aed29b721769ce64e43d57133b074d839d531ed1f28510afb45ace10a1f4b794d6f",
+            ),
+        ];
+        for (key, counter, nonce, expected) in tests.iter() {
+            let block = super::chacha20_block(key, *counter, nonce);
+            assert_eq!(encode_hex(&block), *expected);
+
+            // same as the keystream at the block position
+            let mut keystream = [0u8; 64];
+            let mut c = ChaCha20::new(key, nonce);
+            c.seek(*counter);
+            c.process_mut(&mut keystream);
+            assert_eq!(block, keystream);
+        }
+    }
+
+    // 128 bits key: TC8 of draft-strombergson-chacha-test-vectors, first 2 blocks of keystream
+    #[test]
+    fn test_chacha_128bits_key() {
+        let key = [
+            0xc4, 0x6e, 0xc1, 0xb1, 0x8c, 0xe8, 0xa8, 0x78, 0x72, 0x5a, 0x37, 0xe7, 0x80, 0xdf,
+            0xb7, 0x35,
+        ];
+        let nonce = [0x1a, 0xda, 0x31, 0xd5, 0xcf, 0x68, 0x82, 0x21];
+
+        let mut keystream = [0u8; 128];
+        ChaChaOriginal::<8>::new(&key, &nonce).process_mut(&mut keystream);
+        assert_eq!(
+            encode_hex(&keystream),
+            "6a870108859f679118f3e205e2a56a6826ef5a60a4102ac8d4770059fcb7c7bae02f5ce004a6bfbbea53014dd82107c0aa1c7ce11b7d78f2d50bd3602bbd25940560bb6a84289e0b38f5dd21d6ef6d7737e3ec0fb772da2c71c2397762e5dbbbf449e3d1639ccbfa3e069c4d871ed6395b22aaf35c8da6de2dec3d77880da8e8"
+        );
+
+        let mut keystream = [0u8; 128];
+        ChaChaOriginal::<20>::new(&key, &nonce).process_mut(&mut keystream);
+        assert_eq!(
+            encode_hex(&keystream),
+            "826abdd84460e2e9349f0ef4af5b179b426e4b2d109a9c5bb44000ae51bea90a496beeef62a76850ff3f0402c4ddc99f6db07f151c1c0dfac2e56565d62896255b23132e7b469c7bfb88fa95d44ca5ae3e45e848a4108e98bad7a9eb15512784a6a9e6e591dce674120acaf9040ff50ff3ac30ccfb5e14204f5e4268b90a8804"
+        );
+    }
+
+    #[test]
+    fn counter_overflow() {
+        let mut c = ChaCha20::new(&[0u8; 32], &[0u8; 12]);
+        assert_eq!(c.remaining(), 256 << 30);
+
+        // the last block of the stream
+        c.seek(u32::MAX);
+        assert_eq!(c.remaining(), 64);
+        let mut buf = [0u8; 65];
+        assert_eq!(
+            c.try_process_mut(&mut buf),
+            Err(ChaChaError::CounterOverflow)
+        );
+        assert_eq!(buf, [0u8; 65]);
+        assert_eq!(c.try_process_mut(&mut buf[..63]), Ok(()));
+        assert_eq!(c.try_process_mut(&mut buf[..1]), Ok(()));
+        assert_eq!(c.remaining(), 0);
+        assert_eq!(
+            c.try_process(&[0u8; 1], &mut [0u8; 1]),
+            Err(ChaChaError::CounterOverflow)
+        );
+
+        // the 8 blocks path stops at the end of the stream too
+        c.seek(u32::MAX - 7);
+        let mut buf = [0u8; 512];
+        assert_eq!(c.try_process_mut(&mut buf), Ok(()));
+        assert_eq!(
+            c.try_process_mut(&mut buf[..1]),
+            Err(ChaChaError::CounterOverflow)
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn counter_overflow_panic() {
+        let mut c = ChaCha20::new(&[0u8; 32], &[0u8; 12]);
+        c.seek(u32::MAX);
+        c.process_mut(&mut [0u8; 65]);
+    }
+
+    // process the whole 256 GiB keystream, which takes about 15 minutes in release mode
+    #[test]
+    #[ignore]
+    fn counter_overflow_full_stream() {
+        let mut c = ChaCha20::new(&[0u8; 32], &[0u8; 12]);
+        let mut buf = alloc::vec![0u8; 1 << 20];
+        for _ in 0..(256 << 10) {
+            c.process_mut(&mut buf);
+        }
+        assert_eq!(c.remaining(), 0);
+        assert_eq!(
+            c.try_process_mut(&mut buf[..1]),
+            Err(ChaChaError::CounterOverflow)
+        );
+    }
 }
 
 #[cfg(all(test, feature = "with-bench"))]
@@ -587,7 +1000,7 @@ mod bench {
 
     #[bench]
     pub fn chacha20_10(bh: &mut Bencher) {
-        let mut chacha20 = ChaCha20::new(&[0; 32], &[0; 8]);
+        let mut chacha20 = ChaCha20::new(&[0; 32], &[0; 12]);
         let input = [1u8; 10];
         let mut output = [0u8; 10];
         bh.iter(|| {
@@ -598,7 +1011,7 @@ mod bench {
 
     #[bench]
     pub fn chacha20_1k(bh: &mut Bencher) {
-        let mut chacha20 = ChaCha20::new(&[0; 32], &[0; 8]);
+        let mut chacha20 = ChaCha20::new(&[0; 32], &[0; 12]);
         let input = [1u8; 1024];
         let mut output = [0u8; 1024];
         bh.iter(|| {
@@ -609,7 +1022,7 @@ mod bench {
 
     #[bench]
     pub fn chacha20_64k(bh: &mut Bencher) {
-        let mut chacha20 = ChaCha20::new(&[0; 32], &[0; 8]);
+        let mut chacha20 = ChaCha20::new(&[0; 32], &[0; 12]);
         let input = [1u8; 65536];
         let mut output = [0u8; 65536];
         bh.iter(|| {
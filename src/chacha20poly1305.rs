@@ -11,6 +11,12 @@
 //! you need more control over the memory usage, as the one-shot interface
 //! expects one single call with slices parameter.
 //!
+//! The tag authenticates the whole ciphertext, so the data decrypted incrementally
+//! with [`ContextDecryption::decrypt_unverified`] cannot be trusted, and must not be used,
+//! until [`ContextDecryption::finalize`] returns [`DecryptionResult::Match`]. The last
+//! part of the ciphertext can be decrypted with [`ContextDecryption::decrypt_buffer_and_verify`],
+//! which verifies the tag before writing anything to the output.
+//!
 //! # Examples
 //!
 //! Encrypting using the one-shot interface:
@@ -67,6 +73,7 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+use crate::aead::AeadError;
 use crate::chacha20::ChaCha;
 use crate::constant_time::{Choice, CtEqual};
 use crate::cryptoutil::write_u64_le;
@@ -240,24 +247,46 @@ pub enum DecryptionResult {
 
 impl<const ROUNDS: usize> ContextDecryption<ROUNDS> {
     /// Decrypt input in place
+    ///
+    /// The decrypted data is not authenticated yet, and must not be used
+    /// until `finalize` returns `DecryptionResult::Match`, see [`Self::decrypt_unverified`].
     pub fn decrypt_mut(&mut self, buf: &mut [u8]) {
         self.0.add_encrypted(buf);
         self.0.cipher.process_mut(buf);
     }
 
-    /// Decrypt the input to the output slice
+    /// Decrypt the input to the output slice, without verifying the data
+    ///
+    /// The output is written before the tag is verified, as the tag covers
+    /// the whole ciphertext: the output is unauthenticated data, which must not
+    /// be used (or released) until `finalize` returns `DecryptionResult::Match`.
+    /// When the whole ciphertext is available, `decrypt_buffer_and_verify` doesn't
+    /// write anything to the output if the tag doesn't match.
     ///
     /// Panics:
     ///     if input and output are of different size
-    pub fn decrypt(&mut self, input: &[u8], output: &mut [u8]) {
+    pub fn decrypt_unverified(&mut self, input: &[u8], output: &mut [u8]) {
         assert_eq!(input.len(), output.len());
         self.0.add_encrypted(input);
         self.0.cipher.process(input, output);
     }
 
+    /// Decrypt the input to the output slice
+    ///
+    /// Panics:
+    ///     if input and output are of different size
+    #[deprecated(
+        since = "0.6.0",
+        note = "use `decrypt_unverified`, or `decrypt_buffer_and_verify`"
+    )]
+    pub fn decrypt(&mut self, input: &[u8], output: &mut [u8]) {
+        self.decrypt_unverified(input, output)
+    }
+
     /// Finalize the decryption context and check that the tag match the expected value
     ///
-    #[must_use = "if the result is not checked, then the data will not be verified against tempering"]
+    /// Any data decrypted by the context can only be used if this returns `DecryptionResult::Match`
+    #[must_use = "if the result is not checked, then the data will not be verified against tempering, and the decrypted data must not be used"]
     pub fn finalize(mut self, expected_tag: &Tag) -> DecryptionResult {
         let got_tag = Tag(finalize_raw(&mut self.0));
         if &got_tag == expected_tag {
@@ -266,6 +295,30 @@ impl<const ROUNDS: usize> ContextDecryption<ROUNDS> {
             DecryptionResult::MisMatch
         }
     }
+
+    /// Verify the tag of the last part of the ciphertext, and decrypt it to the output only if the tag match
+    ///
+    /// The tag covers the whole ciphertext, so this is only safe to use when `ciphertext` is all
+    /// the data left to decrypt. If the tag doesn't match, an error is returned and nothing is
+    /// written to the output.
+    ///
+    /// Panics:
+    ///     if ciphertext and output are of different size
+    pub fn decrypt_buffer_and_verify(
+        mut self,
+        ciphertext: &[u8],
+        output: &mut [u8],
+        tag: &Tag,
+    ) -> Result<(), AeadError> {
+        assert_eq!(ciphertext.len(), output.len());
+        self.0.add_encrypted(ciphertext);
+        let got_tag = Tag(finalize_raw(&mut self.0));
+        if &got_tag != tag {
+            return Err(AeadError::TagMismatch);
+        }
+        self.0.cipher.process(ciphertext, output);
+        Ok(())
+    }
 }
 
 /// A ChaCha20+Poly1305 Context
@@ -286,6 +339,21 @@ fn pad16(mac: &mut Poly1305, len: u64) {
 /// Type alias to the common ChaChaPoly1305 with 20 rounds ChaCha
 pub type ChaCha20Poly1305 = ChaChaPoly1305<20>;
 
+/// Type alias to ChaChaPoly1305 with 12 rounds ChaCha
+///
+/// The best known attacks reach 7 rounds of ChaCha, so 12 rounds still keeps a
+/// reasonable security margin, for about 60% of the ChaCha20 cost.
+/// This is not an IETF construction, and is not interoperable with ChaCha20Poly1305.
+pub type ChaCha12Poly1305 = ChaChaPoly1305<12>;
+
+/// Type alias to ChaChaPoly1305 with 8 rounds ChaCha
+///
+/// 8 rounds is only one round above the best known attacks on ChaCha, so this
+/// variant trades most of the security margin for speed (for about 40% of the
+/// ChaCha20 cost). Prefer [`ChaCha12Poly1305`] or [`ChaCha20Poly1305`] unless the speed is critical.
+/// This is not an IETF construction, and is not interoperable with ChaCha20Poly1305.
+pub type ChaCha8Poly1305 = ChaChaPoly1305<8>;
+
 impl<const ROUNDS: usize> ChaChaPoly1305<ROUNDS> {
     /// Create a new ChaCha20Poly1305
     ///
@@ -344,7 +412,8 @@ impl<const ROUNDS: usize> ChaChaPoly1305<ROUNDS> {
     /// Decrypt the input to the output buffer
     ///
     /// if the calculated tag during decryption doesn't match
-    /// the tag in parameter, then the function return False
+    /// the tag in parameter, then the function return False,
+    /// and nothing is written to the output buffer
     ///
     /// Example: Decrypt a simple message with chacha20poly1305 AEAD
     /// using a 64 bits nonce and a 128 bits keys where the first 12 bytes
@@ -380,16 +449,16 @@ impl<const ROUNDS: usize> ChaChaPoly1305<ROUNDS> {
         let mut tag_data = [0u8; 16];
         tag_data.copy_from_slice(tag);
 
-        let mut ctx = self.context.clone().to_decryption();
-
-        ctx.decrypt(input, output);
-        ctx.finalize(&Tag(tag_data)) == DecryptionResult::Match
+        let ctx = self.context.clone().to_decryption();
+        ctx.decrypt_buffer_and_verify(input, output, &Tag(tag_data))
+            .is_ok()
     }
 }
 
 #[cfg(test)]
 mod test {
-    use super::ChaCha20Poly1305;
+    use super::{ChaChaPoly1305, Context, Tag};
+    use crate::aead::AeadError;
 
     struct TestVector {
         key: [u8; 32],
@@ -400,24 +469,54 @@ mod test {
         aad: &'static [u8],
     }
 
-    fn test_vector(v: &TestVector) {
+    fn test_vector<const ROUNDS: usize>(v: &TestVector) {
         let mut tag = [0u8; 16];
         let mut ciphertext = vec![0u8; v.cipher_text.len()];
 
-        let mut context = ChaCha20Poly1305::new(&v.key, &v.nonce, &v.aad);
+        let mut context = ChaChaPoly1305::<ROUNDS>::new(&v.key, &v.nonce, v.aad);
         let mut dcontext = context.clone();
 
         // test encryption
-        context.encrypt(&v.plain_text, &mut ciphertext, &mut tag[..]);
+        context.encrypt(v.plain_text, &mut ciphertext, &mut tag[..]);
 
-        assert_eq!(&ciphertext[..], &v.cipher_text[..]);
+        assert_eq!(&ciphertext[..], v.cipher_text);
         assert_eq!(&tag[..], &v.tag[..]);
 
         // test decryption
         let mut output = vec![0u8; v.plain_text.len()];
-        assert_eq!(dcontext.decrypt(&ciphertext, &mut output, &v.tag[..]), true);
+        assert!(dcontext.decrypt(&ciphertext, &mut output, &v.tag[..]));
+
+        assert_eq!(&output[..], v.plain_text);
+
+        // incremental decryption, verifying the last part before decrypting it
+        let decryption = |tag: &Tag, output: &mut [u8]| {
+            let mut context = Context::<ROUNDS>::new(&v.key, &v.nonce);
+            context.add_data(v.aad);
+            let mut context = context.to_decryption();
+            let half = ciphertext.len() / 2;
+            let (first, last) = output.split_at_mut(half);
+            context.decrypt_unverified(&ciphertext[..half], first);
+            context.decrypt_buffer_and_verify(&ciphertext[half..], last, tag)
+        };
+        let mut output = vec![0u8; v.plain_text.len()];
+        assert_eq!(decryption(&Tag(v.tag), &mut output), Ok(()));
+        assert_eq!(&output[..], v.plain_text);
 
-        assert_eq!(&output[..], &v.plain_text[..]);
+        let mut bad_tag = v.tag;
+        bad_tag[0] ^= 1;
+        let mut output = vec![0u8; v.plain_text.len()];
+        assert_eq!(
+            decryption(&Tag(bad_tag), &mut output),
+            Err(AeadError::TagMismatch)
+        );
+        let half = output.len() / 2;
+        assert!(output[half..].iter().all(|b| *b == 0));
+
+        // the one-shot decryption doesn't write the output on mismatch
+        let mut output = vec![0u8; v.plain_text.len()];
+        let mut dcontext = ChaChaPoly1305::<ROUNDS>::new(&v.key, &v.nonce, v.aad);
+        assert!(!dcontext.decrypt(&ciphertext, &mut output, &bad_tag));
+        assert!(output.iter().all(|b| *b == 0));
     }
 
     #[test]
@@ -529,9 +628,74 @@ mod test {
             },
         ];
         for tv in tests.iter() {
-            test_vector(&tv)
+            test_vector::<20>(tv)
         }
     }
+
+    // reduced rounds variants have no published test vectors: these use the inputs of
+    // RFC 7539 section 2.8.2, with outputs generated by an independent implementation
+    const REDUCED_KEY: [u8; 32] = [
+        0x80, 0x81, 0x82, 0x83, 0x84, 0x85, 0x86, 0x87, 0x88, 0x89, 0x8a, 0x8b, 0x8c, 0x8d, 0x8e,
+        0x8f, 0x90, 0x91, 0x92, 0x93, 0x94, 0x95, 0x96, 0x97, 0x98, 0x99, 0x9a, 0x9b, 0x9c, 0x9d,
+        0x9e, 0x9f,
+    ];
+    const REDUCED_NONCE: [u8; 12] = [
+        0x07, 0x00, 0x00, 0x00, 0x40, 0x41, 0x42, 0x43, 0x44, 0x45, 0x46, 0x47,
+    ];
+    const REDUCED_AAD: &[u8] = &[
+        0x50, 0x51, 0x52, 0x53, 0xc0, 0xc1, 0xc2, 0xc3, 0xc4, 0xc5, 0xc6, 0xc7,
+    ];
+    const REDUCED_PLAIN_TEXT: &[u8] = b"Ladies and Gentlemen of the class of '99: If I could offer you only one tip for the future, sunscreen would be it.";
+
+    #[test]
+    fn test_vectors_chacha8() {
+        test_vector::<8>(&TestVector {
+            key: REDUCED_KEY,
+            nonce: REDUCED_NONCE,
+            plain_text: REDUCED_PLAIN_TEXT,
+            aad: REDUCED_AAD,
+            cipher_text: &[
+                0x92, 0xa6, 0xd1, 0x23, 0x9f, 0x63, 0xd2, 0xf5, 0x62, 0xbd, 0x59, 0x01, 0xd9, 0x0d,
+                0xf4, 0xfc, 0x53, 0xf6, 0x3a, 0x1e, 0xc5, 0x20, 0x1a, 0xa8, 0x06, 0x48, 0x19, 0x8a,
+                0x64, 0x36, 0xf7, 0x17, 0x26, 0xd6, 0x46, 0xa4, 0x96, 0xea, 0x4c, 0x0d, 0xfa, 0x2c,
+                0xcf, 0xc9, 0x94, 0x77, 0x0e, 0x46, 0xe3, 0x04, 0x82, 0x9d, 0x1a, 0xd7, 0x64, 0x90,
+                0xdb, 0x4c, 0x27, 0x23, 0x5c, 0xc6, 0xeb, 0xbf, 0x25, 0x00, 0x3f, 0xd3, 0x0d, 0xa6,
+                0xc4, 0xb7, 0x6b, 0x7a, 0x90, 0xa9, 0x47, 0x22, 0x6c, 0x01, 0x22, 0xe9, 0x09, 0x2f,
+                0xc5, 0x56, 0x11, 0x5f, 0xf7, 0x2e, 0xac, 0x3f, 0x49, 0x70, 0xc2, 0x39, 0xbc, 0xee,
+                0xb5, 0x5d, 0x3f, 0x61, 0xd8, 0x52, 0x0c, 0x10, 0x91, 0x31, 0xd5, 0xa7, 0xa1, 0x51,
+                0x08, 0x64,
+            ],
+            tag: [
+                0xca, 0xe9, 0x43, 0xb1, 0xd1, 0xd6, 0xec, 0xfd, 0x13, 0xef, 0xbe, 0x36, 0xc2, 0xe3,
+                0x56, 0xe0,
+            ],
+        });
+    }
+
+    #[test]
+    fn test_vectors_chacha12() {
+        test_vector::<12>(&TestVector {
+            key: REDUCED_KEY,
+            nonce: REDUCED_NONCE,
+            plain_text: REDUCED_PLAIN_TEXT,
+            aad: REDUCED_AAD,
+            cipher_text: &[
+                0xbb, 0xc9, 0x35, 0xda, 0x15, 0x8b, 0xf5, 0xa6, 0xb9, 0xdf, 0x52, 0x59, 0xd0, 0x3f,
+                0xf5, 0xfe, 0x6d, 0x81, 0x2e, 0x72, 0xad, 0x17, 0x3a, 0x96, 0x49, 0xf4, 0xd4, 0xf3,
+                0xfe, 0x0c, 0x6f, 0xe9, 0xeb, 0x88, 0x8a, 0xb6, 0xc2, 0x65, 0x36, 0x41, 0xcb, 0x86,
+                0x51, 0x6f, 0x30, 0xc5, 0xa5, 0x12, 0x97, 0x62, 0x5b, 0xd5, 0x5c, 0x8e, 0x83, 0x0d,
+                0x92, 0xb6, 0xa0, 0x1c, 0xe8, 0x85, 0x6c, 0xcb, 0x29, 0x20, 0x6e, 0x79, 0x69, 0x6e,
+                0xc7, 0x4b, 0x13, 0x27, 0x69, 0xed, 0x27, 0x6b, 0x72, 0x1a, 0xaa, 0x63, 0x86, 0x86,
+                0x4e, 0x7f, 0xc1, 0x92, 0xee, 0x2d, 0x68, 0x1e, 0x36, 0x47, 0x86, 0xa6, 0xdb, 0x7c,
+                0x1d, 0x69, 0x1a, 0xb8, 0xb0, 0x38, 0x2b, 0x60, 0xa2, 0x37, 0x8b, 0xd7, 0xd4, 0xd6,
+                0x31, 0xd8,
+            ],
+            tag: [
+                0xba, 0x2d, 0xed, 0x46, 0xda, 0xca, 0x2b, 0xd2, 0xbb, 0xdb, 0x67, 0xe4, 0xa3, 0x36,
+                0x3e, 0x87,
+            ],
+        });
+    }
 }
 
 #[cfg(all(test, feature = "with-bench"))]
@@ -544,8 +708,8 @@ mod bench {
         let input = [1u8; 10];
         let aad = [3u8; 10];
         bh.iter(|| {
-            let mut cipher = ChaCha20Poly1305::new(&[0; 32], &[0; 8], &aad);
-            let mut decipher = ChaCha20Poly1305::new(&[0; 32], &[0; 8], &aad);
+            let mut cipher = ChaCha20Poly1305::new(&[0; 32], &[0; 12], &aad);
+            let mut decipher = ChaCha20Poly1305::new(&[0; 32], &[0; 12], &aad);
 
             let mut output = [0u8; 10];
             let mut tag = [0u8; 16];
@@ -561,8 +725,8 @@ mod bench {
         let input = [1u8; 1024];
         let aad = [3u8; 1024];
         bh.iter(|| {
-            let mut cipher = ChaCha20Poly1305::new(&[0; 32], &[0; 8], &aad);
-            let mut decipher = ChaCha20Poly1305::new(&[0; 32], &[0; 8], &aad);
+            let mut cipher = ChaCha20Poly1305::new(&[0; 32], &[0; 12], &aad);
+            let mut decipher = ChaCha20Poly1305::new(&[0; 32], &[0; 12], &aad);
 
             let mut output = [0u8; 1024];
             let mut tag = [0u8; 16];
@@ -579,8 +743,8 @@ mod bench {
         let input = [1u8; 65536];
         let aad = [3u8; 65536];
         bh.iter(|| {
-            let mut cipher = ChaCha20Poly1305::new(&[0; 32], &[0; 8], &aad);
-            let mut decipher = ChaCha20Poly1305::new(&[0; 32], &[0; 8], &aad);
+            let mut cipher = ChaCha20Poly1305::new(&[0; 32], &[0; 12], &aad);
+            let mut decipher = ChaCha20Poly1305::new(&[0; 32], &[0; 12], &aad);
 
             let mut output = [0u8; 65536];
             let mut tag = [0u8; 16];
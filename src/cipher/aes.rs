@@ -0,0 +1,447 @@
+//! AES - Advanced Encryption Standard
+//!
+//! Implementation of the AES-128 and AES-256 block ciphers of [FIPS 197][1], only
+//! in the encryption direction, which is the only one needed by the CTR based modes
+//! (CTR, GCM, CCM, SIV) and by CMAC.
+//!
+//! When the `aes` target feature is enabled at compile time, the hardware instructions
+//! are used on x86 / x86-64 (AES-NI) and on AArch64 (ARMv8 cryptography extensions).
+//!
+//! Otherwise a software implementation using lookup tables (T-tables) is used, which
+//! is not constant time: the memory accesses depend on the key and the data, and are
+//! observable with cache timing attacks by an attacker running on the same machine.
+//!
+//! # Examples
+//!
+//! ```
+//! use cryptoxide::cipher::aes::Aes128;
+//!
+//! let aes = Aes128::new(&[0u8; 16]);
+//! let mut block = [0u8; 16];
+//! aes.encrypt_block(&mut block);
+//! ```
+//!
+//! [1]: <https://csrc.nist.gov/publications/detail/fips/197/final>
+
+#[cfg(all(
+    any(target_arch = "x86", target_arch = "x86_64"),
+    target_feature = "aes",
+    target_feature = "sse2"
+))]
+mod aesni;
+
+#[cfg(all(target_arch = "aarch64", target_feature = "aes"))]
+mod armv8;
+
+use crate::secure_zero::Zeroize;
+
+/// Size of the AES block in bytes
+pub const BLOCK_SIZE: usize = 16;
+
+const SBOX: [u8; 256] = [
+    0x63, 0x7c, 0x77, 0x7b, 0xf2, 0x6b, 0x6f, 0xc5, 0x30, 0x01, 0x67, 0x2b, 0xfe, 0xd7, 0xab, 0x76,
+    0xca, 0x82, 0xc9, 0x7d, 0xfa, 0x59, 0x47, 0xf0, 0xad, 0xd4, 0xa2, 0xaf, 0x9c, 0xa4, 0x72, 0xc0,
+    0xb7, 0xfd, 0x93, 0x26, 0x36, 0x3f, 0xf7, 0xcc, 0x34, 0xa5, 0xe5, 0xf1, 0x71, 0xd8, 0x31, 0x15,
+    0x04, 0xc7, 0x23, 0xc3, 0x18, 0x96, 0x05, 0x9a, 0x07, 0x12, 0x80, 0xe2, 0xeb, 0x27, 0xb2, 0x75,
+    0x09, 0x83, 0x2c, 0x1a, 0x1b, 0x6e, 0x5a, 0xa0, 0x52, 0x3b, 0xd6, 0xb3, 0x29, 0xe3, 0x2f, 0x84,
+    0x53, 0xd1, 0x00, 0xed, 0x20, 0xfc, 0xb1, 0x5b, 0x6a, 0xcb, 0xbe, 0x39, 0x4a, 0x4c, 0x58, 0xcf,
+    0xd0, 0xef, 0xaa, 0xfb, 0x43, 0x4d, 0x33, 0x85, 0x45, 0xf9, 0x02, 0x7f, 0x50, 0x3c, 0x9f, 0xa8,
+    0x51, 0xa3, 0x40, 0x8f, 0x92, 0x9d, 0x38, 0xf5, 0xbc, 0xb6, 0xda, 0x21, 0x10, 0xff, 0xf3, 0xd2,
+    0xcd, 0x0c, 0x13, 0xec, 0x5f, 0x97, 0x44, 0x17, 0xc4, 0xa7, 0x7e, 0x3d, 0x64, 0x5d, 0x19, 0x73,
+    0x60, 0x81, 0x4f, 0xdc, 0x22, 0x2a, 0x90, 0x88, 0x46, 0xee, 0xb8, 0x14, 0xde, 0x5e, 0x0b, 0xdb,
+    0xe0, 0x32, 0x3a, 0x0a, 0x49, 0x06, 0x24, 0x5c, 0xc2, 0xd3, 0xac, 0x62, 0x91, 0x95, 0xe4, 0x79,
+    0xe7, 0xc8, 0x37, 0x6d, 0x8d, 0xd5, 0x4e, 0xa9, 0x6c, 0x56, 0xf4, 0xea, 0x65, 0x7a, 0xae, 0x08,
+    0xba, 0x78, 0x25, 0x2e, 0x1c, 0xa6, 0xb4, 0xc6, 0xe8, 0xdd, 0x74, 0x1f, 0x4b, 0xbd, 0x8b, 0x8a,
+    0x70, 0x3e, 0xb5, 0x66, 0x48, 0x03, 0xf6, 0x0e, 0x61, 0x35, 0x57, 0xb9, 0x86, 0xc1, 0x1d, 0x9e,
+    0xe1, 0xf8, 0x98, 0x11, 0x69, 0xd9, 0x8e, 0x94, 0x9b, 0x1e, 0x87, 0xe9, 0xce, 0x55, 0x28, 0xdf,
+    0x8c, 0xa1, 0x89, 0x0d, 0xbf, 0xe6, 0x42, 0x68, 0x41, 0x99, 0x2d, 0x0f, 0xb0, 0x54, 0xbb, 0x16,
+];
+
+const RCON: [u8; 10] = [0x01, 0x02, 0x04, 0x08, 0x10, 0x20, 0x40, 0x80, 0x1b, 0x36];
+
+// multiplication by x (i.e. 2) in GF(2^8) with the AES polynomial
+const fn xtime(x: u8) -> u8 {
+    (x << 1) ^ (((x >> 7) & 1) * 0x1b)
+}
+
+// T0[x] = (2.S[x], S[x], S[x], 3.S[x]) as big endian word, the other tables
+// being the rotations of T0
+const fn t_table() -> [u32; 256] {
+    let mut t = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let s = SBOX[i];
+        let s2 = xtime(s);
+        let s3 = s2 ^ s;
+        t[i] = ((s2 as u32) << 24) | ((s as u32) << 16) | ((s as u32) << 8) | (s3 as u32);
+        i += 1;
+    }
+    t
+}
+
+const T0: [u32; 256] = t_table();
+
+fn sub_word(w: u32) -> u32 {
+    let b = w.to_be_bytes();
+    u32::from_be_bytes([
+        SBOX[b[0] as usize],
+        SBOX[b[1] as usize],
+        SBOX[b[2] as usize],
+        SBOX[b[3] as usize],
+    ])
+}
+
+// expand the key into the round keys, stored as bytes in the same order as the FIPS 197
+// words, which is also the order expected by the hardware instructions
+fn expand_key(key: &[u8], round_keys: &mut [u8]) {
+    let nk = key.len() / 4;
+    let total = round_keys.len() / 4;
+    round_keys[0..key.len()].copy_from_slice(key);
+    for i in nk..total {
+        let mut temp = u32::from_be_bytes([
+            round_keys[4 * i - 4],
+            round_keys[4 * i - 3],
+            round_keys[4 * i - 2],
+            round_keys[4 * i - 1],
+        ]);
+        if i % nk == 0 {
+            temp = sub_word(temp.rotate_left(8)) ^ ((RCON[i / nk - 1] as u32) << 24);
+        } else if nk > 6 && i % nk == 4 {
+            temp = sub_word(temp);
+        }
+        for j in 0..4 {
+            round_keys[4 * i + j] = round_keys[4 * (i - nk) + j] ^ temp.to_be_bytes()[j];
+        }
+    }
+}
+
+fn round_key(round_keys: &[u8], i: usize) -> [u32; 4] {
+    let mut w = [0u32; 4];
+    for (j, w) in w.iter_mut().enumerate() {
+        let o = 16 * i + 4 * j;
+        *w = u32::from_be_bytes([
+            round_keys[o],
+            round_keys[o + 1],
+            round_keys[o + 2],
+            round_keys[o + 3],
+        ]);
+    }
+    w
+}
+
+// SubBytes, ShiftRows, MixColumns and AddRoundKey
+fn round_soft(s: &[u32; 4], rk: &[u32; 4]) -> [u32; 4] {
+    let mut t = [0u32; 4];
+    for j in 0..4 {
+        t[j] = T0[(s[j] >> 24) as usize]
+            ^ T0[((s[(j + 1) % 4] >> 16) & 0xff) as usize].rotate_right(8)
+            ^ T0[((s[(j + 2) % 4] >> 8) & 0xff) as usize].rotate_right(16)
+            ^ T0[(s[(j + 3) % 4] & 0xff) as usize].rotate_right(24)
+            ^ rk[j];
+    }
+    t
+}
+
+// software encryption with the T-tables
+fn encrypt_block_soft(round_keys: &[u8], block: &mut [u8; BLOCK_SIZE]) {
+    let rounds = round_keys.len() / 16 - 1;
+
+    let rk = round_key(round_keys, 0);
+    let mut s = [0u32; 4];
+    for (j, s) in s.iter_mut().enumerate() {
+        *s = u32::from_be_bytes([
+            block[4 * j],
+            block[4 * j + 1],
+            block[4 * j + 2],
+            block[4 * j + 3],
+        ]) ^ rk[j];
+    }
+
+    for r in 1..rounds {
+        s = round_soft(&s, &round_key(round_keys, r));
+    }
+
+    // last round without MixColumns
+    let rk = round_key(round_keys, rounds);
+    for j in 0..4 {
+        let w = u32::from_be_bytes([
+            SBOX[(s[j] >> 24) as usize],
+            SBOX[((s[(j + 1) % 4] >> 16) & 0xff) as usize],
+            SBOX[((s[(j + 2) % 4] >> 8) & 0xff) as usize],
+            SBOX[(s[(j + 3) % 4] & 0xff) as usize],
+        ]) ^ rk[j];
+        block[4 * j..4 * j + 4].copy_from_slice(&w.to_be_bytes());
+    }
+}
+
+fn encrypt_block(round_keys: &[u8], block: &mut [u8; BLOCK_SIZE]) {
+    #[cfg(all(
+        any(target_arch = "x86", target_arch = "x86_64"),
+        target_feature = "aes",
+        target_feature = "sse2"
+    ))]
+    {
+        return aesni::encrypt_block(round_keys, block);
+    }
+
+    #[cfg(all(target_arch = "aarch64", target_feature = "aes"))]
+    {
+        return armv8::encrypt_block(round_keys, block);
+    }
+
+    #[allow(unreachable_code)]
+    encrypt_block_soft(round_keys, block)
+}
+
+/// One AES round (SubBytes, ShiftRows, MixColumns and AddRoundKey) applied in place
+///
+/// This is the building block of the constructions using the AES round function
+/// directly, like AEGIS, and correspond to the `AESENC` instruction.
+#[cfg_attr(not(feature = "aegis"), allow(dead_code))]
+pub(crate) fn round(block: &mut [u8; BLOCK_SIZE], key: &[u8; BLOCK_SIZE]) {
+    #[cfg(all(
+        any(target_arch = "x86", target_arch = "x86_64"),
+        target_feature = "aes",
+        target_feature = "sse2"
+    ))]
+    {
+        return aesni::round(block, key);
+    }
+
+    #[cfg(all(target_arch = "aarch64", target_feature = "aes"))]
+    {
+        return armv8::round(block, key);
+    }
+
+    #[allow(unreachable_code)]
+    {
+        let s = round_key(block, 0);
+        let t = round_soft(&s, &round_key(key, 0));
+        for (j, w) in t.iter().enumerate() {
+            block[4 * j..4 * j + 4].copy_from_slice(&w.to_be_bytes());
+        }
+    }
+}
+
+macro_rules! aes_impl {
+    ($name:ident, $key_size:literal, $round_keys_size:literal, $doc:literal) => {
+        #[doc = $doc]
+        ///
+        /// Holds the expanded round keys, which are zeroed on drop.
+        #[derive(Clone)]
+        pub struct $name([u8; $round_keys_size]);
+
+        impl $name {
+            #[doc = concat!("Size of the key in bytes (", $key_size, ")")]
+            pub const KEY_SIZE: usize = $key_size;
+
+            /// Create a new cipher instance by expanding the key
+            ///
+            /// # Panics
+            ///
+            #[doc = concat!("Panics if the key is not ", $key_size, " bytes")]
+            pub fn new(key: &[u8]) -> Self {
+                assert_eq!(key.len(), $key_size);
+                let mut round_keys = [0u8; $round_keys_size];
+                expand_key(key, &mut round_keys);
+                $name(round_keys)
+            }
+
+            /// Encrypt a single block in place
+            pub fn encrypt_block(&self, block: &mut [u8; BLOCK_SIZE]) {
+                encrypt_block(&self.0, block)
+            }
+        }
+
+        impl Drop for $name {
+            fn drop(&mut self) {
+                self.0.zeroize();
+            }
+        }
+    };
+}
+
+aes_impl!(Aes128, 16, 176, "AES-128 block cipher (10 rounds)");
+aes_impl!(Aes256, 32, 240, "AES-256 block cipher (14 rounds)");
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Test {
+        key: &'static [u8],
+        plaintext: [u8; 16],
+        ciphertext: [u8; 16],
+    }
+
+    fn encrypt(key: &[u8], block: &mut [u8; 16]) {
+        if key.len() == 16 {
+            Aes128::new(key).encrypt_block(block)
+        } else {
+            Aes256::new(key).encrypt_block(block)
+        }
+    }
+
+    // the S-box is the multiplicative inverse in GF(2^8) followed by an affine transformation
+    #[test]
+    fn sbox() {
+        fn gmul(mut a: u8, mut b: u8) -> u8 {
+            let mut r = 0;
+            while b != 0 {
+                if b & 1 == 1 {
+                    r ^= a;
+                }
+                a = xtime(a);
+                b >>= 1;
+            }
+            r
+        }
+        for a in 0..=255u8 {
+            let inv = (1..=255u8).find(|b| gmul(a, *b) == 1).unwrap_or(0);
+            let s = inv
+                ^ inv.rotate_left(1)
+                ^ inv.rotate_left(2)
+                ^ inv.rotate_left(3)
+                ^ inv.rotate_left(4)
+                ^ 0x63;
+            assert_eq!(SBOX[a as usize], s, "{}", a);
+        }
+    }
+
+    #[test]
+    fn key_expansion() {
+        // FIPS 197 appendix A.1 and A.3, last round key
+        let aes = Aes128::new(&[
+            0x2b, 0x7e, 0x15, 0x16, 0x28, 0xae, 0xd2, 0xa6, 0xab, 0xf7, 0x15, 0x88, 0x09, 0xcf,
+            0x4f, 0x3c,
+        ]);
+        assert_eq!(
+            aes.0[160..176],
+            [
+                0xd0, 0x14, 0xf9, 0xa8, 0xc9, 0xee, 0x25, 0x89, 0xe1, 0x3f, 0x0c, 0xc8, 0xb6, 0x63,
+                0x0c, 0xa6
+            ]
+        );
+        let aes = Aes256::new(&[
+            0x60, 0x3d, 0xeb, 0x10, 0x15, 0xca, 0x71, 0xbe, 0x2b, 0x73, 0xae, 0xf0, 0x85, 0x7d,
+            0x77, 0x81, 0x1f, 0x35, 0x2c, 0x07, 0x3b, 0x61, 0x08, 0xd7, 0x2d, 0x98, 0x10, 0xa3,
+            0x09, 0x14, 0xdf, 0xf4,
+        ]);
+        assert_eq!(
+            aes.0[224..240],
+            [
+                0xfe, 0x48, 0x90, 0xd1, 0xe6, 0x18, 0x8d, 0x0b, 0x04, 0x6d, 0xf3, 0x44, 0x70, 0x6c,
+                0x63, 0x1e
+            ]
+        );
+    }
+
+    #[test]
+    fn vectors() {
+        let tests = [
+            // FIPS 197 appendix B
+            Test {
+                key: &[
+                    0x2b, 0x7e, 0x15, 0x16, 0x28, 0xae, 0xd2, 0xa6, 0xab, 0xf7, 0x15, 0x88, 0x09,
+                    0xcf, 0x4f, 0x3c,
+                ],
+                plaintext: [
+                    0x32, 0x43, 0xf6, 0xa8, 0x88, 0x5a, 0x30, 0x8d, 0x31, 0x31, 0x98, 0xa2, 0xe0,
+                    0x37, 0x07, 0x34,
+                ],
+                ciphertext: [
+                    0x39, 0x25, 0x84, 0x1d, 0x02, 0xdc, 0x09, 0xfb, 0xdc, 0x11, 0x85, 0x97, 0x19,
+                    0x6a, 0x0b, 0x32,
+                ],
+            },
+            // FIPS 197 appendix C.1
+            Test {
+                key: &[
+                    0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c,
+                    0x0d, 0x0e, 0x0f,
+                ],
+                plaintext: [
+                    0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88, 0x99, 0xaa, 0xbb, 0xcc,
+                    0xdd, 0xee, 0xff,
+                ],
+                ciphertext: [
+                    0x69, 0xc4, 0xe0, 0xd8, 0x6a, 0x7b, 0x04, 0x30, 0xd8, 0xcd, 0xb7, 0x80, 0x70,
+                    0xb4, 0xc5, 0x5a,
+                ],
+            },
+            // FIPS 197 appendix C.3
+            Test {
+                key: &[
+                    0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c,
+                    0x0d, 0x0e, 0x0f, 0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17, 0x18, 0x19,
+                    0x1a, 0x1b, 0x1c, 0x1d, 0x1e, 0x1f,
+                ],
+                plaintext: [
+                    0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88, 0x99, 0xaa, 0xbb, 0xcc,
+                    0xdd, 0xee, 0xff,
+                ],
+                ciphertext: [
+                    0x8e, 0xa2, 0xb7, 0xca, 0x51, 0x67, 0x45, 0xbf, 0xea, 0xfc, 0x49, 0x90, 0x4b,
+                    0x49, 0x60, 0x89,
+                ],
+            },
+            // NIST SP 800-38A F.1.5, first block
+            Test {
+                key: &[
+                    0x60, 0x3d, 0xeb, 0x10, 0x15, 0xca, 0x71, 0xbe, 0x2b, 0x73, 0xae, 0xf0, 0x85,
+                    0x7d, 0x77, 0x81, 0x1f, 0x35, 0x2c, 0x07, 0x3b, 0x61, 0x08, 0xd7, 0x2d, 0x98,
+                    0x10, 0xa3, 0x09, 0x14, 0xdf, 0xf4,
+                ],
+                plaintext: [
+                    0x6b, 0xc1, 0xbe, 0xe2, 0x2e, 0x40, 0x9f, 0x96, 0xe9, 0x3d, 0x7e, 0x11, 0x73,
+                    0x93, 0x17, 0x2a,
+                ],
+                ciphertext: [
+                    0xf3, 0xee, 0xd1, 0xbd, 0xb5, 0xd2, 0xa0, 0x3c, 0x06, 0x4b, 0x5a, 0x7e, 0x3d,
+                    0xb1, 0x81, 0xf8,
+                ],
+            },
+        ];
+
+        for (i, t) in tests.iter().enumerate() {
+            let mut block = t.plaintext;
+            encrypt(t.key, &mut block);
+            assert_eq!(block, t.ciphertext, "{}", i);
+
+            // the software implementation, whatever the implementation selected
+            let mut round_keys = [0u8; 240];
+            let round_keys = &mut round_keys[..(t.key.len() / 4 + 7) * 16];
+            expand_key(t.key, round_keys);
+            let mut block = t.plaintext;
+            encrypt_block_soft(round_keys, &mut block);
+            assert_eq!(block, t.ciphertext, "soft {}", i);
+        }
+    }
+
+    #[test]
+    fn single_round() {
+        // FIPS 197 appendix B, state at the start of round 1 and 2, with the round 1 key
+        let mut block = [
+            0x19, 0x3d, 0xe3, 0xbe, 0xa0, 0xf4, 0xe2, 0x2b, 0x9a, 0xc6, 0x8d, 0x2a, 0xe9, 0xf8,
+            0x48, 0x08,
+        ];
+        let round_key = [
+            0xa0, 0xfa, 0xfe, 0x17, 0x88, 0x54, 0x2c, 0xb1, 0x23, 0xa3, 0x39, 0x39, 0x2a, 0x6c,
+            0x76, 0x05,
+        ];
+        round(&mut block, &round_key);
+        assert_eq!(
+            block,
+            [
+                0xa4, 0x9c, 0x7f, 0xf2, 0x68, 0x9f, 0x35, 0x2b, 0x6b, 0x5b, 0xea, 0x43, 0x02, 0x6a,
+                0x50, 0x49
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn invalid_key_length() {
+        Aes128::new(&[0u8; 32]);
+    }
+}
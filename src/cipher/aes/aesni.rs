@@ -0,0 +1,34 @@
+// AES-NI implementation for x86 / x86-64
+
+#[cfg(target_arch = "x86")]
+use core::arch::x86::*;
+
+#[cfg(target_arch = "x86_64")]
+use core::arch::x86_64::*;
+
+use super::BLOCK_SIZE;
+
+#[inline]
+fn load(bytes: &[u8]) -> __m128i {
+    debug_assert!(bytes.len() >= 16);
+    unsafe { _mm_loadu_si128(bytes.as_ptr() as *const __m128i) }
+}
+
+pub(super) fn encrypt_block(round_keys: &[u8], block: &mut [u8; BLOCK_SIZE]) {
+    let rounds = round_keys.len() / 16 - 1;
+    unsafe {
+        let mut state = _mm_xor_si128(load(block), load(&round_keys[0..16]));
+        for r in 1..rounds {
+            state = _mm_aesenc_si128(state, load(&round_keys[16 * r..16 * r + 16]));
+        }
+        state = _mm_aesenclast_si128(state, load(&round_keys[16 * rounds..16 * rounds + 16]));
+        _mm_storeu_si128(block.as_mut_ptr() as *mut __m128i, state);
+    }
+}
+
+pub(super) fn round(block: &mut [u8; BLOCK_SIZE], key: &[u8; BLOCK_SIZE]) {
+    unsafe {
+        let state = _mm_aesenc_si128(load(block), load(key));
+        _mm_storeu_si128(block.as_mut_ptr() as *mut __m128i, state);
+    }
+}
@@ -0,0 +1,28 @@
+// AES implementation for AArch64 with the ARMv8 cryptography extensions
+//
+// AESE performs AddRoundKey, SubBytes and ShiftRows, and AESMC performs MixColumns,
+// so the last round key is added separately.
+
+use core::arch::aarch64::*;
+
+use super::BLOCK_SIZE;
+
+pub(super) fn encrypt_block(round_keys: &[u8], block: &mut [u8; BLOCK_SIZE]) {
+    let rounds = round_keys.len() / 16 - 1;
+    unsafe {
+        let mut state = vld1q_u8(block.as_ptr());
+        for r in 0..rounds - 1 {
+            state = vaesmcq_u8(vaeseq_u8(state, vld1q_u8(round_keys[16 * r..].as_ptr())));
+        }
+        state = vaeseq_u8(state, vld1q_u8(round_keys[16 * (rounds - 1)..].as_ptr()));
+        state = veorq_u8(state, vld1q_u8(round_keys[16 * rounds..].as_ptr()));
+        vst1q_u8(block.as_mut_ptr(), state);
+    }
+}
+
+pub(super) fn round(block: &mut [u8; BLOCK_SIZE], key: &[u8; BLOCK_SIZE]) {
+    unsafe {
+        let state = vaesmcq_u8(vaeseq_u8(vld1q_u8(block.as_ptr()), vdupq_n_u8(0)));
+        vst1q_u8(block.as_mut_ptr(), veorq_u8(state, vld1q_u8(key.as_ptr())));
+    }
+}
@@ -0,0 +1,7 @@
+//! Block ciphers
+//!
+//! Block ciphers are the building blocks of modes of operation (e.g. CTR, GCM, CCM, SIV)
+//! and of some MACs (e.g. CMAC, GMAC), and should not be used directly to encrypt data.
+
+#[cfg(feature = "aes")]
+pub mod aes;
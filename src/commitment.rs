@@ -0,0 +1,161 @@
+//! Pedersen commitment scheme over the Ed25519 curve
+//!
+//! A commitment to the value `v` with the blinding factor `r` is the point
+//! `C = r*G + v*H`, where G is the Ed25519 base point and H another generator.
+//!
+//! The commitment is perfectly hiding: when r is uniformly random, C is uniformly
+//! distributed and reveals nothing of v. It is computationally binding: opening C to
+//! a different value requires knowing the discrete logarithm of H with regard to G.
+//! This is why H needs to be independent of G: it is derived by hashing the string
+//! `"H"` to a point, so that nobody knows its discrete logarithm.
+//!
+//! Commitments are additively homomorphic: the sum of the commitments to `v1` and `v2`
+//! with blinding factors `r1` and `r2` is a commitment to `v1 + v2` with `r1 + r2`.
+//!
+//! # Examples
+//!
+//! ```
+//! use cryptoxide::commitment::PedersenCommitment;
+//! use cryptoxide::curve25519::Scalar;
+//!
+//! let value = Scalar::from_bytes(&[42u8; 32]);
+//! let randomness = Scalar::from_uniform_bytes(&[1u8; 64]); // should be random
+//!
+//! let commitment = PedersenCommitment::commit(&value, &randomness);
+//! assert!(PedersenCommitment::open(&commitment, &value, &randomness));
+//! ```
+
+use crate::curve25519::{ge_add, ge_decompress, Ge, Scalar};
+
+/// The generator H, the first valid point of the sequence SHA512("H" || ctr) (with the
+/// counter as 1 byte), taking the 32 first bytes of the hash and multiplying by the cofactor
+const H: [u8; 32] = [
+    0xcf, 0x7d, 0x45, 0xd5, 0x62, 0xf2, 0x0a, 0x1f, 0x46, 0xc0, 0xd3, 0x74, 0x7e, 0x8c, 0x63, 0x2d,
+    0x6e, 0x15, 0x1d, 0xdf, 0x24, 0x05, 0x6f, 0x47, 0xc2, 0xfa, 0x38, 0x12, 0xa6, 0xc4, 0x5e, 0xe1,
+];
+
+/// Pedersen commitment scheme with the generators G (Ed25519 base point) and H
+pub struct PedersenCommitment;
+
+impl PedersenCommitment {
+    /// The generator H
+    pub fn generator_h() -> Ge {
+        ge_decompress(&H).unwrap()
+    }
+
+    /// Commit to the value with the randomness (blinding factor): `r*G + v*H`
+    pub fn commit(value: &Scalar, randomness: &Scalar) -> Ge {
+        let rg = Ge::scalarmult_base(randomness);
+        let vh = Self::generator_h().scalarmult(value);
+        ge_add(&rg, &vh)
+    }
+
+    /// Verify that the commitment opens to the value with the randomness
+    pub fn open(commitment: &Ge, value: &Scalar, randomness: &Scalar) -> bool {
+        Self::commit(value, randomness).to_bytes() == commitment.to_bytes()
+    }
+
+    /// Add two commitments, giving the commitment to the sum of the values with
+    /// the sum of the randomness
+    pub fn add_commitments(a: &Ge, b: &Ge) -> Ge {
+        ge_add(a, b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curve25519::scalar;
+    use crate::hashing::sha2::Sha512;
+    use core::convert::TryFrom;
+
+    struct Test {
+        value: [u8; 32],
+        randomness: [u8; 32],
+        commitment: [u8; 32],
+    }
+
+    // hash the string to a point in the prime order subgroup, by try and increment
+    fn hash_to_point(s: &[u8]) -> Ge {
+        for ctr in 0..=255u8 {
+            let hash = Sha512::new().update(s).update(&[ctr]).finalize();
+            if let Some(p) = ge_decompress(<&[u8; 32]>::try_from(&hash[0..32]).unwrap()) {
+                return p.double().double().double();
+            }
+        }
+        unreachable!()
+    }
+
+    fn scalar_u64(v: u64) -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+        bytes[0..8].copy_from_slice(&v.to_le_bytes());
+        bytes
+    }
+
+    #[test]
+    fn generator() {
+        assert_eq!(hash_to_point(b"H").to_bytes(), H);
+        assert_ne!(H, Ge::scalarmult_base(&Scalar::ONE).to_bytes());
+    }
+
+    #[test]
+    fn vectors() {
+        // generated with an independent python implementation
+        let tests = [
+            Test {
+                value: scalar_u64(42),
+                randomness: [0x07; 32],
+                commitment: [
+                    0xae, 0xaa, 0xaf, 0x8a, 0x1b, 0xa0, 0x97, 0x2c, 0xe0, 0x30, 0x33, 0x6e, 0xb9,
+                    0xb9, 0x10, 0xa0, 0x01, 0x02, 0x5c, 0x15, 0x74, 0x42, 0x62, 0x94, 0x50, 0xde,
+                    0x72, 0x5c, 0x15, 0x35, 0x8a, 0xd0,
+                ],
+            },
+            Test {
+                value: scalar_u64(1000000),
+                randomness: [
+                    0x13, 0x2d, 0x0c, 0xa6, 0xe9, 0xa1, 0xf3, 0xae, 0x31, 0x6c, 0x12, 0x68, 0x2d,
+                    0x13, 0x2f, 0xfa, 0x0f, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17, 0x18, 0x19,
+                    0x1a, 0x1b, 0x1c, 0x1d, 0x1e, 0x0f,
+                ],
+                commitment: [
+                    0x71, 0x4f, 0x5a, 0xcd, 0xfa, 0xa1, 0x08, 0x20, 0x19, 0xff, 0xaa, 0xa2, 0x09,
+                    0xed, 0xac, 0x90, 0xd2, 0xa7, 0x70, 0xe7, 0x17, 0x10, 0xb0, 0x50, 0xc7, 0x37,
+                    0x3b, 0x81, 0x7d, 0x89, 0xb0, 0x2e,
+                ],
+            },
+        ];
+
+        for (i, t) in tests.iter().enumerate() {
+            let value = Scalar::from_bytes(&t.value);
+            let randomness = Scalar::from_bytes(&t.randomness);
+            let c = PedersenCommitment::commit(&value, &randomness);
+            assert_eq!(c.to_bytes(), t.commitment, "commit {}", i);
+            assert!(
+                PedersenCommitment::open(&c, &value, &randomness),
+                "open {}",
+                i
+            );
+
+            let other_value = Scalar::from_bytes(&scalar_u64(43));
+            assert!(!PedersenCommitment::open(&c, &other_value, &randomness));
+            assert!(!PedersenCommitment::open(&c, &value, &other_value));
+        }
+    }
+
+    #[test]
+    fn homomorphic() {
+        let v1 = Scalar::from_bytes(&scalar_u64(20));
+        let v2 = Scalar::from_bytes(&scalar_u64(22));
+        let r1 = Scalar::from_uniform_bytes(&[1u8; 64]);
+        let r2 = Scalar::from_uniform_bytes(&[2u8; 64]);
+
+        let c = PedersenCommitment::add_commitments(
+            &PedersenCommitment::commit(&v1, &r1),
+            &PedersenCommitment::commit(&v2, &r2),
+        );
+        let v = Scalar::from_bytes(&scalar_u64(42));
+        let r = scalar::muladd(&Scalar::ONE, &r1, &r2);
+        assert!(PedersenCommitment::open(&c, &v, &r));
+    }
+}
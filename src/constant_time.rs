@@ -14,6 +14,12 @@
 //!            this was initially called CtBool but aligned to other implementation.
 //! * CtOption : Constant time Option type.
 //!
+//! And bulk operations on byte buffers:
+//!
+//! * ct_select : constant time selection between two byte arrays
+//! * ct_copy : copy that is not elided or specialised by the compiler
+//! * ct_xor : xor of two byte slices
+//!
 //! Great care has been done to make operation constant so that it's useful in
 //! cryptographic context, but we're not protected from implementation bug,
 //! compiler optimisations, gamma rays and other Moon-Mars alignments.
@@ -21,6 +27,9 @@
 //! The general functionality would be a great addition to the rust core library
 //! to have those type of things built-in and crucially more eyeballs.
 
+use core::ptr;
+use core::sync::atomic::{compiler_fence, Ordering};
+
 /// Constant time boolean
 ///
 /// This implementation uses a u64 under the hood, but it's never exposed
@@ -375,26 +384,69 @@ pub(crate) fn ct_array32_maybe_swap_with<const N: usize>(
 }
 
 #[allow(unused)]
-pub(crate) fn ct_array64_maybe_set<const N: usize>(a: &mut [u64; N], b: &[u64; N], swap: Choice) {
-    let mut tmp = [0; N];
-    let mask = swap.0.wrapping_neg(); // 0 | -1
-    for (xo, (xa, xb)) in tmp.iter_mut().zip(a.iter().zip(b.iter())) {
-        *xo = (*xa ^ *xb) & mask; // 0 if mask is 0 or xa^xb
-    }
-    for (xa, xo) in a.iter_mut().zip(tmp.iter()) {
-        *xa ^= xo;
-    }
+pub(crate) fn ct_array64_select<const N: usize>(
+    a: &[u64; N],
+    b: &[u64; N],
+    choice: Choice,
+) -> [u64; N] {
+    let mut out = [0; N];
+    let mask = choice.0.wrapping_neg(); // 0 | -1
+    for (xo, (xa, xb)) in out.iter_mut().zip(a.iter().zip(b.iter())) {
+        *xo = *xa ^ ((*xa ^ *xb) & mask); // xa if mask is 0 or xb
+    }
+    out
 }
 
 #[allow(unused)]
-pub(crate) fn ct_array32_maybe_set<const N: usize>(a: &mut [i32; N], b: &[i32; N], swap: Choice) {
-    let mut tmp = [0; N];
-    let mask = (swap.0 as u32).wrapping_neg(); // 0 | -1
-    for (xo, (xa, xb)) in tmp.iter_mut().zip(a.iter().zip(b.iter())) {
-        *xo = (*xa ^ *xb) & (mask as i32); // 0 if mask is 0 or xa^xb
+pub(crate) fn ct_array32_select<const N: usize>(
+    a: &[i32; N],
+    b: &[i32; N],
+    choice: Choice,
+) -> [i32; N] {
+    let mut out = [0; N];
+    let mask = (choice.0 as u32).wrapping_neg(); // 0 | -1
+    for (xo, (xa, xb)) in out.iter_mut().zip(a.iter().zip(b.iter())) {
+        *xo = *xa ^ ((*xa ^ *xb) & (mask as i32)); // xa if mask is 0 or xb
+    }
+    out
+}
+
+/// Select in constant time between two byte arrays
+///
+/// Returns `a` if `choice` is false and `b` if `choice` is true
+pub fn ct_select<const N: usize>(a: &[u8; N], b: &[u8; N], choice: Choice) -> [u8; N] {
+    let mut out = [0; N];
+    let mask = (choice.0 as u8).wrapping_neg(); // 0 | -1
+    for (xo, (xa, xb)) in out.iter_mut().zip(a.iter().zip(b.iter())) {
+        *xo = *xa ^ ((*xa ^ *xb) & mask); // xa if mask is 0 or xb
+    }
+    out
+}
+
+/// Copy `src` into `dst`, with volatile writes so that the copy is never elided
+/// or specialised by the compiler
+///
+/// # Panics
+///
+/// Panics if `dst` and `src` don't have the same length
+pub fn ct_copy(dst: &mut [u8], src: &[u8]) {
+    assert_eq!(dst.len(), src.len());
+    for (d, s) in dst.iter_mut().zip(src.iter()) {
+        unsafe { ptr::write_volatile(d, *s) }
     }
-    for (xa, xo) in a.iter_mut().zip(tmp.iter()) {
-        *xa ^= xo;
+    compiler_fence(Ordering::SeqCst);
+}
+
+/// Write `a` xor `b` into `out`, in time that only depends on the length
+///
+/// # Panics
+///
+/// Panics if `a`, `b` and `out` don't have the same length
+pub fn ct_xor(a: &[u8], b: &[u8], out: &mut [u8]) {
+    assert_eq!(a.len(), b.len());
+    assert_eq!(a.len(), out.len());
+    for (o, (x, y)) in out.iter_mut().zip(a.iter().zip(b.iter())) {
+        *o = x ^ y;
     }
 }
 
@@ -430,4 +482,25 @@ mod tests {
         let a: [u8; 4] = [0u8, 1, 2, 3];
         assert_eq!(<&[u8; 4]>::ct_lt(&a, &[1, 1, 2, 3]).is_true(), true);
     }
+
+    #[test]
+    fn bulk_operations() {
+        let a = [1u8, 2, 3, 4];
+        let b = [0xf0u8, 0x0f, 0xff, 0x00];
+        assert_eq!(ct_select(&a, &b, 0u64.ct_nonzero()), a);
+        assert_eq!(ct_select(&a, &b, 1u64.ct_nonzero()), b);
+
+        let mut out = [0u8; 4];
+        ct_xor(&a, &b, &mut out);
+        assert_eq!(out, [0xf1, 0x0d, 0xfc, 0x04]);
+
+        ct_copy(&mut out, &a);
+        assert_eq!(out, a);
+    }
+
+    #[test]
+    #[should_panic]
+    fn ct_xor_length_mismatch() {
+        ct_xor(&[0u8; 4], &[0u8; 3], &mut [0u8; 4]);
+    }
 }
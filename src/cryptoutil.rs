@@ -75,6 +75,19 @@ pub fn read_u32_le(input: &[u8]) -> u32 {
     u32::from_le_bytes(tmp)
 }
 
+/// Read the value of a vector of bytes as a u64 value in little-endian format.
+#[cfg(any(
+    feature = "siphash",
+    all(
+        feature = "poly1305",
+        not(any(target_arch = "arm", feature = "force-32bits"))
+    )
+))]
+pub fn read_u64_le(input: &[u8]) -> u64 {
+    let tmp: [u8; 8] = *<&[u8; 8]>::try_from(input).unwrap();
+    u64::from_le_bytes(tmp)
+}
+
 /*
 /// Read the value of a vector of bytes as a u32 value in big-endian format.
 pub fn read_u32_be(input: &[u8]) -> u32 {
@@ -172,6 +185,12 @@ impl<const N: usize> FixedBuffer<N> {
         self.buffer_idx = 0;
     }
 
+    /// Return the bytes currently buffered
+    #[allow(dead_code)]
+    pub fn buffered(&self) -> &[u8] {
+        &self.buffer[..self.buffer_idx]
+    }
+
     fn zero_until(&mut self, idx: usize) {
         assert!(idx >= self.buffer_idx);
         zero(&mut self.buffer[self.buffer_idx..idx]);
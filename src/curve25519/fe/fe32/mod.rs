@@ -1,4 +1,4 @@
-use crate::constant_time::{ct_array32_maybe_set, ct_array32_maybe_swap_with, Choice, CtEqual};
+use crate::constant_time::{ct_array32_maybe_swap_with, ct_array32_select, Choice, CtEqual};
 use core::cmp::{Eq, PartialEq};
 use core::ops::{Add, Mul, Neg, Sub};
 
@@ -14,26 +14,41 @@ use super::load::{load_3i, load_4i};
 #[derive(Clone)]
 pub struct Fe(pub(crate) [i32; 10]);
 
+impl CtEqual for &Fe {
+    fn ct_eq(self, other: Self) -> Choice {
+        self.to_bytes().ct_eq(&other.to_bytes())
+    }
+    fn ct_ne(self, other: Self) -> Choice {
+        self.ct_eq(other).negate()
+    }
+}
 impl PartialEq for Fe {
     fn eq(&self, other: &Fe) -> bool {
-        let &Fe(self_elems) = self;
-        let &Fe(other_elems) = other;
-        self_elems == other_elems
+        self.ct_eq(other).is_true()
     }
 }
 impl Eq for Fe {}
 
 impl Fe {
+    /// Field Element constant representing 0
     pub const ZERO: Fe = Fe([0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+    /// Field Element constant representing 1
     pub const ONE: Fe = Fe([1, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+    /// Field Element constant representing 2
+    pub const TWO: Fe = Fe([2, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+    /// Field Element constant representing -1
+    pub const MINUS_ONE: Fe = Fe([-1, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+    /// Field Element constant for Sqrt(-1)
     pub const SQRTM1: Fe = Fe([
         -32595792, -7943725, 9377950, 3500415, 12389472, -272473, -25146209, -2005654, 326686,
         11406482,
     ]);
+    /// Field element constant for D
     pub const D: Fe = Fe([
         -10913610, 13857413, -15372611, 6949391, 114729, -8787816, -6275908, -3247719, -18696448,
         -12055116,
     ]);
+    /// Field element constant for D2
     pub const D2: Fe = Fe([
         -21827239, -5839606, -30745221, 13898782, 229458, 15978800, -12551817, -6495438, 29715968,
         9444199,
@@ -373,6 +388,12 @@ impl Neg for &Fe {
 }
 
 impl Fe {
+    /// Create the Field Element from its little-endian byte representation (256 bits)
+    ///
+    /// Note that it doesn't verify that the bytes
+    /// are actually representing an element in the
+    /// range of the field, but will automatically wrap
+    /// the bytes to be in the range
     #[rustfmt::skip]
     pub fn from_bytes(s: &[u8; 32]) -> Fe {
         let mut h0 = load_4i(&s[0..4]);
@@ -427,6 +448,9 @@ impl Fe {
       so floor(2^(-255)(h + 19 2^(-25) h9 + 2^(-1))) = q.
     */
 
+    /// Represent the Field Element as little-endian canonical bytes (256 bits)
+    ///
+    /// Due to the field size, it's guarantee that the highest bit is always 0
     #[rustfmt::skip]
     pub fn to_bytes(&self) -> [u8; 32] {
         let Fe([mut h0, mut h1, mut h2, mut h3, mut h4, mut h5, mut h6, mut h7, mut h8, mut h9]) = *self;
@@ -506,8 +530,11 @@ impl Fe {
         ct_array32_maybe_swap_with(&mut self.0, &mut rhs.0, do_swap);
     }
 
-    pub(crate) fn maybe_set(&mut self, rhs: &Fe, do_swap: Choice) {
-        ct_array32_maybe_set(&mut self.0, &rhs.0, do_swap);
+    /// Select in constant time between two field elements
+    ///
+    /// Returns `a` if `choice` is false and `b` if `choice` is true
+    pub fn conditional_select(a: &Fe, b: &Fe, choice: Choice) -> Fe {
+        Fe(ct_array32_select(&a.0, &b.0, choice))
     }
 
     /*
@@ -521,6 +548,7 @@ impl Fe {
        |h| bounded by 1.1*2^25,1.1*2^24,1.1*2^25,1.1*2^24,etc.
     */
 
+    /// Multiply the field element by the small constant `S0`
     #[rustfmt::skip]
     pub const fn mul_small<const S0: u32>(&self) -> Fe {
         let &Fe(f) = self;
@@ -579,6 +607,7 @@ impl Fe {
     /*
     See fe_mul.c for discussion of implementation strategy.
     */
+    /// Compute the square of the field element
     #[rustfmt::skip]
     pub fn square(&self) -> Fe {
         let Fe([f0, f1, f2, f3, f4, f5, f6, f7, f8, f9]) = *self;
@@ -685,6 +714,9 @@ impl Fe {
             h5 as i32, h6 as i32, h7 as i32, h8 as i32, h9 as i32])
     }
 
+    /// Square the field element N times
+    ///
+    /// square_repeadtly(n) = ((X^2)^2)^2... = X^(2^N)
     pub fn square_repeatdly(&self, n: usize) -> Fe {
         let mut acc = self.square();
         for _ in 1..n {
@@ -693,7 +725,9 @@ impl Fe {
         acc
     }
 
-
+    /// Compute the square of the element and returns its double
+    ///
+    /// this is more efficient than squaring and adding the result together
     #[rustfmt::skip]
     pub fn square_and_double(&self) -> Fe {
         let Fe([f0, f1, f2, f3, f4, f5, f6, f7, f8, f9]) = *self;
@@ -821,10 +855,12 @@ impl Fe {
             h5 as i32, h6 as i32, h7 as i32, h8 as i32, h9 as i32])
     }
 
+    /// Check that the field element is non zero
     pub fn is_nonzero(&self) -> bool {
         CtEqual::ct_ne(&self.to_bytes(), &[0; 32]).into()
     }
 
+    /// Check that the field element is 'negative'
     pub fn is_negative(&self) -> bool {
         (self.to_bytes()[0] & 1) != 0
     }
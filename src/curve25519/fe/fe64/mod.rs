@@ -5,7 +5,7 @@
 //! * [ed25519-donna](https://github.com/floodyberry/ed25519-donna)
 //! * Sandy2x: New Curve25519 Speed Records
 
-use crate::constant_time::{ct_array64_maybe_set, ct_array64_maybe_swap_with, Choice, CtEqual};
+use crate::constant_time::{ct_array64_maybe_swap_with, ct_array64_select, Choice, CtEqual};
 use core::ops::{Add, Mul, Neg, Sub};
 
 pub mod precomp;
@@ -42,6 +42,18 @@ impl Fe {
     /// Field Element constant representing 1
     pub const ONE: Fe = Fe([1, 0, 0, 0, 0]);
 
+    /// Field Element constant representing 2
+    pub const TWO: Fe = Fe([2, 0, 0, 0, 0]);
+
+    /// Field Element constant representing -1 (2^255-20)
+    pub const MINUS_ONE: Fe = Fe([
+        0x7FFFFFFFFFFEC,
+        0x7FFFFFFFFFFFF,
+        0x7FFFFFFFFFFFF,
+        0x7FFFFFFFFFFFF,
+        0x7FFFFFFFFFFFF,
+    ]);
+
     /// Field Element constant for Sqrt(-1)
     pub const SQRTM1: Fe = Fe([
         0x61B274A0EA0B0,
@@ -403,8 +415,11 @@ impl Fe {
         ct_array64_maybe_swap_with(&mut self.0, &mut rhs.0, do_swap);
     }
 
-    pub(crate) fn maybe_set(&mut self, rhs: &Fe, do_swap: Choice) {
-        ct_array64_maybe_set(&mut self.0, &rhs.0, do_swap);
+    /// Select in constant time between two field elements
+    ///
+    /// Returns `a` if `choice` is false and `b` if `choice` is true
+    pub fn conditional_select(a: &Fe, b: &Fe, choice: Choice) -> Fe {
+        Fe(ct_array64_select(&a.0, &b.0, choice))
     }
 }
 
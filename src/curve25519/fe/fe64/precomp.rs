@@ -1,5 +1,3 @@
-// TODO to compute -- all initialized to ONE
-
 use super::super::super::ge::GePrecomp;
 use super::Fe;
 
@@ -24,6 +24,9 @@ pub use fe32::*;
 #[cfg(not(any(any(target_arch = "arm"), feature = "force-32bits")))]
 pub use fe64::*;
 
+use crate::constant_time::{Choice, CtEqual};
+use alloc::vec::Vec;
+
 impl Fe {
     /// Raise a field element to 2^255-23
     pub fn pow25523(&self) -> Fe {
@@ -83,11 +86,152 @@ impl Fe {
 
         z_255_21
     }
+
+    /// Invert all the field elements in place using Montgomery's trick
+    ///
+    /// This costs only 1 inversion and 3*(N-1) multiplications,
+    /// instead of N inversions when inverting each element individually.
+    ///
+    /// Like `invert`, all the elements to invert must be non 0
+    pub fn batch_invert(elements: &mut [Fe]) {
+        if elements.is_empty() {
+            return;
+        }
+
+        // prefixes[i] = elements[0] * ... * elements[i-1]
+        let mut prefixes = Vec::with_capacity(elements.len());
+        let mut acc = Fe::ONE;
+        for e in elements.iter() {
+            prefixes.push(acc.clone());
+            acc = &acc * e;
+        }
+
+        // acc = (elements[0] * ... * elements[i])^-1 while going backward
+        let mut acc = acc.invert();
+        for (e, prefix) in elements.iter_mut().zip(prefixes.iter()).rev() {
+            let inv = &acc * prefix;
+            acc = &acc * e;
+            *e = inv;
+        }
+    }
+
+    /// Check in constant time that two field elements represent the same value
+    fn ct_equal(&self, other: &Fe) -> Choice {
+        CtEqual::ct_eq(&self.to_bytes(), &other.to_bytes())
+    }
+
+    /// Replace the element by its negation if it's negative, in constant time
+    fn ct_abs(&self) -> Fe {
+        Fe::conditional_select(self, &-self, Choice(self.is_negative() as u64))
+    }
+
+    /// Check in constant time if the field element is a quadratic residue
+    ///
+    /// This computes the Legendre symbol `a^((p-1)/2)` which is 1 for non-zero
+    /// squares, -1 for non-squares and 0 for zero. Zero is considered a square.
+    pub fn is_square(&self) -> Choice {
+        // (p-1)/2 = 2^254 - 10 = 4 * (2^252 - 3) + 2
+        let legendre = &self.pow25523().square_repeatdly(2) * &self.square();
+        legendre.ct_equal(&Fe::ONE) | legendre.ct_equal(&Fe::ZERO)
+    }
+
+    /// Compute the square root of the field element
+    ///
+    /// Returns the non-negative square root if the element is a quadratic
+    /// residue (including zero), and `None` otherwise.
+    pub fn sqrt(&self) -> Option<Fe> {
+        let (was_square, r) = Fe::sqrt_ratio(self, &Fe::ONE);
+        if was_square.is_true() {
+            Some(r)
+        } else {
+            None
+        }
+    }
+
+    /// Compute the square root of the ratio `u/v` in constant time
+    ///
+    /// This is the `sqrt_ratio` function for `q = 5 (mod 8)` described in
+    /// [RFC9380 Appendix G](https://www.rfc-editor.org/rfc/rfc9380.html#appendix-G),
+    /// and avoids the need to compute the inversion of `v`.
+    ///
+    /// Returns `(Choice(true), sqrt(u/v))` if `u/v` is a square, and
+    /// `(Choice(false), sqrt(SQRTM1 * u/v))` otherwise. When `u` is zero,
+    /// returns `(Choice(true), 0)`, and when only `v` is zero returns `(Choice(false), 0)`.
+    ///
+    /// The returned root is always the non-negative one.
+    pub fn sqrt_ratio(u: &Fe, v: &Fe) -> (Choice, Fe) {
+        // r = (u * v^3) * (u * v^7)^((p-5)/8)
+        let v3 = &v.square() * v;
+        let v7 = &v3.square() * v;
+        let r = &(u * &v3) * &(u * &v7).pow25523();
+        let check = v * &r.square();
+
+        let neg_u = -u;
+        let correct_sign = check.ct_equal(u);
+        let flipped_sign = check.ct_equal(&neg_u);
+        let flipped_sign_i = check.ct_equal(&(&neg_u * &Fe::SQRTM1));
+
+        let r_prime = &r * &Fe::SQRTM1;
+        let r = Fe::conditional_select(&r, &r_prime, flipped_sign | flipped_sign_i);
+
+        (correct_sign | flipped_sign, r.ct_abs())
+    }
+}
+
+impl From<u64> for Fe {
+    fn from(v: u64) -> Fe {
+        let mut bytes = [0u8; 32];
+        bytes[0..8].copy_from_slice(&v.to_le_bytes());
+        Fe::from_bytes(&bytes)
+    }
+}
+
+impl From<i64> for Fe {
+    fn from(v: i64) -> Fe {
+        let abs = Fe::from(v.unsigned_abs());
+        if v < 0 {
+            -&abs
+        } else {
+            abs
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::curve25519::testrng::{GeneratorOf, GeneratorOf2, GeneratorOf3, GeneratorRaw};
+
+    fn next_fe(gen: &mut GeneratorRaw) -> Fe {
+        Fe::from_bytes(&gen.bytes())
+    }
+
+    #[test]
+    fn from_integers() {
+        let eq = |a: Fe, b: Fe| assert_eq!(a.to_bytes(), b.to_bytes());
+        eq(Fe::from(0u64), Fe::ZERO);
+        eq(Fe::from(1u64), Fe::ONE);
+        eq(Fe::from(2u64), Fe::TWO);
+        eq(Fe::from(-1i64), Fe::MINUS_ONE);
+        eq(&Fe::MINUS_ONE + &Fe::ONE, Fe::ZERO);
+        eq(&Fe::ONE + &Fe::ONE, Fe::TWO);
+
+        // p - 1 = 2^255 - 20
+        let mut minus_one = [0xffu8; 32];
+        minus_one[0] = 0xec;
+        minus_one[31] = 0x7f;
+        assert_eq!(Fe::MINUS_ONE.to_bytes(), minus_one);
+
+        let mut max = [0u8; 32];
+        max[0..8].copy_from_slice(&[0xff; 8]);
+        assert_eq!(Fe::from(u64::MAX).to_bytes(), max);
+        eq(
+            &Fe::from(u64::MAX) + &Fe::ONE,
+            Fe::from(1u64 << 32).square(),
+        );
+        eq(&Fe::from(i64::MIN) + &Fe::from(i64::MAX), Fe::MINUS_ONE);
+        eq(&Fe::from(-123456789i64) + &Fe::from(123456789u64), Fe::ZERO);
+    }
 
     #[test]
     fn bytes_serialization() {
@@ -137,6 +281,113 @@ mod tests {
         assert_eq!(Fe::ZERO.to_bytes(), (&Fe::ONE - &Fe::ONE).to_bytes());
     }
 
+    #[test]
+    fn conditional_select() {
+        let a = Fe::SQRTM1;
+        let b = Fe::D;
+        assert!(Fe::conditional_select(&a, &b, Choice(0)) == a);
+        assert!(Fe::conditional_select(&a, &b, Choice(1)) == b);
+    }
+
+    #[test]
+    fn batch_invert() {
+        let mut elements = Vec::new();
+        for i in 1..10u8 {
+            let mut bytes = [0u8; 32];
+            for (j, b) in bytes.iter_mut().enumerate() {
+                *b = (j as u8).wrapping_mul(37).wrapping_add(i.wrapping_mul(11));
+            }
+            elements.push(Fe::from_bytes(&bytes));
+        }
+        let mut inverted = elements.clone();
+        Fe::batch_invert(&mut inverted);
+        for (e, inv) in elements.iter().zip(inverted.iter()) {
+            assert!(&e.invert() == inv);
+        }
+
+        let mut single = [Fe::SQRTM1];
+        Fe::batch_invert(&mut single);
+        assert!(single[0] == Fe::SQRTM1.invert());
+
+        Fe::batch_invert(&mut []);
+    }
+
+    #[test]
+    fn is_square() {
+        assert!(Fe::ZERO.is_square().is_true());
+        assert!(Fe::ONE.is_square().is_true());
+        // -1 = SQRTM1^2 is a square, but SQRTM1 itself isn't
+        assert!(Fe::SQRTM1.square().is_square().is_true());
+        assert!(Fe::SQRTM1.is_square().is_false());
+
+        let two = &Fe::ONE + &Fe::ONE;
+        assert!(two.is_square().is_false());
+        assert!(two.square().is_square().is_true());
+        // non-square times non-square is a square
+        assert!((&two * &Fe::SQRTM1).is_square().is_true());
+
+        for i in 1..20u8 {
+            let mut bytes = [0u8; 32];
+            for (j, b) in bytes.iter_mut().enumerate() {
+                *b = (j as u8).wrapping_mul(29).wrapping_add(i.wrapping_mul(13));
+            }
+            let x = Fe::from_bytes(&bytes);
+            assert_eq!(x.is_square().is_true(), x.sqrt().is_some());
+        }
+    }
+
+    #[test]
+    fn sqrt() {
+        assert!(Fe::ZERO.sqrt() == Some(Fe::ZERO));
+        // 1 is odd and thus 'negative', so the non-negative root is -1
+        assert!(Fe::ONE.sqrt() == Some(-&Fe::ONE));
+
+        // -1 is a square in the field, with SQRTM1 or its negation as root
+        let minus_one = -&Fe::ONE;
+        let r = minus_one.sqrt().unwrap();
+        assert!(r.square() == minus_one);
+        assert!(!r.is_negative());
+
+        // 2 is not a square modulo 2^255-19
+        let two = &Fe::ONE + &Fe::ONE;
+        assert!(two.sqrt().is_none());
+
+        for i in 1..20u8 {
+            let mut bytes = [0u8; 32];
+            for (j, b) in bytes.iter_mut().enumerate() {
+                *b = (j as u8).wrapping_mul(31).wrapping_add(i.wrapping_mul(17));
+            }
+            let x = Fe::from_bytes(&bytes);
+            let x2 = x.square();
+            let r = x2.sqrt().unwrap();
+            assert!(r == x || r == -&x);
+            assert!(!r.is_negative());
+        }
+    }
+
+    #[test]
+    fn sqrt_ratio() {
+        let (was_square, r) = Fe::sqrt_ratio(&Fe::ZERO, &Fe::ZERO);
+        assert!(was_square.is_true());
+        assert!(r == Fe::ZERO);
+
+        let (was_square, r) = Fe::sqrt_ratio(&Fe::ONE, &Fe::ZERO);
+        assert!(was_square.is_false());
+        assert!(r == Fe::ZERO);
+
+        // 2/4 = 1/2 isn't a square
+        let two = &Fe::ONE + &Fe::ONE;
+        let four = two.square();
+        let (was_square, r) = Fe::sqrt_ratio(&two, &four);
+        assert!(was_square.is_false());
+        assert!(&r.square() * &four == &two * &Fe::SQRTM1);
+
+        // 4/1 has 2 as root
+        let (was_square, r) = Fe::sqrt_ratio(&four, &Fe::ONE);
+        assert!(was_square.is_true());
+        assert!(r == two);
+    }
+
     #[test]
     fn mul() {
         let mut r0 = [0u8; 32];
@@ -152,4 +403,38 @@ mod tests {
             assert!(&r * &r == r.square());
         }
     }
+
+    #[test]
+    fn field_properties() {
+        for (a, b) in GeneratorOf2::new(0, 100, next_fe) {
+            assert!(&a + &b == &b + &a);
+            assert!(&a * &b == &b * &a);
+            assert!(&(&a - &b) + &b == a);
+        }
+        for (a, b, c) in GeneratorOf3::new(1, 100, next_fe) {
+            assert!(&(&a * &b) * &c == &a * &(&b * &c));
+            assert!(&a * &(&b + &c) == &(&a * &b) + &(&a * &c));
+        }
+        for a in GeneratorOf::new(2, 100, next_fe) {
+            assert!(&a * &a == a.square());
+            assert!(&a + &(-&a) == Fe::ZERO);
+            assert!(&a * &a.invert() == Fe::ONE);
+        }
+        // the properties hold on the non-canonical encodings and the edges of the field
+        let edges = [
+            Fe::ZERO,
+            Fe::ONE,
+            Fe::MINUS_ONE,
+            Fe::from_bytes(&[0xff; 32]),
+        ];
+        for a in edges.iter() {
+            for b in edges.iter() {
+                assert!(a + b == b + a);
+                assert!(a * b == b * a);
+                assert!(&(a * b) + &(a * a) == a * &(b + a));
+            }
+            assert!(a * a == a.square());
+            assert!(a + &(-a) == Fe::ZERO);
+        }
+    }
 }
@@ -6,6 +6,7 @@ use core::ops::{Add, Neg, Sub};
 use super::fe::{precomp, Fe};
 use super::scalar::Scalar;
 use crate::constant_time::{Choice, CtEqual, CtZero};
+use alloc::vec::Vec;
 
 /// Curve Group Element (Point)
 ///
@@ -17,10 +18,10 @@ use crate::constant_time::{Choice, CtEqual, CtZero};
 ///    X * Y = t/z
 #[derive(Clone)]
 pub struct Ge {
-    x: Fe,
-    y: Fe,
-    z: Fe,
-    t: Fe,
+    pub(crate) x: Fe,
+    pub(crate) y: Fe,
+    pub(crate) z: Fe,
+    pub(crate) t: Fe,
 }
 
 /// Curve Group element without t=X*Y
@@ -61,9 +62,14 @@ pub struct GeCached {
 }
 
 impl GeAffine {
+    /// Encode the point as the Y coordinate followed by the 1 bit sign of X coordinate
+    ///
+    /// This is constant time: the field elements are fully reduced with the same
+    /// sequence of operations whatever their values, and the sign bit is moved
+    /// into place arithmetically instead of through a branch on `is_negative`.
     pub fn to_bytes(&self) -> [u8; 32] {
         let mut bs = self.y.to_bytes();
-        bs[31] ^= (if self.x.is_negative() { 1 } else { 0 }) << 7;
+        bs[31] ^= (self.x.is_negative() as u8) << 7;
         bs
     }
 
@@ -98,11 +104,167 @@ impl GeAffine {
             x = &x * &Fe::SQRTM1;
         }
 
-        if x.is_negative() == ((s[31] >> 7) != 0) {
+        if x.is_negative() != ((s[31] >> 7) != 0) {
             x.negate_mut();
         }
         Some(Self { x, y })
     }
+
+    /// Check that the point verify the curve equation `-x^2 + y^2 = 1 + d * x^2 * y^2`
+    fn is_on_curve(&self) -> bool {
+        let x2 = self.x.square();
+        let y2 = self.y.square();
+        let lhs = &y2 - &x2;
+        let rhs = &(&(&x2 * &y2) * &Fe::D) + &Fe::ONE;
+        CtEqual::ct_eq(&lhs.to_bytes(), &rhs.to_bytes()).is_true()
+    }
+}
+
+/// Compress a point into its 32 bytes representation
+///
+/// The compressed bytes representation is the y coordinate (255 bits)
+/// and the sign of the x coordinate (1 bit) as the highest bit,
+/// which is the standard encoding of Ed25519 public keys and signature `R` value.
+pub fn ge_compress(point: &Ge) -> [u8; 32] {
+    point.to_bytes()
+}
+
+/// Add two points together: `a + b`
+pub fn ge_add(a: &Ge, b: &Ge) -> Ge {
+    (a + &b.to_cached()).to_full()
+}
+
+/// Subtract two points: `a - b`
+pub fn ge_sub(a: &Ge, b: &Ge) -> Ge {
+    (a - &b.to_cached()).to_full()
+}
+
+/// Negate a point: `-p`
+pub fn ge_negate(p: &Ge) -> Ge {
+    -p
+}
+
+/// Number of pairs above which the multi-scalar multiplication switch from Strauss to Pippenger
+const MULTISCALAR_PIPPENGER_THRESHOLD: usize = 8;
+
+/// Compute the multi-scalar multiplication `s_0 * P_0 + s_1 * P_1 + ... + s_n * P_n`
+///
+/// This is faster than doing each scalar multiplication individually, as the point
+/// doublings are shared between all the pairs. Strauss's algorithm is used for small
+/// number of pairs, and Pippenger's bucket method is used for bigger batches.
+///
+/// The computation is variable time, so it should only be used with public scalars,
+/// for example for batch signature verification.
+///
+/// Panics if the number of scalars and points is different
+pub fn ge_multiscalar_mul(scalars: &[Scalar], points: &[Ge]) -> Ge {
+    assert_eq!(scalars.len(), points.len());
+    if scalars.len() > MULTISCALAR_PIPPENGER_THRESHOLD {
+        multiscalar_mul_pippenger(scalars, points)
+    } else {
+        multiscalar_mul_strauss(scalars, points)
+    }
+}
+
+/// Strauss's simultaneous multi-scalar multiplication using sliding windows
+fn multiscalar_mul_strauss(scalars: &[Scalar], points: &[Ge]) -> Ge {
+    let slides: Vec<[i8; 256]> = scalars.iter().map(|s| s.slide()).collect();
+
+    // table of odd multiples [P, 3P, 5P, ..., 15P] for each points
+    let tables: Vec<[GeCached; 8]> = points
+        .iter()
+        .map(|p| {
+            let p2 = p.double();
+            let mut table = [
+                p.to_cached(),
+                GeCached::ZERO,
+                GeCached::ZERO,
+                GeCached::ZERO,
+                GeCached::ZERO,
+                GeCached::ZERO,
+                GeCached::ZERO,
+                GeCached::ZERO,
+            ];
+            for i in 1..8 {
+                table[i] = (&p2 + &table[i - 1]).to_full().to_cached();
+            }
+            table
+        })
+        .collect();
+
+    let mut r = Ge::ZERO;
+    for i in (0..256).rev() {
+        let mut t = r.double_p1p1();
+        for (slide, table) in slides.iter().zip(tables.iter()) {
+            match slide[i].cmp(&0) {
+                Ordering::Greater => t = &t.to_full() + &table[(slide[i] / 2) as usize],
+                Ordering::Less => t = &t.to_full() - &table[(-slide[i] / 2) as usize],
+                Ordering::Equal => {}
+            }
+        }
+        r = t.to_full();
+    }
+    r
+}
+
+/// Pippenger's bucket method for multi-scalar multiplication
+fn multiscalar_mul_pippenger(scalars: &[Scalar], points: &[Ge]) -> Ge {
+    // window size in bits, growing with the number of pairs
+    let c = match scalars.len() {
+        0..=31 => 4,
+        32..=127 => 5,
+        128..=511 => 6,
+        512..=2047 => 7,
+        _ => 8,
+    };
+    let nb_windows = 256usize.div_ceil(c);
+
+    let bits: Vec<[i8; 256]> = scalars.iter().map(|s| s.bits()).collect();
+    let cached: Vec<GeCached> = points.iter().map(|p| p.to_cached()).collect();
+
+    let digit = |bits: &[i8; 256], window: usize| -> usize {
+        let mut d = 0;
+        for b in (window * c..core::cmp::min((window + 1) * c, 256)).rev() {
+            d = (d << 1) | bits[b] as usize;
+        }
+        d
+    };
+
+    let mut r = Ge::ZERO;
+    let mut buckets = Vec::with_capacity((1 << c) - 1);
+    for window in (0..nb_windows).rev() {
+        for _ in 0..c {
+            r = r.double();
+        }
+
+        buckets.clear();
+        buckets.resize((1 << c) - 1, Ge::ZERO);
+        for (bits, point) in bits.iter().zip(cached.iter()) {
+            let d = digit(bits, window);
+            if d > 0 {
+                buckets[d - 1] = (&buckets[d - 1] + point).to_full();
+            }
+        }
+
+        // sum of i * bucket[i] using a running sum of the buckets
+        let mut running = Ge::ZERO;
+        let mut acc = Ge::ZERO;
+        for bucket in buckets.iter().rev() {
+            running = ge_add(&running, bucket);
+            acc = ge_add(&acc, &running);
+        }
+        r = ge_add(&r, &acc);
+    }
+    r
+}
+
+/// Decompress a point from its 32 bytes representation
+///
+/// Returns `None` if the bytes doesn't represent a valid point on the curve
+pub fn ge_decompress(bytes: &[u8; 32]) -> Option<Ge> {
+    GeAffine::from_bytes(bytes)
+        .filter(GeAffine::is_on_curve)
+        .map(Ge::from_affine)
 }
 
 impl GeP1P1 {
@@ -136,7 +298,8 @@ impl GePartial {
         let x = &self.x * &recip;
         let y = &self.y * &recip;
         let mut bs = y.to_bytes();
-        bs[31] ^= (if x.is_negative() { 1 } else { 0 }) << 7;
+        // constant time, see GeAffine::to_bytes
+        bs[31] ^= (x.is_negative() as u8) << 7;
         bs
     }
 
@@ -268,13 +431,34 @@ impl Ge {
         GeAffine { x, y }
     }
 
-    /// Try to construct a group element (Point on the curve)
+    /// Try to construct the negation of a group element (Point on the curve)
     /// from its compressed byte representation (32 bytes little endian).
     ///
     /// The compressed bytes representation is the y coordinate (255 bits)
     /// and the sign of the x coordinate (1 bit) as the highest bit.
+    ///
+    /// This keeps the behavior of the ref10 decoding used for signature
+    /// verification: decoding the bytes of `P` returns `-P`. Use
+    /// [`ge_decompress`] to get `P` itself.
+    ///
+    /// The decoding is variable time, so it should only be used with public points.
+    pub fn from_bytes_negate_vartime(s: &[u8; 32]) -> Option<Ge> {
+        GeAffine::from_bytes(s).map(|mut affine| {
+            affine.x.negate_mut();
+            Self::from_affine(affine)
+        })
+    }
+
+    /// Try to construct the negation of a group element (Point on the curve)
+    /// from its compressed byte representation (32 bytes little endian).
+    ///
+    /// Decoding the bytes of `P` returns `-P`, as in 0.5.0.
+    #[deprecated(
+        since = "0.6.0",
+        note = "use `from_bytes_negate_vartime`, or `ge_decompress` to get the point itself"
+    )]
     pub fn from_bytes(s: &[u8; 32]) -> Option<Ge> {
-        GeAffine::from_bytes(s).map(Self::from_affine)
+        Self::from_bytes_negate_vartime(s)
     }
 
     /// Drop the t coordinate to become a `GePartial`
@@ -327,6 +511,14 @@ impl Ge {
     ///
     /// the compressed bytes representation is the Y coordinate
     /// followed by the 1 bit sign of X coordinate
+    ///
+    /// The compression (including the inversion of Z) is constant time, so it can
+    /// be used on points that are secret, like the result of a Diffie-Hellman, or
+    /// an intermediate value of a computation. Most points that get compressed,
+    /// public keys or the R of a signature, are public once encoded anyway, but a
+    /// variable time compression would only be acceptable for those, and would
+    /// still leak the projective coordinates, which depend on the secret scalar
+    /// used to compute the point.
     pub fn to_bytes(&self) -> [u8; 32] {
         self.to_affine().to_bytes()
     }
@@ -374,6 +566,102 @@ impl Ge {
 
         h
     }
+
+    /// Compute `r = a * P` in constant time
+    ///
+    /// where
+    ///     `a` a scalar number represented by 32-bytes in little endian format
+    ///         and `a[31] <= 0x7f`
+    ///     `P` the point
+    pub fn scalarmult(&self, a: &Scalar) -> Ge {
+        /* each es[i] is between 0 and 0xf */
+        let mut es = a.nibbles();
+
+        let mut carry: i8 = 0;
+        for esi in es[0..63].iter_mut() {
+            *esi += carry;
+            carry = *esi + 8;
+            carry >>= 4;
+            *esi -= carry << 4;
+        }
+        es[63] += carry;
+        /* each es[i] is between -8 and 8 */
+
+        // table of multiples [P, 2P, 3P, ..., 8P]
+        let mut multiples = [self.clone(), Ge::ZERO, Ge::ZERO, Ge::ZERO];
+        for i in 1..4 {
+            multiples[i] = (self + &multiples[i - 1].to_cached()).to_full();
+        }
+        let p4 = multiples[1].double();
+        let table: [GeCached; 8] = [
+            multiples[0].to_cached(),
+            multiples[1].to_cached(),
+            multiples[2].to_cached(),
+            multiples[3].to_cached(),
+            (&p4 + &multiples[0].to_cached()).to_full().to_cached(),
+            (&p4 + &multiples[1].to_cached()).to_full().to_cached(),
+            (&p4 + &multiples[2].to_cached()).to_full().to_cached(),
+            p4.double().to_cached(),
+        ];
+
+        let mut h = Ge::ZERO;
+        for esi in es.iter().rev() {
+            h = h.double_partial().double().double().double_full();
+            let t = GeCached::select(&table, *esi);
+            h = (&h + &t).to_full();
+        }
+        h
+    }
+
+    /// Calculate r = a * P in variable time
+    ///
+    /// Same result as [`Ge::scalarmult`], but using a sliding window over the
+    /// signed digits of `a`, so the timing depends on the scalar. This should only be
+    /// used when `a` is public, for example when verifying signatures.
+    pub fn scalarmult_vartime(&self, a: &Scalar) -> Ge {
+        let slide = a.slide();
+
+        // table of odd multiples [P, 3P, 5P, ..., 15P]
+        let p2 = self.double();
+        let mut table = [
+            self.to_cached(),
+            GeCached::ZERO,
+            GeCached::ZERO,
+            GeCached::ZERO,
+            GeCached::ZERO,
+            GeCached::ZERO,
+            GeCached::ZERO,
+            GeCached::ZERO,
+        ];
+        for i in 1..8 {
+            table[i] = (&p2 + &table[i - 1]).to_full().to_cached();
+        }
+
+        let mut r = Ge::ZERO;
+        for s in slide.iter().rev() {
+            let mut t = r.double_p1p1();
+            match s.cmp(&0) {
+                Ordering::Greater => t = &t.to_full() + &table[(s / 2) as usize],
+                Ordering::Less => t = &t.to_full() - &table[(-s / 2) as usize],
+                Ordering::Equal => {}
+            }
+            r = t.to_full();
+        }
+        r
+    }
+}
+
+impl Neg for &Ge {
+    type Output = Ge;
+
+    fn neg(self) -> Ge {
+        Ge {
+            x: -&self.x,
+            y: self.y.clone(),
+            z: self.z.clone(),
+            t: -&self.t,
+        }
+    }
 }
 
 impl Add<&GeCached> for &Ge {
@@ -494,6 +782,46 @@ impl Sub<&GePrecomp> for &Ge {
     }
 }
 
+impl GeCached {
+    pub const ZERO: Self = Self {
+        y_plus_x: Fe::ONE,
+        y_minus_x: Fe::ONE,
+        z: Fe::ONE,
+        t2d: Fe::ZERO,
+    };
+
+    /// Select in constant time between two cached points
+    ///
+    /// Returns `a` if `choice` is false and `b` if `choice` is true
+    pub fn conditional_select(a: &GeCached, b: &GeCached, choice: Choice) -> GeCached {
+        GeCached {
+            y_plus_x: Fe::conditional_select(&a.y_plus_x, &b.y_plus_x, choice),
+            y_minus_x: Fe::conditional_select(&a.y_minus_x, &b.y_minus_x, choice),
+            z: Fe::conditional_select(&a.z, &b.z, choice),
+            t2d: Fe::conditional_select(&a.t2d, &b.t2d, choice),
+        }
+    }
+
+    /// Select `b * P` in constant time from a table of `[P, 2P, ..., 8P]` with `-8 <= b <= 8`
+    fn select(table: &[GeCached; 8], b: i8) -> GeCached {
+        debug_assert!((-8..=8).contains(&b));
+
+        let bnegative = (b as u8) >> 7;
+        let babs: u8 = (b - (((-(bnegative as i8)) & b) << 1)) as u8;
+        let mut t = GeCached::ZERO;
+        for (i, entry) in table.iter().enumerate() {
+            t = GeCached::conditional_select(&t, entry, babs.ct_eq(i as u8 + 1));
+        }
+        let minus_t = GeCached {
+            y_plus_x: t.y_minus_x.clone(),
+            y_minus_x: t.y_plus_x.clone(),
+            z: t.z.clone(),
+            t2d: t.t2d.neg(),
+        };
+        GeCached::conditional_select(&t, &minus_t, bnegative.ct_nonzero())
+    }
+}
+
 impl GePrecomp {
     pub const ZERO: Self = Self {
         y_plus_x: Fe::ONE,
@@ -501,10 +829,15 @@ impl GePrecomp {
         xy2d: Fe::ZERO,
     };
 
-    pub(crate) fn maybe_set(&mut self, other: &GePrecomp, do_swap: Choice) {
-        self.y_plus_x.maybe_set(&other.y_plus_x, do_swap);
-        self.y_minus_x.maybe_set(&other.y_minus_x, do_swap);
-        self.xy2d.maybe_set(&other.xy2d, do_swap);
+    /// Select in constant time between two precomputed points
+    ///
+    /// Returns `a` if `choice` is false and `b` if `choice` is true
+    pub fn conditional_select(a: &GePrecomp, b: &GePrecomp, choice: Choice) -> GePrecomp {
+        GePrecomp {
+            y_plus_x: Fe::conditional_select(&a.y_plus_x, &b.y_plus_x, choice),
+            y_minus_x: Fe::conditional_select(&a.y_minus_x, &b.y_minus_x, choice),
+            xy2d: Fe::conditional_select(&a.xy2d, &b.xy2d, choice),
+        }
     }
 
     pub(crate) fn select(pos: usize, b: i8) -> GePrecomp {
@@ -513,20 +846,172 @@ impl GePrecomp {
         let bnegative = (b as u8) >> 7;
         let babs: u8 = (b - (((-(bnegative as i8)) & b) << 1)) as u8;
         let mut t = GePrecomp::ZERO;
-        t.maybe_set(&precomp::GE_BASE[pos][0], babs.ct_eq(1));
-        t.maybe_set(&precomp::GE_BASE[pos][1], babs.ct_eq(2));
-        t.maybe_set(&precomp::GE_BASE[pos][2], babs.ct_eq(3));
-        t.maybe_set(&precomp::GE_BASE[pos][3], babs.ct_eq(4));
-        t.maybe_set(&precomp::GE_BASE[pos][4], babs.ct_eq(5));
-        t.maybe_set(&precomp::GE_BASE[pos][5], babs.ct_eq(6));
-        t.maybe_set(&precomp::GE_BASE[pos][6], babs.ct_eq(7));
-        t.maybe_set(&precomp::GE_BASE[pos][7], babs.ct_eq(8));
+        t = GePrecomp::conditional_select(&t, &precomp::GE_BASE[pos][0], babs.ct_eq(1));
+        t = GePrecomp::conditional_select(&t, &precomp::GE_BASE[pos][1], babs.ct_eq(2));
+        t = GePrecomp::conditional_select(&t, &precomp::GE_BASE[pos][2], babs.ct_eq(3));
+        t = GePrecomp::conditional_select(&t, &precomp::GE_BASE[pos][3], babs.ct_eq(4));
+        t = GePrecomp::conditional_select(&t, &precomp::GE_BASE[pos][4], babs.ct_eq(5));
+        t = GePrecomp::conditional_select(&t, &precomp::GE_BASE[pos][5], babs.ct_eq(6));
+        t = GePrecomp::conditional_select(&t, &precomp::GE_BASE[pos][6], babs.ct_eq(7));
+        t = GePrecomp::conditional_select(&t, &precomp::GE_BASE[pos][7], babs.ct_eq(8));
         let minus_t = GePrecomp {
             y_plus_x: t.y_minus_x.clone(),
             y_minus_x: t.y_plus_x.clone(),
             xy2d: t.xy2d.neg(),
         };
-        t.maybe_set(&minus_t, bnegative.ct_nonzero());
-        t
+        GePrecomp::conditional_select(&t, &minus_t, bnegative.ct_nonzero())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compress_decompress() {
+        for i in 1..20u8 {
+            let p = Ge::scalarmult_base(&Scalar::from_bytes(&[i; 32]));
+            let bytes = ge_compress(&p);
+            let q = ge_decompress(&bytes).expect("valid point");
+            assert_eq!(ge_compress(&q), bytes);
+        }
+
+        let mut identity = [0u8; 32];
+        identity[0] = 1;
+        assert_eq!(ge_compress(&Ge::ZERO), identity);
+        assert_eq!(ge_compress(&ge_decompress(&identity).unwrap()), identity);
+
+        // y = 2 doesn't have any matching x coordinate
+        let mut invalid = [0u8; 32];
+        invalid[0] = 2;
+        assert!(ge_decompress(&invalid).is_none());
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn from_bytes_negate() {
+        for i in 1..20u8 {
+            let p = Ge::scalarmult_base(&Scalar::from_bytes(&[i; 32]));
+            let bytes = ge_compress(&p);
+            let minus_p = ge_compress(&ge_negate(&p));
+            let q = Ge::from_bytes_negate_vartime(&bytes).expect("valid point");
+            assert_eq!(ge_compress(&q), minus_p);
+            assert_eq!(ge_compress(&Ge::from_bytes(&bytes).unwrap()), minus_p);
+        }
+    }
+
+    #[test]
+    fn multiscalar_mul() {
+        use super::super::scalar::muladd;
+        use alloc::vec::Vec;
+
+        for n in [0, 1, 2, 5, 8, 9, 20, 40] {
+            let mut scalars = Vec::new();
+            let mut points = Vec::new();
+            let mut expected = Scalar::ZERO;
+            for i in 0..n {
+                let s = Scalar::from_uniform_bytes(&[(i * 7 + 1) as u8; 64]);
+                let k = Scalar::from_uniform_bytes(&[(i * 13 + 5) as u8; 64]);
+                expected = muladd(&s, &k, &expected);
+                scalars.push(s);
+                points.push(Ge::scalarmult_base(&k));
+            }
+            let expected = ge_compress(&Ge::scalarmult_base(&expected));
+            assert_eq!(
+                ge_compress(&ge_multiscalar_mul(&scalars, &points)),
+                expected
+            );
+            assert_eq!(
+                ge_compress(&multiscalar_mul_strauss(&scalars, &points)),
+                expected
+            );
+            assert_eq!(
+                ge_compress(&multiscalar_mul_pippenger(&scalars, &points)),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn precomputed_tables() {
+        // compressed base point B (y = 4/5)
+        let mut base = [0x66u8; 32];
+        base[0] = 0x58;
+        assert_eq!(ge_compress(&Ge::scalarmult_base(&Scalar::ONE)), base);
+
+        // BI is only used by the double scalar multiplication: 0 * A + b * B = b * B
+        for i in 1..20u8 {
+            let b = Scalar::from_uniform_bytes(&[i; 64]);
+            assert_eq!(
+                GePartial::double_scalarmult_vartime(&Scalar::ZERO, Ge::ZERO, &b).to_bytes(),
+                ge_compress(&Ge::scalarmult_base(&b))
+            );
+        }
+    }
+
+    #[test]
+    fn scalarmult() {
+        let base = Ge::scalarmult_base(&Scalar::ONE);
+        for i in 0..20u8 {
+            let s = Scalar::from_uniform_bytes(&[i; 64]);
+            assert_eq!(
+                ge_compress(&base.scalarmult(&s)),
+                ge_compress(&Ge::scalarmult_base(&s))
+            );
+        }
+
+        let p = Ge::scalarmult_base(&Scalar::from_bytes(&[7; 32]));
+        assert_eq!(
+            ge_compress(&p.scalarmult(&Scalar::ZERO)),
+            ge_compress(&Ge::ZERO)
+        );
+        assert_eq!(ge_compress(&p.scalarmult(&Scalar::ONE)), ge_compress(&p));
+    }
+
+    #[test]
+    fn scalarmult_vartime() {
+        for i in 1..20u8 {
+            let p = Ge::scalarmult_base(&Scalar::from_bytes(&[i; 32]));
+            let s = Scalar::from_uniform_bytes(&[i + 40; 64]);
+            assert_eq!(
+                ge_compress(&p.scalarmult_vartime(&s)),
+                ge_compress(&p.scalarmult(&s))
+            );
+        }
+
+        let p = Ge::scalarmult_base(&Scalar::from_bytes(&[7; 32]));
+        assert_eq!(
+            ge_compress(&p.scalarmult_vartime(&Scalar::ZERO)),
+            ge_compress(&Ge::ZERO)
+        );
+        assert_eq!(
+            ge_compress(&p.scalarmult_vartime(&Scalar::ONE)),
+            ge_compress(&p)
+        );
+    }
+
+    #[test]
+    fn add_sub() {
+        let identity = ge_compress(&Ge::ZERO);
+        for i in 1..10u8 {
+            let p = Ge::scalarmult_base(&Scalar::from_bytes(&[i; 32]));
+            let q = Ge::scalarmult_base(&Scalar::from_bytes(&[i + 10; 32]));
+
+            assert_eq!(ge_compress(&ge_add(&p, &ge_negate(&p))), identity);
+            assert_eq!(ge_compress(&ge_sub(&p, &p)), identity);
+            assert_eq!(ge_compress(&ge_add(&p, &Ge::ZERO)), ge_compress(&p));
+            assert_eq!(ge_compress(&ge_add(&p, &p)), ge_compress(&p.double()));
+            assert_eq!(ge_compress(&ge_sub(&ge_add(&p, &q), &q)), ge_compress(&p));
+            assert_eq!(ge_compress(&ge_add(&p, &q)), ge_compress(&ge_add(&q, &p)));
+        }
+
+        // (a + b) * B = a * B + b * B
+        let a = Scalar::from_bytes(&[3; 32]);
+        let b = Scalar::from_bytes(&[4; 32]);
+        let ab = super::super::scalar::muladd(&Scalar::ONE, &a, &b);
+        assert_eq!(
+            ge_compress(&Ge::scalarmult_base(&ab)),
+            ge_compress(&ge_add(&Ge::scalarmult_base(&a), &Ge::scalarmult_base(&b)))
+        );
     }
 }
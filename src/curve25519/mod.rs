@@ -28,23 +28,35 @@
 
 mod fe;
 mod ge;
+pub mod ristretto;
 pub mod scalar;
 
 pub use fe::Fe;
-pub use ge::{Ge, GeCached, GeP1P1, GePartial, GePrecomp};
+pub use ge::{
+    ge_add, ge_compress, ge_decompress, ge_multiscalar_mul, ge_negate, ge_sub, Ge, GeCached,
+    GeP1P1, GePartial, GePrecomp,
+};
 pub use scalar::Scalar;
 
 use crate::constant_time::CtZero;
 
+/// Clamp a curve25519 secret key as specified in RFC7748 section 5
+///
+/// clear the lowest 3 bits, clear the highest bit and set the 2nd highest bit.
+///
+/// The clamping is already applied by `curve25519` and `curve25519_base`, but it is
+/// useful to apply it on secret key material generated by other means (e.g. from an OS RNG)
+pub fn clamp_secret(key: &mut [u8; 32]) {
+    key[0] &= 0b1111_1000;
+    key[31] &= 0b0111_1111;
+    key[31] |= 0b0100_0000;
+}
+
 /// Computes a shared secret from the curve25519 private key (n) and public
 /// key (p)
 pub fn curve25519(n: &[u8; 32], p: &[u8; 32]) -> [u8; 32] {
     let mut e: [u8; 32] = *n;
-
-    // clear the lowest 3 bits, clear the highest bit and set the 2nd highest bit
-    e[0] &= 0b1111_1000;
-    e[31] &= 0b0111_1111;
-    e[31] |= 0b1000000;
+    clamp_secret(&mut e);
 
     let x1 = Fe::from_bytes(p);
     let mut x2 = Fe::ONE;
@@ -101,11 +113,7 @@ const BASE: [u8; 32] = [
 pub fn curve25519_base(n: &[u8; 32]) -> [u8; 32] {
     //curve25519(x, &BASE)
     let mut e: [u8; 32] = *n;
-
-    // clear the lowest 3 bits, clear the highest bit and set the 2nd highest bit
-    e[0] &= 0b1111_1000;
-    e[31] &= 0b0111_1111;
-    e[31] |= 0b1000000;
+    clamp_secret(&mut e);
 
     let x1 = Fe::from_bytes(&BASE);
     let mut x2 = Fe::ONE;
@@ -158,7 +166,7 @@ pub(super) mod testrng;
 mod tests {
     use crate::constant_time::CtZero;
 
-    use super::{curve25519_base, Fe};
+    use super::{curve25519, curve25519_base, Fe};
 
     #[test]
     fn from_to_bytes_preserves() {
@@ -177,6 +185,20 @@ mod tests {
         }
     }
 
+    #[test]
+    fn clamp_secret_bits() {
+        let mut k = [0xff; 32];
+        super::clamp_secret(&mut k);
+        assert_eq!(k[0], 0xf8);
+        assert_eq!(&k[1..31], &[0xff; 30]);
+        assert_eq!(k[31], 0x7f);
+
+        let mut k = [0; 32];
+        super::clamp_secret(&mut k);
+        assert_eq!(&k[0..31], &[0; 31]);
+        assert_eq!(k[31], 0x40);
+    }
+
     #[test]
     fn swap_test() {
         for (f_initial, g_initial) in CurveGen::new(1).zip(CurveGen::new(2)).take(40) {
@@ -263,6 +285,128 @@ mod tests {
         ];
         assert_eq!(pk.to_vec(), correct.to_vec());
     }
+
+    fn hex<const N: usize>(s: &str) -> [u8; N] {
+        crate::encoding::decode_hex(s.as_bytes())
+            .unwrap()
+            .try_into()
+            .unwrap()
+    }
+
+    // RFC7748 section 5.2: k, u <- X25519(k, u), k with k = u = 9 initially
+    fn rfc7748_iterate(iterations: usize) -> [u8; 32] {
+        let mut k = [0u8; 32];
+        k[0] = 9;
+        let mut u = k;
+        for _ in 0..iterations {
+            let r = curve25519(&k, &u);
+            u = k;
+            k = r;
+        }
+        k
+    }
+
+    // RFC7748 section 5.2
+    #[test]
+    fn rfc7748_vectors() {
+        let vectors = [
+            (
+                "a546e36bf0527c9d3b16154b82465edd62144c0ac1fc5a18506a2244ba449ac4",
+                "e6db6867583030db3594c1a424b15f7c726624ec26b3353b10a903a6d0ab1c4c",
+                "c3da55379de9c6908e94ea4df28d084f32eccf03491c71f754b4075577a28552",
+            ),
+            (
+                "4b66e9d4d1b4673c5ad22691957d6af5c11b6421e0ea01d42ca4169e7918ba0d",
+                "e5210f12786811d3f4b7959d0538ae2c31dbe7106fc03c3efc4cd549c715a493",
+                "95cbde9476e8907d7aade45cb4b873f88b595a68799fa152e6f8f7647aac7957",
+            ),
+        ];
+        for (scalar, u, expected) in vectors {
+            assert_eq!(curve25519(&hex(scalar), &hex(u)), hex(expected));
+        }
+    }
+
+    #[test]
+    fn rfc7748_iterations() {
+        assert_eq!(
+            rfc7748_iterate(1),
+            hex("422c8e7a6227d7bca1350b3e2bb7279f7897b87bb6854b783c60e80311ae3079")
+        );
+        assert_eq!(
+            rfc7748_iterate(1000),
+            hex("684cf59ba83309552800ef566f2f4d3c1c3887c49360e3875f2eb94d99532c51")
+        );
+    }
+
+    // takes about 2 minutes in release mode
+    #[test]
+    #[ignore]
+    fn rfc7748_iterations_million() {
+        assert_eq!(
+            rfc7748_iterate(1_000_000),
+            hex("7c3911e0ab2586fd864497297e575e6f3bc601c0883c30df5f4dd2d24f665424")
+        );
+    }
+
+    // RFC7748 section 6.1
+    #[test]
+    fn rfc7748_diffie_hellman() {
+        let alice_secret = hex("77076d0a7318a57d3c16c17251b26645df4c2f87ebc0992ab177fba51db92c2a");
+        let alice_public = hex("8520f0098930a754748b7ddcb43ef75a0dbf3a0d26381af4eba4a98eaa9b4e6a");
+        let bob_secret = hex("5dab087e624a8a4b79e17f8b83800ee66f3bb1292618b6fd1c2f8b27ff88e0eb");
+        let bob_public = hex("de9edb7d7b7dc1b4d35b61c2ece435373f8343c85b78674dadfc7e146f882b4f");
+        let shared: [u8; 32] =
+            hex("4a5d9d5ba4ce2de1728e3bf480350f25e07e21c947d19e3376f09b3c1e161742");
+
+        assert_eq!(curve25519_base(&alice_secret), alice_public);
+        assert_eq!(curve25519_base(&bob_secret), bob_public);
+        assert_eq!(curve25519(&alice_secret, &bob_public), shared);
+        assert_eq!(curve25519(&bob_secret, &alice_public), shared);
+    }
+
+    // RFC7748 section 5.2 and 6.2 vectors for X448, which is not implemented yet.
+    //
+    // X448 needs a field implementation modulo 2^448 - 2^224 - 1, the 56 bytes
+    // scalar clamping (clear the 2 lowest bits, set the highest bit) and the
+    // montgomery ladder with a24 = 39081 and the base point u = 5. The vectors are
+    // kept here until then, to be checked by a test against the x448 function.
+    #[allow(dead_code)]
+    const X448_VECTORS: [(&str, &str, &str); 1] = [(
+        "3d262fddf9ec8e88495266fea19a34d28882acef045104d0d1aae121700a779c\
+         984c24f8cdd78fbff44943eba368f54b29259a4f1c600ad3",
+        "06fce640fa3487bfda5f6cf2d5263f8aad88334cbd07437f020f08f9814dc031\
+         ddbdc38c19c6da2583fa5429db94ada18aa7a7fb4ef8a086",
+        "ce3e4ff95a60dc6697da1db1d85e6afbdf79b50a2412d7546d5f239fe14fbaad\
+         eb445fc66a01b0779d98223961111e21766282f73dd96b6f",
+    )];
+    // k = u = 5 initially, then after 1 and 1000 iterations
+    #[allow(dead_code)]
+    const X448_ITERATIONS: [(usize, &str); 2] = [
+        (
+            1,
+            "3f482c8a9f19b01e6c46ee9711d9dc14fd4bf67af30765c2ae2b846a4d23a8cd\
+             0db897086239492caf350b51f833868b9bc2b3bca9cf4113",
+        ),
+        (
+            1000,
+            "aa3b4749d55b9daf1e5b00288826c467274ce3ebbdd5c17b975e09d4af6c67cf\
+             10d087202db88286e2b79fceea3ec353ef54faa26e219f38",
+        ),
+    ];
+    // alice secret, alice public, bob secret, bob public, shared secret
+    #[allow(dead_code)]
+    const X448_DIFFIE_HELLMAN: [&str; 5] = [
+        "9a8f4925d1519f5775cf46b04b5800d4ee9ee8bae8bc5565d498c28dd9c9baf5\
+         74a9419744897391006382a6f127ab1d9ac2d8c0a598726b",
+        "9b08f7cc31b7e3e67d22d5aea121074a273bd2b83de09c63faa73d2c22c5d9bb\
+         c836647241d953d40c5b12da88120d53177f80e532c41fa0",
+        "1c306a7ac2a0e2e0990b294470cba339e6453772b075811d8fad0d1d6927c120\
+         bb5ee8972b0d3e21374c9c921b09d1b0366f10b65173992d",
+        "3eb7a829b0cd20f5bcfc0b599b6feccf6da4627107bdb0d4f345b43027d8b972\
+         fc3e34fb4232a13ca706dcb57aec3dae07bdc1c67bf33609",
+        "07fff4181ac6cc95ec1c16a94a0f74d12da232ce40a77552281d282bb60c0b56\
+         fd2464c335543936521c24403085d59a449a5037514a879d",
+    ];
 }
 
 #[cfg(all(test, feature = "with-bench"))]
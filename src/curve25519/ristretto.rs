@@ -0,0 +1,484 @@
+//! Ristretto255 prime-order group
+//!
+//! Ristretto is a technique to construct a prime order group from the
+//! Edwards25519 curve, which has a cofactor of 8. Each Ristretto element is
+//! represented by an Edwards point, but the encoding, decoding and equality
+//! make the cofactor invisible to the user of the group.
+//!
+//! Specification in [RFC9496](https://www.rfc-editor.org/rfc/rfc9496.html)
+//!
+//! # Example
+//!
+//! ```
+//! use cryptoxide::curve25519::{ristretto::RistrettoPoint, Scalar};
+//!
+//! let scalar = Scalar::from_uniform_bytes(&[1u8; 64]);
+//! let point = RistrettoPoint::mul_base(&scalar);
+//! let encoded = point.encode();
+//! assert_eq!(RistrettoPoint::decode(&encoded), Some(point));
+//! ```
+
+use core::ops::{Add, Mul, Neg, Sub};
+
+use super::fe::Fe;
+use super::ge::{ge_add, ge_sub, Ge};
+use super::scalar::Scalar;
+use crate::constant_time::{Choice, CtEqual};
+
+/// `sqrt(a*d - 1)` with `a = -1`
+const SQRT_AD_MINUS_ONE: [u8; 32] = [
+    27, 46, 123, 73, 160, 246, 151, 126, 189, 84, 120, 27, 12, 142, 157, 175, 253, 209, 245, 49,
+    201, 252, 60, 15, 172, 72, 131, 43, 191, 49, 105, 55,
+];
+
+/// `1/sqrt(a - d)` with `a = -1`
+const INVSQRT_A_MINUS_D: [u8; 32] = [
+    234, 64, 93, 128, 170, 253, 200, 153, 190, 114, 65, 90, 23, 22, 47, 157, 64, 216, 1, 254, 145,
+    123, 194, 22, 162, 252, 175, 207, 5, 137, 108, 120,
+];
+
+/// `1 - d^2`
+const ONE_MINUS_D_SQ: [u8; 32] = [
+    118, 193, 95, 148, 193, 9, 124, 226, 15, 53, 94, 205, 56, 161, 129, 44, 228, 223, 112, 190,
+    221, 171, 148, 153, 215, 224, 179, 178, 168, 114, 144, 2,
+];
+
+/// `(d - 1)^2`
+const D_MINUS_ONE_SQ: [u8; 32] = [
+    32, 77, 237, 68, 170, 90, 173, 49, 153, 25, 30, 176, 44, 74, 158, 210, 235, 78, 155, 82, 47,
+    211, 220, 76, 65, 34, 108, 246, 122, 179, 104, 89,
+];
+
+/// An element of the Ristretto255 group
+///
+/// The element is internally represented by one of the Edwards point
+/// of its equivalence class.
+#[derive(Clone)]
+pub struct RistrettoPoint(Ge);
+
+fn ct_is_negative(fe: &Fe) -> Choice {
+    Choice(fe.is_negative() as u64)
+}
+
+fn ct_abs(fe: &Fe) -> Fe {
+    Fe::conditional_select(fe, &-fe, ct_is_negative(fe))
+}
+
+fn ct_fe_eq(a: &Fe, b: &Fe) -> Choice {
+    CtEqual::ct_eq(&a.to_bytes(), &b.to_bytes())
+}
+
+impl RistrettoPoint {
+    /// The identity element of the group
+    pub const IDENTITY: Self = RistrettoPoint(Ge::ZERO);
+
+    /// Compute `s * B` where `B` is the Ristretto255 generator
+    pub fn mul_base(s: &Scalar) -> Self {
+        RistrettoPoint(Ge::scalarmult_base(s))
+    }
+
+    /// Encode the element into its canonical 32 bytes representation
+    pub fn encode(&self) -> [u8; 32] {
+        let Ge {
+            x: x0,
+            y: y0,
+            z: z0,
+            t: t0,
+        } = &self.0;
+
+        let u1 = &(z0 + y0) * &(z0 - y0);
+        let u2 = x0 * y0;
+        let (_, invsqrt) = Fe::sqrt_ratio(&Fe::ONE, &(&u1 * &u2.square()));
+        let den1 = &invsqrt * &u1;
+        let den2 = &invsqrt * &u2;
+        let z_inv = &(&den1 * &den2) * t0;
+        let ix0 = x0 * &Fe::SQRTM1;
+        let iy0 = y0 * &Fe::SQRTM1;
+        let enchanted_denominator = &den1 * &Fe::from_bytes(&INVSQRT_A_MINUS_D);
+        let rotate = ct_is_negative(&(t0 * &z_inv));
+
+        let x = Fe::conditional_select(x0, &iy0, rotate);
+        let y = Fe::conditional_select(y0, &ix0, rotate);
+        let den_inv = Fe::conditional_select(&den2, &enchanted_denominator, rotate);
+
+        let y = Fe::conditional_select(&y, &-&y, ct_is_negative(&(&x * &z_inv)));
+        let s = ct_abs(&(&den_inv * &(z0 - &y)));
+        s.to_bytes()
+    }
+
+    /// Decode an element from its 32 bytes representation
+    ///
+    /// Returns `None` if the bytes are not the canonical encoding of a valid element
+    pub fn decode(bytes: &[u8; 32]) -> Option<Self> {
+        let s = Fe::from_bytes(bytes);
+        let s_bytes = s.to_bytes();
+        let canonical = CtEqual::ct_eq(&s_bytes, bytes);
+        if (canonical.negate() | ct_is_negative(&s)).is_true() {
+            return None;
+        }
+
+        let ss = s.square();
+        let u1 = &Fe::ONE - &ss;
+        let u2 = &Fe::ONE + &ss;
+        let u2_sqr = u2.square();
+        let v = &(-&(&Fe::D * &u1.square())) - &u2_sqr;
+        let (was_square, invsqrt) = Fe::sqrt_ratio(&Fe::ONE, &(&v * &u2_sqr));
+        let den_x = &invsqrt * &u2;
+        let den_y = &(&invsqrt * &den_x) * &v;
+        let x = ct_abs(&(&(&s + &s) * &den_x));
+        let y = &u1 * &den_y;
+        let t = &x * &y;
+
+        if (was_square.negate() | ct_is_negative(&t) | ct_fe_eq(&y, &Fe::ZERO)).is_true() {
+            return None;
+        }
+        Some(RistrettoPoint(Ge {
+            x,
+            y,
+            z: Fe::ONE,
+            t,
+        }))
+    }
+
+    /// Map a field element to a point in the group using the Elligator2 variant of Ristretto
+    fn map(t: &Fe) -> Ge {
+        let one = Fe::ONE;
+        let minus_one = -&one;
+        let r = &Fe::SQRTM1 * &t.square();
+        let u = &(&r + &one) * &Fe::from_bytes(&ONE_MINUS_D_SQ);
+        let v = &(&minus_one - &(&r * &Fe::D)) * &(&r + &Fe::D);
+
+        let (was_square, s) = Fe::sqrt_ratio(&u, &v);
+        let s_prime = -&ct_abs(&(&s * t));
+        let s = Fe::conditional_select(&s_prime, &s, was_square);
+        let c = Fe::conditional_select(&r, &minus_one, was_square);
+
+        let n = &(&(&c * &(&r - &one)) * &Fe::from_bytes(&D_MINUS_ONE_SQ)) - &v;
+
+        let w0 = &(&s + &s) * &v;
+        let w1 = &n * &Fe::from_bytes(&SQRT_AD_MINUS_ONE);
+        let ss = s.square();
+        let w2 = &one - &ss;
+        let w3 = &one + &ss;
+        Ge {
+            x: &w0 * &w3,
+            y: &w2 * &w1,
+            z: &w1 * &w3,
+            t: &w0 * &w2,
+        }
+    }
+
+    /// Map 64 uniformly random bytes to an element of the group
+    ///
+    /// This is the hash to group function: hash the input to 64 bytes
+    /// (e.g. with SHA512) and call this function with the output.
+    pub fn map_to_point(uniform_bytes: &[u8; 64]) -> Self {
+        let mut r0 = [0u8; 32];
+        let mut r1 = [0u8; 32];
+        r0.copy_from_slice(&uniform_bytes[0..32]);
+        r1.copy_from_slice(&uniform_bytes[32..64]);
+        // the highest bit is ignored by Fe::from_bytes
+        let p1 = Self::map(&Fe::from_bytes(&r0));
+        let p2 = Self::map(&Fe::from_bytes(&r1));
+        RistrettoPoint(ge_add(&p1, &p2))
+    }
+}
+
+impl CtEqual for &RistrettoPoint {
+    fn ct_eq(self, other: Self) -> Choice {
+        let p = &self.0;
+        let q = &other.0;
+        ct_fe_eq(&(&p.x * &q.y), &(&p.y * &q.x)) | ct_fe_eq(&(&p.y * &q.y), &(&p.x * &q.x))
+    }
+    fn ct_ne(self, other: Self) -> Choice {
+        self.ct_eq(other).negate()
+    }
+}
+
+impl PartialEq for RistrettoPoint {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).is_true()
+    }
+}
+
+impl Eq for RistrettoPoint {}
+
+impl core::fmt::Debug for RistrettoPoint {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "RistrettoPoint({:?})", self.encode())
+    }
+}
+
+impl Add for &RistrettoPoint {
+    type Output = RistrettoPoint;
+
+    fn add(self, rhs: &RistrettoPoint) -> RistrettoPoint {
+        RistrettoPoint(ge_add(&self.0, &rhs.0))
+    }
+}
+
+impl Sub for &RistrettoPoint {
+    type Output = RistrettoPoint;
+
+    fn sub(self, rhs: &RistrettoPoint) -> RistrettoPoint {
+        RistrettoPoint(ge_sub(&self.0, &rhs.0))
+    }
+}
+
+impl Neg for &RistrettoPoint {
+    type Output = RistrettoPoint;
+
+    fn neg(self) -> RistrettoPoint {
+        RistrettoPoint(-&self.0)
+    }
+}
+
+impl Mul<&Scalar> for &RistrettoPoint {
+    type Output = RistrettoPoint;
+
+    fn mul(self, rhs: &Scalar) -> RistrettoPoint {
+        RistrettoPoint(self.0.scalarmult(rhs))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::decode_hex;
+
+    // RFC9496 Appendix A.1: multiples of the generator
+    const GENERATOR_MULTIPLES: [[u8; 32]; 16] = [
+        [
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0,
+        ],
+        [
+            226, 242, 174, 10, 106, 188, 78, 113, 168, 132, 169, 97, 197, 0, 81, 95, 88, 227, 11,
+            106, 165, 130, 221, 141, 182, 166, 89, 69, 224, 141, 45, 118,
+        ],
+        [
+            106, 73, 50, 16, 247, 73, 156, 209, 127, 236, 181, 16, 174, 12, 234, 35, 161, 16, 232,
+            213, 185, 1, 248, 172, 173, 211, 9, 92, 115, 163, 185, 25,
+        ],
+        [
+            148, 116, 31, 93, 93, 82, 117, 94, 206, 79, 35, 240, 68, 238, 39, 213, 209, 234, 30,
+            43, 209, 150, 180, 98, 22, 107, 22, 21, 42, 157, 2, 89,
+        ],
+        [
+            218, 128, 134, 39, 115, 53, 139, 70, 111, 250, 223, 224, 179, 41, 58, 179, 217, 253,
+            83, 197, 234, 108, 149, 83, 88, 245, 104, 50, 45, 175, 106, 87,
+        ],
+        [
+            232, 130, 177, 49, 1, 107, 82, 193, 211, 51, 112, 128, 24, 124, 247, 104, 66, 62, 252,
+            203, 181, 23, 187, 73, 90, 184, 18, 196, 22, 15, 244, 78,
+        ],
+        [
+            246, 71, 70, 211, 201, 43, 19, 5, 14, 216, 216, 2, 54, 167, 240, 0, 124, 59, 63, 150,
+            47, 91, 167, 147, 209, 154, 96, 30, 187, 29, 244, 3,
+        ],
+        [
+            68, 245, 53, 32, 146, 110, 200, 31, 189, 90, 56, 120, 69, 190, 183, 223, 133, 169, 106,
+            36, 236, 225, 135, 56, 189, 207, 166, 167, 130, 42, 23, 109,
+        ],
+        [
+            144, 50, 147, 216, 242, 40, 126, 190, 16, 226, 55, 77, 193, 165, 62, 11, 200, 135, 229,
+            146, 105, 159, 2, 208, 119, 213, 38, 60, 221, 85, 96, 28,
+        ],
+        [
+            2, 98, 42, 206, 143, 115, 3, 163, 28, 175, 198, 63, 143, 196, 143, 220, 22, 225, 200,
+            200, 210, 52, 178, 240, 214, 104, 82, 130, 169, 7, 96, 49,
+        ],
+        [
+            32, 112, 111, 215, 136, 178, 114, 10, 30, 210, 165, 218, 212, 149, 43, 1, 244, 19, 188,
+            240, 231, 86, 77, 232, 205, 200, 22, 104, 158, 45, 185, 95,
+        ],
+        [
+            188, 232, 63, 139, 165, 221, 47, 165, 114, 134, 76, 36, 186, 24, 16, 249, 82, 43, 198,
+            0, 74, 254, 149, 135, 122, 199, 50, 65, 202, 253, 171, 66,
+        ],
+        [
+            228, 84, 158, 225, 107, 154, 160, 48, 153, 202, 32, 140, 103, 173, 175, 202, 250, 76,
+            63, 62, 78, 83, 3, 222, 96, 38, 227, 202, 143, 248, 68, 96,
+        ],
+        [
+            170, 82, 224, 0, 223, 46, 22, 245, 95, 177, 3, 47, 195, 59, 196, 39, 66, 218, 214, 189,
+            90, 143, 192, 190, 1, 103, 67, 108, 89, 72, 80, 31,
+        ],
+        [
+            70, 55, 107, 128, 244, 9, 178, 157, 194, 181, 246, 240, 197, 37, 145, 153, 8, 150, 229,
+            113, 111, 65, 71, 124, 211, 0, 133, 171, 127, 16, 48, 30,
+        ],
+        [
+            224, 196, 24, 247, 200, 217, 196, 205, 215, 57, 91, 147, 234, 18, 79, 58, 217, 144, 33,
+            187, 104, 29, 252, 51, 2, 169, 217, 154, 46, 83, 230, 78,
+        ],
+    ];
+
+    // RFC9496 Appendix A.2: invalid encodings
+    const BAD_ENCODINGS: [&str; 29] = [
+        // non-canonical field encodings
+        "00ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+        "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f",
+        "f3ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f",
+        "edffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f",
+        // negative field elements
+        "0100000000000000000000000000000000000000000000000000000000000000",
+        "01ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f",
+        "ed57ffd8c914fb201471d1c3d245ce3c746fcbe63a3679d51b6a516ebebe0e20",
+        "c34c4e1826e5d403b78e246e88aa051c36ccf0aafebffe137d148a2bf9104562",
+        "c940e5a4404157cfb1628b108db051a8d439e1a421394ec4ebccb9ec92a8ac78",
+        "47cfc5497c53dc8e61c91d17fd626ffb1c49e2bca94eed052281b510b1117a24",
+        "f1c6165d33367351b0da8f6e4511010c68174a03b6581212c71c0e1d026c3c72",
+        "87260f7a2f12495118360f02c26a470f450dadf34a413d21042b43b9d93e1309",
+        // non-square x^2
+        "26948d35ca62e643e26a83177332e6b6afeb9d08e4268b650f1f5bbd8d81d371",
+        "4eac077a713c57b4f4397629a4145982c661f48044dd3f96427d40b147d9742f",
+        "de6a7b00deadc788eb6b6c8d20c0ae96c2f2019078fa604fee5b87d6e989ad7b",
+        "bcab477be20861e01e4a0e295284146a510150d9817763caf1a6f4b422d67042",
+        "2a292df7e32cababbd9de088d1d1abec9fc0440f637ed2fba145094dc14bea08",
+        "f4a9e534fc0d216c44b218fa0c42d99635a0127ee2e53c712f70609649fdff22",
+        "8268436f8c4126196cf64b3c7ddbda90746a378625f9813dd9b8457077256731",
+        "2810e5cbc2cc4d4eece54f61c6f69758e289aa7ab440b3cbeaa21995c2f4232b",
+        // negative xy value
+        "3eb858e78f5a7254d8c9731174a94f76755fd3941c0ac93735c07ba14579630e",
+        "a45fdc55c76448c049a1ab33f17023edfb2be3581e9c7aade8a6125215e04220",
+        "d483fe813c6ba647ebbfd3ec41adca1c6130c2beeee9d9bf065c8d151c5f396e",
+        "8a2e1d30050198c65a54483123960ccc38aef6848e1ec8f5f780e8523769ba32",
+        "32888462f8b486c68ad7dd9610be5192bbeaf3b443951ac1a8118419d9fa097b",
+        "227142501b9d4355ccba290404bde41575b037693cef1f438c47f8fbf35d1165",
+        "5c37cc491da847cfeb9281d407efc41e15144c876e0170b499a96a22ed31e01e",
+        "445425117cb8c90edcbc7c1cc0e74f747f2c1efa5630a967c64f287792a48a4b",
+        // s = -1, which causes y = 0
+        "ecffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f",
+    ];
+
+    #[test]
+    fn generator_multiples() {
+        let mut p = RistrettoPoint::IDENTITY;
+        let base = RistrettoPoint::mul_base(&Scalar::ONE);
+        for (i, expected) in GENERATOR_MULTIPLES.iter().enumerate() {
+            assert_eq!(&p.encode(), expected, "multiple {}", i);
+
+            let mut s = [0u8; 32];
+            s[0] = i as u8;
+            let q = RistrettoPoint::mul_base(&Scalar::from_bytes(&s));
+            assert_eq!(&q.encode(), expected, "mul_base {}", i);
+            assert_eq!(
+                &(&base * &Scalar::from_bytes(&s)).encode(),
+                expected,
+                "mul {}",
+                i
+            );
+
+            let decoded = RistrettoPoint::decode(expected).expect("valid encoding");
+            assert_eq!(decoded, p);
+            assert_eq!(&decoded.encode(), expected);
+
+            p = &p + &base;
+        }
+    }
+
+    #[test]
+    fn bad_encodings() {
+        for bad in BAD_ENCODINGS.iter() {
+            let bytes: [u8; 32] = decode_hex(bad.as_bytes()).unwrap().try_into().unwrap();
+            assert!(
+                RistrettoPoint::decode(&bytes).is_none(),
+                "bad encoding {}",
+                bad
+            );
+        }
+    }
+
+    #[test]
+    fn arithmetic() {
+        let a = RistrettoPoint::mul_base(&Scalar::from_uniform_bytes(&[1; 64]));
+        let b = RistrettoPoint::mul_base(&Scalar::from_uniform_bytes(&[2; 64]));
+        assert_eq!(&(&a + &b) - &b, a);
+        assert_eq!(&a + &(-&a), RistrettoPoint::IDENTITY);
+        assert_eq!(&a - &a, RistrettoPoint::IDENTITY);
+        assert_eq!(&a + &b, &b + &a);
+
+        let s = Scalar::from_uniform_bytes(&[3; 64]);
+        assert_eq!(&(&a + &b) * &s, &(&a * &s) + &(&b * &s));
+    }
+
+    #[test]
+    fn torsion_equivalence() {
+        // adding the 4-torsion point (sqrt(-1), 0) doesn't change the element
+        let p = RistrettoPoint::mul_base(&Scalar::from_uniform_bytes(&[5; 64]));
+        let torsion = Ge {
+            x: Fe::SQRTM1,
+            y: Fe::ZERO,
+            z: Fe::ONE,
+            t: Fe::ZERO,
+        };
+        let q = RistrettoPoint(ge_add(&p.0, &torsion));
+        assert_eq!(p, q);
+        assert_eq!(p.encode(), q.encode());
+    }
+
+    #[cfg(feature = "sha2")]
+    #[test]
+    fn map_to_point() {
+        use crate::hashing::sha2::Sha512;
+
+        // RFC9496 Appendix A.3: hash to group
+        let vectors: [(&str, [u8; 32]); 7] = [
+            (
+                "Ristretto is traditionally a short shot of espresso coffee",
+                [
+                    48, 102, 248, 42, 26, 116, 125, 69, 18, 13, 23, 64, 241, 67, 88, 83, 26, 143,
+                    4, 187, 255, 230, 168, 25, 248, 109, 254, 80, 244, 74, 10, 70,
+                ],
+            ),
+            (
+                "made with the normal amount of ground coffee but extracted with",
+                [
+                    242, 110, 91, 111, 125, 54, 45, 45, 42, 148, 197, 208, 231, 96, 44, 180, 119,
+                    60, 149, 162, 229, 195, 26, 100, 241, 51, 24, 159, 167, 110, 214, 27,
+                ],
+            ),
+            (
+                "about half the amount of water in the same amount of time",
+                [
+                    0, 108, 205, 42, 158, 104, 103, 230, 162, 197, 206, 168, 61, 51, 2, 204, 157,
+                    225, 40, 221, 42, 154, 87, 221, 142, 231, 185, 215, 255, 224, 40, 38,
+                ],
+            ),
+            (
+                "by using a finer grind.",
+                [
+                    248, 240, 200, 124, 242, 55, 149, 60, 88, 144, 174, 195, 153, 129, 105, 0, 93,
+                    174, 62, 202, 31, 187, 4, 84, 140, 99, 89, 83, 200, 23, 249, 42,
+                ],
+            ),
+            (
+                "This produces a concentrated shot of coffee per volume.",
+                [
+                    174, 129, 231, 222, 223, 32, 164, 151, 225, 12, 48, 74, 118, 92, 23, 103, 164,
+                    45, 110, 6, 2, 151, 88, 210, 215, 232, 239, 124, 196, 196, 17, 121,
+                ],
+            ),
+            (
+                "Just pulling a normal shot short will produce a weaker shot",
+                [
+                    226, 112, 86, 82, 255, 159, 94, 68, 211, 232, 65, 191, 28, 37, 28, 247, 221,
+                    219, 119, 209, 64, 135, 13, 26, 178, 237, 100, 241, 169, 206, 134, 40,
+                ],
+            ),
+            (
+                "and is not a Ristretto as some believe.",
+                [
+                    128, 189, 7, 38, 37, 17, 205, 222, 72, 99, 248, 167, 67, 76, 239, 105, 103, 80,
+                    104, 28, 185, 81, 14, 234, 85, 112, 136, 247, 109, 158, 80, 101,
+                ],
+            ),
+        ];
+        for (label, expected) in vectors.iter() {
+            let hash = Sha512::new().update(label.as_bytes()).finalize();
+            let p = RistrettoPoint::map_to_point(&hash);
+            assert_eq!(&p.encode(), expected, "{}", label);
+        }
+    }
+}
@@ -12,7 +12,71 @@ pub use scalar32::*;
 #[cfg(not(any(any(target_arch = "arm"), feature = "force-32bits")))]
 pub use scalar64::*;
 
+use crate::constant_time::{CtOption, CtZero};
+use core::ops::{Add, Mul, Neg, Sub};
+
+/// The order of the field minus 2 (`l - 2`) in little endian, used as the exponent for inversion
+const L_MINUS_TWO: [u8; 32] = [
+    0xeb, 0xd3, 0xf5, 0x5c, 0x1a, 0x63, 0x12, 0x58, 0xd6, 0x9c, 0xf7, 0xa2, 0xde, 0xf9, 0xde, 0x14,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x10,
+];
+
+/// The order of the field minus 1 (`l - 1`), which is `-1` in the field
+const MINUS_ONE: Scalar = Scalar::from_bytes(&[
+    0xec, 0xd3, 0xf5, 0x5c, 0x1a, 0x63, 0x12, 0x58, 0xd6, 0x9c, 0xf7, 0xa2, 0xde, 0xf9, 0xde, 0x14,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x10,
+]);
+
+impl Add for &Scalar {
+    type Output = Scalar;
+
+    fn add(self, rhs: &Scalar) -> Scalar {
+        muladd(self, &Scalar::ONE, rhs)
+    }
+}
+
+impl Sub for &Scalar {
+    type Output = Scalar;
+
+    fn sub(self, rhs: &Scalar) -> Scalar {
+        muladd(rhs, &MINUS_ONE, self)
+    }
+}
+
+impl Neg for &Scalar {
+    type Output = Scalar;
+
+    fn neg(self) -> Scalar {
+        muladd(self, &MINUS_ONE, &Scalar::ZERO)
+    }
+}
+
+impl Mul for &Scalar {
+    type Output = Scalar;
+
+    fn mul(self, rhs: &Scalar) -> Scalar {
+        muladd(self, rhs, &Scalar::ZERO)
+    }
+}
+
 impl Scalar {
+    /// Compute the multiplicative inverse of the scalar modulo the order of the field
+    ///
+    /// This uses Fermat's little theorem: `s^(l-2) = s^-1 mod l`. The exponent is a
+    /// public constant so the sequence of operations doesn't depend on the scalar value.
+    ///
+    /// The result is not present if the scalar is zero, as zero has no inverse
+    pub fn invert(&self) -> CtOption<Scalar> {
+        let mut r = Scalar::ONE;
+        for i in (0..253).rev() {
+            r = &r * &r;
+            if (L_MINUS_TWO[i >> 3] >> (i & 7)) & 1 == 1 {
+                r = &r * self;
+            }
+        }
+        CtOption::from(((&self.to_bytes()).ct_nonzero(), r))
+    }
+
     #[allow(clippy::needless_range_loop)]
     pub(crate) fn slide(&self) -> [i8; 256] {
         let mut r = self.bits();
@@ -47,6 +111,89 @@ impl Scalar {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::curve25519::testrng::GeneratorOf;
+
+    fn next_scalar(gen: &mut crate::curve25519::testrng::GeneratorRaw) -> Scalar {
+        let bytes: [u8; 64] = gen.bytes();
+        Scalar::from_uniform_bytes(&bytes)
+    }
+
+    #[test]
+    fn canonical() {
+        const L: [u8; 32] = [
+            237, 211, 245, 92, 26, 99, 18, 88, 214, 156, 247, 162, 222, 249, 222, 20, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 16,
+        ];
+        const LM1: [u8; 32] = [
+            236, 211, 245, 92, 26, 99, 18, 88, 214, 156, 247, 162, 222, 249, 222, 20, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 16,
+        ];
+        const LP1: [u8; 32] = [
+            238, 211, 245, 92, 26, 99, 18, 88, 214, 156, 247, 162, 222, 249, 222, 20, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 16,
+        ];
+        assert!(Scalar::from_bytes_canonical(&Scalar::ZERO.to_bytes()).is_some());
+        assert!(Scalar::from_bytes_canonical(&Scalar::ONE.to_bytes()).is_some());
+        // order
+        assert!(Scalar::from_bytes_canonical(&LM1).is_some());
+        assert!(Scalar::from_bytes_canonical(&L).is_none());
+        assert!(Scalar::from_bytes_canonical(&LP1).is_none());
+        assert!(Scalar::from_bytes_canonical(&[0xff; 32]).is_none());
+        // bigger than L in the lowest byte only, but lower in the most significant byte
+        let mut lower = L;
+        lower[0] = 0xff;
+        lower[31] = 0x0f;
+        assert!(Scalar::from_bytes_canonical(&lower).is_some());
+    }
+
+    #[test]
+    fn invert() {
+        assert!(Scalar::ZERO.invert().into_option().is_none());
+        assert_eq!(Scalar::ONE.invert().into_option(), Some(Scalar::ONE));
+        for s in GeneratorOf::new(0, 20, next_scalar) {
+            let inv = s.invert().into_option().expect("non zero scalar");
+            assert_eq!(&s * &inv, Scalar::ONE);
+        }
+    }
+
+    #[test]
+    fn add_sub_neg() {
+        assert_eq!(&Scalar::ONE + &MINUS_ONE, Scalar::ZERO);
+        assert_eq!(-&Scalar::ONE, MINUS_ONE);
+        assert_eq!(-&Scalar::ZERO, Scalar::ZERO);
+        for s in GeneratorOf::new(0, 20, next_scalar) {
+            let t = &s * &s;
+            assert_eq!(&(&s + &t) - &t, s);
+            assert_eq!(&s + &(-&s), Scalar::ZERO);
+            assert_eq!(&Scalar::ZERO - &s, -&s);
+            assert_eq!(&(&s + &s) - &s, s);
+        }
+    }
+
+    #[test]
+    fn conditional_select() {
+        use crate::constant_time::Choice;
+        for s in GeneratorOf::new(0, 20, next_scalar) {
+            let t = -&s;
+            assert_eq!(Scalar::conditional_select(&s, &t, Choice(0)), s);
+            assert_eq!(Scalar::conditional_select(&s, &t, Choice(1)), t);
+        }
+    }
+
+    #[cfg(feature = "sha2")]
+    #[test]
+    fn from_uniform_bytes_sha512() {
+        use crate::hashing::sha2::Sha512;
+
+        let hash = Sha512::new().update(b"cryptoxide").finalize();
+        assert_eq!(
+            Scalar::from_uniform_bytes(&hash).to_bytes(),
+            [
+                18, 116, 243, 205, 118, 196, 33, 87, 22, 95, 137, 149, 117, 46, 160, 67, 209, 167,
+                24, 45, 77, 246, 190, 144, 96, 111, 97, 141, 153, 157, 79, 9
+            ]
+        );
+    }
 
     struct MulAddIv {
         a: [u8; 32],
@@ -1,30 +1,62 @@
 use super::super::fe::load::{load_3i, load_4i};
+use crate::constant_time::{ct_select, Choice};
+use crate::secure_zero::Zeroize;
 
+/// Scalar in the field ℤ/2^252 + 27742317777372353535851937790883648493)
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Scalar([u8; 32]);
 
+impl Drop for Scalar {
+    fn drop(&mut self) {
+        self.0.zeroize()
+    }
+}
+
 impl Scalar {
+    /// The 0 Scalar constant
     pub const ZERO: Self = Scalar([
         0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
         0, 0,
     ]);
 
+    /// The 1 Scalar constant
+    pub const ONE: Self = Scalar([
+        1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        0, 0,
+    ]);
+
+    /// Create a Scalar from bytes
     pub const fn from_bytes(bytes: &[u8; 32]) -> Self {
         Scalar(*bytes)
     }
 
+    /// Serialize the Scalar to its 32 bytes little-endian representation
     pub const fn to_bytes(&self) -> [u8; 32] {
         self.0
     }
 
+    /// Select in constant time between two scalars
+    ///
+    /// Returns `a` if `choice` is false and `b` if `choice` is true
+    pub fn conditional_select(a: &Scalar, b: &Scalar, choice: Choice) -> Scalar {
+        Scalar(ct_select(&a.0, &b.0, choice))
+    }
+
+    /// Same as from_bytes but check whether the value is less than the order of the `Scalar` Field.
     pub fn from_bytes_canonical(bytes: &[u8; 32]) -> Option<Self> {
+        // order of the field in little endian
         const L: [u8; 32] = [
-            0x10, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-            0x00, 0x00, 0x14, 0xde, 0xf9, 0xde, 0xa2, 0xf7, 0x9c, 0xd6, 0x58, 0x12, 0x63, 0x1a,
-            0x5c, 0xf5, 0xd3, 0xed,
+            0xed, 0xd3, 0xf5, 0x5c, 0x1a, 0x63, 0x12, 0x58, 0xd6, 0x9c, 0xf7, 0xa2, 0xde, 0xf9,
+            0xde, 0x14, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x10,
         ];
 
-        fn check_s_lt_l(s: &[u8; 32]) -> bool {
+        // return true if s >= L, by looking from the most significant byte
+        // for the first byte that differs between s and L.
+        //
+        // c is set to 1 if this byte is lesser in s than in L (s < L),
+        // n is set to 0 once a differing byte has been found.
+        fn check_s_ge_l(s: &[u8; 32]) -> bool {
             let mut c: u8 = 0;
             let mut n: u8 = 1;
 
@@ -42,7 +74,7 @@ impl Scalar {
             c == 0
         }
 
-        if check_s_lt_l(bytes) {
+        if check_s_ge_l(bytes) {
             None
         } else {
             Some(Scalar::from_bytes(bytes))
@@ -73,6 +105,14 @@ impl Scalar {
         es
     }
 
+    /// Create a new scalar from 64 bytes (512 bits) reducing
+    /// the scalar to an element of the field
+    #[deprecated(since = "0.6.0", note = "use `from_uniform_bytes`")]
+    #[must_use]
+    pub fn reduce_from_wide_bytes(s: &[u8; 64]) -> Scalar {
+        Self::from_uniform_bytes(s)
+    }
+
     /// Create a new scalar from 64 bytes (512 bits) reducing
     /// the scalar to an element of the field
     ///
@@ -80,9 +120,13 @@ impl Scalar {
     /// s[0]+256*s[1]+...+256^63*s[63] = s
     ///
     /// And the output scalar is a `s % order of field`
+    ///
+    /// This is the standard way to hash to a scalar: hash the input
+    /// to 64 bytes (e.g. with SHA512) and reduce the output with this function.
+    /// The 512 bits of input make the bias of the reduction negligible.
     #[rustfmt::skip]
     #[must_use]
-    pub fn reduce_from_wide_bytes(s: &[u8; 64]) -> Scalar {
+    pub fn from_uniform_bytes(s: &[u8; 64]) -> Scalar {
         let mut s0: i64 = 2097151 & load_3i(s);
         let mut s1: i64 = 2097151 & (load_4i(&s[2..6]) >> 5);
         let mut s2: i64 = 2097151 & (load_3i(&s[5..8]) >> 2);
@@ -4,10 +4,19 @@
 //!
 //! scalar is backed by 5 Limbs in 56 bits unsaturated (except last)
 
+use crate::constant_time::{ct_array64_select, Choice};
+use crate::secure_zero::Zeroize;
+
 /// Scalar in the field ℤ/2^252 + 27742317777372353535851937790883648493)
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Scalar([u64; 5]);
 
+impl Drop for Scalar {
+    fn drop(&mut self) {
+        self.0.zeroize()
+    }
+}
+
 /// Order of Scalar :
 ///
 /// $M = 2^252 + 27742317777372353535851937790883648493$
@@ -116,6 +125,13 @@ impl Scalar {
         Scalar([out0, out1, out2, out3, out4])
     }
 
+    /// Select in constant time between two scalars
+    ///
+    /// Returns `a` if `choice` is false and `b` if `choice` is true
+    pub fn conditional_select(a: &Scalar, b: &Scalar, choice: Choice) -> Scalar {
+        Scalar(ct_array64_select(&a.0, &b.0, choice))
+    }
+
     /// Same as from_bytes but check whether the value is less than the order of the `Scalar` Field.
     pub fn from_bytes_canonical(bytes: &[u8; 32]) -> Option<Self> {
         let scalar = Self::from_bytes(bytes);
@@ -261,6 +277,14 @@ const fn barrett_reduce256(q1: &[u64; 5], r1: &[u64; 5]) -> [u64; 5] {
 }
 
 impl Scalar {
+    /// Create a new scalar from 64 bytes (512 bits) reducing
+    /// the scalar to an element of the field
+    #[deprecated(since = "0.6.0", note = "use `from_uniform_bytes`")]
+    #[must_use]
+    pub const fn reduce_from_wide_bytes(s: &[u8; 64]) -> Scalar {
+        Self::from_uniform_bytes(s)
+    }
+
     /// Create a new scalar from 64 bytes (512 bits) reducing
     /// the scalar to an element of the field
     ///
@@ -268,8 +292,12 @@ impl Scalar {
     /// `s=s[0]+256*s[1]+...+256^63*s[63]`
     ///
     /// And the output scalar is a `s % order of field`
+    ///
+    /// This is the standard way to hash to a scalar: hash the input
+    /// to 64 bytes (e.g. with SHA512) and reduce the output with this function.
+    /// The 512 bits of input make the bias of the reduction negligible.
     #[must_use]
-    pub const fn reduce_from_wide_bytes(s: &[u8; 64]) -> Scalar {
+    pub const fn from_uniform_bytes(s: &[u8; 64]) -> Scalar {
         // load 8 bytes from input[ofs..ofs+7] as little endian u64
         #[inline]
         const fn load(bytes: &[u8; 64], ofs: usize) -> u64 {
@@ -381,6 +409,13 @@ mod tests {
         Scalar::from_bytes(&bytes)
     }
 
+    #[test]
+    #[allow(deprecated)]
+    fn reduce_from_wide_bytes_const() {
+        const WIDE: Scalar = Scalar::reduce_from_wide_bytes(&[0xff; 64]);
+        assert_eq!(WIDE, Scalar::from_uniform_bytes(&[0xff; 64]));
+    }
+
     #[test]
     fn serialization() {
         for scalar in GeneratorOf::new(0, 100, next_scalar) {
@@ -434,33 +469,11 @@ mod tests {
         }
     }
 
-    #[test]
-    fn canonical() {
-        const L: [u8; 32] = [
-            237, 211, 245, 92, 26, 99, 18, 88, 214, 156, 247, 162, 222, 249, 222, 20, 0, 0, 0, 0,
-            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 16,
-        ];
-        const LM1: [u8; 32] = [
-            236, 211, 245, 92, 26, 99, 18, 88, 214, 156, 247, 162, 222, 249, 222, 20, 0, 0, 0, 0,
-            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 16,
-        ];
-        const LP1: [u8; 32] = [
-            238, 211, 245, 92, 26, 99, 18, 88, 214, 156, 247, 162, 222, 249, 222, 20, 0, 0, 0, 0,
-            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 16,
-        ];
-        assert!(Scalar::from_bytes_canonical(&Scalar::ZERO.to_bytes()).is_some());
-        assert!(Scalar::from_bytes_canonical(&Scalar::ONE.to_bytes()).is_some());
-        // order
-        assert!(Scalar::from_bytes_canonical(&LM1).is_some());
-        assert!(Scalar::from_bytes_canonical(&L).is_none());
-        assert!(Scalar::from_bytes_canonical(&LP1).is_none());
-    }
-
     #[test]
     fn reduction() {
-        assert_eq!(Scalar::reduce_from_wide_bytes(&[0; 64]).to_bytes(), [0; 32]);
+        assert_eq!(Scalar::from_uniform_bytes(&[0; 64]).to_bytes(), [0; 32]);
         assert_eq!(
-            Scalar::reduce_from_wide_bytes(&[
+            Scalar::from_uniform_bytes(&[
                 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
                 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
                 0, 0, 0, 0, 0, 0, 0, 0,
@@ -473,7 +486,7 @@ mod tests {
         );
 
         assert_eq!(
-            Scalar::reduce_from_wide_bytes(&[
+            Scalar::from_uniform_bytes(&[
                 30, 1, 102, 252, 230, 223, 126, 62, 154, 62, 25, 173, 159, 16, 157, 227, 21, 140,
                 223, 132, 84, 209, 86, 118, 35, 85, 26, 144, 12, 4, 76, 170, 93, 151, 77, 147, 32,
                 213, 10, 135, 235, 26, 71, 94, 108, 45, 193, 229, 106, 233, 198, 109, 246, 81, 108,
@@ -33,6 +33,12 @@ use crate::chacha20::ChaCha;
 /// * bytes<N>
 /// * fill_bytes<N>
 /// * fill_slice
+///
+/// The position in the stream of random data can be changed with `seek`, which allows
+/// to reproduce non-sequential parts of the stream.
+///
+/// The stream of random data is limited to 2^38 bytes (256 GiB, the limit of the
+/// ChaCha counter), and generating data past this limit panics.
 pub struct Drg<const ROUNDS: usize>(ChaCha<ROUNDS>);
 
 impl<const ROUNDS: usize> Drg<ROUNDS> {
@@ -41,6 +47,22 @@ impl<const ROUNDS: usize> Drg<ROUNDS> {
         Self(ChaCha::new(seed, &[0; 12]))
     }
 
+    /// Set the position of the generator in the stream of random data, in bytes
+    ///
+    /// The following random data is the same as if exactly `byte_offset` bytes
+    /// had been generated since the creation of the generator.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the offset is not less than 2^38 bytes (2^32 blocks of 64 bytes)
+    pub fn seek(&mut self, byte_offset: u64) {
+        assert!(byte_offset < (1 << 38));
+        self.0.seek((byte_offset / 64) as u32);
+        let mut skip = [0u8; 64];
+        self.0
+            .process_mut(&mut skip[0..(byte_offset % 64) as usize]);
+    }
+
     /// Return the next N bytes of random data as a byte array
     pub fn bytes<const N: usize>(&mut self) -> [u8; N] {
         let mut out = [0; N];
@@ -68,3 +90,28 @@ impl<const ROUNDS: usize> Drg<ROUNDS> {
         u32::from_be_bytes(self.bytes())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Drg;
+
+    #[test]
+    fn seek() {
+        let seed = [7u8; 32];
+        let mut stream = [0u8; 300];
+        Drg::<20>::new(&seed).fill_slice(&mut stream);
+
+        let mut drg = Drg::<20>::new(&seed);
+        for offset in [0, 1, 63, 64, 65, 200, 127, 3] {
+            drg.seek(offset as u64);
+            let out = drg.bytes::<80>();
+            assert_eq!(&out[..], &stream[offset..offset + 80], "offset {}", offset);
+        }
+
+        drg.seek(296);
+        assert_eq!(
+            drg.u32(),
+            u32::from_be_bytes([stream[296], stream[297], stream[298], stream[299]])
+        );
+    }
+}
@@ -0,0 +1,334 @@
+//! Hash_DRBG Deterministic Random Bit Generator
+//!
+//! This module implements the Hash_DRBG mechanism as specified in
+//! [NIST SP 800-90A Rev. 1 section 10.1.1][1], using any hash function
+//! implementing [`Digest`], typically SHA-256 or SHA-512.
+//!
+//! The generator doesn't gather entropy by itself: the entropy input has to
+//! come from an entropy source (see the getrandom package), and should be at
+//! least as long as the security strength wanted (32 bytes for SHA-256 and SHA-512).
+//!
+//! ```
+//! use cryptoxide::{drg::hash_drbg::HashDrbg, sha2::Sha256};
+//!
+//! let entropy = [1u8; 32];
+//! let nonce = [2u8; 16];
+//! let mut drbg = HashDrbg::instantiate(Sha256::new(), &entropy, &nonce, b"my application");
+//!
+//! let mut random = [0u8; 48];
+//! drbg.generate(&mut random, None).unwrap();
+//! ```
+//!
+//! [1]: <https://nvlpubs.nist.gov/nistpubs/SpecialPublications/NIST.SP.800-90Ar1.pdf>
+
+use crate::digest::Digest;
+use crate::secure_zero::Zeroize;
+
+// seedlen in bytes for the hash functions with an output of at most 256 bits (440 bits),
+// and for the hash functions with a longer output (888 bits), from SP 800-90A table 2
+const SEEDLEN_SMALL: usize = 55;
+const SEEDLEN_LARGE: usize = 111;
+
+// largest digest output supported, in bytes
+const MAX_OUTLEN: usize = 64;
+
+/// Maximum number of bytes that can be generated by a single call to `generate` (2^19 bits)
+pub const MAX_BYTES_PER_REQUEST: usize = 1 << 16;
+
+/// Number of calls to `generate` after which the generator needs to be reseeded (2^48)
+pub const RESEED_INTERVAL: u64 = 1 << 48;
+
+/// Hash_DRBG errors
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DrbgError {
+    /// The generator has reached its reseed interval, and `reseed` needs to be called
+    ReseedRequired,
+    /// The requested output is longer than `MAX_BYTES_PER_REQUEST`
+    RequestTooLarge,
+}
+
+impl core::fmt::Display for DrbgError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            DrbgError::ReseedRequired => write!(f, "reseed required"),
+            DrbgError::RequestTooLarge => write!(f, "request too large"),
+        }
+    }
+}
+
+/// Hash_DRBG state parametrized by the hashing function
+pub struct HashDrbg<D: Digest> {
+    digest: D,
+    v: [u8; SEEDLEN_LARGE],
+    c: [u8; SEEDLEN_LARGE],
+    seedlen: usize,
+    reseed_counter: u64,
+}
+
+impl<D: Digest> Drop for HashDrbg<D> {
+    fn drop(&mut self) {
+        self.v.zeroize();
+        self.c.zeroize();
+    }
+}
+
+// Hash all the parts into out, which is of the size of the digest output
+fn hash<D: Digest>(digest: &mut D, parts: &[&[u8]], out: &mut [u8]) {
+    digest.reset();
+    for part in parts {
+        digest.input(part);
+    }
+    digest.result(out);
+}
+
+// The Hash_df derivation function (SP 800-90A section 10.3.1), filling all the output
+fn hash_df<D: Digest>(digest: &mut D, input: &[&[u8]], output: &mut [u8]) {
+    let outlen = digest.output_bytes();
+    let bits = ((output.len() * 8) as u32).to_be_bytes();
+    let mut block = [0u8; MAX_OUTLEN];
+    for (i, chunk) in output.chunks_mut(outlen).enumerate() {
+        let counter = [(i + 1) as u8];
+        digest.reset();
+        digest.input(&counter);
+        digest.input(&bits);
+        for part in input {
+            digest.input(part);
+        }
+        digest.result(&mut block[..outlen]);
+        chunk.copy_from_slice(&block[..chunk.len()]);
+    }
+    block.zeroize();
+}
+
+// acc = (acc + x) mod 2^(8*acc.len()), with both numbers in big endian and x not longer than acc
+fn add_be(acc: &mut [u8], x: &[u8]) {
+    let mut carry = 0u16;
+    let offset = acc.len() - x.len();
+    for (i, a) in acc.iter_mut().enumerate().rev() {
+        let b = if i >= offset { x[i - offset] } else { 0 };
+        let sum = *a as u16 + b as u16 + carry;
+        *a = sum as u8;
+        carry = sum >> 8;
+    }
+}
+
+impl<D: Digest> HashDrbg<D> {
+    /// Instantiate the generator from the entropy input, a nonce and an optional
+    /// (can be empty) personalization string
+    ///
+    /// # Panics
+    ///
+    /// Panics if the digest output is longer than 512 bits
+    pub fn instantiate(
+        mut digest: D,
+        entropy: &[u8],
+        nonce: &[u8],
+        personalization: &[u8],
+    ) -> Self {
+        let outlen = digest.output_bytes();
+        assert!(outlen <= MAX_OUTLEN);
+        let seedlen = if outlen <= 32 {
+            SEEDLEN_SMALL
+        } else {
+            SEEDLEN_LARGE
+        };
+        let mut v = [0u8; SEEDLEN_LARGE];
+        hash_df(
+            &mut digest,
+            &[entropy, nonce, personalization],
+            &mut v[..seedlen],
+        );
+        let mut drbg = Self {
+            digest,
+            v,
+            c: [0u8; SEEDLEN_LARGE],
+            seedlen,
+            reseed_counter: 1,
+        };
+        drbg.derive_c();
+        drbg
+    }
+
+    // C = Hash_df(0x00 || V, seedlen)
+    fn derive_c(&mut self) {
+        let seedlen = self.seedlen;
+        hash_df(
+            &mut self.digest,
+            &[&[0x00], &self.v[..seedlen]],
+            &mut self.c[..seedlen],
+        );
+    }
+
+    /// Reseed the generator with new entropy input and an optional (can be empty) additional input
+    pub fn reseed(&mut self, entropy: &[u8], additional: &[u8]) {
+        let seedlen = self.seedlen;
+        let mut v = [0u8; SEEDLEN_LARGE];
+        hash_df(
+            &mut self.digest,
+            &[&[0x01], &self.v[..seedlen], entropy, additional],
+            &mut v[..seedlen],
+        );
+        self.v = v;
+        v.zeroize();
+        self.derive_c();
+        self.reseed_counter = 1;
+    }
+
+    /// Fill the output with random bytes, mixing the additional input if any
+    ///
+    /// An error is returned if the output is longer than `MAX_BYTES_PER_REQUEST`,
+    /// or if the generator needs to be reseeded, in which case the state is unchanged.
+    pub fn generate(
+        &mut self,
+        output: &mut [u8],
+        additional: Option<&[u8]>,
+    ) -> Result<(), DrbgError> {
+        if output.len() > MAX_BYTES_PER_REQUEST {
+            return Err(DrbgError::RequestTooLarge);
+        }
+        if self.reseed_counter > RESEED_INTERVAL {
+            return Err(DrbgError::ReseedRequired);
+        }
+
+        let seedlen = self.seedlen;
+        let outlen = self.digest.output_bytes();
+        let mut block = [0u8; MAX_OUTLEN];
+
+        // V = V + Hash(0x02 || V || additional)
+        if let Some(additional) = additional.filter(|a| !a.is_empty()) {
+            hash(
+                &mut self.digest,
+                &[&[0x02], &self.v[..seedlen], additional],
+                &mut block[..outlen],
+            );
+            add_be(&mut self.v[..seedlen], &block[..outlen]);
+        }
+
+        // Hashgen: output the hashes of V, V + 1, V + 2, ...
+        let mut data = self.v;
+        for chunk in output.chunks_mut(outlen) {
+            hash(&mut self.digest, &[&data[..seedlen]], &mut block[..outlen]);
+            chunk.copy_from_slice(&block[..chunk.len()]);
+            add_be(&mut data[..seedlen], &[1]);
+        }
+        data.zeroize();
+
+        // V = V + Hash(0x03 || V) + C + reseed_counter
+        hash(
+            &mut self.digest,
+            &[&[0x03], &self.v[..seedlen]],
+            &mut block[..outlen],
+        );
+        add_be(&mut self.v[..seedlen], &block[..outlen]);
+        add_be(&mut self.v[..seedlen], &self.c[..seedlen]);
+        add_be(&mut self.v[..seedlen], &self.reseed_counter.to_be_bytes());
+        self.reseed_counter += 1;
+        block.zeroize();
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "sha2"))]
+mod tests {
+    use super::{DrbgError, HashDrbg, MAX_BYTES_PER_REQUEST, RESEED_INTERVAL};
+    use crate::encoding::decode_hex;
+    use crate::sha2::{Sha256, Sha512};
+
+    // CAVS 14.3 Hash_DRBG.rsp (no reseed), [SHA-256] [PredictionResistance = False]
+    // [EntropyInputLen = 256] [NonceLen = 128] [PersonalizationStringLen = 0]
+    // [AdditionalInputLen = 0] [ReturnedBitsLen = 1024], COUNT = 0
+    #[test]
+    fn cavs_sha256() {
+        let entropy =
+            decode_hex(b"a65ad0f345db4e0effe875c3a2e71f42c7129d620ff5c119a9ef55f05185e0fb")
+                .unwrap();
+        let nonce = decode_hex(b"8581f9317517276e06e9607ddbcbcc2e").unwrap();
+        let expected = decode_hex(
+            b"d3e160c35b99f340b2628264d1751060e0045da383ff57a57d73a673d2b8d80d\
+             aaf6a6c35a91bb4579d73fd0c8fed111b0391306828adfed528f018121b3febd\
+             c343e797b87dbb63db1333ded9d1ece177cfa6b71fe8ab1da46624ed6415e51c\
+             cde2c7ca86e283990eeaeb91120415528b2295910281b02dd431f4c9f70427df",
+        )
+        .unwrap();
+
+        let mut drbg = HashDrbg::instantiate(Sha256::new(), &entropy, &nonce, &[]);
+        let mut out = [0u8; 128];
+        drbg.generate(&mut out, None).unwrap();
+        drbg.generate(&mut out, Some(&[])).unwrap();
+        assert_eq!(&out[..], &expected[..]);
+    }
+
+    // generated with a python implementation of SP 800-90A, checked against the CAVS vector above
+    #[test]
+    fn personalization_additional_reseed() {
+        let entropy: [u8; 32] = core::array::from_fn(|i| i as u8);
+        let nonce: [u8; 16] = core::array::from_fn(|i| 32 + i as u8);
+        let reseed_entropy: [u8; 32] = core::array::from_fn(|i| 48 + i as u8);
+
+        let mut drbg = HashDrbg::instantiate(Sha512::new(), &entropy, &nonce, b"personalization");
+        let mut out = [0u8; 64];
+        drbg.generate(&mut out, None).unwrap();
+        assert_eq!(
+            &out[..],
+            &decode_hex(
+                b"7599bf7104c39dcf95774273f2dd2b5e980a31be4f83da3e228025cfebc68d20\
+                 2fea2624beae300906af8b9e05df82f9bc913d0f9c5a381207153fe1b4a61f2e"
+            )
+            .unwrap()[..]
+        );
+        let mut out = [0u8; 100];
+        drbg.generate(&mut out, Some(b"additional")).unwrap();
+        assert_eq!(
+            &out[..],
+            &decode_hex(
+                b"459c52573d35fa4b4daf4d31ccedb688c567c83542cb52a8db4ab766b2a79bb7\
+                 b0c72bbdcbb4bd958ff93ed53f003138d1c2607cae25f94f67b9ab2505223edd\
+                 e9566ed8fe90d10d722c972d5a31b3b2eaa8db49312c0165b5de4a16a10c5925\
+                 396a94ad"
+            )
+            .unwrap()[..]
+        );
+
+        let mut drbg = HashDrbg::instantiate(Sha256::new(), &entropy, &nonce, b"personalization");
+        let mut out = [0u8; 40];
+        drbg.generate(&mut out, Some(b"add1")).unwrap();
+        assert_eq!(
+            &out[..],
+            &decode_hex(
+                b"25f682209c868e426e7ec4d10e5f3f57cd712b370aa381281759d702f3a47530a01198279cb9ae11"
+            )
+            .unwrap()[..]
+        );
+        drbg.reseed(&reseed_entropy, b"reseed");
+        let mut out = [0u8; 70];
+        drbg.generate(&mut out, None).unwrap();
+        assert_eq!(
+            &out[..],
+            &decode_hex(
+                b"53351c614bf9bbd61844093cdb43272c15ced811496e0c4412dc51f0a17bb3b0\
+                 3e4f22de6d26c2c56ff85a9b48eba1363d145f894bdb625570332ec35164a0c5\
+                 8798a590f6f9"
+            )
+            .unwrap()[..]
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let mut drbg = HashDrbg::instantiate(Sha256::new(), &[1; 32], &[2; 16], &[]);
+        let mut out = alloc::vec![0u8; MAX_BYTES_PER_REQUEST + 1];
+        assert_eq!(
+            drbg.generate(&mut out, None),
+            Err(DrbgError::RequestTooLarge)
+        );
+        assert!(drbg.generate(&mut out[1..], None).is_ok());
+
+        drbg.reseed_counter = RESEED_INTERVAL + 1;
+        assert_eq!(
+            drbg.generate(&mut out[..1], None),
+            Err(DrbgError::ReseedRequired)
+        );
+        drbg.reseed(&[3; 32], &[]);
+        assert!(drbg.generate(&mut out[..1], None).is_ok());
+    }
+}
@@ -4,3 +4,6 @@
 
 #[cfg(feature = "chacha")]
 pub mod chacha;
+
+#[cfg(feature = "digest")]
+pub mod hash_drbg;
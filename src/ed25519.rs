@@ -72,7 +72,7 @@ fn clamp_scalar(scalar: &mut [u8]) {
 /// and tweaking the first 32 bytes as a scalar using the clamp mechanism in `clamp_scalar`
 ///
 /// SCALAR(32bytes) | RANDOM(32bytes) = CLAMP(SHA512(private_key))
-fn extended_secret(private_key: &[u8; PRIVATE_KEY_LENGTH]) -> [u8; EXTENDED_KEY_LENGTH] {
+pub(crate) fn extended_secret(private_key: &[u8; PRIVATE_KEY_LENGTH]) -> [u8; EXTENDED_KEY_LENGTH] {
     let mut hash_output = Sha512::new().update(private_key).finalize();
     clamp_scalar(&mut hash_output);
     hash_output
@@ -100,7 +100,12 @@ fn extended_scalar_bytes(extended_secret: &[u8; EXTENDED_KEY_LENGTH]) -> &[u8; 3
 
 /// generate the public key associated with an extended secret key
 pub fn extended_to_public(extended_secret: &[u8; EXTENDED_KEY_LENGTH]) -> [u8; PUBLIC_KEY_LENGTH] {
-    let a = Ge::scalarmult_base(&extended_scalar(extended_secret));
+    // the base point multiplication expects a scalar lower than 2^255, which is not the case
+    // for an extended secret that hasn't been clamped, so reduce it modulo the order of the
+    // base point first, which doesn't change the resulting point.
+    let mut wide = [0u8; 64];
+    wide[0..32].copy_from_slice(extended_scalar_bytes(extended_secret));
+    let a = Ge::scalarmult_base(&Scalar::from_uniform_bytes(&wide));
     a.to_bytes()
 }
 
@@ -129,7 +134,7 @@ fn signature_nonce(extended_secret: &[u8; EXTENDED_KEY_LENGTH], message: &[u8])
         .update(&extended_secret[32..64])
         .update(message)
         .finalize();
-    Scalar::reduce_from_wide_bytes(&hash_output)
+    Scalar::from_uniform_bytes(&hash_output)
 }
 
 /// Generate a signature for the given message using a normal ED25519 secret key
@@ -148,7 +153,7 @@ pub fn signature(message: &[u8], keypair: &[u8; KEYPAIR_LENGTH]) -> [u8; SIGNATU
 
     {
         let hram = Sha512::new().update(&signature).update(message).finalize();
-        let hram = Scalar::reduce_from_wide_bytes(&hram);
+        let hram = Scalar::from_uniform_bytes(&hram);
         let r = scalar::muladd(&hram, &extended_scalar(&az), &nonce);
         signature[32..64].copy_from_slice(&r.to_bytes())
     }
@@ -177,7 +182,7 @@ pub fn signature_extended(
 
     {
         let hram = Sha512::new().update(&signature).update(message).finalize();
-        let hram = Scalar::reduce_from_wide_bytes(&hram);
+        let hram = Scalar::from_uniform_bytes(&hram);
         let r = scalar::muladd(&hram, &extended_scalar(extended_secret), &nonce);
         signature[32..64].copy_from_slice(&r.to_bytes())
     }
@@ -194,7 +199,7 @@ pub fn verify(
     let signature_left = <&[u8; 32]>::try_from(&signature[0..32]).unwrap();
     let signature_right = <&[u8; 32]>::try_from(&signature[32..64]).unwrap();
 
-    let a = match Ge::from_bytes(public_key) {
+    let minus_a = match Ge::from_bytes_negate_vartime(public_key) {
         Some(g) => g,
         None => {
             return false;
@@ -220,9 +225,10 @@ pub fn verify(
         .update(public_key)
         .update(message)
         .finalize();
-    let a_scalar = Scalar::reduce_from_wide_bytes(&hash);
+    let a_scalar = Scalar::from_uniform_bytes(&hash);
 
-    let r = GePartial::double_scalarmult_vartime(&a_scalar, a, &signature_scalar);
+    // R = S*B - H(R,A,M)*A
+    let r = GePartial::double_scalarmult_vartime(&a_scalar, minus_a, &signature_scalar);
     let rcheck = r.to_bytes();
 
     CtEqual::ct_eq(&rcheck, signature_left).into()
@@ -242,6 +248,134 @@ pub fn exchange(public_key: &[u8; 32], private_key: &[u8; PRIVATE_KEY_LENGTH]) -
     shared_mont_x
 }
 
+/// The length of an extended secret key followed by its chain code (96 bytes),
+/// as used by the hierarchical derivation
+pub const EXTENDED_CHAIN_KEY_LENGTH: usize = EXTENDED_KEY_LENGTH + 32;
+
+// index from which the derivation is hardened
+#[cfg(feature = "hmac")]
+const HARDENED_INDEX: u32 = 0x8000_0000;
+
+// x + 8 * y where y is 28 bytes, modulo 2^256
+#[cfg(feature = "hmac")]
+fn add_28_mul8(x: &[u8], y: &[u8]) -> [u8; 32] {
+    let mut out = [0u8; 32];
+    let mut carry = 0u16;
+    for i in 0..32 {
+        let y = if i < 28 { (y[i] as u16) << 3 } else { 0 };
+        let r = x[i] as u16 + y + carry;
+        out[i] = r as u8;
+        carry = r >> 8;
+    }
+    out
+}
+
+// x + y modulo 2^256
+#[cfg(feature = "hmac")]
+fn add_256(x: &[u8], y: &[u8]) -> [u8; 32] {
+    let mut out = [0u8; 32];
+    let mut carry = 0u16;
+    for i in 0..32 {
+        let r = x[i] as u16 + y[i] as u16 + carry;
+        out[i] = r as u8;
+        carry = r >> 8;
+    }
+    out
+}
+
+#[cfg(feature = "hmac")]
+fn derive_child_key(
+    parent_extended: &[u8; EXTENDED_CHAIN_KEY_LENGTH],
+    index: u32,
+) -> [u8; EXTENDED_CHAIN_KEY_LENGTH] {
+    use crate::hmac::Hmac;
+    use crate::mac::Mac;
+    use crate::secure_zero::Zeroize;
+
+    let kl = &parent_extended[0..32];
+    let kr = &parent_extended[32..64];
+    let chain_code = &parent_extended[64..96];
+    let index_bytes = index.to_le_bytes();
+
+    let mut zmac = Hmac::new(crate::sha2::Sha512::new(), chain_code);
+    let mut imac = Hmac::new(crate::sha2::Sha512::new(), chain_code);
+    if index >= HARDENED_INDEX {
+        zmac.input(&[0x00]);
+        zmac.input(&parent_extended[0..64]);
+        imac.input(&[0x01]);
+        imac.input(&parent_extended[0..64]);
+    } else {
+        let mut extended = [0u8; EXTENDED_KEY_LENGTH];
+        extended.copy_from_slice(&parent_extended[0..64]);
+        let public = extended_to_public(&extended);
+        extended.zeroize();
+        zmac.input(&[0x02]);
+        zmac.input(&public);
+        imac.input(&[0x03]);
+        imac.input(&public);
+    }
+    zmac.input(&index_bytes);
+    imac.input(&index_bytes);
+
+    let mut z = [0u8; 64];
+    let mut i = [0u8; 64];
+    zmac.raw_result(&mut z);
+    imac.raw_result(&mut i);
+
+    let mut child = [0u8; EXTENDED_CHAIN_KEY_LENGTH];
+    child[0..32].copy_from_slice(&add_28_mul8(kl, &z[0..28]));
+    child[32..64].copy_from_slice(&add_256(kr, &z[32..64]));
+    child[64..96].copy_from_slice(&i[32..64]);
+    z.zeroize();
+    i.zeroize();
+    child
+}
+
+/// Derive the soft (non-hardened) child `index` of an extended secret key with chain code
+///
+/// The key is 96 bytes: the 64 bytes extended secret key (scalar and nonce key), followed
+/// by the 32 bytes chain code, and the derivation follows the BIP32-Ed25519 scheme
+/// of the "HD Wallets for Ed25519" paper, as used by Cardano (derivation scheme V2, with
+/// the index serialized in little endian).
+///
+/// Soft derivation only depends on the public key and chain code of the parent, so
+/// that the child public keys can also be derived without the parent secret key.
+///
+/// The first 64 bytes of the child can be used with [`signature_extended`] and [`extended_to_public`].
+///
+/// # Panics
+///
+/// Panics if the index is not less than 2^31, which is the hardened index range
+#[cfg(feature = "hmac")]
+pub fn derive_child_key_soft(
+    parent_extended: &[u8; EXTENDED_CHAIN_KEY_LENGTH],
+    index: u32,
+) -> [u8; EXTENDED_CHAIN_KEY_LENGTH] {
+    assert!(index < HARDENED_INDEX, "soft derivation index is hardened");
+    derive_child_key(parent_extended, index)
+}
+
+/// Derive the hardened child `index` of an extended secret key with chain code
+///
+/// Same as [`derive_child_key_soft`], but the derivation depends on the parent secret key,
+/// so the child public key cannot be derived from the parent public key.
+///
+/// # Panics
+///
+/// Panics if the index is less than 2^31: hardened indices are in the range 2^31 to 2^32-1,
+/// commonly written with an apostrophe (e.g. `1852'` is 2^31 + 1852).
+#[cfg(feature = "hmac")]
+pub fn derive_child_key_hard(
+    parent_extended: &[u8; EXTENDED_CHAIN_KEY_LENGTH],
+    index: u32,
+) -> [u8; EXTENDED_CHAIN_KEY_LENGTH] {
+    assert!(
+        index >= HARDENED_INDEX,
+        "hard derivation index is not hardened"
+    );
+    derive_child_key(parent_extended, index)
+}
+
 fn edwards_to_montgomery_x(ed_y: &Fe) -> Fe {
     let ed_z = &Fe::ONE;
     let temp_x = ed_z + ed_y;
@@ -255,7 +389,7 @@ fn edwards_to_montgomery_x(ed_y: &Fe) -> Fe {
 
 #[cfg(test)]
 mod tests {
-    use super::{exchange, keypair, signature, verify};
+    use super::{exchange, extended_to_public, keypair, signature, verify};
     use crate::curve25519::{curve25519, curve25519_base};
     use crate::digest::Digest;
     use crate::sha2::Sha512;
@@ -408,4 +542,386 @@ mod tests {
             ],
         );
     }
+
+    enum TestResult {
+        Valid,
+        Invalid,
+    }
+
+    struct Test {
+        comment: &'static str,
+        public_key: [u8; 32],
+        message: &'static [u8],
+        signature: [u8; 64],
+        result: TestResult,
+    }
+
+    #[test]
+    fn malleability_edge_cases() {
+        // malleability edge cases: non canonical S and R, all-zero and low order public keys.
+        // these are not Wycheproof vectors, they are derived from the RFC 8032 test 1 key and
+        // signature. Wycheproof marks the low order public keys as acceptable; the verification
+        // is cofactorless, so they are accepted only when H(R,A,M)*A is the identity.
+        let tests = [
+            Test {
+                comment: "valid signature (RFC 8032 test 1)",
+                public_key: [
+                    0xd7, 0x5a, 0x98, 0x01, 0x82, 0xb1, 0x0a, 0xb7, 0xd5, 0x4b, 0xfe, 0xd3, 0xc9,
+                    0x64, 0x07, 0x3a, 0x0e, 0xe1, 0x72, 0xf3, 0xda, 0xa6, 0x23, 0x25, 0xaf, 0x02,
+                    0x1a, 0x68, 0xf7, 0x07, 0x51, 0x1a,
+                ],
+                message: b"",
+                signature: [
+                    0xe5, 0x56, 0x43, 0x00, 0xc3, 0x60, 0xac, 0x72, 0x90, 0x86, 0xe2, 0xcc, 0x80,
+                    0x6e, 0x82, 0x8a, 0x84, 0x87, 0x7f, 0x1e, 0xb8, 0xe5, 0xd9, 0x74, 0xd8, 0x73,
+                    0xe0, 0x65, 0x22, 0x49, 0x01, 0x55, 0x5f, 0xb8, 0x82, 0x15, 0x90, 0xa3, 0x3b,
+                    0xac, 0xc6, 0x1e, 0x39, 0x70, 0x1c, 0xf9, 0xb4, 0x6b, 0xd2, 0x5b, 0xf5, 0xf0,
+                    0x59, 0x5b, 0xbe, 0x24, 0x65, 0x51, 0x41, 0x43, 0x8e, 0x7a, 0x10, 0x0b,
+                ],
+                result: TestResult::Valid,
+            },
+            Test {
+                comment: "S replaced by S + l",
+                public_key: [
+                    0xd7, 0x5a, 0x98, 0x01, 0x82, 0xb1, 0x0a, 0xb7, 0xd5, 0x4b, 0xfe, 0xd3, 0xc9,
+                    0x64, 0x07, 0x3a, 0x0e, 0xe1, 0x72, 0xf3, 0xda, 0xa6, 0x23, 0x25, 0xaf, 0x02,
+                    0x1a, 0x68, 0xf7, 0x07, 0x51, 0x1a,
+                ],
+                message: b"",
+                signature: [
+                    0xe5, 0x56, 0x43, 0x00, 0xc3, 0x60, 0xac, 0x72, 0x90, 0x86, 0xe2, 0xcc, 0x80,
+                    0x6e, 0x82, 0x8a, 0x84, 0x87, 0x7f, 0x1e, 0xb8, 0xe5, 0xd9, 0x74, 0xd8, 0x73,
+                    0xe0, 0x65, 0x22, 0x49, 0x01, 0x55, 0x4c, 0x8c, 0x78, 0x72, 0xaa, 0x06, 0x4e,
+                    0x04, 0x9d, 0xbb, 0x30, 0x13, 0xfb, 0xf2, 0x93, 0x80, 0xd2, 0x5b, 0xf5, 0xf0,
+                    0x59, 0x5b, 0xbe, 0x24, 0x65, 0x51, 0x41, 0x43, 0x8e, 0x7a, 0x10, 0x1b,
+                ],
+                result: TestResult::Invalid,
+            },
+            Test {
+                comment: "S replaced by S + 8l",
+                public_key: [
+                    0xd7, 0x5a, 0x98, 0x01, 0x82, 0xb1, 0x0a, 0xb7, 0xd5, 0x4b, 0xfe, 0xd3, 0xc9,
+                    0x64, 0x07, 0x3a, 0x0e, 0xe1, 0x72, 0xf3, 0xda, 0xa6, 0x23, 0x25, 0xaf, 0x02,
+                    0x1a, 0x68, 0xf7, 0x07, 0x51, 0x1a,
+                ],
+                message: b"",
+                signature: [
+                    0xe5, 0x56, 0x43, 0x00, 0xc3, 0x60, 0xac, 0x72, 0x90, 0x86, 0xe2, 0xcc, 0x80,
+                    0x6e, 0x82, 0x8a, 0x84, 0x87, 0x7f, 0x1e, 0xb8, 0xe5, 0xd9, 0x74, 0xd8, 0x73,
+                    0xe0, 0x65, 0x22, 0x49, 0x01, 0x55, 0xc7, 0x57, 0x31, 0xfd, 0x62, 0xbc, 0xce,
+                    0x6c, 0x79, 0x05, 0xf6, 0x87, 0x11, 0xc8, 0xac, 0x12, 0xd3, 0x5b, 0xf5, 0xf0,
+                    0x59, 0x5b, 0xbe, 0x24, 0x65, 0x51, 0x41, 0x43, 0x8e, 0x7a, 0x10, 0x8b,
+                ],
+                result: TestResult::Invalid,
+            },
+            Test {
+                comment: "S = l",
+                public_key: [
+                    0xd7, 0x5a, 0x98, 0x01, 0x82, 0xb1, 0x0a, 0xb7, 0xd5, 0x4b, 0xfe, 0xd3, 0xc9,
+                    0x64, 0x07, 0x3a, 0x0e, 0xe1, 0x72, 0xf3, 0xda, 0xa6, 0x23, 0x25, 0xaf, 0x02,
+                    0x1a, 0x68, 0xf7, 0x07, 0x51, 0x1a,
+                ],
+                message: b"",
+                signature: [
+                    0xe5, 0x56, 0x43, 0x00, 0xc3, 0x60, 0xac, 0x72, 0x90, 0x86, 0xe2, 0xcc, 0x80,
+                    0x6e, 0x82, 0x8a, 0x84, 0x87, 0x7f, 0x1e, 0xb8, 0xe5, 0xd9, 0x74, 0xd8, 0x73,
+                    0xe0, 0x65, 0x22, 0x49, 0x01, 0x55, 0xed, 0xd3, 0xf5, 0x5c, 0x1a, 0x63, 0x12,
+                    0x58, 0xd6, 0x9c, 0xf7, 0xa2, 0xde, 0xf9, 0xde, 0x14, 0x00, 0x00, 0x00, 0x00,
+                    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x10,
+                ],
+                result: TestResult::Invalid,
+            },
+            Test {
+                comment: "S with the high bit set",
+                public_key: [
+                    0xd7, 0x5a, 0x98, 0x01, 0x82, 0xb1, 0x0a, 0xb7, 0xd5, 0x4b, 0xfe, 0xd3, 0xc9,
+                    0x64, 0x07, 0x3a, 0x0e, 0xe1, 0x72, 0xf3, 0xda, 0xa6, 0x23, 0x25, 0xaf, 0x02,
+                    0x1a, 0x68, 0xf7, 0x07, 0x51, 0x1a,
+                ],
+                message: b"",
+                signature: [
+                    0xe5, 0x56, 0x43, 0x00, 0xc3, 0x60, 0xac, 0x72, 0x90, 0x86, 0xe2, 0xcc, 0x80,
+                    0x6e, 0x82, 0x8a, 0x84, 0x87, 0x7f, 0x1e, 0xb8, 0xe5, 0xd9, 0x74, 0xd8, 0x73,
+                    0xe0, 0x65, 0x22, 0x49, 0x01, 0x55, 0x5f, 0xb8, 0x82, 0x15, 0x90, 0xa3, 0x3b,
+                    0xac, 0xc6, 0x1e, 0x39, 0x70, 0x1c, 0xf9, 0xb4, 0x6b, 0xd2, 0x5b, 0xf5, 0xf0,
+                    0x59, 0x5b, 0xbe, 0x24, 0x65, 0x51, 0x41, 0x43, 0x8e, 0x7a, 0x10, 0x8b,
+                ],
+                result: TestResult::Invalid,
+            },
+            Test {
+                comment: "R with the sign bit flipped",
+                public_key: [
+                    0xd7, 0x5a, 0x98, 0x01, 0x82, 0xb1, 0x0a, 0xb7, 0xd5, 0x4b, 0xfe, 0xd3, 0xc9,
+                    0x64, 0x07, 0x3a, 0x0e, 0xe1, 0x72, 0xf3, 0xda, 0xa6, 0x23, 0x25, 0xaf, 0x02,
+                    0x1a, 0x68, 0xf7, 0x07, 0x51, 0x1a,
+                ],
+                message: b"",
+                signature: [
+                    0xe5, 0x56, 0x43, 0x00, 0xc3, 0x60, 0xac, 0x72, 0x90, 0x86, 0xe2, 0xcc, 0x80,
+                    0x6e, 0x82, 0x8a, 0x84, 0x87, 0x7f, 0x1e, 0xb8, 0xe5, 0xd9, 0x74, 0xd8, 0x73,
+                    0xe0, 0x65, 0x22, 0x49, 0x01, 0xd5, 0x5f, 0xb8, 0x82, 0x15, 0x90, 0xa3, 0x3b,
+                    0xac, 0xc6, 0x1e, 0x39, 0x70, 0x1c, 0xf9, 0xb4, 0x6b, 0xd2, 0x5b, 0xf5, 0xf0,
+                    0x59, 0x5b, 0xbe, 0x24, 0x65, 0x51, 0x41, 0x43, 0x8e, 0x7a, 0x10, 0x0b,
+                ],
+                result: TestResult::Invalid,
+            },
+            Test {
+                comment: "R encoded with y = p (non canonical)",
+                public_key: [
+                    0xd7, 0x5a, 0x98, 0x01, 0x82, 0xb1, 0x0a, 0xb7, 0xd5, 0x4b, 0xfe, 0xd3, 0xc9,
+                    0x64, 0x07, 0x3a, 0x0e, 0xe1, 0x72, 0xf3, 0xda, 0xa6, 0x23, 0x25, 0xaf, 0x02,
+                    0x1a, 0x68, 0xf7, 0x07, 0x51, 0x1a,
+                ],
+                message: b"",
+                signature: [
+                    0xed, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+                    0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+                    0xff, 0xff, 0xff, 0xff, 0xff, 0x7f, 0x5f, 0xb8, 0x82, 0x15, 0x90, 0xa3, 0x3b,
+                    0xac, 0xc6, 0x1e, 0x39, 0x70, 0x1c, 0xf9, 0xb4, 0x6b, 0xd2, 0x5b, 0xf5, 0xf0,
+                    0x59, 0x5b, 0xbe, 0x24, 0x65, 0x51, 0x41, 0x43, 0x8e, 0x7a, 0x10, 0x0b,
+                ],
+                result: TestResult::Invalid,
+            },
+            Test {
+                comment: "modified message",
+                public_key: [
+                    0xd7, 0x5a, 0x98, 0x01, 0x82, 0xb1, 0x0a, 0xb7, 0xd5, 0x4b, 0xfe, 0xd3, 0xc9,
+                    0x64, 0x07, 0x3a, 0x0e, 0xe1, 0x72, 0xf3, 0xda, 0xa6, 0x23, 0x25, 0xaf, 0x02,
+                    0x1a, 0x68, 0xf7, 0x07, 0x51, 0x1a,
+                ],
+                message: b"\x00",
+                signature: [
+                    0xe5, 0x56, 0x43, 0x00, 0xc3, 0x60, 0xac, 0x72, 0x90, 0x86, 0xe2, 0xcc, 0x80,
+                    0x6e, 0x82, 0x8a, 0x84, 0x87, 0x7f, 0x1e, 0xb8, 0xe5, 0xd9, 0x74, 0xd8, 0x73,
+                    0xe0, 0x65, 0x22, 0x49, 0x01, 0x55, 0x5f, 0xb8, 0x82, 0x15, 0x90, 0xa3, 0x3b,
+                    0xac, 0xc6, 0x1e, 0x39, 0x70, 0x1c, 0xf9, 0xb4, 0x6b, 0xd2, 0x5b, 0xf5, 0xf0,
+                    0x59, 0x5b, 0xbe, 0x24, 0x65, 0x51, 0x41, 0x43, 0x8e, 0x7a, 0x10, 0x0b,
+                ],
+                result: TestResult::Invalid,
+            },
+            Test {
+                comment: "all-zero public key",
+                public_key: [
+                    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                    0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                ],
+                message: b"",
+                signature: [
+                    0xe5, 0x56, 0x43, 0x00, 0xc3, 0x60, 0xac, 0x72, 0x90, 0x86, 0xe2, 0xcc, 0x80,
+                    0x6e, 0x82, 0x8a, 0x84, 0x87, 0x7f, 0x1e, 0xb8, 0xe5, 0xd9, 0x74, 0xd8, 0x73,
+                    0xe0, 0x65, 0x22, 0x49, 0x01, 0x55, 0x5f, 0xb8, 0x82, 0x15, 0x90, 0xa3, 0x3b,
+                    0xac, 0xc6, 0x1e, 0x39, 0x70, 0x1c, 0xf9, 0xb4, 0x6b, 0xd2, 0x5b, 0xf5, 0xf0,
+                    0x59, 0x5b, 0xbe, 0x24, 0x65, 0x51, 0x41, 0x43, 0x8e, 0x7a, 0x10, 0x0b,
+                ],
+                result: TestResult::Invalid,
+            },
+            Test {
+                comment: "all-zero signature",
+                public_key: [
+                    0xd7, 0x5a, 0x98, 0x01, 0x82, 0xb1, 0x0a, 0xb7, 0xd5, 0x4b, 0xfe, 0xd3, 0xc9,
+                    0x64, 0x07, 0x3a, 0x0e, 0xe1, 0x72, 0xf3, 0xda, 0xa6, 0x23, 0x25, 0xaf, 0x02,
+                    0x1a, 0x68, 0xf7, 0x07, 0x51, 0x1a,
+                ],
+                message: b"",
+                signature: [
+                    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                ],
+                result: TestResult::Invalid,
+            },
+            Test {
+                comment: "low order public key (identity), R = identity, S = 0",
+                public_key: [
+                    0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                    0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                ],
+                message: b"",
+                signature: [
+                    0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                ],
+                result: TestResult::Valid,
+            },
+            Test {
+                comment: "low order public key (order 2), R = identity, S = 0",
+                public_key: [
+                    0xec, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+                    0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+                    0xff, 0xff, 0xff, 0xff, 0xff, 0x7f,
+                ],
+                message: b"",
+                signature: [
+                    0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                ],
+                result: TestResult::Invalid,
+            },
+            Test {
+                comment: "low order public key (order 4), R = identity, S = 0",
+                public_key: [
+                    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                    0x00, 0x00, 0x00, 0x00, 0x00, 0x80,
+                ],
+                message: b"",
+                signature: [
+                    0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                ],
+                result: TestResult::Valid,
+            },
+            Test {
+                comment: "low order public key (order 8), R = identity, S = 0",
+                public_key: [
+                    0xc7, 0x17, 0x6a, 0x70, 0x3d, 0x4d, 0xd8, 0x4f, 0xba, 0x3c, 0x0b, 0x76, 0x0d,
+                    0x10, 0x67, 0x0f, 0x2a, 0x20, 0x53, 0xfa, 0x2c, 0x39, 0xcc, 0xc6, 0x4e, 0xc7,
+                    0xfd, 0x77, 0x92, 0xac, 0x03, 0x7a,
+                ],
+                message: b"",
+                signature: [
+                    0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                ],
+                result: TestResult::Valid,
+            },
+            Test {
+                comment: "low order public key (order 8), R = identity, S = 0",
+                public_key: [
+                    0x26, 0xe8, 0x95, 0x8f, 0xc2, 0xb2, 0x27, 0xb0, 0x45, 0xc3, 0xf4, 0x89, 0xf2,
+                    0xef, 0x98, 0xf0, 0xd5, 0xdf, 0xac, 0x05, 0xd3, 0xc6, 0x33, 0x39, 0xb1, 0x38,
+                    0x02, 0x88, 0x6d, 0x53, 0xfc, 0x05,
+                ],
+                message: b"",
+                signature: [
+                    0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                ],
+                result: TestResult::Invalid,
+            },
+            Test {
+                comment: "non canonical public key (y = p + 1), R = identity, S = 0",
+                public_key: [
+                    0xee, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+                    0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+                    0xff, 0xff, 0xff, 0xff, 0xff, 0x7f,
+                ],
+                message: b"",
+                signature: [
+                    0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                ],
+                result: TestResult::Valid,
+            },
+        ];
+        for t in tests.iter() {
+            let r = verify(t.message, &t.public_key, &t.signature);
+            match t.result {
+                TestResult::Valid => assert!(r, "{}", t.comment),
+                TestResult::Invalid => assert!(!r, "{}", t.comment),
+            }
+        }
+    }
+
+    #[test]
+    fn extended_to_public_unclamped() {
+        // only the scalar modulo the order of the base point matters
+        let mut extended = [0xffu8; 64];
+        let p1 = extended_to_public(&extended);
+        // 2^256 - 1 mod l
+        extended[0..32].copy_from_slice(&[
+            0x1c, 0x95, 0x98, 0x8d, 0x74, 0x31, 0xec, 0xd6, 0x70, 0xcf, 0x7d, 0x73, 0xf4, 0x5b,
+            0xef, 0xc6, 0xfe, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+            0xff, 0xff, 0xff, 0x0f,
+        ]);
+        assert_eq!(p1, extended_to_public(&extended));
+        assert_eq!(extended_to_public(&[0; 64])[0], 1);
+    }
+
+    #[cfg(feature = "hmac")]
+    #[test]
+    fn derive_child_key() {
+        use super::{derive_child_key_hard, derive_child_key_soft, signature_extended};
+        use crate::encoding::{decode_hex, encode_hex};
+
+        // root key of the Cardano ed25519-bip32 tests, with the outputs computed by an
+        // independent implementation of the V2 derivation scheme
+        let root: [u8; 96] = decode_hex(b"f8a29231ee38d6c5bf715d5bac21c750577aa3798b22d79d65bf97d6fadea15adcd1ee1abdf78bd4be64731a12deb94d3671784112eb6f364b871851fd1c9a247384db9ad6003bbd08b3b1ddc0d07a597293ff85e961bf252b331262eddfad0d")
+            .unwrap()
+            .try_into()
+            .unwrap();
+        const H: u32 = 0x8000_0000;
+        let tests: [(&[u32], &str, &str); 3] = [
+            (
+                &[0],
+                "e86a12ba078cdbdf044b488624a50b9f681086c5e7c005222c6fb69e02dfa15a28630505d5878465269ecf096b7ec855780e6e4aed06852676e8ced5bd66d1dad6324d15fe0641021a711f3ef93865b2e41c3cef61b155d57a988156074ce2a8",
+                "10abaae2cf8f9c2d0cee0a880c0c3f6fcaaae9a1edff667fc567a117f6359c20",
+            ),
+            (
+                &[H],
+                "60d399da83ef80d8d4f8d223239efdc2b8fef387e1b5219137ffb4e8fbdea15adc9366b7d003af37c11396de9a83734e30e05e851efa32745c9cd7b42712c890608763770eddf77248ab652984b21b849760d1da74a6f5bd633ce41adceef07a",
+                "9c99845ae0a60881decf7874b94ad02e7540628b588f8b4305d668cc53986978",
+            ),
+            // m/1852'/1815'/0'/0/0
+            (
+                &[H + 1852, H + 1815, H, 0, 0],
+                "60a87574d63c22c67b2a4318ec035142f0f82228db01e8fcf3bd9b3a0ddfa15a0da734060386c705652a182231e0d44b3b81e120d7fb1cae372dae642e63635d87e8865c7087d23277fb9f61045c5e60ec1eaac8ef7d470e1e48e3b14d4cf78f",
+                "1be93c31db3e36fa1c9887b3dd08e560be73b3eb504cd1ed88644c1c39d84621",
+            ),
+        ];
+        for (path, expected_key, expected_public) in tests.iter() {
+            let mut key = root;
+            for index in path.iter() {
+                key = if *index >= H {
+                    derive_child_key_hard(&key, *index)
+                } else {
+                    derive_child_key_soft(&key, *index)
+                };
+            }
+            assert_eq!(encode_hex(&key), *expected_key, "{:?}", path);
+
+            let extended = <[u8; 64]>::try_from(&key[0..64]).unwrap();
+            let public = extended_to_public(&extended);
+            assert_eq!(encode_hex(&public), *expected_public, "{:?}", path);
+
+            let signature = signature_extended(b"message", &extended);
+            assert!(verify(b"message", &public, &signature));
+        }
+    }
+
+    #[cfg(feature = "hmac")]
+    #[test]
+    #[should_panic]
+    fn derive_child_key_soft_hardened_index() {
+        super::derive_child_key_soft(&[0; 96], 0x8000_0000);
+    }
+
+    #[cfg(feature = "hmac")]
+    #[test]
+    #[should_panic]
+    fn derive_child_key_hard_soft_index() {
+        super::derive_child_key_hard(&[0; 96], 0x7fff_ffff);
+    }
 }
@@ -0,0 +1,386 @@
+//! Constant time encoding and decoding of binary data
+//!
+//! These encodings are commonly used to store or transmit secret values
+//! (keys, seeds), so the conversion between values and characters is done
+//! without any table lookup or data dependent branches, to not leak the
+//! secret values through timing.
+//!
+//! # Examples
+//!
+//! ```
+//! use cryptoxide::encoding::{decode_hex, encode_hex};
+//!
+//! let key = [0x01, 0xab, 0xff];
+//! let hex = encode_hex(&key);
+//! assert_eq!(hex, "01abff");
+//! assert_eq!(decode_hex(hex.as_bytes()).unwrap(), key);
+//! ```
+//!
+//! Base64 encoding, with the standard or the URL safe alphabet of RFC4648:
+//!
+//! ```
+//! use cryptoxide::encoding::{decode_base64, encode_base64};
+//!
+//! let b64 = encode_base64(b"foobar");
+//! assert_eq!(b64, b"Zm9vYmFy");
+//! assert_eq!(decode_base64(&b64).unwrap(), b"foobar");
+//! ```
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// Errors when decoding an hexadecimal string
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HexError {
+    /// The input doesn't have an even number of characters
+    OddLength,
+    /// The input contains a character that is not an hexadecimal digit
+    InvalidCharacter,
+}
+
+impl core::fmt::Display for HexError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            HexError::OddLength => write!(f, "odd number of hexadecimal digits"),
+            HexError::InvalidCharacter => write!(f, "invalid hexadecimal character"),
+        }
+    }
+}
+
+/// Errors when decoding a base64 string
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Base64Error {
+    /// The input length is not valid for the padding mode
+    InvalidLength,
+    /// The input contains a character that is not in the alphabet
+    InvalidCharacter,
+    /// The padding is invalid, or the unused bits of the last character are not zero
+    InvalidPadding,
+}
+
+impl core::fmt::Display for Base64Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Base64Error::InvalidLength => write!(f, "invalid base64 length"),
+            Base64Error::InvalidCharacter => write!(f, "invalid base64 character"),
+            Base64Error::InvalidPadding => write!(f, "invalid base64 padding"),
+        }
+    }
+}
+
+/// Base64 alphabets from RFC4648
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Base64Alphabet {
+    /// Standard alphabet, using `+` and `/` (RFC4648 section 4)
+    Standard,
+    /// URL and filename safe alphabet, using `-` and `_` (RFC4648 section 5)
+    UrlSafe,
+}
+
+impl Base64Alphabet {
+    // the characters for the values 62 and 63, that differs between alphabets
+    fn extra(self) -> (i16, i16) {
+        match self {
+            Base64Alphabet::Standard => (b'+' as i16, b'/' as i16),
+            Base64Alphabet::UrlSafe => (b'-' as i16, b'_' as i16),
+        }
+    }
+}
+
+// return the lowercase hexadecimal character of the 4 bits value x
+fn enc4(x: u8) -> u8 {
+    let x = x as i16;
+    let mut c = x + 48; // '0'..'9'
+    c += ((9 - x) >> 8) & 39; // 'a'..'f'
+    c as u8
+}
+
+// return the 4 bits value of the hexadecimal character (either case), or -1 if invalid
+fn dec4(c: u8) -> i16 {
+    let c = c as i16;
+    let mut x = -1;
+    x += (((47 - c) & (c - 58)) >> 8) & (c - 47); // '0'..'9'
+    x += (((64 - c) & (c - 71)) >> 8) & (c - 54); // 'A'..'F'
+    x += (((96 - c) & (c - 103)) >> 8) & (c - 86); // 'a'..'f'
+    x
+}
+
+/// Encode bytes as a lowercase hexadecimal string
+pub fn encode_hex(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len() * 2);
+    for b in bytes {
+        out.push(enc4(b >> 4) as char);
+        out.push(enc4(b & 0xf) as char);
+    }
+    out
+}
+
+/// Encode bytes as lowercase hexadecimal characters in a fixed size output
+///
+/// The output needs to be exactly twice the size of the input
+pub fn encode_hex_at(bytes: &[u8], out: &mut [u8]) {
+    assert_eq!(out.len(), bytes.len() * 2);
+    for (b, o) in bytes.iter().zip(out.chunks_mut(2)) {
+        o[0] = enc4(b >> 4);
+        o[1] = enc4(b & 0xf);
+    }
+}
+
+/// Decode an hexadecimal string (lowercase or uppercase)
+///
+/// All the characters are processed before reporting an invalid character
+pub fn decode_hex(s: &[u8]) -> Result<Vec<u8>, HexError> {
+    if s.len() % 2 != 0 {
+        return Err(HexError::OddLength);
+    }
+    let mut out = Vec::with_capacity(s.len() / 2);
+    let mut invalid = 0;
+    for c in s.chunks(2) {
+        let hi = dec4(c[0]);
+        let lo = dec4(c[1]);
+        invalid |= hi | lo;
+        out.push((((hi & 0xf) << 4) | (lo & 0xf)) as u8);
+    }
+    if invalid < 0 {
+        Err(HexError::InvalidCharacter)
+    } else {
+        Ok(out)
+    }
+}
+
+// return the base64 character of the 6 bits value x
+fn enc6(x: u8, alphabet: Base64Alphabet) -> u8 {
+    let (c62, c63) = alphabet.extra();
+    let x = x as i16;
+    let mut c = x + 65; // 'A'..'Z'
+    c += ((25 - x) >> 8) & 6; // 'a'..'z'
+    c -= ((51 - x) >> 8) & 75; // '0'..'9'
+    c += ((61 - x) >> 8) & (c62 - 58); // 62
+    c += ((62 - x) >> 8) & (c63 - c62 - 1); // 63
+    c as u8
+}
+
+// return the 6 bits value of the base64 character, or -1 if the character is not in the alphabet
+fn dec6(c: u8, alphabet: Base64Alphabet) -> i16 {
+    let (c62, c63) = alphabet.extra();
+    let c = c as i16;
+    let mut x = -1;
+    x += (((64 - c) & (c - 91)) >> 8) & (c - 64); // 'A'..'Z'
+    x += (((96 - c) & (c - 123)) >> 8) & (c - 70); // 'a'..'z'
+    x += (((47 - c) & (c - 58)) >> 8) & (c + 5); // '0'..'9'
+    x += (((c62 - 1 - c) & (c - c62 - 1)) >> 8) & 63; // 62
+    x += (((c63 - 1 - c) & (c - c63 - 1)) >> 8) & 64; // 63
+    x
+}
+
+/// Encode bytes in base64 with the standard alphabet and padding
+pub fn encode_base64(bytes: &[u8]) -> Vec<u8> {
+    encode_base64_with(bytes, Base64Alphabet::Standard, true)
+}
+
+/// Encode bytes in base64 with a specific alphabet, and with or without padding
+pub fn encode_base64_with(bytes: &[u8], alphabet: Base64Alphabet, padding: bool) -> Vec<u8> {
+    let mut out = Vec::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let b0 = chunk[0];
+        let b1 = chunk.get(1).copied().unwrap_or(0);
+        let b2 = chunk.get(2).copied().unwrap_or(0);
+        out.push(enc6(b0 >> 2, alphabet));
+        out.push(enc6(((b0 << 4) | (b1 >> 4)) & 0x3f, alphabet));
+        if chunk.len() > 1 {
+            out.push(enc6(((b1 << 2) | (b2 >> 6)) & 0x3f, alphabet));
+        } else if padding {
+            out.push(b'=');
+        }
+        if chunk.len() > 2 {
+            out.push(enc6(b2 & 0x3f, alphabet));
+        } else if padding {
+            out.push(b'=');
+        }
+    }
+    out
+}
+
+/// Decode a padded base64 string using the standard alphabet
+pub fn decode_base64(s: &[u8]) -> Result<Vec<u8>, Base64Error> {
+    decode_base64_with(s, Base64Alphabet::Standard, true)
+}
+
+/// Decode a base64 string with a specific alphabet, and with or without padding
+///
+/// Only the canonical encoding is accepted: the unused bits of the last character
+/// need to be zero. All the characters are processed before reporting an invalid character.
+pub fn decode_base64_with(
+    s: &[u8],
+    alphabet: Base64Alphabet,
+    padding: bool,
+) -> Result<Vec<u8>, Base64Error> {
+    let s = if padding {
+        if s.len() % 4 != 0 {
+            return Err(Base64Error::InvalidLength);
+        }
+        // the padding length is deduced from the length of the data, so it's not secret
+        let pad = s.iter().rev().take(2).take_while(|c| **c == b'=').count();
+        &s[..s.len() - pad]
+    } else {
+        s
+    };
+    if s.len() % 4 == 1 {
+        return Err(Base64Error::InvalidLength);
+    }
+
+    let mut out = Vec::with_capacity(s.len() * 3 / 4);
+    let mut invalid = 0;
+    let mut non_canonical = 0;
+    for chunk in s.chunks(4) {
+        let mut acc = 0u32;
+        for (i, c) in chunk.iter().enumerate() {
+            let x = dec6(*c, alphabet);
+            invalid |= x;
+            acc |= ((x & 0x3f) as u32) << (18 - 6 * i);
+        }
+        let acc = acc.to_be_bytes();
+        let n = chunk.len() - 1;
+        out.extend_from_slice(&acc[1..1 + n]);
+        non_canonical |= acc[1 + n..].iter().fold(0, |a, b| a | b);
+    }
+    if invalid < 0 {
+        Err(Base64Error::InvalidCharacter)
+    } else if non_canonical != 0 {
+        Err(Base64Error::InvalidPadding)
+    } else {
+        Ok(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hex_all_bytes() {
+        let bytes: Vec<u8> = (0..=255).collect();
+        let hex = encode_hex(&bytes);
+        for (b, c) in bytes.iter().zip(hex.as_bytes().chunks(2)) {
+            assert_eq!(c, format!("{:02x}", b).as_bytes());
+        }
+        assert_eq!(decode_hex(hex.as_bytes()).unwrap(), bytes);
+        assert_eq!(decode_hex(hex.to_uppercase().as_bytes()).unwrap(), bytes);
+
+        let mut out = [0u8; 512];
+        encode_hex_at(&bytes, &mut out);
+        assert_eq!(&out[..], hex.as_bytes());
+    }
+
+    #[test]
+    fn hex_invalid() {
+        assert_eq!(decode_hex(b"abc"), Err(HexError::OddLength));
+        for c in 0..=255u8 {
+            let valid = c.is_ascii_hexdigit();
+            let input = [b'0', c];
+            assert_eq!(decode_hex(&input).is_ok(), valid, "character {}", c);
+            let input = [c, b'0'];
+            assert_eq!(decode_hex(&input).is_ok(), valid, "character {}", c);
+        }
+        assert_eq!(decode_hex(b"00g0ff"), Err(HexError::InvalidCharacter));
+    }
+
+    #[test]
+    fn base64_rfc4648() {
+        let tests: [(&[u8], &[u8]); 7] = [
+            (b"", b""),
+            (b"f", b"Zg=="),
+            (b"fo", b"Zm8="),
+            (b"foo", b"Zm9v"),
+            (b"foob", b"Zm9vYg=="),
+            (b"fooba", b"Zm9vYmE="),
+            (b"foobar", b"Zm9vYmFy"),
+        ];
+        for (data, b64) in tests.iter() {
+            assert_eq!(&encode_base64(data)[..], *b64);
+            assert_eq!(&decode_base64(b64).unwrap()[..], *data);
+
+            let unpadded: Vec<u8> = b64.iter().copied().filter(|c| *c != b'=').collect();
+            let e = encode_base64_with(data, Base64Alphabet::Standard, false);
+            assert_eq!(e, unpadded);
+            let d = decode_base64_with(&unpadded, Base64Alphabet::Standard, false).unwrap();
+            assert_eq!(&d[..], *data);
+        }
+    }
+
+    #[test]
+    fn base64_all_bytes() {
+        for alphabet in [Base64Alphabet::Standard, Base64Alphabet::UrlSafe] {
+            for b in 0..=255u8 {
+                let e = encode_base64_with(&[b], alphabet, true);
+                assert_eq!(decode_base64_with(&e, alphabet, true).unwrap(), [b]);
+            }
+            let bytes: Vec<u8> = (0..=255).collect();
+            let e = encode_base64_with(&bytes, alphabet, false);
+            assert_eq!(decode_base64_with(&e, alphabet, false).unwrap(), bytes);
+        }
+        // the 62 and 63 values, each repeated 4 times, give 3 bytes of 0xfb 0xef 0xbe and 0xff
+        assert_eq!(
+            encode_base64(&[0xfb, 0xef, 0xbe, 0xff, 0xff, 0xff]),
+            b"++++////"
+        );
+        let url = encode_base64_with(
+            &[0xfb, 0xef, 0xbe, 0xff, 0xff, 0xff],
+            Base64Alphabet::UrlSafe,
+            true,
+        );
+        assert_eq!(url, b"----____");
+    }
+
+    #[test]
+    fn base64_invalid() {
+        assert_eq!(decode_base64(b"Zm9"), Err(Base64Error::InvalidLength));
+        assert_eq!(decode_base64(b"Z==="), Err(Base64Error::InvalidCharacter));
+        assert_eq!(decode_base64(b"Zh=="), Err(Base64Error::InvalidPadding));
+        assert_eq!(decode_base64(b"Zm=v"), Err(Base64Error::InvalidCharacter));
+        assert_eq!(decode_base64(b"-_8="), Err(Base64Error::InvalidCharacter));
+        assert_eq!(
+            decode_base64_with(b"+/8", Base64Alphabet::UrlSafe, false),
+            Err(Base64Error::InvalidCharacter)
+        );
+        assert_eq!(
+            decode_base64_with(b"Zg==", Base64Alphabet::Standard, false),
+            Err(Base64Error::InvalidCharacter)
+        );
+        assert_eq!(
+            decode_base64_with(b"Zm9vY", Base64Alphabet::Standard, false),
+            Err(Base64Error::InvalidLength)
+        );
+        for c in 0..=255u8 {
+            let valid = c.is_ascii_alphanumeric() || c == b'+' || c == b'/' || c == b'=';
+            let input = [b'A', b'A', b'A', c];
+            assert_eq!(decode_base64(&input).is_ok(), valid, "character {}", c);
+        }
+    }
+
+    // timing measurement is noisy, so this test needs to be explicitly requested
+    #[test]
+    #[ignore]
+    fn hex_decode_timing() {
+        use std::time::Instant;
+
+        let valid = [b'a'; 4096];
+        let mut invalid_early = valid;
+        invalid_early[0] = b'z';
+
+        let measure = |input: &[u8]| {
+            let start = Instant::now();
+            for _ in 0..1000 {
+                let _ = core::hint::black_box(decode_hex(core::hint::black_box(input)));
+            }
+            start.elapsed().as_nanos() as f64
+        };
+        // warm up
+        measure(&valid);
+
+        let t_valid = measure(&valid);
+        let t_invalid = measure(&invalid_early);
+        let ratio = t_valid / t_invalid;
+        assert!(ratio > 0.8 && ratio < 1.25, "timing ratio {}", ratio);
+    }
+}
@@ -66,3 +66,58 @@ pub enum LastBlock {
     /// To use for all block to compress except the last one
     No,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{b, s};
+
+    const PRIMES: [u128; 8] = [2, 3, 5, 7, 11, 13, 17, 19];
+
+    // floor(sqrt(n * 4^shift)) computed digit by digit (2 bits of input at a time),
+    // so that the input doesn't need to fit in 128 bits, only the root and the remainder
+    fn isqrt_shifted(n: u128, shift: u32) -> u128 {
+        let digits = (128 - n.leading_zeros()).div_ceil(2);
+        let mut root = 0u128;
+        let mut remainder = 0u128;
+        for i in (0..digits + shift).rev() {
+            let digit = if i >= shift {
+                (n >> (2 * (i - shift))) & 0b11
+            } else {
+                0
+            };
+            remainder = (remainder << 2) | digit;
+            let candidate = (root << 2) | 1;
+            root <<= 1;
+            if remainder >= candidate {
+                remainder -= candidate;
+                root |= 1;
+            }
+        }
+        root
+    }
+
+    #[test]
+    fn isqrt() {
+        assert_eq!(isqrt_shifted(0, 0), 0);
+        assert_eq!(isqrt_shifted(15, 0), 3);
+        assert_eq!(isqrt_shifted(16, 0), 4);
+        assert_eq!(isqrt_shifted(2, 1), 2);
+        assert_eq!(isqrt_shifted(1, 10), 1 << 10);
+    }
+
+    #[test]
+    fn iv_b() {
+        // first 64 bits of the fractional part of the square root of the first 8 primes
+        for (iv, p) in b::IV.iter().zip(PRIMES.iter()) {
+            assert_eq!(*iv, isqrt_shifted(*p, 64) as u64);
+        }
+    }
+
+    #[test]
+    fn iv_s() {
+        // first 32 bits of the fractional part of the square root of the first 8 primes
+        for (iv, p) in s::IV.iter().zip(PRIMES.iter()) {
+            assert_eq!(*iv, isqrt_shifted(*p, 32) as u32);
+        }
+    }
+}
@@ -21,6 +21,12 @@ mod avx;
 ))]
 mod avx2;
 
+#[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+mod neon;
+
+#[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+mod wasm;
+
 use common::{b, s};
 
 /// Blake2s Context
@@ -72,6 +78,15 @@ impl EngineS {
                 }
             }
         }
+        #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+        {
+            return neon::compress_s(&mut self.h, &mut self.t, buf, last);
+        }
+        #[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+        {
+            return wasm::compress_s(&mut self.h, &mut self.t, buf, last);
+        }
+        #[allow(unreachable_code)]
         reference::compress_s(&mut self.h, &mut self.t, buf, last)
     }
 }
@@ -138,6 +153,35 @@ impl EngineB {
                 }
             }
         }
+        #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+        {
+            return neon::compress_b(&mut self.h, &mut self.t, buf, last);
+        }
+        #[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+        {
+            return wasm::compress_b(&mut self.h, &mut self.t, buf, last);
+        }
+        #[allow(unreachable_code)]
         reference::compress_b(&mut self.h, &mut self.t, buf, last)
     }
 }
+
+/// Blake2b compression function
+///
+/// Compress one 128 bytes block of `message` into the chaining value `h`, using the
+/// byte counter `t` (which include the bytes of this block) and the final block flag,
+/// with the best backend available (AVX2, AVX, NEON, SIMD128 or the reference implementation)
+///
+/// This is the function F of the [RFC7693](https://www.rfc-editor.org/rfc/rfc7693#section-3.2),
+/// and is only useful to protocols building on the raw compression function, otherwise
+/// the hashing context should be used instead.
+pub fn blake2b_compress(h: &mut [u64; 8], message: &[u8; 128], t: [u64; 2], last_block: bool) {
+    let mut engine = EngineB { h: *h, t };
+    let last = if last_block {
+        LastBlock::Yes
+    } else {
+        LastBlock::No
+    };
+    engine.compress(message, last);
+    *h = engine.h;
+}
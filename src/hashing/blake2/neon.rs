@@ -0,0 +1,230 @@
+// NEON implementation for AArch64
+//
+// Blake2b keeps each row of the 4x4 state in 2 vectors of 2x64 bits (lo and hi),
+// while Blake2s keeps each row of the 4x4 state in 1 vector of 4x32 bits.
+//
+// The diagonal step is done by rotating the rows 2, 3 and 4 respectively by 1, 2 and 3 words,
+// which make the diagonals into columns, and then rotating back after the G function.
+
+use super::common::{b, s, LastBlock, SIGMA};
+use crate::cryptoutil::{read_u32v_le, read_u64v_le};
+use core::arch::aarch64::*;
+
+#[inline(always)]
+unsafe fn rotate32_u64(x: uint64x2_t) -> uint64x2_t {
+    vsriq_n_u64::<32>(vshlq_n_u64::<32>(x), x)
+}
+
+#[inline(always)]
+unsafe fn rotate24_u64(x: uint64x2_t) -> uint64x2_t {
+    vsriq_n_u64::<24>(vshlq_n_u64::<40>(x), x)
+}
+
+#[inline(always)]
+unsafe fn rotate16_u64(x: uint64x2_t) -> uint64x2_t {
+    vsriq_n_u64::<16>(vshlq_n_u64::<48>(x), x)
+}
+
+#[inline(always)]
+unsafe fn rotate63_u64(x: uint64x2_t) -> uint64x2_t {
+    vsriq_n_u64::<63>(vshlq_n_u64::<1>(x), x)
+}
+
+#[inline(always)]
+unsafe fn rotate16_u32(x: uint32x4_t) -> uint32x4_t {
+    vsriq_n_u32::<16>(vshlq_n_u32::<16>(x), x)
+}
+
+#[inline(always)]
+unsafe fn rotate12_u32(x: uint32x4_t) -> uint32x4_t {
+    vsriq_n_u32::<12>(vshlq_n_u32::<20>(x), x)
+}
+
+#[inline(always)]
+unsafe fn rotate8_u32(x: uint32x4_t) -> uint32x4_t {
+    vsriq_n_u32::<8>(vshlq_n_u32::<24>(x), x)
+}
+
+#[inline(always)]
+unsafe fn rotate7_u32(x: uint32x4_t) -> uint32x4_t {
+    vsriq_n_u32::<7>(vshlq_n_u32::<25>(x), x)
+}
+
+#[inline(always)]
+unsafe fn load_m2(m: &[u64; 16], i: usize, j: usize) -> uint64x2_t {
+    let v = [m[i], m[j]];
+    vld1q_u64(v.as_ptr())
+}
+
+#[inline(always)]
+unsafe fn load_m4(m: &[u32; 16], i: usize, j: usize, k: usize, l: usize) -> uint32x4_t {
+    let v = [m[i], m[j], m[k], m[l]];
+    vld1q_u32(v.as_ptr())
+}
+
+// G function on 2 columns at once, with the message words m1 (first addition) and m2 (second addition)
+macro_rules! g_b {
+    ($a:ident, $b:ident, $c:ident, $d:ident, $m1:expr, $m2:expr) => {
+        $a = vaddq_u64(vaddq_u64($a, $b), $m1);
+        $d = rotate32_u64(veorq_u64($d, $a));
+        $c = vaddq_u64($c, $d);
+        $b = rotate24_u64(veorq_u64($b, $c));
+        $a = vaddq_u64(vaddq_u64($a, $b), $m2);
+        $d = rotate16_u64(veorq_u64($d, $a));
+        $c = vaddq_u64($c, $d);
+        $b = rotate63_u64(veorq_u64($b, $c));
+    };
+}
+
+macro_rules! g_s {
+    ($a:ident, $b:ident, $c:ident, $d:ident, $m1:expr, $m2:expr) => {
+        $a = vaddq_u32(vaddq_u32($a, $b), $m1);
+        $d = rotate16_u32(veorq_u32($d, $a));
+        $c = vaddq_u32($c, $d);
+        $b = rotate12_u32(veorq_u32($b, $c));
+        $a = vaddq_u32(vaddq_u32($a, $b), $m2);
+        $d = rotate8_u32(veorq_u32($d, $a));
+        $c = vaddq_u32($c, $d);
+        $b = rotate7_u32(veorq_u32($b, $c));
+    };
+}
+
+pub fn compress_b(h: &mut [u64; 8], t: &mut [u64; 2], buf: &[u8], last: LastBlock) {
+    let mut m = [0u64; 16];
+    read_u64v_le(&mut m, buf);
+    let f0 = if last == LastBlock::Yes { !0 } else { 0 };
+    let tf = [t[0], t[1], f0, 0];
+
+    unsafe {
+        let mut a_lo = vld1q_u64(h[0..2].as_ptr());
+        let mut a_hi = vld1q_u64(h[2..4].as_ptr());
+        let mut b_lo = vld1q_u64(h[4..6].as_ptr());
+        let mut b_hi = vld1q_u64(h[6..8].as_ptr());
+        let mut c_lo = vld1q_u64(b::IV[0..2].as_ptr());
+        let mut c_hi = vld1q_u64(b::IV[2..4].as_ptr());
+        let mut d_lo = veorq_u64(
+            vld1q_u64(b::IV[4..6].as_ptr()),
+            vld1q_u64(tf[0..2].as_ptr()),
+        );
+        let mut d_hi = veorq_u64(
+            vld1q_u64(b::IV[6..8].as_ptr()),
+            vld1q_u64(tf[2..4].as_ptr()),
+        );
+
+        for sigma in SIGMA.iter().take(b::ROUNDS) {
+            // columns
+            g_b!(
+                a_lo,
+                b_lo,
+                c_lo,
+                d_lo,
+                load_m2(&m, sigma[0], sigma[2]),
+                load_m2(&m, sigma[1], sigma[3])
+            );
+            g_b!(
+                a_hi,
+                b_hi,
+                c_hi,
+                d_hi,
+                load_m2(&m, sigma[4], sigma[6]),
+                load_m2(&m, sigma[5], sigma[7])
+            );
+
+            // diagonalize
+            let (t_lo, t_hi) = (vextq_u64::<1>(b_lo, b_hi), vextq_u64::<1>(b_hi, b_lo));
+            b_lo = t_lo;
+            b_hi = t_hi;
+            core::mem::swap(&mut c_lo, &mut c_hi);
+            let (t_lo, t_hi) = (vextq_u64::<1>(d_hi, d_lo), vextq_u64::<1>(d_lo, d_hi));
+            d_lo = t_lo;
+            d_hi = t_hi;
+
+            // diagonals
+            g_b!(
+                a_lo,
+                b_lo,
+                c_lo,
+                d_lo,
+                load_m2(&m, sigma[8], sigma[10]),
+                load_m2(&m, sigma[9], sigma[11])
+            );
+            g_b!(
+                a_hi,
+                b_hi,
+                c_hi,
+                d_hi,
+                load_m2(&m, sigma[12], sigma[14]),
+                load_m2(&m, sigma[13], sigma[15])
+            );
+
+            // undiagonalize
+            let (t_lo, t_hi) = (vextq_u64::<1>(b_hi, b_lo), vextq_u64::<1>(b_lo, b_hi));
+            b_lo = t_lo;
+            b_hi = t_hi;
+            core::mem::swap(&mut c_lo, &mut c_hi);
+            let (t_lo, t_hi) = (vextq_u64::<1>(d_lo, d_hi), vextq_u64::<1>(d_hi, d_lo));
+            d_lo = t_lo;
+            d_hi = t_hi;
+        }
+
+        let h01 = veorq_u64(vld1q_u64(h[0..2].as_ptr()), veorq_u64(a_lo, c_lo));
+        let h23 = veorq_u64(vld1q_u64(h[2..4].as_ptr()), veorq_u64(a_hi, c_hi));
+        let h45 = veorq_u64(vld1q_u64(h[4..6].as_ptr()), veorq_u64(b_lo, d_lo));
+        let h67 = veorq_u64(vld1q_u64(h[6..8].as_ptr()), veorq_u64(b_hi, d_hi));
+        vst1q_u64(h[0..2].as_mut_ptr(), h01);
+        vst1q_u64(h[2..4].as_mut_ptr(), h23);
+        vst1q_u64(h[4..6].as_mut_ptr(), h45);
+        vst1q_u64(h[6..8].as_mut_ptr(), h67);
+    }
+}
+
+pub fn compress_s(h: &mut [u32; 8], t: &mut [u32; 2], buf: &[u8], last: LastBlock) {
+    let mut m = [0u32; 16];
+    read_u32v_le(&mut m, buf);
+    let f0 = if last == LastBlock::Yes { !0 } else { 0 };
+    let tf = [t[0], t[1], f0, 0];
+
+    unsafe {
+        let mut a = vld1q_u32(h[0..4].as_ptr());
+        let mut b = vld1q_u32(h[4..8].as_ptr());
+        let mut c = vld1q_u32(s::IV[0..4].as_ptr());
+        let mut d = veorq_u32(vld1q_u32(s::IV[4..8].as_ptr()), vld1q_u32(tf.as_ptr()));
+
+        for sigma in SIGMA.iter().take(s::ROUNDS) {
+            // columns
+            g_s!(
+                a,
+                b,
+                c,
+                d,
+                load_m4(&m, sigma[0], sigma[2], sigma[4], sigma[6]),
+                load_m4(&m, sigma[1], sigma[3], sigma[5], sigma[7])
+            );
+
+            // diagonalize
+            b = vextq_u32::<1>(b, b);
+            c = vextq_u32::<2>(c, c);
+            d = vextq_u32::<3>(d, d);
+
+            // diagonals
+            g_s!(
+                a,
+                b,
+                c,
+                d,
+                load_m4(&m, sigma[8], sigma[10], sigma[12], sigma[14]),
+                load_m4(&m, sigma[9], sigma[11], sigma[13], sigma[15])
+            );
+
+            // undiagonalize
+            b = vextq_u32::<3>(b, b);
+            c = vextq_u32::<2>(c, c);
+            d = vextq_u32::<1>(d, d);
+        }
+
+        let h0 = veorq_u32(vld1q_u32(h[0..4].as_ptr()), veorq_u32(a, c));
+        let h1 = veorq_u32(vld1q_u32(h[4..8].as_ptr()), veorq_u32(b, d));
+        vst1q_u32(h[0..4].as_mut_ptr(), h0);
+        vst1q_u32(h[4..8].as_mut_ptr(), h1);
+    }
+}
@@ -0,0 +1,205 @@
+// WebAssembly SIMD128 implementation
+//
+// Same layout as the NEON implementation: Blake2b keeps each row of the 4x4 state in
+// 2 vectors of 2x64 bits (lo and hi), while Blake2s keeps each row of the 4x4 state
+// in 1 vector of 4x32 bits, and the diagonal step is done by rotating the rows.
+
+use super::common::{b, s, LastBlock, SIGMA};
+use crate::cryptoutil::{read_u32v_le, read_u64v_le};
+use core::arch::wasm32::*;
+
+#[inline(always)]
+unsafe fn rotate_u64<const N: u32>(x: v128) -> v128 {
+    v128_or(u64x2_shr(x, N), i64x2_shl(x, 64 - N))
+}
+
+#[inline(always)]
+unsafe fn rotate_u32<const N: u32>(x: v128) -> v128 {
+    v128_or(u32x4_shr(x, N), i32x4_shl(x, 32 - N))
+}
+
+#[inline(always)]
+unsafe fn load_m2(m: &[u64; 16], i: usize, j: usize) -> v128 {
+    u64x2(m[i], m[j])
+}
+
+#[inline(always)]
+unsafe fn load_m4(m: &[u32; 16], i: usize, j: usize, k: usize, l: usize) -> v128 {
+    u32x4(m[i], m[j], m[k], m[l])
+}
+
+// G function on 2 columns at once, with the message words m1 (first addition) and m2 (second addition)
+macro_rules! g_b {
+    ($a:ident, $b:ident, $c:ident, $d:ident, $m1:expr, $m2:expr) => {
+        $a = i64x2_add(i64x2_add($a, $b), $m1);
+        $d = rotate_u64::<32>(v128_xor($d, $a));
+        $c = i64x2_add($c, $d);
+        $b = rotate_u64::<24>(v128_xor($b, $c));
+        $a = i64x2_add(i64x2_add($a, $b), $m2);
+        $d = rotate_u64::<16>(v128_xor($d, $a));
+        $c = i64x2_add($c, $d);
+        $b = rotate_u64::<63>(v128_xor($b, $c));
+    };
+}
+
+macro_rules! g_s {
+    ($a:ident, $b:ident, $c:ident, $d:ident, $m1:expr, $m2:expr) => {
+        $a = i32x4_add(i32x4_add($a, $b), $m1);
+        $d = rotate_u32::<16>(v128_xor($d, $a));
+        $c = i32x4_add($c, $d);
+        $b = rotate_u32::<12>(v128_xor($b, $c));
+        $a = i32x4_add(i32x4_add($a, $b), $m2);
+        $d = rotate_u32::<8>(v128_xor($d, $a));
+        $c = i32x4_add($c, $d);
+        $b = rotate_u32::<7>(v128_xor($b, $c));
+    };
+}
+
+pub fn compress_b(h: &mut [u64; 8], t: &mut [u64; 2], buf: &[u8], last: LastBlock) {
+    let mut m = [0u64; 16];
+    read_u64v_le(&mut m, buf);
+    let f0 = if last == LastBlock::Yes { !0 } else { 0 };
+    let tf = [t[0], t[1], f0, 0];
+
+    unsafe {
+        let mut a_lo = u64x2(h[0], h[1]);
+        let mut a_hi = u64x2(h[2], h[3]);
+        let mut b_lo = u64x2(h[4], h[5]);
+        let mut b_hi = u64x2(h[6], h[7]);
+        let mut c_lo = u64x2(b::IV[0], b::IV[1]);
+        let mut c_hi = u64x2(b::IV[2], b::IV[3]);
+        let mut d_lo = v128_xor(u64x2(b::IV[4], b::IV[5]), u64x2(tf[0], tf[1]));
+        let mut d_hi = v128_xor(u64x2(b::IV[6], b::IV[7]), u64x2(tf[2], tf[3]));
+
+        for sigma in SIGMA.iter().take(b::ROUNDS) {
+            // columns
+            g_b!(
+                a_lo,
+                b_lo,
+                c_lo,
+                d_lo,
+                load_m2(&m, sigma[0], sigma[2]),
+                load_m2(&m, sigma[1], sigma[3])
+            );
+            g_b!(
+                a_hi,
+                b_hi,
+                c_hi,
+                d_hi,
+                load_m2(&m, sigma[4], sigma[6]),
+                load_m2(&m, sigma[5], sigma[7])
+            );
+
+            // diagonalize
+            let (t_lo, t_hi) = (
+                i64x2_shuffle::<1, 2>(b_lo, b_hi),
+                i64x2_shuffle::<1, 2>(b_hi, b_lo),
+            );
+            b_lo = t_lo;
+            b_hi = t_hi;
+            core::mem::swap(&mut c_lo, &mut c_hi);
+            let (t_lo, t_hi) = (
+                i64x2_shuffle::<1, 2>(d_hi, d_lo),
+                i64x2_shuffle::<1, 2>(d_lo, d_hi),
+            );
+            d_lo = t_lo;
+            d_hi = t_hi;
+
+            // diagonals
+            g_b!(
+                a_lo,
+                b_lo,
+                c_lo,
+                d_lo,
+                load_m2(&m, sigma[8], sigma[10]),
+                load_m2(&m, sigma[9], sigma[11])
+            );
+            g_b!(
+                a_hi,
+                b_hi,
+                c_hi,
+                d_hi,
+                load_m2(&m, sigma[12], sigma[14]),
+                load_m2(&m, sigma[13], sigma[15])
+            );
+
+            // undiagonalize
+            let (t_lo, t_hi) = (
+                i64x2_shuffle::<1, 2>(b_hi, b_lo),
+                i64x2_shuffle::<1, 2>(b_lo, b_hi),
+            );
+            b_lo = t_lo;
+            b_hi = t_hi;
+            core::mem::swap(&mut c_lo, &mut c_hi);
+            let (t_lo, t_hi) = (
+                i64x2_shuffle::<1, 2>(d_lo, d_hi),
+                i64x2_shuffle::<1, 2>(d_hi, d_lo),
+            );
+            d_lo = t_lo;
+            d_hi = t_hi;
+        }
+
+        let h01 = v128_xor(u64x2(h[0], h[1]), v128_xor(a_lo, c_lo));
+        let h23 = v128_xor(u64x2(h[2], h[3]), v128_xor(a_hi, c_hi));
+        let h45 = v128_xor(u64x2(h[4], h[5]), v128_xor(b_lo, d_lo));
+        let h67 = v128_xor(u64x2(h[6], h[7]), v128_xor(b_hi, d_hi));
+        v128_store(h[0..2].as_mut_ptr() as *mut v128, h01);
+        v128_store(h[2..4].as_mut_ptr() as *mut v128, h23);
+        v128_store(h[4..6].as_mut_ptr() as *mut v128, h45);
+        v128_store(h[6..8].as_mut_ptr() as *mut v128, h67);
+    }
+}
+
+pub fn compress_s(h: &mut [u32; 8], t: &mut [u32; 2], buf: &[u8], last: LastBlock) {
+    let mut m = [0u32; 16];
+    read_u32v_le(&mut m, buf);
+    let f0 = if last == LastBlock::Yes { !0 } else { 0 };
+    let tf = [t[0], t[1], f0, 0];
+
+    unsafe {
+        let mut a = u32x4(h[0], h[1], h[2], h[3]);
+        let mut b = u32x4(h[4], h[5], h[6], h[7]);
+        let mut c = u32x4(s::IV[0], s::IV[1], s::IV[2], s::IV[3]);
+        let mut d = v128_xor(
+            u32x4(s::IV[4], s::IV[5], s::IV[6], s::IV[7]),
+            u32x4(tf[0], tf[1], tf[2], tf[3]),
+        );
+
+        for sigma in SIGMA.iter().take(s::ROUNDS) {
+            // columns
+            g_s!(
+                a,
+                b,
+                c,
+                d,
+                load_m4(&m, sigma[0], sigma[2], sigma[4], sigma[6]),
+                load_m4(&m, sigma[1], sigma[3], sigma[5], sigma[7])
+            );
+
+            // diagonalize
+            b = i32x4_shuffle::<1, 2, 3, 0>(b, b);
+            c = i32x4_shuffle::<2, 3, 0, 1>(c, c);
+            d = i32x4_shuffle::<3, 0, 1, 2>(d, d);
+
+            // diagonals
+            g_s!(
+                a,
+                b,
+                c,
+                d,
+                load_m4(&m, sigma[8], sigma[10], sigma[12], sigma[14]),
+                load_m4(&m, sigma[9], sigma[11], sigma[13], sigma[15])
+            );
+
+            // undiagonalize
+            b = i32x4_shuffle::<3, 0, 1, 2>(b, b);
+            c = i32x4_shuffle::<2, 3, 0, 1>(c, c);
+            d = i32x4_shuffle::<1, 2, 3, 0>(d, d);
+        }
+
+        let h0 = v128_xor(u32x4(h[0], h[1], h[2], h[3]), v128_xor(a, c));
+        let h1 = v128_xor(u32x4(h[4], h[5], h[6], h[7]), v128_xor(b, d));
+        v128_store(h[0..4].as_mut_ptr() as *mut v128, h0);
+        v128_store(h[4..8].as_mut_ptr() as *mut v128, h1);
+    }
+}
@@ -25,12 +25,22 @@
 //! let mac = context.finalize();
 //! ```
 //!
+//! One-shot MAC with the full size output (64 bytes):
+//!
+//! ```
+//! use cryptoxide::hashing::blake2b;
+//!
+//! let mac = blake2b::blake2b_mac(b"secret key", b"hello world");
+//! ```
+//!
 //!
 //! [1]: <https://eprint.iacr.org/2013/322.pdf>
 
 use super::blake2::{EngineB as Engine, LastBlock};
 use crate::cryptoutil::{write_u64v_le, zero};
 
+pub use super::blake2::blake2b_compress;
+
 /// Blake2b Algorithm parametrized by the number of bits to output
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Blake2b<const BITS: usize>;
@@ -69,6 +79,9 @@ pub struct ContextDyn {
     outlen: usize,
 }
 
+impl_io_write!([const BITS: usize] Context<BITS>);
+impl_io_write!(ContextDyn);
+
 impl<const BITS: usize> Context<BITS> {
     /// Create a new Blake2b context with a specific output size in bytes
     ///
@@ -369,6 +382,30 @@ context_finalize!(256);
 context_finalize!(384);
 context_finalize!(512);
 
+/// Compute the Blake2b MAC of the data with the key, returning the 64 bytes keyed hash
+///
+/// This is the native keyed mode of Blake2b (the key zero padded to a full block is
+/// hashed first), and not HMAC.
+///
+/// # Panics
+///
+/// Panics if the key is longer than 64 bytes
+pub fn blake2b_mac(key: &[u8], data: &[u8]) -> [u8; 64] {
+    assert!(
+        key.len() <= Engine::MAX_KEYLEN,
+        "Blake2b key longer than 64 bytes"
+    );
+    Context::<512>::new_keyed(key).update(data).finalize()
+}
+
+/// Compute the Blake2b-512 digest of all the data read from the reader, in chunks of 64KB
+#[cfg(feature = "std")]
+pub fn blake2b_reader<R: std::io::Read>(reader: R) -> std::io::Result<[u8; 64]> {
+    let mut context = Context::<512>::new();
+    super::read_chunks(reader, |chunk| context.update_mut(chunk))?;
+    Ok(context.finalize())
+}
+
 #[cfg(test)]
 mod digest_tests {
     use super::super::tests::{test_hashing, Test};
@@ -398,6 +435,18 @@ mod digest_tests {
             |ctx| ctx.reset(),
         )
     }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_reader() {
+        use super::blake2b_reader;
+        use std::io::Cursor;
+
+        let data: alloc::vec::Vec<u8> = (0..150_001u32).map(|i| (i % 251) as u8).collect();
+
+        let digest = blake2b_reader(Cursor::new(&data)).unwrap();
+        assert_eq!(digest, Context::<512>::new().update(&data).finalize());
+    }
 }
 
 #[cfg(test)]
@@ -434,6 +483,112 @@ mod mac_tests {
             |ctx, key| ctx.reset_with_key(key),
         )
     }
+
+    #[test]
+    fn blake2b_mac_kat() {
+        // keyed KATs of the BLAKE2 reference (key 0..64, input 0..len), checked with python's hashlib
+        let key: [u8; 64] = core::array::from_fn(|i| i as u8);
+        let input: [u8; 255] = core::array::from_fn(|i| i as u8);
+        let kats: [(usize, [u8; 64]); 3] = [
+            (
+                0,
+                [
+                    0x10, 0xeb, 0xb6, 0x77, 0x00, 0xb1, 0x86, 0x8e, 0xfb, 0x44, 0x17, 0x98, 0x7a,
+                    0xcf, 0x46, 0x90, 0xae, 0x9d, 0x97, 0x2f, 0xb7, 0xa5, 0x90, 0xc2, 0xf0, 0x28,
+                    0x71, 0x79, 0x9a, 0xaa, 0x47, 0x86, 0xb5, 0xe9, 0x96, 0xe8, 0xf0, 0xf4, 0xeb,
+                    0x98, 0x1f, 0xc2, 0x14, 0xb0, 0x05, 0xf4, 0x2d, 0x2f, 0xf4, 0x23, 0x34, 0x99,
+                    0x39, 0x16, 0x53, 0xdf, 0x7a, 0xef, 0xcb, 0xc1, 0x3f, 0xc5, 0x15, 0x68,
+                ],
+            ),
+            (
+                1,
+                [
+                    0x96, 0x1f, 0x6d, 0xd1, 0xe4, 0xdd, 0x30, 0xf6, 0x39, 0x01, 0x69, 0x0c, 0x51,
+                    0x2e, 0x78, 0xe4, 0xb4, 0x5e, 0x47, 0x42, 0xed, 0x19, 0x7c, 0x3c, 0x5e, 0x45,
+                    0xc5, 0x49, 0xfd, 0x25, 0xf2, 0xe4, 0x18, 0x7b, 0x0b, 0xc9, 0xfe, 0x30, 0x49,
+                    0x2b, 0x16, 0xb0, 0xd0, 0xbc, 0x4e, 0xf9, 0xb0, 0xf3, 0x4c, 0x70, 0x03, 0xfa,
+                    0xc0, 0x9a, 0x5e, 0xf1, 0x53, 0x2e, 0x69, 0x43, 0x02, 0x34, 0xce, 0xbd,
+                ],
+            ),
+            (
+                255,
+                [
+                    0x14, 0x27, 0x09, 0xd6, 0x2e, 0x28, 0xfc, 0xcc, 0xd0, 0xaf, 0x97, 0xfa, 0xd0,
+                    0xf8, 0x46, 0x5b, 0x97, 0x1e, 0x82, 0x20, 0x1d, 0xc5, 0x10, 0x70, 0xfa, 0xa0,
+                    0x37, 0x2a, 0xa4, 0x3e, 0x92, 0x48, 0x4b, 0xe1, 0xc1, 0xe7, 0x3b, 0xa1, 0x09,
+                    0x06, 0xd5, 0xd1, 0x85, 0x3d, 0xb6, 0xa4, 0x10, 0x6e, 0x0a, 0x7b, 0xf9, 0x80,
+                    0x0d, 0x37, 0x3d, 0x6d, 0xee, 0x2d, 0x46, 0xd6, 0x2e, 0xf2, 0xa4, 0x61,
+                ],
+            ),
+        ];
+        for (len, expected) in kats.iter() {
+            assert_eq!(
+                &super::blake2b_mac(&key, &input[..*len]),
+                expected,
+                "length {}",
+                len
+            );
+        }
+
+        // a key shorter than the maximum is zero padded
+        assert_eq!(
+            super::blake2b_mac(b"key", b"hello world"),
+            [
+                0x25, 0x6f, 0x10, 0xbc, 0x2c, 0x63, 0x23, 0x88, 0x62, 0x1e, 0x8c, 0x0f, 0xfa, 0x7b,
+                0x59, 0x6d, 0xd2, 0xa8, 0x71, 0xe1, 0x3c, 0x2d, 0xe4, 0x0b, 0x9c, 0x4a, 0xc0, 0x2e,
+                0xce, 0xb0, 0x8d, 0xfd, 0xad, 0x6b, 0x28, 0x6b, 0x2b, 0xd2, 0x6b, 0x4b, 0x7b, 0x82,
+                0xfe, 0x23, 0x86, 0x78, 0xbf, 0xbf, 0xc9, 0x8c, 0x35, 0xb7, 0x33, 0x4b, 0xbc, 0x7b,
+                0x98, 0x58, 0xca, 0x22, 0x02, 0xad, 0x95, 0xcd
+            ]
+        );
+        assert_eq!(
+            super::blake2b_mac(&[], b"abc"),
+            Blake2b::<512>::new().update(b"abc").finalize()
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn blake2b_mac_key_too_long() {
+        super::blake2b_mac(&[0; 65], b"data");
+    }
+}
+
+#[cfg(test)]
+mod compress_tests {
+    use super::{blake2b_compress, Blake2b, Engine};
+
+    #[test]
+    fn single_block() {
+        // a single block message of 128 bytes hashed with blake2b-512, with no key,
+        // starting from the IV tweaked with the parameters of the engine
+        let mut message = [0u8; 128];
+        for (i, m) in message.iter_mut().enumerate() {
+            *m = i as u8;
+        }
+        let expected = [
+            0x23, 0x19, 0xe3, 0x78, 0x9c, 0x47, 0xe2, 0xda, 0xa5, 0xfe, 0x80, 0x7f, 0x61, 0xbe,
+            0xc2, 0xa1, 0xa6, 0x53, 0x7f, 0xa0, 0x3f, 0x19, 0xff, 0x32, 0xe8, 0x7e, 0xec, 0xbf,
+            0xd6, 0x4b, 0x7e, 0x0e, 0x8c, 0xcf, 0xf4, 0x39, 0xac, 0x33, 0x3b, 0x04, 0x0f, 0x19,
+            0xb0, 0xc4, 0xdd, 0xd1, 0x1a, 0x61, 0xe2, 0x4a, 0xc1, 0xfe, 0x0f, 0x10, 0xa0, 0x39,
+            0x80, 0x6c, 0x5d, 0xcc, 0x0d, 0xa3, 0xd1, 0x15,
+        ];
+
+        let mut h = Engine::new(64, 0).h;
+        blake2b_compress(&mut h, &message, [128, 0], true);
+
+        let mut out = [0u8; 64];
+        for (o, w) in out.chunks_mut(8).zip(h.iter()) {
+            o.copy_from_slice(&w.to_le_bytes());
+        }
+        assert_eq!(out, expected);
+        assert_eq!(out, Blake2b::<512>::new().update(&message).finalize());
+
+        // not being the last block changes the output
+        let mut h = Engine::new(64, 0).h;
+        blake2b_compress(&mut h, &message, [128, 0], false);
+        assert_ne!(h[0].to_le_bytes(), expected[0..8]);
+    }
 }
 
 #[cfg(all(test, feature = "with-bench"))]
@@ -25,6 +25,14 @@
 //! let mac = context.finalize();
 //! ```
 //!
+//! One-shot MAC with the full size output (32 bytes):
+//!
+//! ```
+//! use cryptoxide::hashing::blake2s;
+//!
+//! let mac = blake2s::blake2s_mac(b"secret key", b"hello world");
+//! ```
+//!
 //!
 //! [1]: <https://eprint.iacr.org/2013/322.pdf>
 
@@ -69,6 +77,9 @@ pub struct ContextDyn {
     outlen: usize,
 }
 
+impl_io_write!([const BITS: usize] Context<BITS>);
+impl_io_write!(ContextDyn);
+
 impl<const BITS: usize> Context<BITS> {
     /// Create a new Blake2s context with a specific output size in bytes
     ///
@@ -367,6 +378,22 @@ macro_rules! context_finalize {
 context_finalize!(224);
 context_finalize!(256);
 
+/// Compute the Blake2s MAC of the data with the key, returning the 32 bytes keyed hash
+///
+/// This is the native keyed mode of Blake2s (the key zero padded to a full block is
+/// hashed first), and not HMAC.
+///
+/// # Panics
+///
+/// Panics if the key is longer than 32 bytes
+pub fn blake2s_mac(key: &[u8], data: &[u8]) -> [u8; 32] {
+    assert!(
+        key.len() <= Engine::MAX_KEYLEN,
+        "Blake2s key longer than 32 bytes"
+    );
+    Context::<256>::new_keyed(key).update(data).finalize()
+}
+
 #[cfg(test)]
 mod digest_tests {
     use super::super::tests::{test_hashing, Test};
@@ -440,6 +467,67 @@ mod mac_tests {
             |ctx, key| ctx.reset_with_key(key),
         )
     }
+
+    #[test]
+    fn blake2s_mac_kat() {
+        // keyed KATs of the BLAKE2 reference (key 0..32, input 0..len), checked with python's hashlib
+        let key: [u8; 32] = core::array::from_fn(|i| i as u8);
+        let input: [u8; 255] = core::array::from_fn(|i| i as u8);
+        let kats: [(usize, [u8; 32]); 3] = [
+            (
+                0,
+                [
+                    0x48, 0xa8, 0x99, 0x7d, 0xa4, 0x07, 0x87, 0x6b, 0x3d, 0x79, 0xc0, 0xd9, 0x23,
+                    0x25, 0xad, 0x3b, 0x89, 0xcb, 0xb7, 0x54, 0xd8, 0x6a, 0xb7, 0x1a, 0xee, 0x04,
+                    0x7a, 0xd3, 0x45, 0xfd, 0x2c, 0x49,
+                ],
+            ),
+            (
+                1,
+                [
+                    0x40, 0xd1, 0x5f, 0xee, 0x7c, 0x32, 0x88, 0x30, 0x16, 0x6a, 0xc3, 0xf9, 0x18,
+                    0x65, 0x0f, 0x80, 0x7e, 0x7e, 0x01, 0xe1, 0x77, 0x25, 0x8c, 0xdc, 0x0a, 0x39,
+                    0xb1, 0x1f, 0x59, 0x80, 0x66, 0xf1,
+                ],
+            ),
+            (
+                255,
+                [
+                    0x3f, 0xb7, 0x35, 0x06, 0x1a, 0xbc, 0x51, 0x9d, 0xfe, 0x97, 0x9e, 0x54, 0xc1,
+                    0xee, 0x5b, 0xfa, 0xd0, 0xa9, 0xd8, 0x58, 0xb3, 0x31, 0x5b, 0xad, 0x34, 0xbd,
+                    0xe9, 0x99, 0xef, 0xd7, 0x24, 0xdd,
+                ],
+            ),
+        ];
+        for (len, expected) in kats.iter() {
+            assert_eq!(
+                &super::blake2s_mac(&key, &input[..*len]),
+                expected,
+                "length {}",
+                len
+            );
+        }
+
+        // a key shorter than the maximum is zero padded
+        assert_eq!(
+            super::blake2s_mac(b"key", b"hello world"),
+            [
+                0x6e, 0xcf, 0x3d, 0xfb, 0xe4, 0x4b, 0x28, 0xad, 0x3a, 0x7c, 0xe8, 0x91, 0xd0, 0x17,
+                0x13, 0xe3, 0x1f, 0x96, 0x57, 0xee, 0x00, 0x06, 0x9b, 0x6f, 0x26, 0x4a, 0x05, 0x2f,
+                0x6b, 0xea, 0x50, 0xb7
+            ]
+        );
+        assert_eq!(
+            super::blake2s_mac(&[], b"abc"),
+            Blake2s::<256>::new().update(b"abc").finalize()
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn blake2s_mac_key_too_long() {
+        super::blake2s_mac(&[0; 33], b"data");
+    }
 }
 
 #[cfg(all(test, feature = "with-bench"))]
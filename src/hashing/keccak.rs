@@ -8,6 +8,18 @@
 //! * `Keccak256`
 //! * `Keccak384`
 //! * `Keccak512`
+//!
+//! # Keccak and SHA-3
+//!
+//! Keccak and SHA-3 use the same permutation and rate, but SHA-3 (FIPS 202) appends
+//! the domain separation bits `01` before the padding (first padding byte `0x06`) while
+//! the original Keccak doesn't (first padding byte `0x01`), so the outputs are different:
+//!
+//! * Keccak-256("") = `c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470`
+//! * SHA3-256("") = `a7ffc6f8bf1ed76651c14756a061d662f580ff4de43b49fa82d80a4b80f8434a`
+//!
+//! Ethereum (addresses, transactions and ABI selectors) uses `Keccak256` from this
+//! module, often named "SHA3" in the Ethereum ecosystem, and not [`super::sha3::Sha3_256`].
 use super::sha3::{Engine, B};
 
 macro_rules! keccak_impl {
@@ -35,6 +47,8 @@ macro_rules! keccak_impl {
         #[derive(Clone)]
         pub struct $context(Engine<$digestlength, 0>);
 
+        impl_io_write!($context);
+
         impl $context {
             /// Construct a new `Keccak` object
             pub const fn new() -> Self {
@@ -91,6 +105,91 @@ keccak_impl!(Keccak512, Context512, 64, "Keccak512");
 mod tests {
     use super::super::tests::{test_hashing, Test};
     use super::*;
+    use crate::encoding::encode_hex;
+
+    #[test]
+    fn test_keccak_256() {
+        let tests = [
+            Test {
+                input: b"",
+                output: [
+                    0xc5, 0xd2, 0x46, 0x01, 0x86, 0xf7, 0x23, 0x3c, 0x92, 0x7e, 0x7d, 0xb2, 0xdc,
+                    0xc7, 0x03, 0xc0, 0xe5, 0x00, 0xb6, 0x53, 0xca, 0x82, 0x27, 0x3b, 0x7b, 0xfa,
+                    0xd8, 0x04, 0x5d, 0x85, 0xa4, 0x70,
+                ],
+            },
+            Test {
+                input: b"The quick brown fox jumps over the lazy dog",
+                output: [
+                    0x4d, 0x74, 0x1b, 0x6f, 0x1e, 0xb2, 0x9c, 0xb2, 0xa9, 0xb9, 0x91, 0x1c, 0x82,
+                    0xf5, 0x6f, 0xa8, 0xd7, 0x3b, 0x04, 0x95, 0x9d, 0x3d, 0x9d, 0x22, 0x28, 0x95,
+                    0xdf, 0x6c, 0x0b, 0x28, 0xaa, 0x15,
+                ],
+            },
+        ];
+        test_hashing(
+            &tests,
+            Keccak256,
+            |_| Context256::new(),
+            |ctx, input| ctx.update(input),
+            |ctx, input| ctx.update_mut(input),
+            |ctx| ctx.finalize(),
+            |ctx| ctx.finalize_reset(),
+            |ctx| ctx.reset(),
+        )
+    }
+
+    #[test]
+    fn keccak_256_is_not_sha3_256() {
+        assert_eq!(
+            encode_hex(&Keccak256::new().finalize()),
+            "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+        );
+        assert_eq!(
+            encode_hex(&super::super::sha3::Sha3_256::new().finalize()),
+            "a7ffc6f8bf1ed76651c14756a061d662f580ff4de43b49fa82d80a4b80f8434a"
+        );
+    }
+
+    #[test]
+    fn ethereum() {
+        // web3.eth.accounts.hashMessage / ethers.hashMessage (EIP-191 personal message)
+        fn hash_message(message: &[u8]) -> [u8; 32] {
+            let prefix = alloc::format!("\x19Ethereum Signed Message:\n{}", message.len());
+            Keccak256::new()
+                .update(prefix.as_bytes())
+                .update(message)
+                .finalize()
+        }
+        assert_eq!(
+            encode_hex(&hash_message(b"Hello World")),
+            "a1de988600a42c4b4ab089b619297c17d53cffae5d5120d82d8a92d0bb3b78f2"
+        );
+        assert_eq!(
+            encode_hex(&hash_message(&[0x42])),
+            "d18c12b87124f9ceb7e1d3a5d06a5ac92ecab15931417e8d1558d9a263f99d63"
+        );
+
+        // function selectors and event topics of the ERC-20 ABI
+        let tests = [
+            (
+                "transfer(address,uint256)",
+                "a9059cbb2ab09eb219583f4a59a5d0623ade346d962bcd4e46b11da047c9049b",
+            ),
+            (
+                "balanceOf(address)",
+                "70a08231b98ef4ca268c9cc3f6b4590e4bfec28280db06bb5d45e689f2a360be",
+            ),
+            (
+                "Transfer(address,address,uint256)",
+                "ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef",
+            ),
+        ];
+        for (signature, hash) in tests.iter() {
+            let output = Keccak256::new().update(signature.as_bytes()).finalize();
+            assert_eq!(&encode_hex(&output), hash, "{}", signature);
+        }
+    }
 
     #[test]
     fn test_keccak_512() {
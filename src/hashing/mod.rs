@@ -41,6 +41,50 @@
 //! ```
 //!
 
+// Implement `std::io::Write` for a hashing context by forwarding the data to `update_mut`
+#[cfg(any(
+    feature = "blake2",
+    feature = "sha1",
+    feature = "sha2",
+    feature = "sha3",
+    feature = "ripemd160"
+))]
+macro_rules! impl_io_write {
+    ([$($generics:tt)*] $ctx:ty) => {
+        #[cfg(feature = "std")]
+        impl<$($generics)*> std::io::Write for $ctx {
+            fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                self.update_mut(buf);
+                Ok(buf.len())
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+    };
+    ($ctx:ty) => {
+        impl_io_write!([] $ctx);
+    };
+}
+
+// Feed all the data of the reader to `update`, in chunks of up to 64KB
+#[cfg(all(feature = "std", any(feature = "sha2", feature = "blake2")))]
+fn read_chunks<R: std::io::Read, F: FnMut(&[u8])>(
+    mut reader: R,
+    mut update: F,
+) -> std::io::Result<()> {
+    let mut buf = alloc::vec![0u8; 64 * 1024];
+    loop {
+        match reader.read(&mut buf) {
+            Ok(0) => return Ok(()),
+            Ok(n) => update(&buf[..n]),
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+}
+
 #[cfg(feature = "blake2")]
 mod blake2;
 
@@ -142,6 +186,8 @@ pub fn sha3_224(input: &[u8]) -> [u8; 28] {
 
 #[cfg(feature = "sha3")]
 /// Compute SHA3-256 on the input and return the digest
+///
+/// This is the FIPS 202 SHA-3, which is not the Keccak256 used by Ethereum, see [`keccak256`].
 pub fn sha3_256(input: &[u8]) -> [u8; 32] {
     sha3::Sha3_256::new().update(input).finalize()
 }
@@ -166,6 +212,9 @@ pub fn keccak224(input: &[u8]) -> [u8; 28] {
 
 #[cfg(feature = "sha3")]
 /// Compute KECCAK256 on the input and return the digest
+///
+/// This is the original Keccak padding used by Ethereum, which is different from
+/// [`sha3_256`], see [`keccak`] for details.
 pub fn keccak256(input: &[u8]) -> [u8; 32] {
     keccak::Keccak256::new().update(input).finalize()
 }
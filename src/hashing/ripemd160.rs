@@ -337,6 +337,8 @@ fn process_msg_blocks(data: &[u8], h: &mut [u32; DIGEST_BUF_LEN]) {
 // initial state value
 const H: [u32; 5] = [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476, 0xc3d2e1f0];
 
+impl_io_write!(Context);
+
 impl Context {
     /// Construct a new `Ripemd160` object
     pub const fn new() -> Self {
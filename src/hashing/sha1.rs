@@ -384,6 +384,8 @@ const H3: u32 = 0x10325476;
 const H4: u32 = 0xC3D2E1F0;
 const H: [u32; STATE_LEN] = [H0, H1, H2, H3, H4];
 
+impl_io_write!(Context);
+
 impl Context {
     /// Construct a new default SHA1 context
     pub const fn new() -> Self {
@@ -22,6 +22,10 @@ impl Engine {
         self.h = *h;
     }
 
+    pub(super) fn h(&self) -> &[u64; STATE_LEN] {
+        &self.h
+    }
+
     /// Process a block in bytes with the SHA-2 32bits algorithm.
     pub fn blocks(&mut self, block: &[u8]) {
         assert_eq!(block.len() % BLOCK_LEN_BYTES, 0);
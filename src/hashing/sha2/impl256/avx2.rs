@@ -1,3 +1,9 @@
+// AVX2 implementation, computing the message schedules of 8 independent blocks
+// at once in 256 bits registers (each 32 bits lane holding the word of one block),
+// then doing the compression of each block with the scalar ALU.
+//
+// The remaining blocks (less than 8) are processed with the SSE4.1 implementation.
+
 #[cfg(target_arch = "x86")]
 use core::arch::x86::*;
 
@@ -9,9 +15,9 @@ use super::sse41;
 
 const K32: [u32; 64] = reference::K32;
 
-// takes 32 bits word from four data 64 bytes block and pack it into one simd u32x4
+// takes 32 bits word from eight data 64 bytes block and pack it into one simd u32x8
 //
-//   | block 3 | block 2 | block 1 | block 0 |
+//   | block 7 | block 6 | block 5 | block 4 | block 3 | block 2 | block 1 | block 0 |
 //
 // x86 architecture can (currently) do unaligned load (at a cost if unaligned)
 unsafe fn gather(block: *const u8) -> __m256i {
@@ -229,7 +235,7 @@ pub(crate) fn digest_block(state: &mut [u32; 8], mut block: &[u8]) {
     unsafe {
         let mut schedule = [_mm256_set1_epi32(0); 64];
         while block.len() >= 512 {
-            message_schedule_8ways(&mut schedule, &block);
+            message_schedule_8ways(&mut schedule, block);
             compress_8ways(state, &schedule);
             block = &block[512..]
         }
@@ -2,51 +2,64 @@
 //!
 //! Reference implementation come from haskell's cryptonite cbits
 //!
-//! SSE and AVX optimisation are coming from
+//! SSE and AVX2 optimisation are coming from
 //! <https://eprint.iacr.org/2012/067.pdf> and consist
-//! of creating the message schedule of 4 (SSE) or 8 (AVX) blocks
+//! of creating the message schedule of 4 (SSE) or 8 (AVX2) blocks
 //! at a time, then using the standard ALU to do the compression.
 //!
+//! When the SHA extensions are available, the rounds and the message
+//! schedule are done with the dedicated instructions instead.
+//!
 
 #[cfg(all(target_arch = "aarch64", feature = "use-stdsimd"))]
 mod aarch64;
 
 #[cfg(all(
     any(target_arch = "x86", target_arch = "x86_64"),
-    target_feature = "avx"
+    target_feature = "avx2"
 ))]
-mod avx;
+mod avx2;
 
+#[cfg(all(
+    any(target_arch = "x86", target_arch = "x86_64"),
+    target_feature = "sha",
+    target_feature = "sse4.1"
+))]
+mod sha_ni;
 #[cfg(all(
     any(target_arch = "x86", target_arch = "x86_64"),
     target_feature = "sse4.1"
 ))]
 mod sse41;
-//TODO not finished yet
-//#[cfg(all(target_arch = "x86_64", target_feature = "sha"))]
-//mod x64sha;
 
 // software implementation valid for all architectures
 mod reference;
 
+pub(crate) use reference::digest_block_const;
+
 pub(crate) fn digest_block(state: &mut [u32; 8], block: &[u8]) {
     #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
     {
         /// in waiting for https://github.com/rust-lang/rfcs/pull/2725
-        #[cfg(target_feature = "avx")]
-        const HAS_AVX: bool = true;
-        #[cfg(not(target_feature = "avx"))]
-        const HAS_AVX: bool = false;
+        #[cfg(target_feature = "avx2")]
+        const HAS_AVX2: bool = true;
+        #[cfg(not(target_feature = "avx2"))]
+        const HAS_AVX2: bool = false;
 
         #[cfg(target_feature = "sse4.1")]
         const HAS_SSE41: bool = true;
         #[cfg(not(target_feature = "sse4.1"))]
         const HAS_SSE41: bool = false;
 
-        #[cfg(target_feature = "avx")]
+        #[cfg(all(target_feature = "sha", target_feature = "sse4.1"))]
+        if true {
+            return sha_ni::digest_block(state, block);
+        }
+
+        #[cfg(target_feature = "avx2")]
         {
-            if HAS_AVX {
-                return avx::digest_block(state, block);
+            if HAS_AVX2 {
+                return avx2::digest_block(state, block);
             }
         }
 
@@ -1,22 +1,22 @@
 use crate::cryptoutil::read_u32v_be;
 
 #[inline(always)]
-pub(crate) fn e0(x: u32) -> u32 {
+pub(crate) const fn e0(x: u32) -> u32 {
     x.rotate_right(2) ^ x.rotate_right(13) ^ x.rotate_right(22)
 }
 
 #[inline(always)]
-pub(crate) fn e1(x: u32) -> u32 {
+pub(crate) const fn e1(x: u32) -> u32 {
     x.rotate_right(6) ^ x.rotate_right(11) ^ x.rotate_right(25)
 }
 
 #[inline(always)]
-fn s0(x: u32) -> u32 {
+const fn s0(x: u32) -> u32 {
     x.rotate_right(7) ^ x.rotate_right(18) ^ (x >> 3)
 }
 
 #[inline(always)]
-fn s1(x: u32) -> u32 {
+const fn s1(x: u32) -> u32 {
     x.rotate_right(17) ^ x.rotate_right(19) ^ (x >> 10)
 }
 
@@ -99,3 +99,48 @@ pub(crate) fn digest_block(state: &mut [u32; 8], block: &[u8]) {
         i += 64;
     }
 }
+
+/// Same as the compression of a single block but usable in const context,
+/// to compute some digests at compile time.
+pub(crate) const fn digest_block_const(state: [u32; 8], block: &[u8; 64]) -> [u32; 8] {
+    let mut w = [0u32; 64];
+    let mut i = 0;
+    while i < 16 {
+        w[i] = u32::from_be_bytes([
+            block[4 * i],
+            block[4 * i + 1],
+            block[4 * i + 2],
+            block[4 * i + 3],
+        ]);
+        i += 1;
+    }
+    while i < 64 {
+        w[i] = s1(w[i - 2])
+            .wrapping_add(w[i - 7])
+            .wrapping_add(s0(w[i - 15]))
+            .wrapping_add(w[i - 16]);
+        i += 1;
+    }
+
+    let mut v = state;
+    i = 0;
+    while i < 64 {
+        let [a, b, c, d, e, f, g, h] = v;
+        let t1 = h
+            .wrapping_add(e1(e))
+            .wrapping_add(g ^ (e & (f ^ g)))
+            .wrapping_add(K32[i])
+            .wrapping_add(w[i]);
+        let t2 = e0(a).wrapping_add((a & b) | (c & (a | b)));
+        v = [t1.wrapping_add(t2), a, b, c, d.wrapping_add(t1), e, f, g];
+        i += 1;
+    }
+
+    let mut out = state;
+    i = 0;
+    while i < 8 {
+        out[i] = out[i].wrapping_add(v[i]);
+        i += 1;
+    }
+    out
+}
@@ -0,0 +1,87 @@
+// SHA extensions (SHA-NI) implementation
+//
+// The state is kept in 2 registers as ABEF and CDGH, which is the layout
+// expected by the sha256rnds2 instruction doing 2 rounds at a time. The message
+// schedule is computed 4 words at a time with sha256msg1 and sha256msg2.
+
+#[cfg(target_arch = "x86")]
+use core::arch::x86::*;
+
+#[cfg(target_arch = "x86_64")]
+use core::arch::x86_64::*;
+
+use super::reference;
+
+const K32: [u32; 64] = reference::K32;
+
+// 4 rounds using the message words w (4 words), and the constants K starting at 4*i
+macro_rules! rounds4 {
+    ($abef:ident, $cdgh:ident, $w:expr, $i:expr) => {
+        let t1 = _mm_add_epi32(
+            $w,
+            _mm_loadu_si128(K32[4 * $i..].as_ptr() as *const __m128i),
+        );
+        $cdgh = _mm_sha256rnds2_epu32($cdgh, $abef, t1);
+        let t2 = _mm_shuffle_epi32(t1, 0x0E);
+        $abef = _mm_sha256rnds2_epu32($abef, $cdgh, t2);
+    };
+}
+
+// compute the next 4 words of the message schedule from the previous 16 words
+macro_rules! schedule {
+    ($w0:expr, $w1:expr, $w2:expr, $w3:expr) => {
+        _mm_sha256msg2_epu32(
+            _mm_add_epi32(_mm_sha256msg1_epu32($w0, $w1), _mm_alignr_epi8($w3, $w2, 4)),
+            $w3,
+        )
+    };
+}
+
+#[target_feature(enable = "sha,sse2,ssse3,sse4.1")]
+unsafe fn digest_blocks(state: &mut [u32; 8], mut block: &[u8]) {
+    let bswap_mask = _mm_set_epi64x(0x0c0d0e0f_08090a0b, 0x04050607_00010203);
+
+    // DCBA, HGFE => ABEF, CDGH
+    #[allow(clippy::cast_ptr_alignment)]
+    let s = state.as_mut_ptr() as *mut __m128i;
+    let dcba = _mm_shuffle_epi32(_mm_loadu_si128(s), 0xB1); // CDAB
+    let hgfe = _mm_shuffle_epi32(_mm_loadu_si128(s.add(1)), 0x1B); // EFGH
+    let mut abef = _mm_alignr_epi8(dcba, hgfe, 8);
+    let mut cdgh = _mm_blend_epi16(hgfe, dcba, 0xF0);
+
+    while block.len() >= 64 {
+        let abef_save = abef;
+        let cdgh_save = cdgh;
+
+        #[allow(clippy::cast_ptr_alignment)]
+        let p = block.as_ptr() as *const __m128i;
+        let mut w = [
+            _mm_shuffle_epi8(_mm_loadu_si128(p), bswap_mask),
+            _mm_shuffle_epi8(_mm_loadu_si128(p.add(1)), bswap_mask),
+            _mm_shuffle_epi8(_mm_loadu_si128(p.add(2)), bswap_mask),
+            _mm_shuffle_epi8(_mm_loadu_si128(p.add(3)), bswap_mask),
+        ];
+
+        for i in 0..4 {
+            rounds4!(abef, cdgh, w[i], i);
+        }
+        for i in 4..16 {
+            w[i % 4] = schedule!(w[i % 4], w[(i + 1) % 4], w[(i + 2) % 4], w[(i + 3) % 4]);
+            rounds4!(abef, cdgh, w[i % 4], i);
+        }
+
+        abef = _mm_add_epi32(abef, abef_save);
+        cdgh = _mm_add_epi32(cdgh, cdgh_save);
+        block = &block[64..];
+    }
+
+    // ABEF, CDGH => DCBA, HGFE
+    let feba = _mm_shuffle_epi32(abef, 0x1B);
+    let dchg = _mm_shuffle_epi32(cdgh, 0xB1);
+    _mm_storeu_si128(s, _mm_blend_epi16(feba, dchg, 0xF0));
+    _mm_storeu_si128(s.add(1), _mm_alignr_epi8(dchg, feba, 8));
+}
+
+pub(crate) fn digest_block(state: &mut [u32; 8], block: &[u8]) {
+    unsafe { digest_blocks(state, block) }
+}
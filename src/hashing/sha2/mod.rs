@@ -50,10 +50,13 @@ mod eng512;
 mod impl256;
 mod impl512;
 mod initials;
+mod selftest;
 
-use crate::cryptoutil::FixedBuffer;
+use crate::cryptoutil::{read_u32v_be, read_u64v_be, write_u32v_be, write_u64v_be, FixedBuffer};
 use initials::*;
 
+pub use selftest::sha256_self_test;
+
 macro_rules! digest {
     (256 $name:ident, $ctxname:ident, $output_fn: ident, $output_bits:expr, $state:ident) => {
         digest!(
@@ -64,6 +67,7 @@ macro_rules! digest {
             $output_fn,
             $output_bits,
             64,
+            112,
             $state
         );
     };
@@ -76,10 +80,11 @@ macro_rules! digest {
             $output_fn,
             $output_bits,
             128,
+            216,
             $state
         );
     };
-    (@internal $name:ident, $ctxname:ident, $init:ident, $output_fn:ident, $output_bits:expr, $block_size:literal, $state: ident) => {
+    (@internal $name:ident, $ctxname:ident, $init:ident, $output_fn:ident, $output_bits:expr, $block_size:literal, $export_size:literal, $state: ident) => {
         /// Hash Algorithm
         #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
         pub struct $name;
@@ -104,6 +109,8 @@ macro_rules! digest {
             engine: $init,
         }
 
+        impl_io_write!($ctxname);
+
         impl $ctxname {
             /// Create a new hashing algorithm context
             pub const fn new() -> Self {
@@ -151,6 +158,22 @@ macro_rules! digest {
             pub fn reset(&mut self) {
                 self.engine.reset(&$state);
             }
+
+            /// Export the context state, so that the hashing can be resumed later with `import_state`
+            ///
+            /// The exported state contains the intermediate hash values, the number of bytes
+            /// processed, a finished flag and the buffered input not yet processed. Note that
+            /// this state is as sensitive as the data hashed so far.
+            pub fn export_state(&self) -> [u8; $export_size] {
+                self.engine.export()
+            }
+
+            /// Create a context from a state previously exported with `export_state`
+            ///
+            /// Returns `None` if the state is not internally consistent
+            pub fn import_state(state: &[u8; $export_size]) -> Option<Self> {
+                $init::import(state).map(|engine| Self { engine })
+            }
         }
     };
 }
@@ -185,6 +208,36 @@ impl Engine512 {
         self.buffer.input(input, |input| self_state.blocks(input));
     }
 
+    // Export layout:
+    // H (8 x u64 BE) | PROCESSED_BYTES (u128 BE) | FLAG (1 byte, always 0) | RESERVED (7 bytes) | BUFFER (128 bytes)
+    fn export(&self) -> [u8; 216] {
+        let mut out = [0u8; 216];
+        write_u64v_be(&mut out[0..64], self.state.h());
+        out[64..80].copy_from_slice(&self.processed_bytes.to_be_bytes());
+        let buffered = self.buffer.buffered();
+        out[88..88 + buffered.len()].copy_from_slice(buffered);
+        out
+    }
+
+    fn import(bytes: &[u8; 216]) -> Option<Self> {
+        let mut h = [0u64; eng512::STATE_LEN];
+        read_u64v_be(&mut h, &bytes[0..64]);
+        let processed_bytes = u128::from_be_bytes(bytes[64..80].try_into().unwrap());
+        if processed_bytes > (u128::MAX >> 3) || bytes[80..88].iter().any(|b| *b != 0) {
+            return None;
+        }
+        let buffered = (processed_bytes % 128) as usize;
+        if bytes[88 + buffered..].iter().any(|b| *b != 0) {
+            return None;
+        }
+        let mut engine = Engine512::new(&h);
+        engine.processed_bytes = processed_bytes;
+        engine
+            .buffer
+            .input(&bytes[88..88 + buffered], |_| unreachable!());
+        Some(engine)
+    }
+
     fn finish(&mut self) {
         let self_state = &mut self.state;
         self.buffer
@@ -228,6 +281,48 @@ impl Engine256 {
         self.buffer.input(input, |input| self_state.blocks(input));
     }
 
+    // Export layout:
+    // H (8 x u32 BE) | PROCESSED_BYTES (u64 BE) | FINISHED (1 byte) | RESERVED (7 bytes) | BUFFER (64 bytes)
+    fn export(&self) -> [u8; 112] {
+        let mut out = [0u8; 112];
+        write_u32v_be(&mut out[0..32], self.state.h());
+        out[32..40].copy_from_slice(&self.processed_bytes.to_be_bytes());
+        out[40] = self.finished as u8;
+        let buffered = self.buffer.buffered();
+        out[48..48 + buffered.len()].copy_from_slice(buffered);
+        out
+    }
+
+    fn import(bytes: &[u8; 112]) -> Option<Self> {
+        let mut h = [0u32; eng256::STATE_LEN];
+        read_u32v_be(&mut h, &bytes[0..32]);
+        let processed_bytes = u64::from_be_bytes(bytes[32..40].try_into().unwrap());
+        let finished = match bytes[40] {
+            0 => false,
+            1 => true,
+            _ => return None,
+        };
+        if processed_bytes > (u64::MAX >> 3) || bytes[41..48].iter().any(|b| *b != 0) {
+            return None;
+        }
+        // once finished, the buffer has been entirely consumed by the padding
+        let buffered = if finished {
+            0
+        } else {
+            (processed_bytes % 64) as usize
+        };
+        if bytes[48 + buffered..].iter().any(|b| *b != 0) {
+            return None;
+        }
+        let mut engine = Engine256::new(&h);
+        engine.processed_bytes = processed_bytes;
+        engine.finished = finished;
+        engine
+            .buffer
+            .input(&bytes[48..48 + buffered], |_| unreachable!());
+        Some(engine)
+    }
+
     fn finish(&mut self) {
         if self.finished {
             return;
@@ -264,6 +359,22 @@ digest!(
 digest!(256 Sha256, Context256, output_256bits_at, 256, H256);
 digest!(256 Sha224, Context224, output_224bits_at, 224, H224);
 
+/// Compute the SHA-256 digest of all the data read from the reader, in chunks of 64KB
+#[cfg(feature = "std")]
+pub fn sha256_reader<R: std::io::Read>(reader: R) -> std::io::Result<[u8; 32]> {
+    let mut context = Context256::new();
+    super::read_chunks(reader, |chunk| context.update_mut(chunk))?;
+    Ok(context.finalize())
+}
+
+/// Compute the SHA-512 digest of all the data read from the reader, in chunks of 64KB
+#[cfg(feature = "std")]
+pub fn sha512_reader<R: std::io::Read>(reader: R) -> std::io::Result<[u8; 64]> {
+    let mut context = Context512::new();
+    super::read_chunks(reader, |chunk| context.update_mut(chunk))?;
+    Ok(context.finalize())
+}
+
 #[cfg(test)]
 mod tests {
     use super::super::tests::{test_hashing, Test};
@@ -444,8 +555,24 @@ mod tests {
 
     #[test]
     fn test_sha256() {
-        // Examples from wikipedia
+        // Examples from wikipedia, and from NIST FIPS 180-4 (one and two blocks messages)
         let tests = [
+            Test {
+                input: b"abc",
+                output: [
+                    0xba, 0x78, 0x16, 0xbf, 0x8f, 0x01, 0xcf, 0xea, 0x41, 0x41, 0x40, 0xde, 0x5d,
+                    0xae, 0x22, 0x23, 0xb0, 0x03, 0x61, 0xa3, 0x96, 0x17, 0x7a, 0x9c, 0xb4, 0x10,
+                    0xff, 0x61, 0xf2, 0x00, 0x15, 0xad,
+                ],
+            },
+            Test {
+                input: b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq",
+                output: [
+                    0x24, 0x8d, 0x6a, 0x61, 0xd2, 0x06, 0x38, 0xb8, 0xe5, 0xc0, 0x26, 0x93, 0x0c,
+                    0x3e, 0x60, 0x39, 0xa3, 0x3c, 0xe4, 0x59, 0x64, 0xff, 0x21, 0x67, 0xf6, 0xec,
+                    0xed, 0xd4, 0x19, 0xdb, 0x06, 0xc1,
+                ],
+            },
             Test {
                 input: b"",
                 output: [
@@ -483,6 +610,11 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_sha256_self_test() {
+        assert!(super::sha256_self_test());
+    }
+
     #[test]
     fn test_sha224() {
         // Examples from wikipedia
@@ -523,6 +655,115 @@ mod tests {
             |ctx| ctx.reset(),
         )
     }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_sha256_io_write() {
+        use std::io::Write;
+
+        let data = resume_input();
+        let mut ctx = Context256::new();
+        let copied = std::io::copy(&mut std::io::Cursor::new(&data), &mut ctx).unwrap();
+        assert_eq!(copied, data.len() as u64);
+        assert_eq!(ctx.finalize(), Context256::new().update(&data).finalize());
+
+        let mut ctx = Context256::new();
+        let animal = "dog";
+        write!(ctx, "The quick brown fox jumps over the lazy {}", animal).unwrap();
+        ctx.flush().unwrap();
+        assert_eq!(
+            ctx.finalize(),
+            [
+                0xd7, 0xa8, 0xfb, 0xb3, 0x07, 0xd7, 0x80, 0x94, 0x69, 0xca, 0x9a, 0xbc, 0xb0, 0x08,
+                0x2e, 0x4f, 0x8d, 0x56, 0x51, 0xe4, 0x6d, 0x3c, 0xdb, 0x76, 0x2d, 0x02, 0xd0, 0xbf,
+                0x37, 0xc9, 0xe5, 0x92,
+            ]
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_reader() {
+        use super::{sha256_reader, sha512_reader};
+        use std::io::Cursor;
+
+        // larger than the 64KB chunks, and not a multiple of the block size
+        let data: alloc::vec::Vec<u8> = (0..200_003u32).map(|i| (i % 251) as u8).collect();
+
+        let digest256 = sha256_reader(Cursor::new(&data)).unwrap();
+        let digest512 = sha512_reader(Cursor::new(&data)).unwrap();
+        assert_eq!(digest256, Context256::new().update(&data).finalize());
+        assert_eq!(digest512, Context512::new().update(&data).finalize());
+
+        assert_eq!(
+            sha256_reader(&b""[..]).unwrap(),
+            Context256::new().finalize()
+        );
+    }
+
+    fn resume_input() -> alloc::vec::Vec<u8> {
+        (0..2 * 1024 * 1024).map(|i| (i % 251) as u8).collect()
+    }
+
+    #[test]
+    fn test_sha256_export_import() {
+        let expected = [
+            0x1e, 0x07, 0x5c, 0x8d, 0x47, 0x8a, 0xd2, 0x18, 0x44, 0xe3, 0x3e, 0x83, 0x0a, 0x69,
+            0x5e, 0xf0, 0x3a, 0x4d, 0x24, 0x88, 0xb6, 0x9e, 0xe2, 0x75, 0xbd, 0x89, 0x47, 0x61,
+            0x8b, 0xb1, 0xbe, 0x1e,
+        ];
+        let data = resume_input();
+        let (first, second) = data.split_at(1024 * 1024);
+
+        let state = Context256::new().update(first).export_state();
+        let ctx = Context256::import_state(&state).unwrap();
+        assert_eq!(ctx.update(second).finalize(), expected);
+
+        // export with some buffered data
+        let state = Context256::new().update(&first[..1000]).export_state();
+        let ctx = Context256::import_state(&state).unwrap();
+        assert_eq!(
+            ctx.update(&first[1000..]).update(second).finalize(),
+            expected
+        );
+
+        // invalid flag, reserved bytes and buffer inconsistent with the number of bytes processed
+        for i in [40, 41, 47, 48 + 1000 % 64, 111] {
+            let mut bad = state;
+            bad[i] ^= 0x2;
+            assert!(Context256::import_state(&bad).is_none());
+        }
+    }
+
+    #[test]
+    fn test_sha512_export_import() {
+        let expected = [
+            0xeb, 0x0e, 0x38, 0xfa, 0x0c, 0x2d, 0xd9, 0x2c, 0x3a, 0xe2, 0x9a, 0x6b, 0x05, 0xf7,
+            0x43, 0x93, 0x54, 0x75, 0x16, 0x08, 0x9d, 0x64, 0x0f, 0xc9, 0x8d, 0xda, 0x82, 0x89,
+            0x58, 0x2a, 0x36, 0x31, 0x7f, 0xe3, 0x59, 0xbc, 0xd9, 0xd0, 0xbd, 0xa4, 0xe9, 0xd2,
+            0x5f, 0xc0, 0xa6, 0x30, 0x03, 0xd6, 0x46, 0x5c, 0x72, 0x9b, 0xdc, 0x98, 0xf2, 0x8a,
+            0x31, 0x89, 0x32, 0x07, 0x17, 0x29, 0xd8, 0x25,
+        ];
+        let data = resume_input();
+        let (first, second) = data.split_at(1024 * 1024);
+
+        let state = Context512::new().update(first).export_state();
+        let ctx = Context512::import_state(&state).unwrap();
+        assert_eq!(ctx.update(second).finalize(), expected);
+
+        let state = Context512::new().update(&first[..1000]).export_state();
+        let ctx = Context512::import_state(&state).unwrap();
+        assert_eq!(
+            ctx.update(&first[1000..]).update(second).finalize(),
+            expected
+        );
+
+        for i in [80, 87, 88 + 1000 % 128, 215] {
+            let mut bad = state;
+            bad[i] ^= 0x2;
+            assert!(Context512::import_state(&bad).is_none());
+        }
+    }
 }
 
 #[cfg(all(test, feature = "with-bench"))]
@@ -0,0 +1,64 @@
+//! SHA-256 known answer self-test
+//!
+//! FIPS 140-3 requires a cryptographic module to test its algorithms before
+//! their first use. The test hashes the empty string with the engine selected
+//! for the platform (SHA extensions, AVX2, SSE4.1 or the reference implementation)
+//! and compares it to the known digest.
+//!
+//! With the `std` feature, the test runs once on the first use of the SHA-256
+//! engine and panics on failure.
+
+use super::impl256::{digest_block, digest_block_const};
+use super::initials::H256;
+use crate::constant_time::CtEqual;
+
+// SHA-256 of the empty string
+const EXPECTED: [u8; 32] = [
+    0xe3, 0xb0, 0xc4, 0x42, 0x98, 0xfc, 0x1c, 0x14, 0x9a, 0xfb, 0xf4, 0xc8, 0x99, 0x6f, 0xb9, 0x24,
+    0x27, 0xae, 0x41, 0xe4, 0x64, 0x9b, 0x93, 0x4c, 0xa4, 0x95, 0x99, 0x1b, 0x78, 0x52, 0xb8, 0x55,
+];
+
+// The padded empty message: the terminating bit followed by a zero length
+const EMPTY_BLOCK: [u8; 64] = {
+    let mut block = [0u8; 64];
+    block[0] = 0x80;
+    block
+};
+
+// Check at build time that the expected digest is correct, by evaluating
+// the reference compression function in const context
+const _: () = {
+    let h = digest_block_const(H256, &EMPTY_BLOCK);
+    let mut i = 0;
+    while i < 32 {
+        let byte = h[i / 4].to_be_bytes()[i % 4];
+        assert!(byte == EXPECTED[i], "invalid SHA-256 self-test digest");
+        i += 1;
+    }
+};
+
+/// Run the SHA-256 known answer test, returning whether the engine
+/// compute the expected digest of the empty string.
+pub fn sha256_self_test() -> bool {
+    let mut h = H256;
+    digest_block(&mut h, &EMPTY_BLOCK);
+    let mut digest = [0u8; 32];
+    for (out, w) in digest.chunks_mut(4).zip(h.iter()) {
+        out.copy_from_slice(&w.to_be_bytes());
+    }
+    digest.ct_eq(&EXPECTED).is_true()
+}
+
+#[cfg(feature = "std")]
+pub(super) fn self_test_once() {
+    static SELF_TEST: std::sync::Once = std::sync::Once::new();
+    SELF_TEST.call_once(|| {
+        if !sha256_self_test() {
+            panic!(
+                "SHA-256 self-test failed on {}-{}",
+                std::env::consts::ARCH,
+                std::env::consts::OS
+            );
+        }
+    });
+}
@@ -23,12 +23,42 @@
 //! let out : [u8; 32] = context.update(b"abc").finalize();
 //!
 //! ```
+//!
+//! The underlying sponge construction is available as [`KeccakSponge`], to build
+//! other constructions on the Keccak-f\[1600\] permutation (e.g. SHAKE, cSHAKE or KMAC).
+//! The input is absorbed, then padded with an explicit domain separation, and the
+//! output squeezed as many times as needed:
+//!
+//! ```rust
+//! use cryptoxide::hashing::sha3::KeccakSponge;
+//!
+//! // SHAKE256 (rate of 136 bytes, domain separation 0x1f)
+//! let mut sponge = KeccakSponge::new(136);
+//! sponge.absorb(b"some ");
+//! sponge.absorb(b"input");
+//! sponge.pad_and_switch_to_squeezing(0x1f);
+//!
+//! let mut first = [0u8; 32];
+//! let mut next = [0u8; 100];
+//! sponge.squeeze(&mut first);
+//! sponge.squeeze(&mut next);
+//! ```
+//!
+//! KangarooTwelve and TurboSHAKE use a 12 rounds variant of the permutation, and
+//! can't be built on the sponge.
 
 use alloc::vec;
 use core::cmp;
 
 use crate::cryptoutil::{read_u64v_le, write_u64v_le, zero};
 
+#[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+mod neon;
+
+mod sponge;
+
+pub use sponge::KeccakSponge;
+
 pub(super) const B: usize = 200;
 const NROUNDS: usize = 24;
 const RC: [u64; 24] = [
@@ -65,15 +95,29 @@ const PIL: [usize; 24] = [
 ];
 const M5: [usize; 10] = [0, 1, 2, 3, 4, 0, 1, 2, 3, 4];
 
-// Code based on Keccak-compact64.c from ref implementation.
-#[allow(clippy::needless_range_loop)]
 fn keccak_f(state: &mut [u8; B]) {
     let mut s: [u64; 25] = [0; 25];
+    read_u64v_le(&mut s, state);
+    keccak_f1600(&mut s);
+    write_u64v_le(state, &s);
+}
+
+// The Keccak-f[1600] permutation on the 25 lanes of the state
+fn keccak_f1600(s: &mut [u64; 25]) {
+    #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+    {
+        return neon::keccak_f1600(s);
+    }
+    #[allow(unreachable_code)]
+    keccak_f1600_reference(s)
+}
+
+// Code based on Keccak-compact64.c from ref implementation.
+#[allow(clippy::needless_range_loop)]
+fn keccak_f1600_reference(s: &mut [u64; 25]) {
     let mut t: [u64; 1] = [0; 1];
     let mut c: [u64; 5] = [0; 5];
 
-    read_u64v_le(&mut s, state);
-
     for round in 0..NROUNDS {
         // Theta
         for x in 0..5 {
@@ -107,8 +151,6 @@ fn keccak_f(state: &mut [u8; B]) {
         // Iota
         s[0] ^= RC[round];
     }
-
-    write_u64v_le(state, &s);
 }
 
 /// Engine for Keccak implementation where
@@ -318,6 +360,8 @@ macro_rules! sha3_impl {
         #[derive(Clone)]
         pub struct $context(Engine<$digestlength, 2>);
 
+        impl_io_write!($context);
+
         impl $context {
             /// Create a new SHA3 Context
             pub const fn new() -> Self {
@@ -544,3 +588,33 @@ mod tests {
         )
     }
 }
+
+#[cfg(all(test, feature = "with-bench"))]
+mod bench {
+    use super::Context256;
+    use test::Bencher;
+
+    #[bench]
+    pub fn sha3_256_64k(bh: &mut Bencher) {
+        let bytes = [1u8; 65536];
+        bh.iter(|| {
+            let _ = Context256::new().update(&bytes).finalize();
+        });
+        bh.bytes = bytes.len() as u64;
+    }
+
+    #[bench]
+    pub fn keccak_f_reference(bh: &mut Bencher) {
+        let mut state = [1u64; 25];
+        bh.iter(|| super::keccak_f1600_reference(&mut state));
+        bh.bytes = super::B as u64;
+    }
+
+    #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+    #[bench]
+    pub fn keccak_f_neon(bh: &mut Bencher) {
+        let mut state = [1u64; 25];
+        bh.iter(|| super::neon::keccak_f1600(&mut state));
+        bh.bytes = super::B as u64;
+    }
+}
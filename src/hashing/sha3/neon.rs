@@ -0,0 +1,82 @@
+// NEON implementation of Keccak-f[1600] for AArch64
+//
+// The state is kept as 25 lanes of 64 bits, and the Theta and Chi steps are
+// computed on pairs of lanes (x = 0,1 and x = 2,3) in 2x64 bits vectors, with
+// the fifth lane of each row done with scalar operations. Rho and Pi only move
+// and rotate single lanes, so they are kept scalar.
+
+use super::{NROUNDS, PIL, RC, ROTC};
+use core::arch::aarch64::*;
+
+#[inline(always)]
+unsafe fn rotate1_u64(x: uint64x2_t) -> uint64x2_t {
+    vsriq_n_u64::<63>(vshlq_n_u64::<1>(x), x)
+}
+
+#[inline(always)]
+unsafe fn load2(a: u64, b: u64) -> uint64x2_t {
+    let v = [a, b];
+    vld1q_u64(v.as_ptr())
+}
+
+pub fn keccak_f1600(s: &mut [u64; 25]) {
+    unsafe {
+        for rc in RC.iter().take(NROUNDS) {
+            // Theta
+            let mut c01 = vld1q_u64(s[0..2].as_ptr());
+            let mut c23 = vld1q_u64(s[2..4].as_ptr());
+            let mut c4 = s[4];
+            for y in 1..5 {
+                c01 = veorq_u64(c01, vld1q_u64(s[y * 5..y * 5 + 2].as_ptr()));
+                c23 = veorq_u64(c23, vld1q_u64(s[y * 5 + 2..y * 5 + 4].as_ptr()));
+                c4 ^= s[y * 5 + 4];
+            }
+            let c0 = vgetq_lane_u64::<0>(c01);
+            let c3 = vgetq_lane_u64::<1>(c23);
+            // c12 = (c1, c2) and c34 = (c3, c4)
+            let c12 = vextq_u64::<1>(c01, c23);
+            let c34 = load2(c3, c4);
+            let d01 = veorq_u64(load2(c4, c0), rotate1_u64(c12));
+            let d23 = veorq_u64(c12, rotate1_u64(c34));
+            let d4 = c3 ^ c0.rotate_left(1);
+            for y in 0..5 {
+                let row = &mut s[y * 5..y * 5 + 5];
+                vst1q_u64(
+                    row[0..2].as_mut_ptr(),
+                    veorq_u64(vld1q_u64(row[0..2].as_ptr()), d01),
+                );
+                vst1q_u64(
+                    row[2..4].as_mut_ptr(),
+                    veorq_u64(vld1q_u64(row[2..4].as_ptr()), d23),
+                );
+                row[4] ^= d4;
+            }
+
+            // Rho Pi
+            let mut t = s[1];
+            for (pil, rotc) in PIL.iter().zip(ROTC.iter()) {
+                let c = s[*pil];
+                s[*pil] = t.rotate_left(*rotc);
+                t = c;
+            }
+
+            // Chi
+            for y in 0..5 {
+                let row = &mut s[y * 5..y * 5 + 5];
+                let a01 = vld1q_u64(row[0..2].as_ptr());
+                let a23 = vld1q_u64(row[2..4].as_ptr());
+                let a44 = vdupq_n_u64(row[4]);
+                let a12 = vextq_u64::<1>(a01, a23);
+                let a34 = vextq_u64::<1>(a23, a44);
+                let a40 = vextq_u64::<1>(a44, a01);
+                let a4 = row[4] ^ (!row[0] & row[1]);
+                vst1q_u64(row[0..2].as_mut_ptr(), veorq_u64(a01, vbicq_u64(a23, a12)));
+                vst1q_u64(row[2..4].as_mut_ptr(), veorq_u64(a23, vbicq_u64(a40, a34)));
+                row[4] = a4;
+            }
+
+            // Iota
+            s[0] ^= rc;
+        }
+    }
+}
@@ -0,0 +1,215 @@
+//! Keccak sponge construction
+//!
+//! The sponge absorbs the input into the first `rate` bytes of the 1600 bits
+//! Keccak state, applying the Keccak-f\[1600\] permutation each time `rate` bytes
+//! have been absorbed. Once padded, the output is squeezed out of the same
+//! `rate` bytes, applying the permutation each time they have been consumed.
+//!
+//! The domain separation bits given to `pad_and_switch_to_squeezing` are appended
+//! to the input before the final bit of the padding, combined with the first bit of
+//! the padding as in the FIPS 202 notation:
+//!
+//! * Keccak: `0x01`
+//! * SHA-3: `0x06`
+//! * SHAKE: `0x1f`
+//! * cSHAKE (and KMAC): `0x04`
+//!
+//! ```
+//! use cryptoxide::hashing::sha3::KeccakSponge;
+//!
+//! // SHAKE128 of "abc"
+//! let mut sponge = KeccakSponge::new(168);
+//! sponge.absorb(b"abc");
+//! sponge.pad_and_switch_to_squeezing(0x1f);
+//! let mut out = [0u8; 64];
+//! sponge.squeeze(&mut out);
+//! ```
+
+use super::{keccak_f1600, B};
+
+/// Keccak sponge over the Keccak-f\[1600\] permutation, with a rate in bytes
+#[derive(Clone)]
+pub struct KeccakSponge {
+    state: [u64; 25],
+    pos: usize,
+    rate: usize,
+    squeezing: bool,
+}
+
+impl KeccakSponge {
+    /// Create a new sponge with a rate of `rate` bytes, the capacity being the rest of the 200 bytes
+    /// (e.g. 136 for SHA3-256 and SHAKE256, 168 for SHAKE128)
+    ///
+    /// # Panics
+    ///
+    /// Panics if the rate is 0, or not less than 200
+    pub fn new(rate: usize) -> Self {
+        assert!(rate > 0 && rate < B);
+        Self {
+            state: [0; 25],
+            pos: 0,
+            rate,
+            squeezing: false,
+        }
+    }
+
+    /// The rate of the sponge in bytes
+    pub fn rate(&self) -> usize {
+        self.rate
+    }
+
+    #[inline]
+    fn xor_byte(&mut self, pos: usize, byte: u8) {
+        self.state[pos / 8] ^= (byte as u64) << (8 * (pos % 8));
+    }
+
+    /// Absorb the input into the sponge
+    ///
+    /// # Panics
+    ///
+    /// Panics if the sponge has already switched to squeezing
+    pub fn absorb(&mut self, input: &[u8]) {
+        assert!(
+            !self.squeezing,
+            "Invalid state, absorb phase already finalized."
+        );
+        let mut input = input;
+        while !input.is_empty() {
+            if self.pos % 8 == 0 && input.len() >= 8 && self.pos + 8 <= self.rate {
+                let (lane, rest) = input.split_at(8);
+                self.state[self.pos / 8] ^= u64::from_le_bytes(lane.try_into().unwrap());
+                self.pos += 8;
+                input = rest;
+            } else {
+                self.xor_byte(self.pos, input[0]);
+                self.pos += 1;
+                input = &input[1..];
+            }
+            if self.pos == self.rate {
+                keccak_f1600(&mut self.state);
+                self.pos = 0;
+            }
+        }
+    }
+
+    /// Append the domain separation bits and the padding, and switch the sponge to squeezing
+    ///
+    /// # Panics
+    ///
+    /// Panics if the sponge has already switched to squeezing
+    pub fn pad_and_switch_to_squeezing(&mut self, domain_sep: u8) {
+        assert!(
+            !self.squeezing,
+            "Invalid state, absorb phase already finalized."
+        );
+        self.xor_byte(self.pos, domain_sep);
+        self.xor_byte(self.rate - 1, 0x80);
+        keccak_f1600(&mut self.state);
+        self.pos = 0;
+        self.squeezing = true;
+    }
+
+    /// Squeeze the next bytes of output from the sponge
+    ///
+    /// # Panics
+    ///
+    /// Panics if the sponge hasn't been switched to squeezing
+    pub fn squeeze(&mut self, output: &mut [u8]) {
+        assert!(self.squeezing, "Invalid state, the sponge is not padded.");
+        for o in output.iter_mut() {
+            if self.pos == self.rate {
+                keccak_f1600(&mut self.state);
+                self.pos = 0;
+            }
+            *o = (self.state[self.pos / 8] >> (8 * (self.pos % 8))) as u8;
+            self.pos += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::KeccakSponge;
+    use crate::encoding::decode_hex;
+    use crate::hashing::{keccak, sha3};
+
+    fn sponge(rate: usize, domain_sep: u8, input: &[u8], output: &mut [u8]) {
+        let mut sponge = KeccakSponge::new(rate);
+        sponge.absorb(input);
+        sponge.pad_and_switch_to_squeezing(domain_sep);
+        sponge.squeeze(output);
+    }
+
+    #[test]
+    fn hashes() {
+        let input: [u8; 1000] = core::array::from_fn(|i| (i % 251) as u8);
+        for len in [0, 1, 7, 8, 135, 136, 137, 300, 1000] {
+            let input = &input[..len];
+            let mut out = [0u8; 32];
+            sponge(136, 0x06, input, &mut out);
+            assert_eq!(out, sha3::Sha3_256::new().update(input).finalize());
+            sponge(136, 0x01, input, &mut out);
+            assert_eq!(out, keccak::Keccak256::new().update(input).finalize());
+            let mut out = [0u8; 64];
+            sponge(72, 0x06, input, &mut out);
+            assert_eq!(out, sha3::Sha3_512::new().update(input).finalize());
+        }
+    }
+
+    #[test]
+    fn shake() {
+        let mut out = [0u8; 32];
+        sponge(168, 0x1f, b"", &mut out);
+        assert_eq!(
+            &out[..],
+            &decode_hex(b"7f9c2ba4e88f827d616045507605853ed73b8093f6efbc88eb1a6eacfa66ef26")
+                .unwrap()
+        );
+        sponge(136, 0x1f, b"", &mut out);
+        assert_eq!(
+            &out[..],
+            &decode_hex(b"46b9dd2b0ba88d13233b3feb743eeb243fcd52ea62b81b82b50c27646ed5762f")
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn incremental() {
+        // SHAKE128 of (i % 251) for i in 0..1000, computed with python's hashlib
+        let input: [u8; 1000] = core::array::from_fn(|i| (i % 251) as u8);
+        let expected = decode_hex(
+            b"a72440f7f5aa7c14c8e0187420611da7e2ba62f5bb2e88a91b9c9448cac30078\
+              cc321c13735bc6799f955dea38f171355b3ebccc9a09639b92f0f2f91ba0d6d4\
+              15d366c872dcfa18d715bb12041115850d1096489070d2febf2ffd986f53de7d\
+              b306585567056f53553d68f789766711d9a0585dda15ff0b8ade8f6de3131ffa\
+              5bec44a58bc041e1818b713e0d6613ab401da4772b05cac9ba879bff4d97e68a\
+              84716528a4b9fb7e7ad47fbb929819bd47dea3f407a8d14285e2ab4f96a07f13\
+              312d73f25c0b28a4c2a35d14aaf86a5063205f626ad69e95eaf287d48c6928af\
+              0e43acc93dc91edf7eb472aa9cab1ead68dcf8eb0ecc5178f37a3ff6d6408ec8",
+        )
+        .unwrap();
+
+        let mut out = [0u8; 256];
+        sponge(168, 0x1f, &input, &mut out);
+        assert_eq!(&out[..], &expected[..]);
+
+        let mut s = KeccakSponge::new(168);
+        for chunk in input.chunks(13) {
+            s.absorb(chunk);
+        }
+        s.pad_and_switch_to_squeezing(0x1f);
+        let mut out = [0u8; 256];
+        for chunk in out.chunks_mut(11) {
+            s.squeeze(chunk);
+        }
+        assert_eq!(&out[..], &expected[..]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn absorb_after_squeezing() {
+        let mut s = KeccakSponge::new(136);
+        s.pad_and_switch_to_squeezing(0x06);
+        s.absorb(b"abc");
+    }
+}
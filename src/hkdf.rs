@@ -54,6 +54,10 @@ pub fn hkdf_expand<D: Digest>(mut digest: D, prk: &[u8], info: &[u8], okm: &mut
     digest.reset();
 
     let mut mac = Hmac::new(digest, prk);
+    expand(&mut mac, info, okm);
+}
+
+fn expand<D: Digest>(mac: &mut Hmac<D>, info: &[u8], okm: &mut [u8]) {
     let os = mac.output_bytes();
     let mut t: Vec<u8> = repeat(0).take(os).collect();
     let mut n: u8 = 0;
@@ -75,13 +79,261 @@ pub fn hkdf_expand<D: Digest>(mut digest: D, prk: &[u8], info: &[u8], okm: &mut
     }
 }
 
+/// HKDF errors
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HkdfError {
+    /// The pseudorandom key is shorter than the digest output
+    PrkTooShort,
+    /// The requested output is longer than 255 times the digest output
+    OutputTooLong,
+}
+
+impl core::fmt::Display for HkdfError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            HkdfError::PrkTooShort => write!(f, "pseudorandom key too short"),
+            HkdfError::OutputTooLong => write!(f, "output too long"),
+        }
+    }
+}
+
+/// Incremental HKDF-Extract
+///
+/// The input keying material can be fed in multiple parts with `input_ikm`,
+/// then the pseudorandom key is returned by `finalize`.
+///
+/// ```
+/// use cryptoxide::{sha2::Sha256, hkdf::HkdfExtract};
+///
+/// let mut extract = HkdfExtract::new(Sha256::new(), Some(b"salt"));
+/// extract.input_ikm(b"input");
+/// let prk = extract.finalize();
+/// ```
+pub struct HkdfExtract<D: Digest> {
+    mac: Hmac<D>,
+}
+
+impl<D: Digest> HkdfExtract<D> {
+    /// Create a new HKDF-Extract context with an optional salt
+    ///
+    /// No salt is equivalent to a salt of `digest.output_bytes()` zeros
+    pub fn new(mut digest: D, salt: Option<&[u8]>) -> Self {
+        digest.reset();
+        // HMAC pads the key with zeros, so the empty salt is the same as the zero salt
+        let mac = Hmac::new(digest, salt.unwrap_or(&[]));
+        Self { mac }
+    }
+
+    /// Add input keying material to the context
+    pub fn input_ikm(&mut self, ikm: &[u8]) {
+        self.mac.input(ikm)
+    }
+
+    /// Finalize the context and write the pseudorandom key in `prk`
+    ///
+    /// `prk` need to be of the size of the digest output
+    pub fn finalize_at(mut self, prk: &mut [u8]) {
+        assert!(prk.len() == self.mac.output_bytes());
+        self.mac.raw_result(prk);
+    }
+
+    /// Finalize the context and return the pseudorandom key
+    pub fn finalize(self) -> Vec<u8> {
+        let mut prk: Vec<u8> = repeat(0).take(self.mac.output_bytes()).collect();
+        self.finalize_at(&mut prk);
+        prk
+    }
+}
+
+/// HKDF-Expand from a pseudorandom key
+///
+/// The same pseudorandom key can be expanded multiple times with different `info`
+///
+/// ```
+/// use cryptoxide::{sha2::Sha256, hkdf::HkdfExpand};
+///
+/// let prk = [1u8; 32];
+/// let expand = HkdfExpand::new(Sha256::new(), &prk).unwrap();
+/// let mut key = [0u8; 32];
+/// expand.expand(b"key", &mut key).unwrap();
+/// ```
+pub struct HkdfExpand<D: Digest> {
+    digest: D,
+    prk: Vec<u8>,
+}
+
+impl<D: Digest + Clone> HkdfExpand<D> {
+    /// Create a new HKDF-Expand context from a pseudorandom key of at least
+    /// `digest.output_bytes()` octets
+    pub fn new(mut digest: D, prk: &[u8]) -> Result<Self, HkdfError> {
+        if prk.len() < digest.output_bytes() {
+            return Err(HkdfError::PrkTooShort);
+        }
+        digest.reset();
+        Ok(Self {
+            digest,
+            prk: prk.to_vec(),
+        })
+    }
+
+    /// Fill `okm` with the output keying material for the context `info`
+    ///
+    /// `okm` can't be longer than 255 times the digest output
+    pub fn expand(&self, info: &[u8], okm: &mut [u8]) -> Result<(), HkdfError> {
+        if okm.len() > 255 * self.digest.output_bytes() {
+            return Err(HkdfError::OutputTooLong);
+        }
+        let mut mac = Hmac::new(self.digest.clone(), &self.prk);
+        expand(&mut mac, info, okm);
+        Ok(())
+    }
+}
+
+/// HKDF-Expand stream deriving successive keys from a single pseudorandom key
+///
+/// Each derivation appends a 32 bits big endian counter to the context information,
+/// and increments it, so that successive derivations with the same label produce
+/// independent key material.
+///
+/// ```
+/// use cryptoxide::{sha2::Sha256, hkdf::HkdfExpandStream};
+///
+/// let prk = [1u8; 32];
+/// let mut stream = HkdfExpandStream::new(Sha256::new(), &prk).unwrap();
+/// let key1: [u8; 32] = stream.next_key(b"key");
+/// let key2: [u8; 32] = stream.next_key(b"key");
+/// assert_ne!(key1, key2);
+/// ```
+pub struct HkdfExpandStream<D: Digest> {
+    expand: HkdfExpand<D>,
+    counter: u32,
+}
+
+impl<D: Digest + Clone> HkdfExpandStream<D> {
+    /// Create a new HKDF-Expand stream from a pseudorandom key of at least
+    /// `digest.output_bytes()` octets
+    pub fn new(digest: D, prk: &[u8]) -> Result<Self, HkdfError> {
+        let expand = HkdfExpand::new(digest, prk)?;
+        Ok(Self { expand, counter: 0 })
+    }
+
+    /// Derive the next key of `N` bytes for the `label`
+    ///
+    /// `N` can't be longer than 255 times the digest output, otherwise this function will panic.
+    pub fn next_key<const N: usize>(&mut self, label: &[u8]) -> [u8; N] {
+        let mut key = [0u8; N];
+        self.expand_into(label, &mut key)
+            .expect("HKDF size limit exceeded.");
+        key
+    }
+
+    /// Fill `output` with the next output keying material for the context `info`
+    ///
+    /// `output` can't be longer than 255 times the digest output
+    pub fn expand_into(&mut self, info: &[u8], output: &mut [u8]) -> Result<(), HkdfError> {
+        let mut info_counter = Vec::with_capacity(info.len() + 4);
+        info_counter.extend_from_slice(info);
+        info_counter.extend_from_slice(&self.counter.to_be_bytes());
+        self.expand.expand(&info_counter, output)?;
+        self.counter = self
+            .counter
+            .checked_add(1)
+            .expect("HKDF stream counter exhausted.");
+        Ok(())
+    }
+}
+
+/// HKDF context, with the pseudorandom key extracted from the input keying material,
+/// ready to be expanded
+///
+/// `Hkdf` is generic over the digest used by HMAC, and aliases are defined
+/// for the common digests, for example:
+///
+/// ```
+/// use cryptoxide::{sha2::Sha256, hkdf::HkdfSha256};
+///
+/// let hkdf = HkdfSha256::new(Sha256::new(), Some(b"salt"), b"input");
+/// let mut key = [0u8; 32];
+/// hkdf.expand(b"key", &mut key).unwrap();
+/// ```
+pub struct Hkdf<D: Digest> {
+    expand: HkdfExpand<D>,
+}
+
+impl<D: Digest + Clone> Hkdf<D> {
+    /// Extract the pseudorandom key from the input keying material and the optional salt
+    pub fn new(digest: D, salt: Option<&[u8]>, ikm: &[u8]) -> Self {
+        let mut extract = HkdfExtract::new(digest.clone(), salt);
+        extract.input_ikm(ikm);
+        let prk = extract.finalize();
+        let expand = HkdfExpand { digest, prk };
+        Self { expand }
+    }
+
+    /// Create the context from an already extracted pseudorandom key
+    pub fn from_prk(digest: D, prk: &[u8]) -> Result<Self, HkdfError> {
+        let expand = HkdfExpand::new(digest, prk)?;
+        Ok(Self { expand })
+    }
+
+    /// The pseudorandom key
+    pub fn prk(&self) -> &[u8] {
+        &self.expand.prk
+    }
+
+    /// Fill `okm` with the output keying material for the context `info`
+    ///
+    /// `okm` can't be longer than 255 times the digest output
+    pub fn expand(&self, info: &[u8], okm: &mut [u8]) -> Result<(), HkdfError> {
+        self.expand.expand(info, okm)
+    }
+}
+
+/// HKDF with HMAC-SHA256
+#[cfg(feature = "sha2")]
+pub type HkdfSha256 = Hkdf<crate::sha2::Sha256>;
+
+/// HKDF with HMAC-SHA512
+#[cfg(feature = "sha2")]
+pub type HkdfSha512 = Hkdf<crate::sha2::Sha512>;
+
+/// HKDF with HMAC-SHA3-256
+#[cfg(feature = "sha3")]
+pub type HkdfSha3_256 = Hkdf<crate::sha3::Sha3_256>;
+
+/// HKDF with HMAC-SHA3-512
+#[cfg(feature = "sha3")]
+pub type HkdfSha3_512 = Hkdf<crate::sha3::Sha3_512>;
+
+/// HKDF with HMAC-BLAKE2b
+///
+/// This is HMAC over BLAKE2b, not the native keyed mode of BLAKE2b. The digest
+/// is typically created with a 64 bytes output: `Blake2b::new(64)`
+///
+/// This is not the key derivation of Zcash Sapling: its `PRF^expand` and the
+/// ZIP 32 derivation use BLAKE2b-512 with a personalization string directly,
+/// without HMAC or HKDF, so the Zcash test vectors don't apply to this type.
+#[cfg(feature = "blake2")]
+pub type HkdfBlake2b = Hkdf<crate::blake2b::Blake2b>;
+
+/// HKDF with HMAC-BLAKE2s
+///
+/// This is HMAC over BLAKE2s, not the native keyed mode of BLAKE2s. The digest
+/// is typically created with a 32 bytes output: `Blake2s::new(32)`
+#[cfg(feature = "blake2")]
+pub type HkdfBlake2s = Hkdf<crate::blake2s::Blake2s>;
+
 #[cfg(test)]
 mod test {
     use alloc::vec::Vec;
     use core::iter::repeat;
 
     use crate::digest::Digest;
-    use crate::hkdf::{hkdf_expand, hkdf_extract};
+    use crate::hkdf::{
+        hkdf_expand, hkdf_extract, Hkdf, HkdfError, HkdfExpand, HkdfExpandStream, HkdfExtract,
+    };
+    #[cfg(feature = "sha1")]
+    use crate::sha1::Sha1;
     use crate::sha2::Sha256;
 
     struct TestVector<D: Digest> {
@@ -95,6 +347,32 @@ mod test {
         okm: Vec<u8>,
     }
 
+    fn run_vectors<D: Digest + Clone>(test_vectors: &[TestVector<D>]) {
+        for t in test_vectors.iter() {
+            let mut prk: Vec<u8> = repeat(0).take(t.prk.len()).collect();
+            hkdf_extract(t.digest.clone(), &t.salt[..], &t.ikm[..], &mut prk);
+            assert!(prk == t.prk);
+
+            let mut okm: Vec<u8> = repeat(0).take(t.okm.len()).collect();
+            assert!(okm.len() == t.l);
+            hkdf_expand(t.digest.clone(), &prk[..], &t.info[..], &mut okm);
+            assert!(okm == t.okm);
+
+            // same with the incremental extract and the expand context
+            let mut extract = HkdfExtract::new(t.digest.clone(), Some(&t.salt[..]));
+            let (ikm1, ikm2) = t.ikm.split_at(t.ikm.len() / 2);
+            extract.input_ikm(ikm1);
+            extract.input_ikm(ikm2);
+            let prk = extract.finalize();
+            assert!(prk == t.prk);
+
+            let mut okm: Vec<u8> = repeat(0).take(t.okm.len()).collect();
+            let expand = HkdfExpand::new(t.digest.clone(), &prk).unwrap();
+            expand.expand(&t.info[..], &mut okm).unwrap();
+            assert!(okm == t.okm);
+        }
+    }
+
     #[test]
     fn test_hkdf_rfc5869_sha256_vectors() {
         let test_vectors = vec![
@@ -157,15 +435,217 @@ mod test {
             },
         ];
 
-        for t in test_vectors.iter() {
-            let mut prk: Vec<u8> = repeat(0).take(t.prk.len()).collect();
-            hkdf_extract(t.digest.clone(), &t.salt[..], &t.ikm[..], &mut prk);
-            assert!(prk == t.prk);
+        run_vectors(&test_vectors);
+    }
 
-            let mut okm: Vec<u8> = repeat(0).take(t.okm.len()).collect();
-            assert!(okm.len() == t.l);
-            hkdf_expand(t.digest.clone(), &prk[..], &t.info[..], &mut okm);
-            assert!(okm == t.okm);
+    #[cfg(feature = "sha1")]
+    #[test]
+    fn test_hkdf_rfc5869_sha1_vectors() {
+        let test_vectors = vec![
+            TestVector {
+                digest: Sha1::new(),
+                ikm: repeat(0x0b).take(11).collect(),
+                salt: (0x00..=0x0c).collect(),
+                info: (0xf0..=0xf9).collect(),
+                l: 42,
+                prk: vec![
+                    0x9b, 0x6c, 0x18, 0xc4, 0x32, 0xa7, 0xbf, 0x8f, 0x0e, 0x71, 0xc8, 0xeb, 0x88,
+                    0xf4, 0xb3, 0x0b, 0xaa, 0x2b, 0xa2, 0x43,
+                ],
+                okm: vec![
+                    0x08, 0x5a, 0x01, 0xea, 0x1b, 0x10, 0xf3, 0x69, 0x33, 0x06, 0x8b, 0x56, 0xef,
+                    0xa5, 0xad, 0x81, 0xa4, 0xf1, 0x4b, 0x82, 0x2f, 0x5b, 0x09, 0x15, 0x68, 0xa9,
+                    0xcd, 0xd4, 0xf1, 0x55, 0xfd, 0xa2, 0xc2, 0x2e, 0x42, 0x24, 0x78, 0xd3, 0x05,
+                    0xf3, 0xf8, 0x96,
+                ],
+            },
+            TestVector {
+                digest: Sha1::new(),
+                ikm: (0x00..=0x4f).collect(),
+                salt: (0x60..=0xaf).collect(),
+                info: (0xb0..=0xff).map(|x| x as u8).collect(),
+                l: 82,
+                prk: vec![
+                    0x8a, 0xda, 0xe0, 0x9a, 0x2a, 0x30, 0x70, 0x59, 0x47, 0x8d, 0x30, 0x9b, 0x26,
+                    0xc4, 0x11, 0x5a, 0x22, 0x4c, 0xfa, 0xf6,
+                ],
+                okm: vec![
+                    0x0b, 0xd7, 0x70, 0xa7, 0x4d, 0x11, 0x60, 0xf7, 0xc9, 0xf1, 0x2c, 0xd5, 0x91,
+                    0x2a, 0x06, 0xeb, 0xff, 0x6a, 0xdc, 0xae, 0x89, 0x9d, 0x92, 0x19, 0x1f, 0xe4,
+                    0x30, 0x56, 0x73, 0xba, 0x2f, 0xfe, 0x8f, 0xa3, 0xf1, 0xa4, 0xe5, 0xad, 0x79,
+                    0xf3, 0xf3, 0x34, 0xb3, 0xb2, 0x02, 0xb2, 0x17, 0x3c, 0x48, 0x6e, 0xa3, 0x7c,
+                    0xe3, 0xd3, 0x97, 0xed, 0x03, 0x4c, 0x7f, 0x9d, 0xfe, 0xb1, 0x5c, 0x5e, 0x92,
+                    0x73, 0x36, 0xd0, 0x44, 0x1f, 0x4c, 0x43, 0x00, 0xe2, 0xcf, 0xf0, 0xd0, 0x90,
+                    0x0b, 0x52, 0xd3, 0xb4,
+                ],
+            },
+            TestVector {
+                digest: Sha1::new(),
+                ikm: repeat(0x0b).take(22).collect(),
+                salt: vec![],
+                info: vec![],
+                l: 42,
+                prk: vec![
+                    0xda, 0x8c, 0x8a, 0x73, 0xc7, 0xfa, 0x77, 0x28, 0x8e, 0xc6, 0xf5, 0xe7, 0xc2,
+                    0x97, 0x78, 0x6a, 0xa0, 0xd3, 0x2d, 0x01,
+                ],
+                okm: vec![
+                    0x0a, 0xc1, 0xaf, 0x70, 0x02, 0xb3, 0xd7, 0x61, 0xd1, 0xe5, 0x52, 0x98, 0xda,
+                    0x9d, 0x05, 0x06, 0xb9, 0xae, 0x52, 0x05, 0x72, 0x20, 0xa3, 0x06, 0xe0, 0x7b,
+                    0x6b, 0x87, 0xe8, 0xdf, 0x21, 0xd0, 0xea, 0x00, 0x03, 0x3d, 0xe0, 0x39, 0x84,
+                    0xd3, 0x49, 0x18,
+                ],
+            },
+            TestVector {
+                digest: Sha1::new(),
+                ikm: repeat(0x0c).take(22).collect(),
+                salt: repeat(0x00).take(20).collect(),
+                info: vec![],
+                l: 42,
+                prk: vec![
+                    0x2a, 0xdc, 0xca, 0xda, 0x18, 0x77, 0x9e, 0x7c, 0x20, 0x77, 0xad, 0x2e, 0xb1,
+                    0x9d, 0x3f, 0x3e, 0x73, 0x13, 0x85, 0xdd,
+                ],
+                okm: vec![
+                    0x2c, 0x91, 0x11, 0x72, 0x04, 0xd7, 0x45, 0xf3, 0x50, 0x0d, 0x63, 0x6a, 0x62,
+                    0xf6, 0x4f, 0x0a, 0xb3, 0xba, 0xe5, 0x48, 0xaa, 0x53, 0xd4, 0x23, 0xb0, 0xd1,
+                    0xf2, 0x7e, 0xbb, 0xa6, 0xf5, 0xe5, 0x67, 0x3a, 0x08, 0x1d, 0x70, 0xcc, 0xe7,
+                    0xac, 0xfc, 0x48,
+                ],
+            },
+        ];
+        run_vectors(&test_vectors);
+    }
+
+    #[test]
+    fn test_hkdf_no_salt_and_limits() {
+        // RFC 5869 A.3: no salt is the same as an empty salt
+        let ikm = [0x0b; 22];
+        let mut prk = [0u8; 32];
+        hkdf_extract(Sha256::new(), &[], &ikm, &mut prk);
+        let mut extract = HkdfExtract::new(Sha256::new(), None);
+        extract.input_ikm(&ikm);
+        assert_eq!(extract.finalize(), prk);
+
+        assert_eq!(
+            HkdfExpand::new(Sha256::new(), &prk[..31]).err(),
+            Some(HkdfError::PrkTooShort)
+        );
+        let expand = HkdfExpand::new(Sha256::new(), &prk).unwrap();
+        let mut okm = [0u8; 255 * 32 + 1];
+        assert_eq!(expand.expand(b"", &mut okm), Err(HkdfError::OutputTooLong));
+        assert_eq!(expand.expand(b"", &mut okm[..255 * 32]), Ok(()));
+    }
+
+    #[test]
+    fn test_hkdf_expand_stream() {
+        let prk = [0x07; 32];
+        let expand = HkdfExpand::new(Sha256::new(), &prk).unwrap();
+        let mut stream = HkdfExpandStream::new(Sha256::new(), &prk).unwrap();
+
+        // the n-th derivation is the expansion of info || n (32 bits big endian)
+        let mut expected = [[0u8; 42]; 3];
+        for (i, e) in expected.iter_mut().enumerate() {
+            let mut info = b"label".to_vec();
+            info.extend_from_slice(&(i as u32).to_be_bytes());
+            expand.expand(&info, e).unwrap();
         }
+        let key0: [u8; 42] = stream.next_key(b"label");
+        let key1: [u8; 42] = stream.next_key(b"label");
+        let mut key2 = [0u8; 42];
+        stream.expand_into(b"label", &mut key2).unwrap();
+        assert_eq!([key0, key1, key2], expected);
+
+        let mut okm = [0u8; 255 * 32 + 1];
+        assert_eq!(
+            stream.expand_into(b"", &mut okm),
+            Err(HkdfError::OutputTooLong)
+        );
+        assert_eq!(
+            HkdfExpandStream::new(Sha256::new(), &prk[..31]).err(),
+            Some(HkdfError::PrkTooShort)
+        );
+    }
+
+    // RFC 5869 A.1 inputs with other digests, vectors generated with python's hmac and hashlib
+    fn run_rfc5869_a1<D: Digest + Clone>(digest: D, expected: &[u8; 42]) {
+        let ikm = [0x0b; 22];
+        let salt: Vec<u8> = (0x00..=0x0c).collect();
+        let info: Vec<u8> = (0xf0..=0xf9).collect();
+        let hkdf = Hkdf::new(digest.clone(), Some(&salt), &ikm);
+        let mut okm = [0u8; 42];
+        hkdf.expand(&info, &mut okm).unwrap();
+        assert_eq!(&okm, expected);
+
+        let hkdf = Hkdf::from_prk(digest, hkdf.prk()).unwrap();
+        let mut okm = [0u8; 42];
+        hkdf.expand(&info, &mut okm).unwrap();
+        assert_eq!(&okm, expected);
+    }
+
+    #[test]
+    fn test_hkdf_sha256() {
+        let expected = [
+            0x3c, 0xb2, 0x5f, 0x25, 0xfa, 0xac, 0xd5, 0x7a, 0x90, 0x43, 0x4f, 0x64, 0xd0, 0x36,
+            0x2f, 0x2a, 0x2d, 0x2d, 0x0a, 0x90, 0xcf, 0x1a, 0x5a, 0x4c, 0x5d, 0xb0, 0x2d, 0x56,
+            0xec, 0xc4, 0xc5, 0xbf, 0x34, 0x00, 0x72, 0x08, 0xd5, 0xb8, 0x87, 0x18, 0x58, 0x65,
+        ];
+        run_rfc5869_a1(Sha256::new(), &expected);
+    }
+
+    #[cfg(feature = "sha2")]
+    #[test]
+    fn test_hkdf_sha512() {
+        const OKM: [u8; 42] = [
+            0x83, 0x23, 0x90, 0x08, 0x6c, 0xda, 0x71, 0xfb, 0x47, 0x62, 0x5b, 0xb5, 0xce, 0xb1,
+            0x68, 0xe4, 0xc8, 0xe2, 0x6a, 0x1a, 0x16, 0xed, 0x34, 0xd9, 0xfc, 0x7f, 0xe9, 0x2c,
+            0x14, 0x81, 0x57, 0x93, 0x38, 0xda, 0x36, 0x2c, 0xb8, 0xd9, 0xf9, 0x25, 0xd7, 0xcb,
+        ];
+        run_rfc5869_a1(crate::sha2::Sha512::new(), &OKM);
+    }
+
+    #[cfg(feature = "sha3")]
+    #[test]
+    fn test_hkdf_sha3_256() {
+        const OKM: [u8; 42] = [
+            0x0c, 0x51, 0x60, 0x50, 0x1d, 0x65, 0x02, 0x1d, 0xea, 0xf2, 0xc1, 0x4f, 0x5a, 0xbc,
+            0xe0, 0x4c, 0x5b, 0xd2, 0x63, 0x5a, 0xbc, 0xee, 0xba, 0x61, 0xc2, 0xed, 0xb6, 0xe8,
+            0xed, 0x72, 0x67, 0x49, 0x00, 0x55, 0x77, 0x28, 0xf2, 0xc9, 0xf2, 0xc4, 0xc1, 0x79,
+        ];
+        run_rfc5869_a1(crate::sha3::Sha3_256::new(), &OKM);
+    }
+
+    #[cfg(feature = "sha3")]
+    #[test]
+    fn test_hkdf_sha3_512() {
+        const OKM: [u8; 42] = [
+            0x40, 0xe9, 0xf1, 0x7e, 0x9b, 0xf2, 0xef, 0x99, 0x42, 0x5c, 0x2b, 0x23, 0xcc, 0xdf,
+            0x20, 0xa0, 0x18, 0xea, 0x55, 0x13, 0xf9, 0xae, 0x68, 0xe1, 0xea, 0x8c, 0x62, 0x6d,
+            0xeb, 0x57, 0xdf, 0xa4, 0xd5, 0x6c, 0x27, 0xcc, 0xf2, 0xa2, 0xa2, 0x44, 0x88, 0xa5,
+        ];
+        run_rfc5869_a1(crate::sha3::Sha3_512::new(), &OKM);
+    }
+
+    // there is no published HKDF-BLAKE2b vector: Zcash Sapling doesn't use HKDF (see HkdfBlake2b)
+    #[cfg(feature = "blake2")]
+    #[test]
+    fn test_hkdf_blake2b() {
+        const OKM: [u8; 42] = [
+            0x88, 0x15, 0xe1, 0xa8, 0x5b, 0x5e, 0x90, 0xe6, 0x17, 0x43, 0x23, 0xfd, 0xd1, 0x80,
+            0x24, 0x88, 0x87, 0xa7, 0x13, 0x8a, 0xf6, 0xdc, 0x5c, 0x83, 0x20, 0xfd, 0xe2, 0x1a,
+            0x60, 0xa0, 0x78, 0x80, 0x82, 0x67, 0xd6, 0xa4, 0x1b, 0x6a, 0x93, 0x8d, 0x7b, 0x30,
+        ];
+        run_rfc5869_a1(crate::blake2b::Blake2b::new(64), &OKM);
+    }
+
+    #[cfg(feature = "blake2")]
+    #[test]
+    fn test_hkdf_blake2s() {
+        const OKM: [u8; 42] = [
+            0x14, 0x72, 0xc3, 0x1f, 0x2f, 0xf7, 0x68, 0xc7, 0x1b, 0x19, 0xf8, 0x80, 0x36, 0x83,
+            0xee, 0x3b, 0x13, 0xc1, 0xa5, 0xfb, 0x3e, 0xa5, 0x9c, 0x0c, 0x3b, 0xf0, 0xd4, 0x4a,
+            0x4a, 0x40, 0xdc, 0xd4, 0x32, 0x9d, 0x9c, 0xd8, 0x5b, 0xbe, 0x35, 0xa1, 0xb3, 0xe7,
+        ];
+        run_rfc5869_a1(crate::blake2s::Blake2s::new(32), &OKM);
     }
 }
@@ -13,6 +13,15 @@
 //! h.input(input);
 //! let mac = h.result();
 //! ```
+//!
+//! `Hmac` is generic over any [`Digest`], and aliases are defined for the
+//! common digests, e.g. `HmacSha256`, `HmacSha3_256` or `HmacBlake2b`.
+//!
+//! Note that HMAC over BLAKE2 (`HmacBlake2b`, `HmacBlake2s`) is a different
+//! construction from the native keyed mode of BLAKE2 (`Blake2b::new_keyed`),
+//! and the two produce different outputs for the same key and data. The keyed
+//! mode is faster and is the recommended MAC for BLAKE2, unless HMAC is
+//! required by a protocol (e.g. HKDF, PBKDF2).
 
 use core::iter::repeat;
 
@@ -81,6 +90,18 @@ impl<D: Digest> Hmac<D> {
     }
 }
 
+#[cfg(feature = "std")]
+impl<D: Digest> std::io::Write for Hmac<D> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.input(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
 impl<D: Digest> Mac for Hmac<D> {
     fn input(&mut self, data: &[u8]) {
         assert!(!self.finished);
@@ -121,6 +142,30 @@ impl<D: Digest> Mac for Hmac<D> {
     }
 }
 
+/// HMAC-SHA256
+#[cfg(feature = "sha2")]
+pub type HmacSha256 = Hmac<crate::sha2::Sha256>;
+
+/// HMAC-SHA512
+#[cfg(feature = "sha2")]
+pub type HmacSha512 = Hmac<crate::sha2::Sha512>;
+
+/// HMAC-SHA3-256
+#[cfg(feature = "sha3")]
+pub type HmacSha3_256 = Hmac<crate::sha3::Sha3_256>;
+
+/// HMAC-SHA3-512
+#[cfg(feature = "sha3")]
+pub type HmacSha3_512 = Hmac<crate::sha3::Sha3_512>;
+
+/// HMAC-BLAKE2b, typically created with a 64 bytes output digest: `Blake2b::new(64)`
+#[cfg(feature = "blake2")]
+pub type HmacBlake2b = Hmac<crate::blake2b::Blake2b>;
+
+/// HMAC-BLAKE2s, typically created with a 32 bytes output digest: `Blake2s::new(32)`
+#[cfg(feature = "blake2")]
+pub type HmacBlake2s = Hmac<crate::blake2s::Blake2s>;
+
 #[cfg(test)]
 mod test {
     use crate::hmac::Hmac;
@@ -215,4 +260,138 @@ mod test {
         h.raw_result(&mut output);
         assert_eq!(&output[..], &expected[..]);
     }
+
+    // keys longer than the block size are hashed first (RFC2104 section 2)
+    #[cfg(all(feature = "sha2", feature = "sha3"))]
+    #[test]
+    fn hmac_long_key() {
+        use crate::encoding::decode_hex;
+        use crate::hmac::{HmacSha256, HmacSha3_256, HmacSha512};
+        use crate::sha2::Sha512;
+        use crate::sha3::Sha3_256;
+
+        // RFC4231 test case 6
+        let key = [0xaa; 131];
+        let data = b"Test Using Larger Than Block-Size Key - Hash Key First";
+        let mut h: HmacSha256 = Hmac::new(Sha256::new(), &key);
+        h.input(data);
+        assert_eq!(
+            h.result().code(),
+            &decode_hex(b"60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54")
+                .unwrap()[..]
+        );
+        let mut h: HmacSha512 = Hmac::new(Sha512::new(), &key);
+        h.input(data);
+        assert_eq!(
+            h.result().code(),
+            &decode_hex(
+                b"80b24263c7c1a3ebb71493c1dd7be8b49b46d1f41b4aeec1121b013783f8f352\
+                  6b56d037e05f2598bd0fd2215d6a1e5295e64f73f63f0aec8b915a985d786598"
+            )
+            .unwrap()[..]
+        );
+
+        // 200 bytes key, generated with python's hmac module
+        let key: [u8; 200] = core::array::from_fn(|i| (i % 251) as u8);
+        let data = b"oversized key data";
+        let mut h: HmacSha256 = Hmac::new(Sha256::new(), &key);
+        h.input(data);
+        assert_eq!(
+            h.result().code(),
+            &decode_hex(b"455a3c4d44f9ec834e9a96d2591bee19f51051c68cec2ec3d7f1ef77b936e9ee")
+                .unwrap()[..]
+        );
+        let mut h: HmacSha512 = Hmac::new(Sha512::new(), &key);
+        h.input(data);
+        assert_eq!(
+            h.result().code(),
+            &decode_hex(
+                b"e470a25ecd515a2a84a85c49dae1358f33517966afd49d9043835b1604f65827\
+                  c834102295c4022d497e8714179e30c24b68127db1c7645e5a0584264381ca2b"
+            )
+            .unwrap()[..]
+        );
+        let mut h: HmacSha3_256 = Hmac::new(Sha3_256::new(), &key);
+        h.input(data);
+        assert_eq!(
+            h.result().code(),
+            &decode_hex(b"c5a61d97abe76376eaf00eb5a22cf3de32a6f74eb4c47a05e23a17312eb940b6")
+                .unwrap()[..]
+        );
+
+        // around the block size, a key is equivalent to its hash only when it's longer
+        for len in [63, 64, 65] {
+            let key = &key[..len];
+            let mut h: HmacSha256 = Hmac::new(Sha256::new(), key);
+            h.input(data);
+            let mut hashed: HmacSha256 = Hmac::new(Sha256::new(), &crate::hashing::sha256(key));
+            hashed.input(data);
+            assert_eq!(h.result() == hashed.result(), len > 64);
+        }
+    }
+
+    #[cfg(feature = "sha3")]
+    #[test]
+    fn hmac_sha3() {
+        use crate::hmac::{HmacSha3_256, HmacSha3_512};
+        use crate::sha3::{Sha3_256, Sha3_512};
+
+        let t = &tests()[1];
+        let expected256 = [
+            0xc7, 0xd4, 0x07, 0x2e, 0x78, 0x88, 0x77, 0xae, 0x35, 0x96, 0xbb, 0xb0, 0xda, 0x73,
+            0xb8, 0x87, 0xc9, 0x17, 0x1f, 0x93, 0x09, 0x5b, 0x29, 0x4a, 0xe8, 0x57, 0xfb, 0xe2,
+            0x64, 0x5e, 0x1b, 0xa5,
+        ];
+        let mut h: HmacSha3_256 = Hmac::new(Sha3_256::new(), t.key);
+        h.input(t.data);
+        assert_eq!(h.result().code(), &expected256[..]);
+
+        let expected512 = [
+            0x5a, 0x4b, 0xfe, 0xab, 0x61, 0x66, 0x42, 0x7c, 0x7a, 0x36, 0x47, 0xb7, 0x47, 0x29,
+            0x2b, 0x83, 0x84, 0x53, 0x7c, 0xdb, 0x89, 0xaf, 0xb3, 0xbf, 0x56, 0x65, 0xe4, 0xc5,
+            0xe7, 0x09, 0x35, 0x0b, 0x28, 0x7b, 0xae, 0xc9, 0x21, 0xfd, 0x7c, 0xa0, 0xee, 0x7a,
+            0x0c, 0x31, 0xd0, 0x22, 0xa9, 0x5e, 0x1f, 0xc9, 0x2b, 0xa9, 0xd7, 0x7d, 0xf8, 0x83,
+            0x96, 0x02, 0x75, 0xbe, 0xb4, 0xe6, 0x20, 0x24,
+        ];
+        let mut h: HmacSha3_512 = Hmac::new(Sha3_512::new(), t.key);
+        h.input(t.data);
+        assert_eq!(h.result().code(), &expected512[..]);
+    }
+
+    #[cfg(feature = "blake2")]
+    #[test]
+    fn hmac_blake2b_is_not_keyed_blake2b() {
+        use crate::blake2b::Blake2b;
+        use crate::hmac::HmacBlake2b;
+
+        let t = &tests()[1];
+        let expected = [
+            0x6f, 0xf8, 0x84, 0xf8, 0xdd, 0xc2, 0xa6, 0x58, 0x6b, 0x3c, 0x98, 0xa4, 0xcd, 0x6e,
+            0xbd, 0xf1, 0x4e, 0xc1, 0x02, 0x04, 0xb6, 0x71, 0x00, 0x73, 0xeb, 0x58, 0x65, 0xad,
+            0xe3, 0x7a, 0x26, 0x43, 0xb8, 0x80, 0x7c, 0x13, 0x35, 0xd1, 0x07, 0xec, 0xdb, 0x9f,
+            0xfe, 0xae, 0xb6, 0x82, 0x8c, 0x46, 0x25, 0xba, 0x17, 0x2c, 0x66, 0x37, 0x9e, 0xfc,
+            0xd2, 0x22, 0xc2, 0xde, 0x11, 0x72, 0x7a, 0xb4,
+        ];
+        let mut h: HmacBlake2b = Hmac::new(Blake2b::new(64), t.key);
+        h.input(t.data);
+        let hmac = h.result();
+        assert_eq!(hmac.code(), &expected[..]);
+
+        let mut keyed = Blake2b::new_keyed(64, t.key);
+        keyed.input(t.data);
+        assert_ne!(keyed.result().code(), hmac.code());
+    }
+
+    #[cfg(all(feature = "std", feature = "sha2"))]
+    #[test]
+    fn hmac_io_write() {
+        use crate::hmac::HmacSha256;
+        use std::io::Write;
+
+        for t in tests().iter() {
+            let mut h: HmacSha256 = Hmac::new(Sha256::new(), t.key);
+            h.write_all(t.data).unwrap();
+            assert_eq!(h.result().code(), t.expected);
+        }
+    }
 }
@@ -0,0 +1,553 @@
+//! HPKE - Hybrid Public Key Encryption
+//!
+//! Implementation of the base mode of [RFC9180][1] with the ciphersuite:
+//!
+//! * KEM: DHKEM(X25519, HKDF-SHA256)
+//! * KDF: HKDF-SHA256
+//! * AEAD: ChaCha20Poly1305
+//!
+//! As there's no source of randomness in this crate, the key pair generation and the
+//! encapsulation take the input keying material (at least 32 bytes of randomness),
+//! and are respectively the `DeriveKeyPair` function and the deterministic version of
+//! `Encap` of the specification.
+//!
+//! # Examples
+//!
+//! ```
+//! use cryptoxide::hpke::{Kem, setup_base_sender, setup_base_receiver};
+//!
+//! // the input keying materials should come from a high quality random source
+//! let (public_r, secret_r) = Kem::derive_key_pair(&[1u8; 32]);
+//!
+//! let (enc, mut sender) = setup_base_sender(&public_r, b"info", &[2u8; 32]).unwrap();
+//! let ciphertext = sender.seal(b"aad", b"message").unwrap();
+//!
+//! let mut receiver = setup_base_receiver(&enc, &secret_r, b"info").unwrap();
+//! assert_eq!(receiver.open(b"aad", &ciphertext).unwrap(), b"message");
+//! ```
+//!
+//! A single message can be encrypted and decrypted with the single-shot API:
+//!
+//! ```
+//! use cryptoxide::hpke::{Kem, hpke_seal, hpke_open};
+//!
+//! let (public_r, secret_r) = Kem::derive_key_pair(&[1u8; 32]);
+//!
+//! let (enc, ciphertext) = hpke_seal(&public_r, b"info", b"aad", b"message", &[2u8; 32]).unwrap();
+//! let plaintext = hpke_open(&secret_r, &enc, b"info", b"aad", &ciphertext).unwrap();
+//! assert_eq!(plaintext, b"message");
+//! ```
+//!
+//! [1]: <https://www.rfc-editor.org/rfc/rfc9180>
+
+use alloc::vec::Vec;
+
+use crate::chacha20poly1305::ChaCha20Poly1305;
+use crate::hkdf::{HkdfExpand, HkdfExtract};
+use crate::secure_zero::Zeroize;
+use crate::sha2::Sha256;
+use crate::x25519::{PublicKey, SecretKey};
+
+/// Size of the encapsulated key
+pub const ENC_LENGTH: usize = 32;
+
+/// Size of the AEAD tag appended to the ciphertext
+pub const TAG_LENGTH: usize = 16;
+
+// "KEM" || I2OSP(kem_id, 2), with DHKEM(X25519, HKDF-SHA256) = 0x0020
+const KEM_SUITE_ID: &[u8] = b"KEM\x00\x20";
+
+// "HPKE" || I2OSP(kem_id, 2) || I2OSP(kdf_id, 2) || I2OSP(aead_id, 2),
+// with HKDF-SHA256 = 0x0001 and ChaCha20Poly1305 = 0x0003
+const HPKE_SUITE_ID: &[u8] = b"HPKE\x00\x20\x00\x01\x00\x03";
+
+const MODE_BASE: u8 = 0x00;
+
+/// HPKE errors
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HpkeError {
+    /// The encapsulated key doesn't have the expected length
+    InvalidEncapsulation,
+    /// The Diffie-Hellman output is all zeros, the public key is a low order point
+    LowOrderPoint,
+    /// The ciphertext cannot be authenticated
+    OpenError,
+    /// The sequence number of the context is exhausted
+    MessageLimitReached,
+    /// The exported length is longer than 255 times the hash output
+    ExportTooLong,
+}
+
+impl core::fmt::Display for HpkeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            HpkeError::InvalidEncapsulation => write!(f, "invalid encapsulated key"),
+            HpkeError::LowOrderPoint => write!(f, "low order point"),
+            HpkeError::OpenError => write!(f, "decryption failed"),
+            HpkeError::MessageLimitReached => write!(f, "message limit reached"),
+            HpkeError::ExportTooLong => write!(f, "export too long"),
+        }
+    }
+}
+
+// LabeledExtract(salt, label, ikm)
+fn labeled_extract(suite_id: &[u8], salt: &[u8], label: &[u8], ikm: &[u8]) -> [u8; 32] {
+    let mut extract = HkdfExtract::new(Sha256::new(), Some(salt));
+    extract.input_ikm(b"HPKE-v1");
+    extract.input_ikm(suite_id);
+    extract.input_ikm(label);
+    extract.input_ikm(ikm);
+    let mut prk = [0u8; 32];
+    extract.finalize_at(&mut prk);
+    prk
+}
+
+// LabeledExpand(prk, label, info, L)
+fn labeled_expand(
+    suite_id: &[u8],
+    prk: &[u8; 32],
+    label: &[u8],
+    info: &[u8],
+    out: &mut [u8],
+) -> Result<(), HpkeError> {
+    let length = u16::try_from(out.len()).map_err(|_| HpkeError::ExportTooLong)?;
+    let mut labeled_info = Vec::with_capacity(2 + 7 + suite_id.len() + label.len() + info.len());
+    labeled_info.extend_from_slice(&length.to_be_bytes());
+    labeled_info.extend_from_slice(b"HPKE-v1");
+    labeled_info.extend_from_slice(suite_id);
+    labeled_info.extend_from_slice(label);
+    labeled_info.extend_from_slice(info);
+    HkdfExpand::new(Sha256::new(), prk)
+        .unwrap()
+        .expand(&labeled_info, out)
+        .map_err(|_| HpkeError::ExportTooLong)
+}
+
+/// DHKEM(X25519, HKDF-SHA256)
+pub struct Kem;
+
+impl Kem {
+    /// Derive a key pair from the input keying material `ikm`, which should have
+    /// at least 32 bytes of entropy
+    pub fn derive_key_pair(ikm: &[u8]) -> (PublicKey, SecretKey) {
+        let mut dkp_prk = labeled_extract(KEM_SUITE_ID, b"", b"dkp_prk", ikm);
+        let mut sk = [0u8; 32];
+        labeled_expand(KEM_SUITE_ID, &dkp_prk, b"sk", b"", &mut sk).unwrap();
+        dkp_prk.zeroize();
+        let secret = SecretKey::from(sk);
+        sk.zeroize();
+        (secret.public_key(), secret)
+    }
+
+    fn extract_and_expand(dh: &[u8], kem_context: &[u8]) -> [u8; 32] {
+        let mut eae_prk = labeled_extract(KEM_SUITE_ID, b"", b"eae_prk", dh);
+        let mut shared_secret = [0u8; 32];
+        labeled_expand(
+            KEM_SUITE_ID,
+            &eae_prk,
+            b"shared_secret",
+            kem_context,
+            &mut shared_secret,
+        )
+        .unwrap();
+        eae_prk.zeroize();
+        shared_secret
+    }
+
+    /// Generate a shared secret for the recipient, using an ephemeral key pair derived
+    /// from `ikm_e`, and return the encapsulated key along with the shared secret
+    pub fn encap(
+        recipient_public: &PublicKey,
+        ikm_e: &[u8],
+    ) -> Result<([u8; ENC_LENGTH], [u8; 32]), HpkeError> {
+        let (public_e, secret_e) = Self::derive_key_pair(ikm_e);
+        let dh = secret_e
+            .diffie_hellman(recipient_public)
+            .map_err(|_| HpkeError::LowOrderPoint)?;
+
+        let mut enc = [0u8; ENC_LENGTH];
+        enc.copy_from_slice(public_e.as_ref());
+        let mut kem_context = [0u8; 64];
+        kem_context[0..32].copy_from_slice(&enc);
+        kem_context[32..64].copy_from_slice(recipient_public.as_ref());
+
+        Ok((enc, Self::extract_and_expand(dh.as_ref(), &kem_context)))
+    }
+
+    /// Recover the shared secret from the encapsulated key `enc` with the recipient secret key
+    pub fn decap(enc: &[u8], recipient_secret: &SecretKey) -> Result<[u8; 32], HpkeError> {
+        let public_e = PublicKey::try_from(enc).map_err(|()| HpkeError::InvalidEncapsulation)?;
+        let dh = recipient_secret
+            .diffie_hellman(&public_e)
+            .map_err(|_| HpkeError::LowOrderPoint)?;
+
+        let mut kem_context = [0u8; 64];
+        kem_context[0..32].copy_from_slice(enc);
+        kem_context[32..64].copy_from_slice(recipient_secret.public_key().as_ref());
+
+        Ok(Self::extract_and_expand(dh.as_ref(), &kem_context))
+    }
+}
+
+/// HPKE encryption context, created by [`setup_base_sender`] or [`setup_base_receiver`]
+///
+/// Each call to `seal` or `open` uses the next nonce, so the messages need to be
+/// opened in the same order as they were sealed.
+pub struct Context {
+    key: [u8; 32],
+    base_nonce: [u8; 12],
+    exporter_secret: [u8; 32],
+    seq: u64,
+}
+
+impl Drop for Context {
+    fn drop(&mut self) {
+        self.key.zeroize();
+        self.base_nonce.zeroize();
+        self.exporter_secret.zeroize();
+    }
+}
+
+impl Context {
+    // KeySchedule for the base mode (no psk)
+    fn key_schedule(shared_secret: &[u8; 32], info: &[u8]) -> Self {
+        let psk_id_hash = labeled_extract(HPKE_SUITE_ID, b"", b"psk_id_hash", b"");
+        let info_hash = labeled_extract(HPKE_SUITE_ID, b"", b"info_hash", info);
+        let mut key_schedule_context = [0u8; 65];
+        key_schedule_context[0] = MODE_BASE;
+        key_schedule_context[1..33].copy_from_slice(&psk_id_hash);
+        key_schedule_context[33..65].copy_from_slice(&info_hash);
+
+        let mut secret = labeled_extract(HPKE_SUITE_ID, shared_secret, b"secret", b"");
+
+        let mut context = Context {
+            key: [0; 32],
+            base_nonce: [0; 12],
+            exporter_secret: [0; 32],
+            seq: 0,
+        };
+        for (label, out) in [
+            (&b"key"[..], &mut context.key[..]),
+            (b"base_nonce", &mut context.base_nonce[..]),
+            (b"exp", &mut context.exporter_secret[..]),
+        ] {
+            labeled_expand(HPKE_SUITE_ID, &secret, label, &key_schedule_context, out).unwrap();
+        }
+        secret.zeroize();
+        context
+    }
+
+    // base_nonce xor I2OSP(seq, 12)
+    fn nonce(&self) -> [u8; 12] {
+        let mut nonce = self.base_nonce;
+        for (n, s) in nonce[4..].iter_mut().zip(self.seq.to_be_bytes().iter()) {
+            *n ^= s;
+        }
+        nonce
+    }
+
+    fn increment_seq(&mut self) -> Result<(), HpkeError> {
+        self.seq = self
+            .seq
+            .checked_add(1)
+            .ok_or(HpkeError::MessageLimitReached)?;
+        Ok(())
+    }
+
+    /// Encrypt the plaintext with the associated data, and return the ciphertext
+    /// followed by the tag
+    pub fn seal(&mut self, aad: &[u8], plaintext: &[u8]) -> Result<Vec<u8>, HpkeError> {
+        let mut ciphertext = alloc::vec![0u8; plaintext.len() + TAG_LENGTH];
+        let (out, tag) = ciphertext.split_at_mut(plaintext.len());
+        ChaCha20Poly1305::new(&self.key, &self.nonce(), aad).encrypt(plaintext, out, tag);
+        self.increment_seq()?;
+        Ok(ciphertext)
+    }
+
+    /// Decrypt the ciphertext (followed by the tag) with the associated data, and
+    /// return the plaintext
+    pub fn open(&mut self, aad: &[u8], ciphertext: &[u8]) -> Result<Vec<u8>, HpkeError> {
+        if ciphertext.len() < TAG_LENGTH {
+            return Err(HpkeError::OpenError);
+        }
+        let (input, tag) = ciphertext.split_at(ciphertext.len() - TAG_LENGTH);
+        let mut plaintext = alloc::vec![0u8; input.len()];
+        if !ChaCha20Poly1305::new(&self.key, &self.nonce(), aad).decrypt(input, &mut plaintext, tag)
+        {
+            plaintext.zeroize();
+            return Err(HpkeError::OpenError);
+        }
+        self.increment_seq()?;
+        Ok(plaintext)
+    }
+
+    /// Export a secret of the length of `out` for the `exporter_context`
+    ///
+    /// `out` can't be longer than 255 times 32 bytes
+    pub fn export(&self, exporter_context: &[u8], out: &mut [u8]) -> Result<(), HpkeError> {
+        labeled_expand(
+            HPKE_SUITE_ID,
+            &self.exporter_secret,
+            b"sec",
+            exporter_context,
+            out,
+        )
+    }
+}
+
+/// Setup the sender context for the recipient public key, using the ephemeral
+/// input keying material `ikm_e`, and return the encapsulated key to send to the recipient
+pub fn setup_base_sender(
+    recipient_public: &PublicKey,
+    info: &[u8],
+    ikm_e: &[u8],
+) -> Result<([u8; ENC_LENGTH], Context), HpkeError> {
+    let (enc, mut shared_secret) = Kem::encap(recipient_public, ikm_e)?;
+    let context = Context::key_schedule(&shared_secret, info);
+    shared_secret.zeroize();
+    Ok((enc, context))
+}
+
+/// Setup the recipient context from the encapsulated key `enc` and the recipient secret key
+pub fn setup_base_receiver(
+    enc: &[u8],
+    recipient_secret: &SecretKey,
+    info: &[u8],
+) -> Result<Context, HpkeError> {
+    let mut shared_secret = Kem::decap(enc, recipient_secret)?;
+    let context = Context::key_schedule(&shared_secret, info);
+    shared_secret.zeroize();
+    Ok(context)
+}
+
+/// Encrypt a single message to the recipient public key, using the ephemeral input keying
+/// material `ikm_e`, and return the encapsulated key and the ciphertext followed by the tag
+///
+/// This is the single-shot `SealBase` of the specification, equivalent to
+/// [`setup_base_sender`] followed by a single [`Context::seal`]
+pub fn hpke_seal(
+    recipient_public: &PublicKey,
+    info: &[u8],
+    aad: &[u8],
+    plaintext: &[u8],
+    ikm_e: &[u8],
+) -> Result<([u8; ENC_LENGTH], Vec<u8>), HpkeError> {
+    let (enc, mut context) = setup_base_sender(recipient_public, info, ikm_e)?;
+    let ciphertext = context.seal(aad, plaintext)?;
+    Ok((enc, ciphertext))
+}
+
+/// Decrypt a single message from the encapsulated key `enc` and the ciphertext (followed
+/// by the tag) with the recipient secret key, and return the plaintext
+///
+/// This is the single-shot `OpenBase` of the specification, equivalent to
+/// [`setup_base_receiver`] followed by a single [`Context::open`]
+pub fn hpke_open(
+    recipient_secret: &SecretKey,
+    enc: &[u8],
+    info: &[u8],
+    aad: &[u8],
+    ciphertext: &[u8],
+) -> Result<Vec<u8>, HpkeError> {
+    setup_base_receiver(enc, recipient_secret, info)?.open(aad, ciphertext)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // RFC 9180 appendix A.2.1, DHKEM(X25519, HKDF-SHA256), HKDF-SHA256, ChaCha20Poly1305
+    const IKM_E: [u8; 32] = [
+        0x90, 0x9a, 0x9b, 0x35, 0xd3, 0xdc, 0x47, 0x13, 0xa5, 0xe7, 0x2a, 0x4d, 0xa2, 0x74, 0xb5,
+        0x5d, 0x3d, 0x38, 0x21, 0xa3, 0x7e, 0x5d, 0x09, 0x9e, 0x74, 0xa6, 0x47, 0xdb, 0x58, 0x3a,
+        0x90, 0x4b,
+    ];
+    const IKM_R: [u8; 32] = [
+        0x1a, 0xc0, 0x1f, 0x18, 0x1f, 0xdf, 0x9f, 0x35, 0x27, 0x97, 0x65, 0x51, 0x61, 0xc5, 0x8b,
+        0x75, 0xc6, 0x56, 0xa6, 0xcc, 0x27, 0x16, 0xdc, 0xb6, 0x63, 0x72, 0xda, 0x83, 0x55, 0x42,
+        0xe1, 0xdf,
+    ];
+    const PK_R: [u8; 32] = [
+        0x43, 0x10, 0xee, 0x97, 0xd8, 0x8c, 0xc1, 0xf0, 0x88, 0xa5, 0x57, 0x6c, 0x77, 0xab, 0x0c,
+        0xf5, 0xc3, 0xac, 0x79, 0x7f, 0x3d, 0x95, 0x13, 0x9c, 0x6c, 0x84, 0xb5, 0x42, 0x9c, 0x59,
+        0x66, 0x2a,
+    ];
+    const ENC: [u8; 32] = [
+        0x1a, 0xfa, 0x08, 0xd3, 0xde, 0xc0, 0x47, 0xa6, 0x43, 0x88, 0x51, 0x63, 0xf1, 0x18, 0x04,
+        0x76, 0xfa, 0x7d, 0xdb, 0x54, 0xc6, 0xa8, 0x02, 0x9e, 0xa3, 0x3f, 0x95, 0x79, 0x6b, 0xf2,
+        0xac, 0x4a,
+    ];
+    const SHARED_SECRET: [u8; 32] = [
+        0x0b, 0xbe, 0x78, 0x49, 0x04, 0x12, 0xb4, 0xbb, 0xea, 0x48, 0x12, 0x66, 0x6f, 0x79, 0x16,
+        0x93, 0x2b, 0x82, 0x8b, 0xba, 0x79, 0x94, 0x24, 0x24, 0xab, 0xb6, 0x52, 0x44, 0x93, 0x0d,
+        0x69, 0xa7,
+    ];
+    const INFO: &[u8] = b"Ode on a Grecian Urn";
+    const PT: &[u8] = b"Beauty is truth, truth beauty";
+
+    #[test]
+    fn rfc9180_kem() {
+        let (public_r, secret_r) = Kem::derive_key_pair(&IKM_R);
+        assert_eq!(public_r.as_ref(), &PK_R);
+
+        let (enc, shared_secret) = Kem::encap(&public_r, &IKM_E).unwrap();
+        assert_eq!(enc, ENC);
+        assert_eq!(shared_secret, SHARED_SECRET);
+        assert_eq!(Kem::decap(&enc, &secret_r), Ok(SHARED_SECRET));
+    }
+
+    #[test]
+    fn rfc9180_key_schedule() {
+        let (public_r, secret_r) = Kem::derive_key_pair(&IKM_R);
+        let (enc, mut sender) = setup_base_sender(&public_r, INFO, &IKM_E).unwrap();
+        assert_eq!(
+            sender.key,
+            [
+                0xad, 0x27, 0x44, 0xde, 0x8e, 0x17, 0xf4, 0xeb, 0xba, 0x57, 0x5b, 0x3f, 0x5f, 0x5a,
+                0x8f, 0xa1, 0xf6, 0x9c, 0x2a, 0x07, 0xf6, 0xe7, 0x50, 0x0b, 0xc6, 0x0c, 0xa6, 0xe3,
+                0xe3, 0xec, 0x1c, 0x91
+            ]
+        );
+        assert_eq!(
+            sender.base_nonce,
+            [0x5c, 0x4d, 0x98, 0x15, 0x06, 0x61, 0xb8, 0x48, 0x85, 0x3b, 0x54, 0x7f]
+        );
+        assert_eq!(
+            sender.exporter_secret,
+            [
+                0xa3, 0xb0, 0x10, 0xd4, 0x99, 0x48, 0x90, 0xe2, 0xc6, 0x96, 0x8a, 0x36, 0xf6, 0x44,
+                0x70, 0xd3, 0xc8, 0x24, 0xc8, 0xf5, 0x02, 0x99, 0x42, 0xfe, 0xb1, 0x1e, 0x7a, 0x74,
+                0xb2, 0x92, 0x19, 0x22
+            ]
+        );
+
+        let ct0 = [
+            0x1c, 0x52, 0x50, 0xd8, 0x03, 0x4e, 0xc2, 0xb7, 0x84, 0xba, 0x2c, 0xfd, 0x69, 0xdb,
+            0xdb, 0x8a, 0xf4, 0x06, 0xcf, 0xe3, 0xff, 0x93, 0x8e, 0x13, 0x1f, 0x0d, 0xef, 0x8c,
+            0x8b, 0x60, 0xb4, 0xdb, 0x21, 0x99, 0x3c, 0x62, 0xce, 0x81, 0x88, 0x3d, 0x2d, 0xd1,
+            0xb5, 0x1a, 0x28,
+        ];
+        let ct1 = [
+            0x6b, 0x53, 0xc0, 0x51, 0xe4, 0x19, 0x9c, 0x51, 0x8d, 0xe7, 0x95, 0x94, 0xe1, 0xc4,
+            0xab, 0x18, 0xb9, 0x6f, 0x08, 0x15, 0x49, 0xd4, 0x5c, 0xe0, 0x15, 0xbe, 0x00, 0x20,
+            0x90, 0xbb, 0x11, 0x9e, 0x85, 0x28, 0x53, 0x37, 0xcc, 0x95, 0xba, 0x5f, 0x59, 0x99,
+            0x2d, 0xc9, 0x8c,
+        ];
+        assert_eq!(sender.seal(b"Count-0", PT).unwrap(), ct0);
+        assert_eq!(sender.seal(b"Count-1", PT).unwrap(), ct1);
+
+        let mut receiver = setup_base_receiver(&enc, &secret_r, INFO).unwrap();
+        // a failure to open doesn't move the sequence number forward
+        assert_eq!(receiver.open(b"Count-1", &ct0), Err(HpkeError::OpenError));
+        assert_eq!(receiver.open(b"Count-0", &ct0).unwrap(), PT);
+        assert_eq!(receiver.open(b"Count-1", &ct1).unwrap(), PT);
+
+        let mut exported = [0u8; 32];
+        for (context, expected) in [
+            (
+                &b""[..],
+                [
+                    0x4b, 0xbd, 0x62, 0x43, 0xb8, 0xbb, 0x54, 0xce, 0xc3, 0x11, 0xfa, 0xc9, 0xdf,
+                    0x81, 0x84, 0x1b, 0x6f, 0xd6, 0x1f, 0x56, 0x53, 0x8a, 0x77, 0x5e, 0x7c, 0x80,
+                    0xa9, 0xf4, 0x01, 0x60, 0x60, 0x6e,
+                ],
+            ),
+            (
+                b"\x00",
+                [
+                    0x8c, 0x1d, 0xf1, 0x47, 0x32, 0x58, 0x0e, 0x55, 0x01, 0xb0, 0x0f, 0x82, 0xb1,
+                    0x0a, 0x16, 0x47, 0xb4, 0x07, 0x13, 0x19, 0x1b, 0x7c, 0x12, 0x40, 0xac, 0x80,
+                    0xe2, 0xb6, 0x88, 0x08, 0xba, 0x69,
+                ],
+            ),
+            (
+                b"TestContext",
+                [
+                    0x5a, 0xcb, 0x09, 0x21, 0x11, 0x39, 0xc4, 0x3b, 0x30, 0x90, 0x48, 0x9a, 0x9d,
+                    0xa4, 0x33, 0xe8, 0xa3, 0x0e, 0xe7, 0x18, 0x8b, 0xa8, 0xb0, 0xa9, 0xa1, 0xcc,
+                    0xf0, 0xc2, 0x29, 0x28, 0x3e, 0x53,
+                ],
+            ),
+        ] {
+            receiver.export(context, &mut exported).unwrap();
+            assert_eq!(exported, expected);
+        }
+        assert_eq!(
+            receiver.export(b"", &mut [0u8; 255 * 32 + 1]),
+            Err(HpkeError::ExportTooLong)
+        );
+    }
+
+    #[test]
+    fn rfc9180_single_shot() {
+        let (public_r, secret_r) = Kem::derive_key_pair(&IKM_R);
+        let (enc, ciphertext) = hpke_seal(&public_r, INFO, b"Count-0", PT, &IKM_E).unwrap();
+        assert_eq!(enc, ENC);
+        assert_eq!(
+            ciphertext,
+            [
+                0x1c, 0x52, 0x50, 0xd8, 0x03, 0x4e, 0xc2, 0xb7, 0x84, 0xba, 0x2c, 0xfd, 0x69, 0xdb,
+                0xdb, 0x8a, 0xf4, 0x06, 0xcf, 0xe3, 0xff, 0x93, 0x8e, 0x13, 0x1f, 0x0d, 0xef, 0x8c,
+                0x8b, 0x60, 0xb4, 0xdb, 0x21, 0x99, 0x3c, 0x62, 0xce, 0x81, 0x88, 0x3d, 0x2d, 0xd1,
+                0xb5, 0x1a, 0x28,
+            ]
+        );
+        assert_eq!(
+            hpke_open(&secret_r, &enc, INFO, b"Count-0", &ciphertext).unwrap(),
+            PT
+        );
+        assert_eq!(
+            hpke_open(&secret_r, &enc, b"other info", b"Count-0", &ciphertext),
+            Err(HpkeError::OpenError)
+        );
+        assert_eq!(
+            hpke_open(&secret_r, &enc[..31], INFO, b"Count-0", &ciphertext),
+            Err(HpkeError::InvalidEncapsulation)
+        );
+    }
+
+    #[test]
+    fn interop() {
+        // single shot encryption (empty aad) generated with the python cryptography package
+        let message = [
+            0x8c, 0x86, 0xc2, 0xbf, 0xd1, 0xb1, 0x33, 0x38, 0x40, 0x06, 0xbc, 0xce, 0xc7, 0xf2,
+            0xc4, 0xb2, 0x5c, 0x67, 0xa9, 0x2a, 0x83, 0x74, 0x11, 0xc8, 0xb8, 0x8b, 0x5e, 0xfd,
+            0x02, 0x0f, 0xb6, 0x45, 0xcc, 0xfc, 0xac, 0xea, 0x01, 0xf7, 0x27, 0x94, 0xdd, 0x02,
+            0xe0, 0x34, 0x64, 0xdd, 0xd7, 0xa8, 0x88, 0x63, 0xdc, 0x9c, 0x17, 0x98, 0x51, 0x95,
+            0xb3, 0xca, 0x6c, 0x81, 0x0f, 0xcc, 0xae, 0xcf, 0x0e, 0xa8, 0xa2,
+        ];
+        let (_, secret_r) = Kem::derive_key_pair(&IKM_R);
+        let mut receiver =
+            setup_base_receiver(&message[..ENC_LENGTH], &secret_r, b"interop").unwrap();
+        assert_eq!(
+            receiver.open(b"", &message[ENC_LENGTH..]).unwrap(),
+            b"single shot message"
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let (public_r, secret_r) = Kem::derive_key_pair(&IKM_R);
+        assert_eq!(
+            Kem::decap(&ENC[..31], &secret_r).err(),
+            Some(HpkeError::InvalidEncapsulation)
+        );
+        // the all-zero public key is a low order point
+        let low_order = PublicKey::from([0u8; 32]);
+        assert_eq!(
+            Kem::encap(&low_order, &IKM_E).err(),
+            Some(HpkeError::LowOrderPoint)
+        );
+        assert_eq!(
+            Kem::decap(&[0u8; 32], &secret_r).err(),
+            Some(HpkeError::LowOrderPoint)
+        );
+
+        let (enc, mut sender) = setup_base_sender(&public_r, INFO, &IKM_E).unwrap();
+        let mut ciphertext = sender.seal(b"", PT).unwrap();
+        ciphertext[0] ^= 1;
+        let mut receiver = setup_base_receiver(&enc, &secret_r, INFO).unwrap();
+        assert_eq!(receiver.open(b"", &ciphertext), Err(HpkeError::OpenError));
+        assert_eq!(receiver.open(b"", &[0u8; 15]), Err(HpkeError::OpenError));
+    }
+}
@@ -0,0 +1,224 @@
+//! JWS - JSON Web Signature with EdDSA
+//!
+//! Implementation of the JWS compact serialization ([RFC7515][1]) signed with
+//! Ed25519, as defined by the `EdDSA` algorithm of [RFC8037][2]:
+//!
+//! ```text
+//! base64url(header) || "." || base64url(payload) || "." || base64url(signature)
+//! ```
+//!
+//! The tokens are created with the protected header `{"alg":"EdDSA","crv":"Ed25519"}`.
+//! As there's no JSON parser in this crate, the verification only accepts this header
+//! or the minimal `{"alg":"EdDSA"}` header of the RFC8037 examples, byte for byte.
+//!
+//! # Examples
+//!
+//! ```
+//! use cryptoxide::{ed25519, jws};
+//!
+//! // the extended secret key should be derived from a high quality random source
+//! let extended_secret = [1u8; 64];
+//! let public_key = ed25519::extended_to_public(&extended_secret);
+//! let token = jws::jws_sign(b"{\"sub\":\"1234\"}", &extended_secret);
+//! let payload = jws::jws_verify(&token, &public_key).unwrap();
+//! assert_eq!(payload, b"{\"sub\":\"1234\"}");
+//! ```
+//!
+//! [1]: <https://www.rfc-editor.org/rfc/rfc7515>
+//! [2]: <https://www.rfc-editor.org/rfc/rfc8037>
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::constant_time::CtEqual;
+use crate::ed25519;
+use crate::encoding::{decode_base64_with, encode_base64_with, Base64Alphabet};
+
+/// Protected header of the tokens created by [`jws_sign`]
+pub const HEADER: &[u8] = b"{\"alg\":\"EdDSA\",\"crv\":\"Ed25519\"}";
+
+// minimal header of the RFC8037 examples, also accepted when verifying
+const HEADER_ALG_ONLY: &[u8] = b"{\"alg\":\"EdDSA\"}";
+
+/// JWS errors
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JwsError {
+    /// The token is not made of 3 parts separated by a `.`
+    InvalidFormat,
+    /// A part of the token is not valid base64url
+    InvalidEncoding,
+    /// The protected header is not one of the supported EdDSA headers
+    InvalidHeader,
+    /// The signature is not 64 bytes
+    InvalidLength,
+    /// The signature is not valid
+    InvalidSignature,
+}
+
+impl core::fmt::Display for JwsError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            JwsError::InvalidFormat => write!(f, "invalid token format"),
+            JwsError::InvalidEncoding => write!(f, "invalid token encoding"),
+            JwsError::InvalidHeader => write!(f, "invalid token header"),
+            JwsError::InvalidLength => write!(f, "invalid signature length"),
+            JwsError::InvalidSignature => write!(f, "invalid signature"),
+        }
+    }
+}
+
+fn base64url(bytes: &[u8]) -> String {
+    let encoded = encode_base64_with(bytes, Base64Alphabet::UrlSafe, false);
+    // the base64 alphabet is only made of ascii characters
+    String::from_utf8(encoded).unwrap()
+}
+
+fn from_base64url(s: &str) -> Result<Vec<u8>, JwsError> {
+    decode_base64_with(s.as_bytes(), Base64Alphabet::UrlSafe, false)
+        .map_err(|_| JwsError::InvalidEncoding)
+}
+
+// sign the payload with a specific protected header
+fn sign_with_header(
+    header: &[u8],
+    payload: &[u8],
+    extended_secret: &[u8; ed25519::EXTENDED_KEY_LENGTH],
+) -> String {
+    let mut token = base64url(header);
+    token.push('.');
+    token.push_str(&base64url(payload));
+    let signature = ed25519::signature_extended(token.as_bytes(), extended_secret);
+    token.push('.');
+    token.push_str(&base64url(&signature));
+    token
+}
+
+/// Sign the payload with an extended Ed25519 secret key, and return the JWS
+/// compact serialization of the token
+pub fn jws_sign(payload: &[u8], extended_secret: &[u8; ed25519::EXTENDED_KEY_LENGTH]) -> String {
+    sign_with_header(HEADER, payload, extended_secret)
+}
+
+/// Verify the signature of the JWS token with the Ed25519 public key, and return its payload
+pub fn jws_verify(
+    token: &str,
+    public_key: &[u8; ed25519::PUBLIC_KEY_LENGTH],
+) -> Result<Vec<u8>, JwsError> {
+    let (signing_input, signature) = token.rsplit_once('.').ok_or(JwsError::InvalidFormat)?;
+    let (header, payload) = signing_input
+        .split_once('.')
+        .ok_or(JwsError::InvalidFormat)?;
+    if payload.contains('.') {
+        return Err(JwsError::InvalidFormat);
+    }
+
+    let header = from_base64url(header)?;
+    let supported = |expected: &[u8]| {
+        header.len() == expected.len() && bool::from(CtEqual::ct_eq(&header[..], expected))
+    };
+    if !supported(HEADER) && !supported(HEADER_ALG_ONLY) {
+        return Err(JwsError::InvalidHeader);
+    }
+
+    let signature = from_base64url(signature)?;
+    let signature = <&[u8; ed25519::SIGNATURE_LENGTH]>::try_from(&signature[..])
+        .map_err(|_| JwsError::InvalidLength)?;
+    if !ed25519::verify(signing_input.as_bytes(), public_key, signature) {
+        return Err(JwsError::InvalidSignature);
+    }
+    from_base64url(payload)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::decode_hex;
+
+    // RFC8037 A.1 and A.2
+    const SECRET_KEY: &str = "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60";
+    const PUBLIC_KEY: &str = "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a";
+    const PAYLOAD: &[u8] = b"Example of Ed25519 signing";
+
+    fn keys() -> ([u8; 64], [u8; 32]) {
+        let secret = <[u8; 32]>::try_from(&decode_hex(SECRET_KEY.as_bytes()).unwrap()[..]).unwrap();
+        let public = <[u8; 32]>::try_from(&decode_hex(PUBLIC_KEY.as_bytes()).unwrap()[..]).unwrap();
+        (ed25519::extended_secret(&secret), public)
+    }
+
+    #[test]
+    fn rfc8037_a4() {
+        let token = "eyJhbGciOiJFZERTQSJ9.RXhhbXBsZSBvZiBFZDI1NTE5IHNpZ25pbmc.hgyY0il_MGCjP0JzlnLWG1PPOt7-09PGcvMg3AIbQR6dWbhijcNR4ki4iylGjg5BhVsPt9g7sVvpAr_MuM0KAg";
+        let (extended_secret, public_key) = keys();
+        assert_eq!(
+            sign_with_header(HEADER_ALG_ONLY, PAYLOAD, &extended_secret),
+            token
+        );
+        assert_eq!(jws_verify(token, &public_key).unwrap(), PAYLOAD);
+    }
+
+    #[test]
+    fn sign_verify() {
+        // generated with an independent python implementation
+        let expected = "eyJhbGciOiJFZERTQSIsImNydiI6IkVkMjU1MTkifQ.RXhhbXBsZSBvZiBFZDI1NTE5IHNpZ25pbmc.5SzJkNV9nRSZtCUYXsK2IotTDRZX9oJxM8N9zb6LJmyj_M0XrKHN0epnsY7HutPB8aD3dMTt2_p4-Eo4yuufBw";
+        let (extended_secret, public_key) = keys();
+        let token = jws_sign(PAYLOAD, &extended_secret);
+        assert_eq!(token, expected);
+        assert_eq!(jws_verify(&token, &public_key).unwrap(), PAYLOAD);
+
+        let empty = jws_sign(b"", &extended_secret);
+        assert_eq!(jws_verify(&empty, &public_key).unwrap(), b"");
+    }
+
+    #[test]
+    fn invalid() {
+        let (extended_secret, public_key) = keys();
+        let token = jws_sign(PAYLOAD, &extended_secret);
+        let (header, rest) = token.split_once('.').unwrap();
+        let (payload, signature) = rest.split_once('.').unwrap();
+
+        assert_eq!(
+            jws_verify(&alloc::format!("{}.{}", header, payload), &public_key),
+            Err(JwsError::InvalidFormat)
+        );
+        assert_eq!(
+            jws_verify(&alloc::format!("{}.{}.", token, payload), &public_key),
+            Err(JwsError::InvalidFormat)
+        );
+        assert_eq!(
+            jws_verify(&alloc::format!("{}!", token), &public_key),
+            Err(JwsError::InvalidEncoding)
+        );
+        let none = base64url(b"{\"alg\":\"none\"}");
+        assert_eq!(
+            jws_verify(
+                &alloc::format!("{}.{}.{}", none, payload, signature),
+                &public_key
+            ),
+            Err(JwsError::InvalidHeader)
+        );
+        assert_eq!(
+            jws_verify(
+                &alloc::format!("{}.{}.{}", header, payload, &signature[..80]),
+                &public_key
+            ),
+            Err(JwsError::InvalidLength)
+        );
+        let other = base64url(b"Other payload");
+        assert_eq!(
+            jws_verify(
+                &alloc::format!("{}.{}.{}", header, other, signature),
+                &public_key
+            ),
+            Err(JwsError::InvalidSignature)
+        );
+        // the signature covers the header
+        let alg_only = base64url(HEADER_ALG_ONLY);
+        assert_eq!(
+            jws_verify(
+                &alloc::format!("{}.{}.{}", alg_only, payload, signature),
+                &public_key
+            ),
+            Err(JwsError::InvalidSignature)
+        );
+    }
+}
@@ -31,55 +31,75 @@
 //!
 //! ## Notes
 //!
-//! The size of the salt is not verified, so this implementation can use invalid
-//! salt that are out of the realm of expected value for this parameter. this
-//! is left to the user, but the recommendation is to follow the expectation of salt length.
+//! The size of the salt is not verified by `argon2` and `argon2_at`, so they can use invalid
+//! salt that are out of the realm of expected value for this parameter. The `hash`
+//! and `argon2_no_alloc` functions reject salt shorter than 8 bytes. An empty password
+//! is allowed by the specification, and is not rejected.
 //!
-//! The memory-kb parameter is automatically enforced to be at minimum, 8 times the level
-//! of the parameter, so if a user chose an invalid memory-kb, the implementation
-//! will automatically and silently override the parameter value.
+//! Parameters created with the [`Builder`] are validated, and invalid combinations
+//! are reported as errors. The deprecated setters of [`Params`] automatically enforce
+//! the memory-kb parameter to be at minimum, 8 times the level of parallelism, so if a user
+//! chose an invalid memory-kb, the implementation will silently override the parameter value.
 //!
 //! When comparing the ARGON2 tag, always use a constant time equality function.
 //! Using non constant time equality could expose your software to timing
 //! attack.
 //!
-//! This implementation doesn't provide support for the ARGON2 serialized string.
-//! This is left to the user since the URL-like textual format might not be
-//! appropriate in some settings and depending on context the user might want a
+//! The ARGON2 serialized string (PHC string format) is supported with `argon2_phc_encode`
+//! and `argon2_phc_decode`, but depending on context the user might want a
 //! different format for the parameters (e.g. database text columns, etc).
+//! A password is verified against a PHC string with `argon2_verify_phc`.
+//!
+//! ```
+//! use cryptoxide::kdf::argon2;
+//!
+//! let params = argon2::Params::argon2id();
+//! let tag = argon2::hash(&params, b"my-password", b"saltsaltsaltsalt", b"", b"").unwrap();
+//! let phc = argon2::argon2_phc_encode(&params, b"saltsaltsaltsalt", &tag);
+//! let (params, salt, tag) = argon2::argon2_phc_decode(&phc).unwrap();
+//! assert_eq!(argon2::argon2_verify_phc(&phc, b"my-password"), Ok(true));
+//! ```
 //!
 
+use crate::constant_time::CtEqual;
 use crate::cryptoutil::xor_array64_mut;
+use crate::encoding::{decode_base64_with, encode_base64_with, Base64Alphabet};
 use crate::hashing::blake2b;
+use crate::secure_zero::Zeroize;
 use alloc::borrow::ToOwned;
-use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::String;
 use alloc::vec;
+use alloc::vec::Vec;
 use core::num::NonZeroU32;
 use core::ops::{BitXorAssign, Index, IndexMut};
 
 /// Parameters for argon2
 ///
 /// use `Params::argon2d`, `Params::argon2i` or `Params::argon2id` to initialize
-/// the structure for each possible variant of argon2, then use a builder type
-/// setter to set the other parameters, typically:
+/// the structure for each possible variant of argon2 with its default parameters,
+/// `Params::recommended` for the RFC9106 recommended parameters, or
+/// a [`Builder`] to set and validate all the parameters, typically:
 ///
 /// ```
 /// use cryptoxide::kdf::argon2;
 ///
-/// let params = argon2::Params::argon2d()
-///     .memory_kb(32)
-///     .unwrap()
+/// let params = argon2::Builder::new()
+///     .memory_kb(65536)
 ///     .iterations(3)
-///     .unwrap()
-///     .parallelism(4)
+///     .parallelism(1)
+///     .tag_length(32)
+///     .build()
 ///     .unwrap();
 /// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Params {
     parallelism: NonZeroU32,
     iterations: NonZeroU32,
     memory_kb: u32,
     version: u32,
     hash_type: Type,
+    tag_length: u32,
 
     // -----------
     // calculated from parallelism and memory_kb
@@ -89,8 +109,11 @@ pub struct Params {
     lane_length: u32,
 }
 
+// Minimum salt length in bytes, as required by the specification
+pub(crate) const MIN_SALT_LENGTH: usize = 8;
+
 /// Possible type of parameters errors when setting values to the various parameters
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum InvalidParam {
     /// At least 1 level of parallelism should be used
     ParallelismZero,
@@ -102,6 +125,14 @@ pub enum InvalidParam {
     UnknownVersion,
     /// Memory requirement too high
     MemoryTooHigh,
+    /// Memory should be at least 8 times the level of parallelism in kilobytes
+    MemoryTooLow,
+    /// Tag should be at least 4 bytes
+    TagTooShort,
+    /// Salt should be at least 8 bytes
+    SaltTooShort,
+    /// The memory provided has less blocks than needed by the parameters
+    BlockMemoryTooSmall,
 }
 
 impl Params {
@@ -112,6 +143,7 @@ impl Params {
             memory_kb: 32,
             version: 0x13, // by default version = 19
             hash_type,
+            tag_length: 32,
             memory_blocks: 32,
             segment_length: 8,
             lane_length: 32,
@@ -125,6 +157,7 @@ impl Params {
     /// * version=19
     /// * parallelism=1
     /// * iteration=1
+    /// * tag_length=32
     pub fn argon2d() -> Self {
         Params::def(Type::Argon2d)
     }
@@ -136,6 +169,7 @@ impl Params {
     /// * version=19
     /// * parallelism=1
     /// * iteration=1
+    /// * tag_length=32
     pub fn argon2id() -> Self {
         Params::def(Type::Argon2id)
     }
@@ -147,11 +181,33 @@ impl Params {
     /// * version=19
     /// * parallelism=1
     /// * iteration=1
+    /// * tag_length=32
     pub fn argon2i() -> Self {
         Params::def(Type::Argon2i)
     }
 
+    /// Create the ARGON2ID parameters recommended by RFC9106 section 4 for
+    /// memory-constrained environments
+    ///
+    /// Parameters are set to:
+    /// * memory_kb=64mb (65536kb)
+    /// * version=19
+    /// * parallelism=4
+    /// * iteration=3
+    /// * tag_length=32
+    ///
+    /// RFC9106 also recommends a salt of 16 bytes with those parameters
+    pub fn recommended() -> Self {
+        Builder::new()
+            .memory_kb(65536)
+            .iterations(3)
+            .parallelism(4)
+            .build()
+            .unwrap()
+    }
+
     /// Set the memory_kb value to the value
+    #[deprecated(since = "0.6.0", note = "use `Builder`")]
     pub fn memory_kb(mut self, memory_kb: u32) -> Result<Self, InvalidParam> {
         self.memory_kb = memory_kb;
         self.parallelism_override_memory();
@@ -161,11 +217,9 @@ impl Params {
     /// Set the parallelism value to the chosen value
     ///
     /// If the chosen value is not supported by argon2, then a failure is raised here
+    #[deprecated(since = "0.6.0", note = "use `Builder`")]
     pub fn parallelism(mut self, parallelism: u32) -> Result<Self, InvalidParam> {
-        if parallelism >= 0x1000000 {
-            return Err(InvalidParam::ParallelismTooHigh);
-        }
-        self.parallelism = NonZeroU32::new(parallelism).ok_or(InvalidParam::ParallelismZero)?;
+        self.parallelism = check_parallelism(parallelism)?;
         self.parallelism_override_memory();
         Ok(self)
     }
@@ -173,6 +227,7 @@ impl Params {
     /// Set the iterations value to the chosen value
     ///
     /// If the chosen value is not supported by argon2, then a failure is raised here
+    #[deprecated(since = "0.6.0", note = "use `Builder`")]
     pub fn iterations(mut self, iterations: u32) -> Result<Self, InvalidParam> {
         self.iterations = NonZeroU32::new(iterations).ok_or(InvalidParam::IterationsZero)?;
         Ok(self)
@@ -182,11 +237,9 @@ impl Params {
     ///
     /// Only version 19 (0x13) and 16 (0x10) are supported here, any other value
     /// will raise a failure.
+    #[deprecated(since = "0.6.0", note = "use `Builder`")]
     pub fn version(mut self, version: u32) -> Result<Self, InvalidParam> {
-        if !(version == 0x13 || version == 0x10) {
-            return Err(InvalidParam::UnknownVersion);
-        }
-        self.version = version;
+        self.version = check_version(version)?;
         Ok(self)
     }
 
@@ -197,34 +250,160 @@ impl Params {
             memory_blocks = 8 * self.parallelism.get();
             self.memory_kb = memory_blocks;
         }
+        self.set_lanes_geometry();
+    }
+
+    /// Number of memory blocks (1024 bytes each) used by these parameters
+    pub fn memory_blocks(&self) -> usize {
+        self.memory_blocks as usize
+    }
 
-        // memory_block is memory_kb rounded up to the parallelism level * 4
-        self.segment_length = memory_blocks / (self.parallelism.get() * SYNC_POINTS);
+    fn set_lanes_geometry(&mut self) {
+        // memory_block is memory_kb rounded down to the parallelism level * 4
+        self.segment_length = self.memory_kb / (self.parallelism.get() * SYNC_POINTS);
         self.memory_blocks = self.segment_length * (self.parallelism.get() * SYNC_POINTS);
         self.lane_length = self.segment_length * SYNC_POINTS;
     }
 }
 
+fn check_parallelism(parallelism: u32) -> Result<NonZeroU32, InvalidParam> {
+    if parallelism >= 0x1000000 {
+        return Err(InvalidParam::ParallelismTooHigh);
+    }
+    NonZeroU32::new(parallelism).ok_or(InvalidParam::ParallelismZero)
+}
+
+fn check_version(version: u32) -> Result<u32, InvalidParam> {
+    if !(version == 0x13 || version == 0x10) {
+        return Err(InvalidParam::UnknownVersion);
+    }
+    Ok(version)
+}
+
+/// Builder for argon2 [`Params`]
+///
+/// Contrary to the setters of [`Params`], all the values are validated
+/// together when calling `build`, and invalid combinations are reported
+/// as errors instead of being silently adjusted.
+///
+/// The builder starts with the ARGON2ID variant, and the same defaults
+/// as [`Params::argon2id`]
+#[derive(Clone, Debug)]
+pub struct Builder {
+    hash_type: Type,
+    memory_kb: u32,
+    iterations: u32,
+    parallelism: u32,
+    version: u32,
+    tag_length: u32,
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Builder {
+    /// Create a new builder for ARGON2ID with default parameters
+    pub fn new() -> Self {
+        Self {
+            hash_type: Type::Argon2id,
+            memory_kb: 32,
+            iterations: 1,
+            parallelism: 1,
+            version: 0x13,
+            tag_length: 32,
+        }
+    }
+
+    /// Set the argon2 variant
+    pub fn hash_type(mut self, hash_type: Type) -> Self {
+        self.hash_type = hash_type;
+        self
+    }
+
+    /// Set the amount of memory to use in kilobytes, need to be at least 8 times the parallelism
+    pub fn memory_kb(mut self, memory_kb: u32) -> Self {
+        self.memory_kb = memory_kb;
+        self
+    }
+
+    /// Set the number of iterations, need to be at least 1
+    pub fn iterations(mut self, iterations: u32) -> Self {
+        self.iterations = iterations;
+        self
+    }
+
+    /// Set the degree of parallelism, need to be between 1 and 2^24-1
+    pub fn parallelism(mut self, parallelism: u32) -> Self {
+        self.parallelism = parallelism;
+        self
+    }
+
+    /// Set the version, only version 19 (0x13) and 16 (0x10) are supported
+    pub fn version(mut self, version: u32) -> Self {
+        self.version = version;
+        self
+    }
+
+    /// Set the length of the tag produced by [`hash`], need to be at least 4 bytes
+    pub fn tag_length(mut self, tag_length: u32) -> Self {
+        self.tag_length = tag_length;
+        self
+    }
+
+    /// Validate the parameters and create the [`Params`]
+    pub fn build(self) -> Result<Params, InvalidParam> {
+        let parallelism = check_parallelism(self.parallelism)?;
+        let iterations = NonZeroU32::new(self.iterations).ok_or(InvalidParam::IterationsZero)?;
+        let version = check_version(self.version)?;
+        if self.memory_kb < 8 * parallelism.get() {
+            return Err(InvalidParam::MemoryTooLow);
+        }
+        if self.tag_length < 4 {
+            return Err(InvalidParam::TagTooShort);
+        }
+        let mut params = Params {
+            parallelism,
+            iterations,
+            memory_kb: self.memory_kb,
+            version,
+            hash_type: self.hash_type,
+            tag_length: self.tag_length,
+            memory_blocks: 0,
+            segment_length: 0,
+            lane_length: 0,
+        };
+        params.set_lanes_geometry();
+        Ok(params)
+    }
+}
+
 const SYNC_POINTS: u32 = 4; // sync points per lanes
 
 const BLOCK_SIZE_U64: usize = 128; // 1024 bytes in u64's
 const BLOCK_SIZE: usize = BLOCK_SIZE_U64 * 8;
 
+/// A 1024 bytes block of the ARGON2 memory matrix
+///
+/// Only useful to provide the memory to [`argon2_no_alloc`]
 #[derive(Clone)]
-struct Block([u64; BLOCK_SIZE_U64]);
+pub struct Block([u64; BLOCK_SIZE_U64]);
 
 impl Block {
-    pub fn new() -> Block {
+    /// Create a new zeroed block
+    pub const fn new() -> Block {
         Block([0u64; BLOCK_SIZE_U64])
     }
 
-    pub fn as_u8(&self) -> &[u8] {
+    fn as_u8(&self) -> &[u8] {
         let bytes: &[u8; BLOCK_SIZE] =
             unsafe { &*(&self.0 as *const [u64; BLOCK_SIZE_U64] as *const [u8; BLOCK_SIZE]) };
         bytes
     }
 
-    pub fn as_u8_mut(&mut self) -> &mut [u8; BLOCK_SIZE] {
+    fn as_u8_mut(&mut self) -> &mut [u8; BLOCK_SIZE] {
         let bytes: &mut [u8; BLOCK_SIZE] =
             unsafe { &mut *(&mut self.0 as *mut [u64; BLOCK_SIZE_U64] as *mut [u8; BLOCK_SIZE]) };
         bytes
@@ -251,23 +430,29 @@ impl IndexMut<usize> for Block {
 }
 
 /// Structure representing the memory matrix.
-struct Memory {
+struct Memory<'a> {
     lane_length: u32,
-    blocks: Box<[Block]>,
+    blocks: &'a mut [Block],
+}
+
+impl Drop for Memory<'_> {
+    fn drop(&mut self) {
+        for block in self.blocks.iter_mut() {
+            block.0.zeroize()
+        }
+    }
 }
 
-impl Memory {
+impl<'a> Memory<'a> {
     /// number of elements per row (length of a lane)
     fn stride(&self) -> u32 {
         self.lane_length
     }
 
-    fn new(params: &Params) -> Memory {
-        let nb_blocks = params.parallelism.get() as usize * params.lane_length as usize;
-        let blocks = vec![Block::new(); nb_blocks].into_boxed_slice();
+    fn new(params: &Params, blocks: &'a mut [Block]) -> Memory<'a> {
         Memory {
             lane_length: params.lane_length,
-            blocks,
+            blocks: &mut blocks[..params.memory_blocks()],
         }
     }
 
@@ -427,9 +612,13 @@ fn fill_block(prev_block: &Block, ref_block: &Block, next_block: &mut Block, wit
 
 fn fill_segment(params: &Params, position: &BlockPos, memory: &mut Memory) {
     let mut position = position.clone();
-    let data_independent_addressing = (params.hash_type == Type::Argon2i)
-        || (params.hash_type == Type::Argon2id && position.pass == 0)
-            && (position.slice < (SYNC_POINTS / 2));
+    // Argon2id uses data-independent addressing (like Argon2i) for the first half
+    // of the first pass, and data-dependent addressing (like Argon2d) afterwards
+    let data_independent_addressing = match params.hash_type {
+        Type::Argon2d => false,
+        Type::Argon2i => true,
+        Type::Argon2id => position.pass == 0 && position.slice < (SYNC_POINTS / 2),
+    };
     let zero_block = Block::new();
     let mut input_block = Block::new();
     let mut address_block = Block::new();
@@ -510,18 +699,18 @@ fn fill_segment(params: &Params, position: &BlockPos, memory: &mut Memory) {
     }
 }
 
-/// compute the output hash for a variable length output hash.
+/// compute the output hash for a variable length output hash (Blake2b-long, RFC9106 section 3.3)
 ///
-/// if the output expected is more than 64 bytes, then it is just a simple BLAKE2 hash,
-/// but when we need to produce more than 64 bytes, it is computed at most 64 bytes at a time and
-/// by hashing repeatdly the current context
+/// if the output expected is at most 64 bytes, then it is just a simple BLAKE2 hash,
+/// but when we need to produce more than 64 bytes, it is computed 32 bytes at a time
+/// by hashing repeatdly the previous 64 bytes hash, and the last hash produces the remaining bytes
 ///
-/// initial hash : V0 = H(LE32(OUTPUT_LEN) | input);
+/// initial hash : V1 = H(LE32(OUTPUT_LEN) | input);
 fn hprime(output: &mut [u8], input: &[u8]) {
     if output.len() <= 64 {
         blake2b::ContextDyn::new(output.len())
             .update(&(output.len() as u32).to_le_bytes())
-            .update(&input)
+            .update(input)
             .finalize_at(output);
         return;
     }
@@ -675,11 +864,15 @@ fn p(
     gb(v3, v4, v9, v14);
 }
 
+/// Argon2 variant
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[repr(u32)]
-enum Type {
+pub enum Type {
+    /// Data-dependent memory access, faster but susceptible to side-channel attacks
     Argon2d = 0,
+    /// Data-independent memory access, suitable for password hashing
     Argon2i = 1,
+    /// Hybrid of Argon2i and Argon2d, recommended by RFC9106
     Argon2id = 2,
 }
 
@@ -738,10 +931,57 @@ pub fn argon2_at(
     tag: &mut [u8],
 ) {
     let h0 = H0::new(&params, password, salt, key, aad, tag.len() as u32);
-    let mut memory = Memory::new(params);
+    let mut blocks = vec![Block::new(); params.memory_blocks()];
+    let mut memory = Memory::new(params, &mut blocks);
     process(&params, &h0, &mut memory, tag);
 }
 
+/// Number of blocks of memory needed at most for a memory parameter of `memory_kb` kilobytes
+///
+/// The number of blocks used is the memory rounded down to a multiple of 4 times the
+/// parallelism (see [`Params::memory_blocks`]), which is never more than `memory_kb`,
+/// so this can be used to size a static array of blocks for [`argon2_no_alloc`]
+pub const fn block_memory_size(memory_kb: u32) -> usize {
+    memory_kb as usize
+}
+
+/// Same as [`argon2_at`] but using the caller provided memory instead of allocating it
+///
+/// The memory needs at least [`Params::memory_blocks`] blocks, and the salt, contrary to
+/// `argon2_at`, needs to be at least 8 bytes. The memory is zeroed before returning.
+///
+/// ```
+/// use cryptoxide::kdf::argon2::{self, Block};
+///
+/// const MEMORY_KB: u32 = 64;
+/// const ZERO: Block = Block::new();
+///
+/// let params = argon2::Builder::new().memory_kb(MEMORY_KB).build().unwrap();
+/// let mut memory = [ZERO; argon2::block_memory_size(MEMORY_KB)];
+/// let mut tag = [0u8; 32];
+/// argon2::argon2_no_alloc(&params, b"my-password", b"saltsaltsaltsalt", b"", b"", &mut tag, &mut memory).unwrap();
+/// ```
+pub fn argon2_no_alloc(
+    params: &Params,
+    password: &[u8],
+    salt: &[u8],
+    key: &[u8],
+    aad: &[u8],
+    tag: &mut [u8],
+    memory: &mut [Block],
+) -> Result<(), InvalidParam> {
+    if salt.len() < MIN_SALT_LENGTH {
+        return Err(InvalidParam::SaltTooShort);
+    }
+    if memory.len() < params.memory_blocks() {
+        return Err(InvalidParam::BlockMemoryTooSmall);
+    }
+    let h0 = H0::new(params, password, salt, key, aad, tag.len() as u32);
+    let mut memory = Memory::new(params, memory);
+    process(params, &h0, &mut memory, tag);
+    Ok(())
+}
+
 /// Generate the ARGON2 output from the parameters, password, salt, key and AAD
 pub fn argon2<const T: usize>(
     params: &Params,
@@ -752,11 +992,177 @@ pub fn argon2<const T: usize>(
 ) -> [u8; T] {
     let mut tag = [0u8; T];
     let h0 = H0::new(&params, password, salt, key, aad, T as u32);
-    let mut memory = Memory::new(params);
+    let mut blocks = vec![Block::new(); params.memory_blocks()];
+    let mut memory = Memory::new(params, &mut blocks);
     process(&params, &h0, &mut memory, &mut tag);
     tag
 }
 
+/// Generate the ARGON2 output of `params` tag length, from the parameters, password, salt, key and AAD
+///
+/// Contrary to `argon2` and `argon2_at`, the salt length is checked to be at least 8 bytes
+pub fn hash(
+    params: &Params,
+    password: &[u8],
+    salt: &[u8],
+    key: &[u8],
+    aad: &[u8],
+) -> Result<Vec<u8>, InvalidParam> {
+    if salt.len() < MIN_SALT_LENGTH {
+        return Err(InvalidParam::SaltTooShort);
+    }
+    let mut tag = vec![0u8; params.tag_length as usize];
+    argon2_at(params, password, salt, key, aad, &mut tag);
+    Ok(tag)
+}
+
+/// Possible errors when decoding a PHC string
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PhcError {
+    /// The string doesn't follow the PHC string format
+    InvalidFormat,
+    /// The algorithm is not one of `argon2d`, `argon2i` or `argon2id`
+    UnknownAlgorithm,
+    /// The salt or the hash are not valid B64 (base64 without padding)
+    InvalidBase64,
+    /// The parameters are not valid argon2 parameters
+    InvalidParam(InvalidParam),
+}
+
+impl core::fmt::Display for PhcError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            PhcError::InvalidFormat => write!(f, "invalid PHC string format"),
+            PhcError::UnknownAlgorithm => write!(f, "unknown algorithm"),
+            PhcError::InvalidBase64 => write!(f, "invalid base64"),
+            PhcError::InvalidParam(e) => write!(f, "invalid parameter: {:?}", e),
+        }
+    }
+}
+
+/// Encode the parameters, salt and hash in the PHC string format
+///
+/// e.g. `$argon2id$v=19$m=65536,t=3,p=4$<B64 salt>$<B64 hash>`
+///
+/// The tag length is implied by the length of `hash`
+pub fn argon2_phc_encode(params: &Params, salt: &[u8], hash: &[u8]) -> String {
+    let name = match params.hash_type {
+        Type::Argon2d => "argon2d",
+        Type::Argon2i => "argon2i",
+        Type::Argon2id => "argon2id",
+    };
+    format!(
+        "${}$v={}$m={},t={},p={}${}${}",
+        name,
+        params.version,
+        params.memory_kb,
+        params.iterations,
+        params.parallelism,
+        b64_encode(salt),
+        b64_encode(hash)
+    )
+}
+
+/// Decode a PHC string into the parameters, the salt and the hash
+///
+/// The tag length of the returned parameters is set to the length of the hash.
+/// When the version is not present, the version 16 (0x10) is assumed, as
+/// in the reference implementation.
+pub fn argon2_phc_decode(phc: &str) -> Result<(Params, Vec<u8>, Vec<u8>), PhcError> {
+    let mut parts = phc.split('$');
+    if parts.next() != Some("") {
+        return Err(PhcError::InvalidFormat);
+    }
+    let hash_type = match parts.next() {
+        Some("argon2d") => Type::Argon2d,
+        Some("argon2i") => Type::Argon2i,
+        Some("argon2id") => Type::Argon2id,
+        Some(_) => return Err(PhcError::UnknownAlgorithm),
+        None => return Err(PhcError::InvalidFormat),
+    };
+    let mut part = parts.next().ok_or(PhcError::InvalidFormat)?;
+    let version = match part.strip_prefix("v=") {
+        None => 0x10,
+        Some(v) => {
+            part = parts.next().ok_or(PhcError::InvalidFormat)?;
+            phc_decimal(v)?
+        }
+    };
+
+    let mut values = part.split(',');
+    let mut param = |name: &str| {
+        values
+            .next()
+            .and_then(|kv| kv.strip_prefix(name))
+            .and_then(|kv| kv.strip_prefix('='))
+            .ok_or(PhcError::InvalidFormat)
+            .and_then(phc_decimal)
+    };
+    let memory_kb = param("m")?;
+    let iterations = param("t")?;
+    let parallelism = param("p")?;
+    if values.next().is_some() {
+        return Err(PhcError::InvalidFormat);
+    }
+
+    let salt = parts.next().ok_or(PhcError::InvalidFormat)?;
+    let hash = parts.next().ok_or(PhcError::InvalidFormat)?;
+    if parts.next().is_some() {
+        return Err(PhcError::InvalidFormat);
+    }
+    let salt = b64_decode(salt).ok_or(PhcError::InvalidBase64)?;
+    let hash = b64_decode(hash).ok_or(PhcError::InvalidBase64)?;
+    if salt.len() < MIN_SALT_LENGTH {
+        return Err(PhcError::InvalidParam(InvalidParam::SaltTooShort));
+    }
+
+    let params = Builder::new()
+        .hash_type(hash_type)
+        .version(version)
+        .memory_kb(memory_kb)
+        .iterations(iterations)
+        .parallelism(parallelism)
+        .tag_length(u32::try_from(hash.len()).map_err(|_| PhcError::InvalidFormat)?)
+        .build()
+        .map_err(PhcError::InvalidParam)?;
+    Ok((params, salt, hash))
+}
+
+/// Verify a password against a PHC string
+///
+/// The hash is recomputed from the password with the parameters and the salt of the
+/// PHC string, and compared in constant time with the hash of the PHC string.
+///
+/// Returns `Ok(true)` if the password matches, `Ok(false)` if it doesn't, and an
+/// error if the PHC string cannot be decoded.
+pub fn argon2_verify_phc(phc: &str, password: &[u8]) -> Result<bool, PhcError> {
+    let (params, salt, expected) = argon2_phc_decode(phc)?;
+    let mut tag = hash(&params, password, &salt, b"", b"").map_err(PhcError::InvalidParam)?;
+    let valid = CtEqual::ct_eq(&tag[..], &expected[..]).is_true();
+    tag.zeroize();
+    Ok(valid)
+}
+
+// PHC decimal values are made of digits only, without any leading zero
+fn phc_decimal(s: &str) -> Result<u32, PhcError> {
+    if s.is_empty() || !s.bytes().all(|c| c.is_ascii_digit()) || (s.len() > 1 && s.starts_with('0'))
+    {
+        return Err(PhcError::InvalidFormat);
+    }
+    s.parse().map_err(|_| PhcError::InvalidFormat)
+}
+
+// B64 encoding (standard base64 alphabet without padding) as used by the PHC string format.
+fn b64_encode(bytes: &[u8]) -> String {
+    let encoded = encode_base64_with(bytes, Base64Alphabet::Standard, false);
+    // base64 characters are all ascii
+    String::from_utf8(encoded).unwrap()
+}
+
+fn b64_decode(s: &str) -> Option<Vec<u8>> {
+    decode_base64_with(s.as_bytes(), Base64Alphabet::Standard, false).ok()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -764,18 +1170,18 @@ mod tests {
     // test vectors from
     // https://www.rfc-editor.org/rfc/rfc9106.html
 
-    fn rfc9106_params(params: Params) -> Params {
-        params
+    fn rfc9106_params(hash_type: Type) -> Params {
+        Builder::new()
+            .hash_type(hash_type)
             .memory_kb(32)
-            .unwrap()
             .iterations(3)
-            .unwrap()
             .parallelism(4)
+            .build()
             .unwrap()
     }
 
-    fn run_std(params: Params, expected: &[u8; 32]) {
-        let params = rfc9106_params(params);
+    fn run_std(hash_type: Type, expected: &[u8; 32]) {
+        let params = rfc9106_params(hash_type);
         let tag = argon2(&params, &[0x01; 32], &[0x02; 16], &[0x03; 8], &[0x04; 12]);
 
         assert_eq!(*expected, tag, "expected tag failed")
@@ -788,7 +1194,7 @@ mod tests {
             0x42, 0x94, 0xf8, 0x68, 0xe3, 0xbe, 0x39, 0x84, 0xf3, 0xc1, 0xa1, 0x3a, 0x4d, 0xb9,
             0xfa, 0xbe, 0x4a, 0xcb,
         ];
-        run_std(Params::argon2d(), &EXPECTED)
+        run_std(Type::Argon2d, &EXPECTED)
     }
 
     #[test]
@@ -798,7 +1204,7 @@ mod tests {
             0xbd, 0xa1, 0xc8, 0xde, 0x6b, 0x01, 0x6d, 0xd3, 0x88, 0xd2, 0x99, 0x52, 0xa4, 0xc4,
             0x67, 0x2b, 0x6c, 0xe8,
         ];
-        run_std(Params::argon2i(), &EXPECTED)
+        run_std(Type::Argon2i, &EXPECTED)
     }
 
     #[test]
@@ -808,6 +1214,410 @@ mod tests {
             0x53, 0xc9, 0xd0, 0x1e, 0xf0, 0x45, 0x2d, 0x75, 0xb6, 0x5e, 0xb5, 0x25, 0x20, 0xe9,
             0x6b, 0x01, 0xe6, 0x59,
         ];
-        run_std(Params::argon2id(), &EXPECTED);
+        run_std(Type::Argon2id, &EXPECTED);
+    }
+
+    #[test]
+    fn argon2id_no_key_aad() {
+        // (iterations, parallelism, memory_kb, tag) generated with the reference implementation,
+        // the last one spans multiple address blocks per segment
+        let vectors: [(u32, u32, u32, [u8; 32]); 3] = [
+            (
+                1,
+                1,
+                64,
+                [
+                    0xe7, 0x93, 0xd6, 0x4e, 0xf7, 0x5d, 0x58, 0xf5, 0x03, 0xd4, 0x63, 0x1b, 0x2b,
+                    0x14, 0x9f, 0x7f, 0x80, 0x12, 0x7c, 0x5f, 0x39, 0x93, 0xd8, 0x9b, 0x1c, 0x9b,
+                    0x78, 0x1e, 0x51, 0xd0, 0xb4, 0x13,
+                ],
+            ),
+            (
+                2,
+                2,
+                256,
+                [
+                    0xc1, 0x12, 0xd2, 0xee, 0x6b, 0x9d, 0x51, 0x44, 0x13, 0xf8, 0x06, 0x24, 0x31,
+                    0x87, 0x95, 0x21, 0x86, 0xe8, 0xf1, 0x9c, 0xac, 0xab, 0x80, 0xf2, 0x0a, 0x82,
+                    0x3d, 0x54, 0x9f, 0x11, 0x1d, 0x2c,
+                ],
+            ),
+            (
+                4,
+                1,
+                1024,
+                [
+                    0x86, 0xf9, 0xb1, 0x32, 0x36, 0x4f, 0x42, 0xab, 0xf9, 0xc3, 0xc9, 0x99, 0xd2,
+                    0x86, 0x19, 0x87, 0xa8, 0xca, 0xbc, 0x80, 0x4f, 0xb9, 0x78, 0xf9, 0xd5, 0xbb,
+                    0x59, 0xd5, 0xf0, 0x5b, 0x7e, 0x49,
+                ],
+            ),
+        ];
+        for (iterations, parallelism, memory_kb, expected) in vectors {
+            let params = Builder::new()
+                .memory_kb(memory_kb)
+                .iterations(iterations)
+                .parallelism(parallelism)
+                .build()
+                .unwrap();
+            let tag = argon2::<32>(&params, b"password", b"somesaltsomesalt", b"", b"");
+            assert_eq!(
+                expected, tag,
+                "t={} p={} m={}",
+                iterations, parallelism, memory_kb
+            );
+        }
+    }
+
+    #[test]
+    fn argon2id_output_length() {
+        // (tag length, BLAKE2b-256 of the tag) generated with the reference implementation,
+        // exercising the Blake2b-long construction for tags longer than 64 bytes
+        let vectors: [(usize, [u8; 32]); 6] = [
+            (
+                4,
+                [
+                    0x4a, 0x1c, 0x0f, 0xde, 0x5e, 0x30, 0xff, 0xc7, 0x55, 0x16, 0xee, 0x89, 0x54,
+                    0x8e, 0x33, 0x19, 0xf6, 0xf3, 0x24, 0x17, 0x5a, 0x79, 0x2a, 0x99, 0x5c, 0xb3,
+                    0x7d, 0x34, 0xd0, 0x1e, 0xf7, 0xfa,
+                ],
+            ),
+            (
+                32,
+                [
+                    0xe2, 0x5b, 0x4d, 0xa8, 0x6e, 0x97, 0x7e, 0xf3, 0xbe, 0x82, 0xad, 0x24, 0x5d,
+                    0x09, 0xc8, 0x28, 0x87, 0x6d, 0xb4, 0xc3, 0xc6, 0x7e, 0xaf, 0x16, 0x51, 0xb9,
+                    0xec, 0xd0, 0x0b, 0x77, 0xd1, 0x12,
+                ],
+            ),
+            (
+                64,
+                [
+                    0xa3, 0x41, 0x81, 0x32, 0x63, 0x94, 0x18, 0xf0, 0x24, 0x92, 0x2c, 0xe7, 0xa6,
+                    0xaa, 0xbe, 0x7d, 0xcb, 0x9e, 0x1b, 0x95, 0x2a, 0x90, 0x8a, 0x09, 0xcf, 0x81,
+                    0x17, 0x70, 0xff, 0x6a, 0xd2, 0xd2,
+                ],
+            ),
+            (
+                65,
+                [
+                    0xa3, 0x36, 0xe6, 0x0f, 0x11, 0xde, 0x37, 0xaa, 0x76, 0xf5, 0x91, 0x77, 0xb8,
+                    0x6a, 0xe2, 0x3a, 0x67, 0xc9, 0x3d, 0x72, 0x01, 0xd0, 0xf8, 0x2d, 0xaa, 0xbf,
+                    0x84, 0x84, 0xcf, 0xd3, 0xb1, 0x27,
+                ],
+            ),
+            (
+                128,
+                [
+                    0x5d, 0x70, 0xd4, 0xe9, 0x3f, 0xd2, 0x34, 0xce, 0x6b, 0x68, 0x62, 0xdf, 0xea,
+                    0x40, 0x22, 0x9a, 0x01, 0x99, 0x79, 0x69, 0x04, 0x91, 0xe3, 0xe5, 0xa5, 0x31,
+                    0x0b, 0x30, 0x1a, 0x66, 0x33, 0x6e,
+                ],
+            ),
+            (
+                1024,
+                [
+                    0xf4, 0x3a, 0x4a, 0x2f, 0x9a, 0x41, 0x38, 0x22, 0x4d, 0x61, 0x9b, 0x52, 0xa8,
+                    0xdc, 0x30, 0xd7, 0x66, 0xc8, 0x07, 0xe9, 0x91, 0x99, 0x38, 0x57, 0x66, 0xab,
+                    0x7e, 0x23, 0xb2, 0x9d, 0x9f, 0x9a,
+                ],
+            ),
+        ];
+        let params = Builder::new()
+            .memory_kb(64)
+            .iterations(2)
+            .parallelism(2)
+            .build()
+            .unwrap();
+        for (tag_length, expected) in vectors {
+            let mut tag = vec![0u8; tag_length];
+            argon2_at(
+                &params,
+                b"password",
+                b"somesaltsomesalt",
+                b"",
+                b"",
+                &mut tag,
+            );
+            let digest = blake2b::Context::<256>::new().update(&tag).finalize();
+            assert_eq!(expected, digest, "tag length {}", tag_length);
+        }
+    }
+
+    #[test]
+    fn builder_validation() {
+        assert_eq!(
+            Builder::new().parallelism(0).build(),
+            Err(InvalidParam::ParallelismZero)
+        );
+        assert_eq!(
+            Builder::new().parallelism(0x1000000).build(),
+            Err(InvalidParam::ParallelismTooHigh)
+        );
+        assert_eq!(
+            Builder::new().iterations(0).build(),
+            Err(InvalidParam::IterationsZero)
+        );
+        assert_eq!(
+            Builder::new().version(0x12).build(),
+            Err(InvalidParam::UnknownVersion)
+        );
+        assert_eq!(
+            Builder::new().memory_kb(31).parallelism(4).build(),
+            Err(InvalidParam::MemoryTooLow)
+        );
+        assert_eq!(
+            Builder::new().tag_length(3).build(),
+            Err(InvalidParam::TagTooShort)
+        );
+        assert_eq!(Builder::new().build(), Ok(Params::argon2id()));
+
+        let params = Params::recommended();
+        assert_eq!(params.hash_type, Type::Argon2id);
+        assert_eq!(params.memory_kb, 65536);
+        assert_eq!(params.iterations.get(), 3);
+        assert_eq!(params.parallelism.get(), 4);
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn deprecated_setters() {
+        let params = Params::argon2i()
+            .memory_kb(32)
+            .unwrap()
+            .iterations(3)
+            .unwrap()
+            .parallelism(4)
+            .unwrap();
+        assert_eq!(params, rfc9106_params(Type::Argon2i));
+
+        // memory is silently raised to 8 times the parallelism
+        let params = Params::argon2id().parallelism(8).unwrap();
+        assert_eq!(params.memory_kb, 64);
+    }
+
+    #[test]
+    fn hash_checks() {
+        let params = Builder::new().tag_length(48).build().unwrap();
+        assert_eq!(
+            hash(&params, b"password", b"short", b"", b""),
+            Err(InvalidParam::SaltTooShort)
+        );
+        assert_eq!(
+            hash(&params, b"password", b"saltsal", b"", b""),
+            Err(InvalidParam::SaltTooShort)
+        );
+        let tag = hash(&params, b"password", b"somesalt", b"", b"").unwrap();
+        let mut expected = [0u8; 48];
+        argon2_at(&params, b"password", b"somesalt", b"", b"", &mut expected);
+        assert_eq!(tag, expected);
+    }
+
+    #[test]
+    fn no_alloc() {
+        const ZERO: Block = Block::new();
+        let mut memory = [ZERO; block_memory_size(100)];
+        for hash_type in [Type::Argon2d, Type::Argon2i, Type::Argon2id] {
+            // 100kb with parallelism 3 is rounded down to 96 blocks
+            let params = Builder::new()
+                .hash_type(hash_type)
+                .memory_kb(100)
+                .iterations(2)
+                .parallelism(3)
+                .build()
+                .unwrap();
+            assert_eq!(params.memory_blocks(), 96);
+
+            let mut expected = [0u8; 32];
+            argon2_at(
+                &params,
+                b"password",
+                b"somesalt",
+                b"key",
+                b"ad",
+                &mut expected,
+            );
+            let mut tag = [0u8; 32];
+            argon2_no_alloc(
+                &params,
+                b"password",
+                b"somesalt",
+                b"key",
+                b"ad",
+                &mut tag,
+                &mut memory,
+            )
+            .unwrap();
+            assert_eq!(tag, expected);
+            assert!(memory.iter().all(|b| b.0.iter().all(|w| *w == 0)));
+
+            assert_eq!(
+                argon2_no_alloc(
+                    &params,
+                    b"password",
+                    b"somesalt",
+                    b"key",
+                    b"ad",
+                    &mut tag,
+                    &mut memory[..95],
+                ),
+                Err(InvalidParam::BlockMemoryTooSmall)
+            );
+            assert_eq!(
+                argon2_no_alloc(
+                    &params,
+                    b"password",
+                    b"saltsal",
+                    b"key",
+                    b"ad",
+                    &mut tag,
+                    &mut memory,
+                ),
+                Err(InvalidParam::SaltTooShort)
+            );
+        }
+    }
+
+    #[test]
+    fn b64() {
+        let vectors: [(&[u8], &str); 7] = [
+            (b"", ""),
+            (b"f", "Zg"),
+            (b"fo", "Zm8"),
+            (b"foo", "Zm9v"),
+            (b"foob", "Zm9vYg"),
+            (b"fooba", "Zm9vYmE"),
+            (&[0xfb, 0xff, 0xbf], "+/+/"),
+        ];
+        for (bytes, encoded) in vectors {
+            assert_eq!(b64_encode(bytes), encoded);
+            assert_eq!(b64_decode(encoded).as_deref(), Some(bytes));
+        }
+        let all: Vec<u8> = (0..=255).collect();
+        assert_eq!(b64_decode(&b64_encode(&all)), Some(all));
+
+        assert_eq!(b64_decode("Z"), None);
+        assert_eq!(b64_decode("Zg=="), None);
+        assert_eq!(b64_decode("Zh"), None);
+        assert_eq!(b64_decode("Zm9-"), None);
+    }
+
+    #[test]
+    fn phc() {
+        // generated with the reference implementation
+        const PHC: &str = "$argon2id$v=19$m=256,t=2,p=2$c29tZXNhbHRzb21lc2FsdA$wRLS7mudUUQT+AYkMYeVIYbo8Zysq4DyCoI9VJ8RHSw";
+        let (params, salt, tag) = argon2_phc_decode(PHC).unwrap();
+        let expected = Builder::new()
+            .memory_kb(256)
+            .iterations(2)
+            .parallelism(2)
+            .build()
+            .unwrap();
+        assert_eq!(params, expected);
+        assert_eq!(salt, b"somesaltsomesalt");
+        assert_eq!(hash(&params, b"password", &salt, b"", b"").unwrap(), tag);
+        assert_eq!(argon2_phc_encode(&params, &salt, &tag), PHC);
+
+        const PHC2: &str = "$argon2id$v=19$m=64,t=1,p=1$c2FsdHNhbHQ$JfLXztcAfZRCC4Zc9yiY5Q5UEqs";
+        let (params, salt, tag) = argon2_phc_decode(PHC2).unwrap();
+        assert_eq!(params.tag_length, 20);
+        assert_eq!(hash(&params, b"password", &salt, b"", b"").unwrap(), tag);
+        assert_eq!(argon2_phc_encode(&params, &salt, &tag), PHC2);
+
+        let (params, _, _) = argon2_phc_decode("$argon2d$m=64,t=1,p=1$c2FsdHNhbHQ$JfLXzg").unwrap();
+        assert_eq!(params.hash_type, Type::Argon2d);
+        assert_eq!(params.version, 0x10);
+    }
+
+    #[test]
+    fn phc_verify() {
+        const PHC: &str = "$argon2id$v=19$m=256,t=2,p=2$c29tZXNhbHRzb21lc2FsdA$wRLS7mudUUQT+AYkMYeVIYbo8Zysq4DyCoI9VJ8RHSw";
+        assert_eq!(argon2_verify_phc(PHC, b"password"), Ok(true));
+        assert_eq!(argon2_verify_phc(PHC, b"passwore"), Ok(false));
+        assert_eq!(argon2_verify_phc(PHC, b""), Ok(false));
+
+        // a different tag length, salt or parameter doesn't match
+        const PHC2: &str = "$argon2id$v=19$m=64,t=1,p=1$c2FsdHNhbHQ$JfLXztcAfZRCC4Zc9yiY5Q5UEqs";
+        assert_eq!(argon2_verify_phc(PHC2, b"password"), Ok(true));
+        assert_eq!(
+            argon2_verify_phc(
+                "$argon2id$v=19$m=64,t=1,p=1$c2FsdHNhbHQ$JfLXztcAfZRCC4Zc9yiY5Q",
+                b"password"
+            ),
+            Ok(false)
+        );
+        assert_eq!(
+            argon2_verify_phc(
+                "$argon2id$v=19$m=64,t=2,p=1$c2FsdHNhbHQ$JfLXztcAfZRCC4Zc9yiY5Q5UEqs",
+                b"password"
+            ),
+            Ok(false)
+        );
+
+        assert_eq!(
+            argon2_verify_phc("$argon2id$v=19$m=64,t=1,p=1$c2FsdHNhbHQ", b"password"),
+            Err(PhcError::InvalidFormat)
+        );
+        assert_eq!(
+            argon2_verify_phc("$argon2id$v=19$m=64,t=1,p=1$c2FsdA$JfLXzg", b"password"),
+            Err(PhcError::InvalidParam(InvalidParam::SaltTooShort))
+        );
+    }
+
+    #[test]
+    fn phc_invalid() {
+        let invalid = [
+            (
+                "argon2id$v=19$m=64,t=1,p=1$c2FsdHNhbHQ$JfLXzg",
+                PhcError::InvalidFormat,
+            ),
+            (
+                "$scrypt$v=19$m=64,t=1,p=1$c2FsdHNhbHQ$JfLXzg",
+                PhcError::UnknownAlgorithm,
+            ),
+            (
+                "$argon2id$v=19$t=1,m=64,p=1$c2FsdHNhbHQ$JfLXzg",
+                PhcError::InvalidFormat,
+            ),
+            (
+                "$argon2id$v=19$m=064,t=1,p=1$c2FsdHNhbHQ$JfLXzg",
+                PhcError::InvalidFormat,
+            ),
+            (
+                "$argon2id$v=19$m=64,t=1,p=1,x=1$c2FsdHNhbHQ$JfLXzg",
+                PhcError::InvalidFormat,
+            ),
+            (
+                "$argon2id$v=19$m=64,t=1,p=1$c2FsdHNhbHQ",
+                PhcError::InvalidFormat,
+            ),
+            (
+                "$argon2id$v=19$m=64,t=1,p=1$c2FsdHNhbHQ$JfLXzg$",
+                PhcError::InvalidFormat,
+            ),
+            (
+                "$argon2id$v=19$m=64,t=1,p=1$c2FsdHNhbHQ=$JfLXzg",
+                PhcError::InvalidBase64,
+            ),
+            (
+                "$argon2id$v=18$m=64,t=1,p=1$c2FsdHNhbHQ$JfLXzg",
+                PhcError::InvalidParam(InvalidParam::UnknownVersion),
+            ),
+            (
+                "$argon2id$v=19$m=64,t=0,p=1$c2FsdHNhbHQ$JfLXzg",
+                PhcError::InvalidParam(InvalidParam::IterationsZero),
+            ),
+            (
+                "$argon2id$v=19$m=64,t=1,p=1$c2FsdA$JfLXzg",
+                PhcError::InvalidParam(InvalidParam::SaltTooShort),
+            ),
+            (
+                "$argon2id$v=19$m=64,t=1,p=1$c2FsdHNhbHQ$JfLX",
+                PhcError::InvalidParam(InvalidParam::TagTooShort),
+            ),
+        ];
+        for (phc, err) in invalid {
+            assert_eq!(argon2_phc_decode(phc).err(), Some(err), "{}", phc);
+        }
     }
 }
@@ -0,0 +1,263 @@
+//! Balloon - memory-hard password hashing
+//!
+//! Balloon is defined in [Balloon Hashing: A Memory-Hard Function Providing Provable
+//! Protection Against Sequential Attacks][1] (Corrigan-Gibbs, Boneh and Schechter).
+//!
+//! This implements the single-threaded Balloon construction (algorithm 1 of the paper)
+//! instantiated with SHA3-256, where:
+//!
+//! * `s_cost` is the number of 32 bytes blocks of the buffer,
+//! * `t_cost` is the number of mixing rounds over the buffer,
+//! * `delta` is the number of pseudorandomly chosen blocks hashed in each step.
+//!
+//! The integers (the counter and the (t, m, i) index block) are encoded as 64 bits
+//! little endian, and the pseudorandom block index is the little endian integer
+//! value of the index hash modulo `s_cost`.
+//!
+//! ## Usage
+//!
+//! ```
+//! use cryptoxide::kdf::balloon;
+//!
+//! let params = balloon::Params::new(1024, 3, 3).unwrap();
+//! let output: [u8; 32] = balloon::balloon(&params, b"my-password", b"saltsaltsaltsalt");
+//! ```
+//!
+//! When comparing the output, always use a constant time equality function.
+//!
+//! [1]: <https://eprint.iacr.org/2016/027.pdf>
+
+use crate::hashing::sha3::Context256;
+use alloc::vec;
+
+/// Size in bytes of a Balloon block, which is the output of SHA3-256
+pub const BLOCK_SIZE: usize = 32;
+
+/// Balloon parameters
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Params {
+    s_cost: u32,
+    t_cost: u32,
+    delta: u32,
+}
+
+/// Possible errors when creating the Balloon parameters
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BalloonError {
+    /// s_cost should be at least 1
+    SCostZero,
+    /// t_cost should be at least 1
+    TCostZero,
+    /// delta should be at least 1
+    DeltaZero,
+    /// The memory needed (s_cost * 32 bytes) doesn't fit in usize
+    MemoryOverflow,
+}
+
+impl core::fmt::Display for BalloonError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            BalloonError::SCostZero => write!(f, "s_cost is zero"),
+            BalloonError::TCostZero => write!(f, "t_cost is zero"),
+            BalloonError::DeltaZero => write!(f, "delta is zero"),
+            BalloonError::MemoryOverflow => write!(f, "memory requirement overflow"),
+        }
+    }
+}
+
+impl Params {
+    /// Create new Balloon parameters
+    ///
+    /// * `s_cost` - the number of blocks of 32 bytes used
+    /// * `t_cost` - the number of rounds
+    /// * `delta` - the number of dependencies per block in each round, the paper recommends 3
+    pub fn new(s_cost: u32, t_cost: u32, delta: u32) -> Result<Params, BalloonError> {
+        if s_cost == 0 {
+            return Err(BalloonError::SCostZero);
+        }
+        if t_cost == 0 {
+            return Err(BalloonError::TCostZero);
+        }
+        if delta == 0 {
+            return Err(BalloonError::DeltaZero);
+        }
+        usize::try_from(s_cost)
+            .ok()
+            .and_then(|s| s.checked_mul(BLOCK_SIZE))
+            .ok_or(BalloonError::MemoryOverflow)?;
+        Ok(Params {
+            s_cost,
+            t_cost,
+            delta,
+        })
+    }
+
+    /// Return the memory used in bytes
+    pub fn memory_size(&self) -> usize {
+        self.s_cost as usize * BLOCK_SIZE
+    }
+}
+
+// H(cnt, inputs...) and increment the counter
+fn hash(cnt: &mut u64, inputs: &[&[u8]]) -> [u8; BLOCK_SIZE] {
+    let mut ctx = Context256::new();
+    ctx.update_mut(&cnt.to_le_bytes());
+    for input in inputs {
+        ctx.update_mut(input);
+    }
+    *cnt += 1;
+    ctx.finalize()
+}
+
+// interpret the block as a little endian integer, and reduce it modulo n
+fn block_mod(block: &[u8; BLOCK_SIZE], n: u32) -> usize {
+    let n = n as u64;
+    let mut r = 0u64;
+    for b in block.iter().rev() {
+        r = ((r << 8) | *b as u64) % n;
+    }
+    r as usize
+}
+
+/// Compute the Balloon-SHA3-256 hash of the password and salt
+pub fn balloon(params: &Params, password: &[u8], salt: &[u8]) -> [u8; BLOCK_SIZE] {
+    let s_cost = params.s_cost as usize;
+    let mut cnt = 0u64;
+    let mut buf = vec![[0u8; BLOCK_SIZE]; s_cost];
+
+    // expand the input into the buffer
+    buf[0] = hash(&mut cnt, &[password, salt]);
+    for m in 1..s_cost {
+        buf[m] = hash(&mut cnt, &[&buf[m - 1]]);
+    }
+
+    // mix the buffer content
+    for t in 0..params.t_cost as u64 {
+        for m in 0..s_cost {
+            let prev = buf[(m + s_cost - 1) % s_cost];
+            buf[m] = hash(&mut cnt, &[&prev, &buf[m]]);
+
+            for i in 0..params.delta as u64 {
+                let mut idx_block = [0u8; 24];
+                idx_block[0..8].copy_from_slice(&t.to_le_bytes());
+                idx_block[8..16].copy_from_slice(&(m as u64).to_le_bytes());
+                idx_block[16..24].copy_from_slice(&i.to_le_bytes());
+                let other = block_mod(&hash(&mut cnt, &[salt, &idx_block]), params.s_cost);
+                buf[m] = hash(&mut cnt, &[&buf[m], &buf[other]]);
+            }
+        }
+    }
+
+    buf[s_cost - 1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn params() {
+        assert_eq!(Params::new(0, 1, 1), Err(BalloonError::SCostZero));
+        assert_eq!(Params::new(1, 0, 1), Err(BalloonError::TCostZero));
+        assert_eq!(Params::new(1, 1, 0), Err(BalloonError::DeltaZero));
+        assert_eq!(Params::new(16, 3, 3).unwrap().memory_size(), 512);
+    }
+
+    struct Test {
+        password: &'static [u8],
+        salt: &'static [u8],
+        s_cost: u32,
+        t_cost: u32,
+        delta: u32,
+        output: [u8; 32],
+    }
+
+    #[test]
+    fn vectors() {
+        // generated with an independent implementation of the same construction
+        let tests = [
+            Test {
+                password: b"password",
+                salt: b"salt",
+                s_cost: 1,
+                t_cost: 1,
+                delta: 3,
+                output: [
+                    0xdb, 0xb8, 0x60, 0x28, 0x1c, 0x7f, 0x72, 0xad, 0xf0, 0x56, 0x2e, 0x3b, 0x84,
+                    0x98, 0xf2, 0x7e, 0xbd, 0xca, 0x8a, 0xca, 0xfe, 0x82, 0x79, 0xef, 0xf6, 0x0b,
+                    0xf8, 0xe9, 0x74, 0x43, 0xde, 0x79,
+                ],
+            },
+            Test {
+                password: b"password",
+                salt: b"salt",
+                s_cost: 16,
+                t_cost: 3,
+                delta: 3,
+                output: [
+                    0xb3, 0x33, 0xf6, 0x1c, 0x34, 0xff, 0x54, 0xcd, 0x65, 0xd8, 0xe9, 0xc2, 0xfc,
+                    0xcf, 0x06, 0xbb, 0xdc, 0x5f, 0xe8, 0x60, 0xd3, 0xde, 0xe0, 0x8c, 0x0d, 0x74,
+                    0x07, 0xac, 0x6b, 0x7c, 0x61, 0x01,
+                ],
+            },
+            Test {
+                password: b"",
+                salt: b"salt",
+                s_cost: 64,
+                t_cost: 2,
+                delta: 4,
+                output: [
+                    0x66, 0xbe, 0xcb, 0xfb, 0xe7, 0x3c, 0xa7, 0x87, 0xf7, 0xc4, 0xc3, 0xd2, 0x32,
+                    0x37, 0x8e, 0x9d, 0x5a, 0x6c, 0x5f, 0x50, 0xc8, 0xb3, 0x09, 0xad, 0x93, 0xf7,
+                    0x11, 0xbe, 0x30, 0xcf, 0xa5, 0x47,
+                ],
+            },
+            Test {
+                password: b"hunter42",
+                salt: b"examplesalt",
+                s_cost: 1024,
+                t_cost: 3,
+                delta: 3,
+                output: [
+                    0x32, 0x83, 0x4b, 0x6e, 0x33, 0x68, 0xd7, 0xc7, 0xbf, 0x4a, 0x9c, 0x24, 0x3f,
+                    0x0d, 0x04, 0xd2, 0xdc, 0x63, 0x06, 0x97, 0x9a, 0x7f, 0x52, 0x16, 0x59, 0x82,
+                    0x57, 0xef, 0x3d, 0x8a, 0xb7, 0xcf,
+                ],
+            },
+        ];
+        for t in tests.iter() {
+            let params = Params::new(t.s_cost, t.t_cost, t.delta).unwrap();
+            assert_eq!(
+                balloon(&params, t.password, t.salt),
+                t.output,
+                "s_cost={} t_cost={} delta={}",
+                t.s_cost,
+                t.t_cost,
+                t.delta
+            );
+        }
+    }
+}
+
+#[cfg(all(test, feature = "with-bench"))]
+mod bench {
+    use super::{balloon, Params};
+    use test::Bencher;
+
+    // 1MB of memory for both balloon and scrypt (N * r * 128 bytes)
+
+    #[bench]
+    pub fn balloon_1mb(bh: &mut Bencher) {
+        let params = Params::new(32768, 1, 3).unwrap();
+        bh.iter(|| balloon(&params, b"password", b"saltsaltsaltsalt"));
+    }
+
+    #[cfg(feature = "scrypt")]
+    #[bench]
+    pub fn scrypt_1mb(bh: &mut Bencher) {
+        use crate::scrypt::ScryptParams;
+        let params = ScryptParams::new(10, 8, 1);
+        let mut output = [0u8; 32];
+        bh.iter(|| params.run(b"password", b"saltsaltsaltsalt", &mut output));
+    }
+}
@@ -0,0 +1,121 @@
+//! KDF in Counter Mode
+//!
+//! The KDF in Counter Mode is defined in [NIST SP 800-108][1] section 5.1, and uses
+//! HMAC with the digest `D` as the pseudorandom function. Each block of the output is:
+//!
+//! ```text
+//! K(i) = HMAC(KI, [i]32 || Label || 0x00 || Context || [L]32)
+//! ```
+//!
+//! where the counter `i` starts at 1, `L` is the output length in bits, and both
+//! are encoded as 32 bits big endian.
+//!
+//! ## Usage
+//!
+//! ```
+//! use cryptoxide::{kdf::counter_kdf::counter_kdf, sha2::Sha256};
+//!
+//! let mut key = [0u8; 32];
+//! counter_kdf(Sha256::new(), b"key derivation key", b"label", b"context", &mut key).unwrap();
+//! ```
+//!
+//! [1]: <https://csrc.nist.gov/publications/detail/sp/800-108/rev-1/final>
+
+use super::KdfError;
+use crate::digest::Digest;
+use crate::hmac::Hmac;
+use crate::mac::Mac;
+use alloc::vec;
+
+/// Derive `output` from the key derivation key `ki`, the `label` and the `context`
+///
+/// The output can't be empty, and its length in bits need to fit in 32 bits.
+pub fn counter_kdf<D: Digest>(
+    mut digest: D,
+    ki: &[u8],
+    label: &[u8],
+    context: &[u8],
+    output: &mut [u8],
+) -> Result<(), KdfError> {
+    if output.is_empty() {
+        return Err(KdfError::InvalidOutputLength);
+    }
+    let l = output
+        .len()
+        .checked_mul(8)
+        .and_then(|l| u32::try_from(l).ok())
+        .ok_or(KdfError::InvalidOutputLength)?;
+
+    digest.reset();
+    let mut mac = Hmac::new(digest, ki);
+    let mut block = vec![0u8; mac.output_bytes()];
+
+    // the number of blocks is less than 2^32 since L fits in 32 bits
+    for (i, chunk) in output.chunks_mut(block.len()).enumerate() {
+        mac.input(&(i as u32 + 1).to_be_bytes());
+        mac.input(label);
+        mac.input(&[0]);
+        mac.input(context);
+        mac.input(&l.to_be_bytes());
+        mac.raw_result(&mut block);
+        mac.reset();
+        chunk.copy_from_slice(&block[..chunk.len()]);
+    }
+    Ok(())
+}
+
+#[cfg(all(test, feature = "sha2"))]
+mod tests {
+    use super::*;
+    use crate::sha2::{Sha256, Sha512};
+
+    #[test]
+    fn vectors() {
+        // generated with an independent implementation using python's hmac and hashlib
+        let ki: [u8; 32] = core::array::from_fn(|i| i as u8);
+
+        let mut output = [0u8; 32];
+        counter_kdf(Sha256::new(), &ki, b"label", b"context", &mut output).unwrap();
+        assert_eq!(
+            output,
+            [
+                0x30, 0x37, 0x90, 0xcf, 0xe3, 0x63, 0xab, 0xe9, 0x68, 0x2d, 0xbf, 0xff, 0x59, 0x41,
+                0xf2, 0x3b, 0x32, 0xad, 0xdc, 0x96, 0xda, 0x72, 0xf4, 0xc7, 0xe5, 0xb2, 0x0e, 0x9f,
+                0x59, 0xa4, 0xe5, 0x70,
+            ]
+        );
+
+        // multiple blocks, with a partial last block
+        let mut output = [0u8; 80];
+        counter_kdf(Sha256::new(), &ki, b"label", b"context", &mut output).unwrap();
+        assert_eq!(
+            output,
+            [
+                0x79, 0x3c, 0x8c, 0x63, 0x22, 0x23, 0x4b, 0x76, 0x06, 0x1b, 0xb6, 0xbe, 0x85, 0x8d,
+                0x89, 0x9c, 0xf6, 0xc2, 0xcf, 0x3a, 0x2c, 0xf0, 0x75, 0xf2, 0x2b, 0xed, 0x2b, 0x8a,
+                0x17, 0xd1, 0x0f, 0xf1, 0xcd, 0x9a, 0x6c, 0xdf, 0xd6, 0xc0, 0xf4, 0xbc, 0xae, 0xa6,
+                0xf6, 0x70, 0x0b, 0x4d, 0xec, 0xc8, 0xcb, 0x28, 0xa0, 0x19, 0x96, 0x8a, 0x85, 0x9f,
+                0x4e, 0x2d, 0x4e, 0xc8, 0xc6, 0x86, 0xdb, 0xd5, 0xa3, 0x3e, 0x69, 0x43, 0x41, 0x3e,
+                0x3d, 0xb9, 0xb6, 0xc0, 0x4a, 0x28, 0x02, 0x2f, 0x93, 0x6e,
+            ]
+        );
+
+        let mut output = [0u8; 20];
+        counter_kdf(Sha512::new(), b"key", b"", b"", &mut output).unwrap();
+        assert_eq!(
+            output,
+            [
+                0x5d, 0xb6, 0xec, 0x3b, 0x0a, 0x0c, 0x7b, 0x70, 0x77, 0x54, 0xa9, 0xee, 0x5d, 0xd6,
+                0x7a, 0x00, 0xaf, 0xb6, 0x7c, 0x1d,
+            ]
+        );
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            counter_kdf(Sha256::new(), b"key", b"", b"", &mut []),
+            Err(KdfError::InvalidOutputLength)
+        );
+    }
+}
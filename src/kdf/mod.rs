@@ -1,4 +1,276 @@
 //! Key Derivation Function (KDF) Algorithms
+//!
+//! The password hashing algorithms are also available through the [`PasswordHasher`]
+//! trait, which allows to swap the algorithm without changing the calling code:
+//!
+//! ```
+//! use cryptoxide::kdf::{PasswordHasher, Scrypt};
+//! use cryptoxide::scrypt::ScryptParams;
+//!
+//! fn store<H: PasswordHasher>(params: &H::Params, password: &[u8]) -> [u8; 32] {
+//!     let mut output = [0u8; 32];
+//!     H::hash(params, password, b"saltsaltsaltsalt", &mut output).unwrap();
+//!     output
+//! }
+//!
+//! let params = ScryptParams::builder().log_n(10).build().unwrap();
+//! let stored = store::<Scrypt>(&params, b"password");
+//! assert!(Scrypt::verify(&params, b"password", b"saltsaltsaltsalt", &stored).is_ok());
+//! ```
 
 #[cfg(feature = "argon2")]
 pub mod argon2;
+
+#[cfg(feature = "balloon")]
+pub mod balloon;
+
+#[cfg(feature = "hmac")]
+pub mod counter_kdf;
+
+use crate::constant_time::CtEqual;
+use alloc::vec;
+
+/// Errors of the [`PasswordHasher`] operations
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KdfError {
+    /// The parameters are not valid for the algorithm
+    InvalidParams,
+    /// The salt is shorter than the minimum supported by the algorithm
+    SaltTooShort,
+    /// The output length is not supported by the algorithm
+    InvalidOutputLength,
+    /// The computed hash doesn't match the expected hash
+    VerificationFailed,
+}
+
+impl core::fmt::Display for KdfError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            KdfError::InvalidParams => write!(f, "invalid parameters"),
+            KdfError::SaltTooShort => write!(f, "salt too short"),
+            KdfError::InvalidOutputLength => write!(f, "invalid output length"),
+            KdfError::VerificationFailed => write!(f, "verification failed"),
+        }
+    }
+}
+
+/// Password hashing algorithm
+pub trait PasswordHasher {
+    /// The parameters of the algorithm
+    type Params;
+
+    /// Hash the password with the salt, filling the whole output
+    fn hash(
+        params: &Self::Params,
+        password: &[u8],
+        salt: &[u8],
+        output: &mut [u8],
+    ) -> Result<(), KdfError>;
+
+    /// Verify that the password and salt hash to the expected value
+    ///
+    /// The hash is computed with the length of `expected`, and compared in constant time.
+    fn verify(
+        params: &Self::Params,
+        password: &[u8],
+        salt: &[u8],
+        expected: &[u8],
+    ) -> Result<(), KdfError> {
+        let mut output = vec![0u8; expected.len()];
+        Self::hash(params, password, salt, &mut output)?;
+        if CtEqual::ct_eq(&output[..], expected).into() {
+            Ok(())
+        } else {
+            Err(KdfError::VerificationFailed)
+        }
+    }
+}
+
+/// ARGON2 password hashing, using [`argon2::Params`]
+///
+/// The salt needs to be at least 8 bytes and the output at least 4 bytes.
+/// The tag length of the parameters is ignored in favor of the output length.
+#[cfg(feature = "argon2")]
+#[derive(Debug, Clone, Copy)]
+pub struct Argon2;
+
+#[cfg(feature = "argon2")]
+impl PasswordHasher for Argon2 {
+    type Params = argon2::Params;
+
+    fn hash(
+        params: &Self::Params,
+        password: &[u8],
+        salt: &[u8],
+        output: &mut [u8],
+    ) -> Result<(), KdfError> {
+        if salt.len() < argon2::MIN_SALT_LENGTH {
+            return Err(KdfError::SaltTooShort);
+        }
+        if output.len() < 4 {
+            return Err(KdfError::InvalidOutputLength);
+        }
+        argon2::argon2_at(params, password, salt, b"", b"", output);
+        Ok(())
+    }
+}
+
+/// SCRYPT password hashing, using [`ScryptParams`](crate::scrypt::ScryptParams)
+#[cfg(feature = "scrypt")]
+#[derive(Debug, Clone, Copy)]
+pub struct Scrypt;
+
+#[cfg(feature = "scrypt")]
+impl PasswordHasher for Scrypt {
+    type Params = crate::scrypt::ScryptParams;
+
+    fn hash(
+        params: &Self::Params,
+        password: &[u8],
+        salt: &[u8],
+        output: &mut [u8],
+    ) -> Result<(), KdfError> {
+        use crate::scrypt::{scrypt_memory_requirement, scrypt_no_alloc, ScryptError};
+
+        let (v_len, xy_len) = scrypt_memory_requirement(params);
+        let mut v_block = vec![0u8; v_len];
+        let mut xy_block = vec![0u8; xy_len];
+        scrypt_no_alloc(params, password, salt, output, &mut v_block, &mut xy_block).map_err(|e| {
+            match e {
+                ScryptError::InvalidOutputLength => KdfError::InvalidOutputLength,
+                _ => KdfError::InvalidParams,
+            }
+        })
+    }
+}
+
+/// Parameters of [`Pbkdf2Hmac`]: the digest used by HMAC and the number of iterations
+#[cfg(all(feature = "pbkdf2", feature = "hmac"))]
+#[derive(Debug, Clone)]
+pub struct Pbkdf2Params<D> {
+    /// The digest algorithm used as the HMAC hash function
+    pub digest: D,
+    /// The number of iterations, need to be at least 1
+    pub iterations: u32,
+}
+
+#[cfg(all(feature = "pbkdf2", feature = "hmac"))]
+impl<D> Pbkdf2Params<D> {
+    /// Create new PBKDF2 parameters
+    pub fn new(digest: D, iterations: u32) -> Self {
+        Self { digest, iterations }
+    }
+}
+
+/// PBKDF2 password hashing using HMAC with the digest `D`, using [`Pbkdf2Params`]
+#[cfg(all(feature = "pbkdf2", feature = "hmac"))]
+#[derive(Debug, Clone, Copy)]
+pub struct Pbkdf2Hmac<D>(core::marker::PhantomData<D>);
+
+#[cfg(all(feature = "pbkdf2", feature = "hmac"))]
+impl<D: crate::digest::Digest + Clone> PasswordHasher for Pbkdf2Hmac<D> {
+    type Params = Pbkdf2Params<D>;
+
+    fn hash(
+        params: &Self::Params,
+        password: &[u8],
+        salt: &[u8],
+        output: &mut [u8],
+    ) -> Result<(), KdfError> {
+        if params.iterations == 0 {
+            return Err(KdfError::InvalidParams);
+        }
+        if output.is_empty() {
+            return Err(KdfError::InvalidOutputLength);
+        }
+        let mut mac = crate::hmac::Hmac::new(params.digest.clone(), password);
+        crate::pbkdf2::pbkdf2(&mut mac, salt, params.iterations, output);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "argon2")]
+    #[test]
+    fn argon2() {
+        let params = argon2::Builder::new().memory_kb(64).build().unwrap();
+        let mut output = [0u8; 32];
+        Argon2::hash(&params, b"password", b"somesalt", &mut output).unwrap();
+        let expected = argon2::hash(&params, b"password", b"somesalt", b"", b"").unwrap();
+        assert_eq!(&output[..], &expected[..]);
+
+        assert_eq!(
+            Argon2::verify(&params, b"password", b"somesalt", &expected),
+            Ok(())
+        );
+        assert_eq!(
+            Argon2::verify(&params, b"passw0rd", b"somesalt", &expected),
+            Err(KdfError::VerificationFailed)
+        );
+        assert_eq!(
+            Argon2::hash(&params, b"password", b"short", &mut output),
+            Err(KdfError::SaltTooShort)
+        );
+        assert_eq!(
+            Argon2::hash(&params, b"password", b"somesalt", &mut output[..3]),
+            Err(KdfError::InvalidOutputLength)
+        );
+    }
+
+    #[cfg(feature = "scrypt")]
+    #[test]
+    fn scrypt() {
+        use crate::scrypt::ScryptParams;
+
+        // RFC7914 test vector 1, truncated
+        let params = ScryptParams::builder().log_n(4).r(1).p(1).build().unwrap();
+        let expected = [
+            0x77, 0xd6, 0x57, 0x62, 0x38, 0x65, 0x7b, 0x20, 0x3b, 0x19, 0xca, 0x42, 0xc1, 0x8a,
+            0x04, 0x97,
+        ];
+        let mut output = [0u8; 16];
+        Scrypt::hash(&params, b"", b"", &mut output).unwrap();
+        assert_eq!(output, expected);
+
+        assert_eq!(Scrypt::verify(&params, b"", b"", &expected), Ok(()));
+        assert_eq!(
+            Scrypt::verify(&params, b"password", b"", &expected),
+            Err(KdfError::VerificationFailed)
+        );
+        assert_eq!(
+            Scrypt::hash(&params, b"", b"", &mut []),
+            Err(KdfError::InvalidOutputLength)
+        );
+    }
+
+    #[cfg(all(feature = "pbkdf2", feature = "hmac", feature = "sha2"))]
+    #[test]
+    fn pbkdf2_hmac() {
+        use crate::sha2::Sha256;
+
+        // RFC7914 section 11, PBKDF2-HMAC-SHA256 with 1 iteration truncated to 16 bytes
+        let params = Pbkdf2Params::new(Sha256::new(), 1);
+        let expected = [
+            0x55, 0xac, 0x04, 0x6e, 0x56, 0xe3, 0x08, 0x9f, 0xec, 0x16, 0x91, 0xc2, 0x25, 0x44,
+            0xb6, 0x05,
+        ];
+        assert_eq!(
+            Pbkdf2Hmac::verify(&params, b"passwd", b"salt", &expected),
+            Ok(())
+        );
+        let mut tampered = expected;
+        tampered[15] ^= 1;
+        assert_eq!(
+            Pbkdf2Hmac::verify(&params, b"passwd", b"salt", &tampered),
+            Err(KdfError::VerificationFailed)
+        );
+        let params = Pbkdf2Params::new(Sha256::new(), 0);
+        assert_eq!(
+            Pbkdf2Hmac::hash(&params, b"passwd", b"salt", &mut [0u8; 16]),
+            Err(KdfError::InvalidParams)
+        );
+    }
+}
@@ -18,7 +18,7 @@
 //! * Maintained
 //! * Extended ED25519 support for extended secret key (64 bytes) support
 //! * Proper implementation of ChaChaPoly1305
-//! * Many cryptographic algorithms removed: AES, Blowfish, Fortuna, RC4, RIPEMD160, Whirlpool, MD5, SHA1.
+//! * Many cryptographic algorithms removed: Blowfish, Fortuna, RC4, RIPEMD160, Whirlpool, MD5, SHA1.
 //!
 //! As with everything cryptographic implementations, please make sure it suits your security requirements,
 //! and review and audit before using.
@@ -43,10 +43,12 @@ extern crate test;
 
 extern crate alloc;
 
-#[cfg(test)]
+#[cfg(any(test, feature = "std"))]
 #[macro_use]
 extern crate std;
 
+pub mod aead;
+
 #[cfg(feature = "blake2")]
 pub mod blake2b;
 
@@ -62,6 +64,11 @@ pub mod chacha20;
 #[cfg(all(feature = "chacha", feature = "poly1305"))]
 pub mod chacha20poly1305;
 
+pub mod cipher;
+
+#[cfg(feature = "commitment")]
+pub mod commitment;
+
 #[cfg(feature = "curve25519")]
 pub mod curve25519;
 
@@ -79,23 +86,47 @@ pub mod drg;
 pub mod ed25519;
 #[cfg(feature = "hkdf")]
 pub mod hkdf;
+#[cfg(feature = "hpke")]
+pub mod hpke;
+#[cfg(feature = "jws")]
+pub mod jws;
 
 pub mod kdf;
 
+#[cfg(feature = "noise")]
+pub mod noise;
+
+#[cfg(feature = "oprf")]
+pub mod oprf;
+
+#[cfg(feature = "paseto")]
+pub mod paseto;
+
+pub mod pake;
+
 #[cfg(feature = "hmac")]
 pub mod hmac;
 #[cfg(feature = "mac")]
 pub mod mac;
+#[cfg(feature = "multisig")]
+pub mod multisig;
 #[cfg(feature = "pbkdf2")]
 pub mod pbkdf2;
 #[cfg(feature = "poly1305")]
 pub mod poly1305;
 #[cfg(feature = "scrypt")]
 pub mod scrypt;
+#[cfg(feature = "siphash")]
+pub mod siphash;
+#[cfg(feature = "sss")]
+pub mod sss;
 
 #[cfg(feature = "salsa")]
 pub mod salsa20;
 
+#[cfg(feature = "schnorr")]
+pub mod schnorr;
+
 #[cfg(feature = "sha1")]
 pub mod sha1;
 
@@ -105,6 +136,9 @@ pub mod sha2;
 #[cfg(feature = "sha3")]
 pub mod sha3;
 
+#[cfg(feature = "vrf")]
+pub mod vrf;
+
 #[cfg(feature = "ripemd160")]
 pub mod ripemd160;
 
@@ -112,3 +146,5 @@ mod cryptoutil;
 mod simd;
 
 pub mod constant_time;
+pub mod encoding;
+pub mod secure_zero;
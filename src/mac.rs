@@ -9,6 +9,7 @@
  */
 
 use crate::constant_time::CtEqual;
+use alloc::vec;
 use alloc::vec::Vec;
 
 /**
@@ -45,6 +46,19 @@ pub trait Mac {
      * Get the size of the Mac code, in bytes.
      */
     fn output_bytes(&self) -> usize;
+
+    /**
+     * Verify that the Mac code is equal to the expected code. The comparison is done in
+     * constant time. Return false if the expected code doesn't have the size of the Mac code.
+     */
+    fn verify(&mut self, expected: &[u8]) -> bool {
+        if expected.len() != self.output_bytes() {
+            return false;
+        }
+        let mut code = vec![0u8; self.output_bytes()];
+        self.raw_result(&mut code);
+        CtEqual::ct_eq(&code[..], expected).into()
+    }
 }
 
 /**
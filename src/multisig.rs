@@ -0,0 +1,287 @@
+//! Ed25519 2-of-2 multi-signature
+//!
+//! A simple Schnorr based protocol where two parties, each holding a secret scalar `a_i`,
+//! jointly produce a standard Ed25519 signature, verifiable with [`ed25519::verify`]
+//! against the aggregated public key `A = A_1 + A_2`:
+//!
+//! 1. each party generates a fresh nonce `r_i` and sends the commitment `SHA512(R_i)` to its
+//!    public nonce `R_i = r_i*B` with [`round1`],
+//! 2. once it has received the commitment of the other party, each party reveals its public
+//!    nonce `R_i` with [`round2`],
+//! 3. each party checks the public nonce of the other party against its commitment, computes
+//!    `R = R_1 + R_2`, `k = SHA512(R || A || message)` and its partial signature
+//!    `s_i = r_i + k*a_i` with [`round3`],
+//! 4. the partial signatures are combined into the signature `R || s_1 + s_2` with [`combine`].
+//!
+//! The commitment round is what makes it safe to run several signing sessions concurrently:
+//! without it, a party choosing its public nonce after seeing the other party's ones can
+//! forge signatures across concurrent sessions (Wagner's algorithm, the ROS attack).
+//! A public nonce must never be revealed before the commitment of the other party is received.
+//!
+//! The nonce needs to be fresh for each message and each signing session: reusing a nonce
+//! reveals the secret scalar. As there's no source of randomness in this crate, the caller
+//! provides 32 random bytes to [`round1`], and the nonce is consumed by the following rounds.
+//!
+//! The aggregation of public keys by addition is subject to rogue key attacks: each party
+//! needs to check that the other party knows the secret key of its public key (for example
+//! with a signature of its public key) before aggregating the keys.
+//!
+//! The partial signatures are not verified by [`combine`], the resulting signature
+//! should be verified with [`ed25519::verify`].
+//!
+//! # Examples
+//!
+//! ```
+//! use cryptoxide::curve25519::Scalar;
+//! use cryptoxide::{ed25519, multisig};
+//!
+//! // secret keys and random bytes only for example !
+//! let secret1 = Scalar::from_uniform_bytes(&[1; 64]);
+//! let secret2 = Scalar::from_uniform_bytes(&[2; 64]);
+//! let public = multisig::aggregate_public_key(
+//!     &multisig::public_key(&secret1),
+//!     &multisig::public_key(&secret2),
+//! )
+//! .unwrap();
+//!
+//! let (commitment1, nonce1) = multisig::round1(&secret1, &[3; 32]);
+//! let (commitment2, nonce2) = multisig::round1(&secret2, &[4; 32]);
+//!
+//! let (public_nonce1, nonce1) = multisig::round2(nonce1, &commitment2);
+//! let (public_nonce2, nonce2) = multisig::round2(nonce2, &commitment1);
+//!
+//! let (s1, r1) = multisig::round3(&secret1, nonce1, &public_nonce2, &public, b"message").unwrap();
+//! let (s2, r2) = multisig::round3(&secret2, nonce2, &public_nonce1, &public, b"message").unwrap();
+//!
+//! let signature = multisig::combine(&s1, &s2, &r1, &r2);
+//! assert!(ed25519::verify(b"message", &public, &signature));
+//! ```
+//!
+//! [`ed25519::verify`]: crate::ed25519::verify
+
+use crate::curve25519::{ge_add, ge_decompress, Ge, Scalar};
+use crate::hashing::sha2::Sha512;
+use crate::secure_zero::Zeroize;
+
+/// Multi-signature errors
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MultisigError {
+    /// The public key is not a valid point
+    InvalidPublicKey,
+    /// The public nonce of the other party is not a valid point
+    InvalidPublicNonce,
+    /// The public nonce of the other party doesn't match its commitment
+    CommitmentMismatch,
+}
+
+impl core::fmt::Display for MultisigError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            MultisigError::InvalidPublicKey => write!(f, "invalid public key"),
+            MultisigError::InvalidPublicNonce => write!(f, "invalid public nonce"),
+            MultisigError::CommitmentMismatch => write!(f, "public nonce doesn't match commitment"),
+        }
+    }
+}
+
+/// Secret nonce of a signing session, which can only be used once
+pub struct Nonce {
+    r: Scalar,
+    public_nonce: Ge,
+}
+
+/// Secret nonce of a signing session after its public nonce has been revealed,
+/// along with the commitment of the other party
+pub struct RevealedNonce {
+    r: Scalar,
+    public_nonce: Ge,
+    peer_commitment: [u8; 64],
+}
+
+// The commitment to a public nonce
+fn nonce_commitment(public_nonce: &[u8; 32]) -> [u8; 64] {
+    Sha512::new().update(public_nonce).finalize()
+}
+
+/// The public key `a*B` of the secret scalar
+pub fn public_key(secret: &Scalar) -> [u8; 32] {
+    Ge::scalarmult_base(secret).to_bytes()
+}
+
+/// Aggregate the public keys of the two parties into the public key verifying the signatures
+pub fn aggregate_public_key(a: &[u8; 32], b: &[u8; 32]) -> Result<[u8; 32], MultisigError> {
+    let a = ge_decompress(a).ok_or(MultisigError::InvalidPublicKey)?;
+    let b = ge_decompress(b).ok_or(MultisigError::InvalidPublicKey)?;
+    Ok(ge_add(&a, &b).to_bytes())
+}
+
+/// Generate a fresh nonce from 32 random bytes, and return the commitment to the public
+/// nonce to send to the other party along with the secret nonce
+///
+/// The nonce is derived from the random bytes and the secret, so that a bad random
+/// source doesn't directly reveal the nonce, but the random bytes still need to be
+/// different for each signing session.
+pub fn round1(my_secret: &Scalar, random: &[u8; 32]) -> ([u8; 64], Nonce) {
+    let mut secret_bytes = my_secret.to_bytes();
+    let mut hash = Sha512::new()
+        .update(random)
+        .update(&secret_bytes)
+        .finalize();
+    let r = Scalar::from_uniform_bytes(&hash);
+    secret_bytes.zeroize();
+    hash.zeroize();
+
+    let public_nonce = Ge::scalarmult_base(&r);
+    let commitment = nonce_commitment(&public_nonce.to_bytes());
+    (commitment, Nonce { r, public_nonce })
+}
+
+/// Record the commitment of the other party, and return the public nonce to send to
+/// the other party along with the secret nonce
+///
+/// The public nonce should only be sent once the commitment of the other party has been
+/// received, which is enforced by taking it as a parameter.
+pub fn round2(my_nonce: Nonce, peer_commitment: &[u8; 64]) -> ([u8; 32], RevealedNonce) {
+    let Nonce { r, public_nonce } = my_nonce;
+    let revealed = RevealedNonce {
+        r,
+        public_nonce,
+        peer_commitment: *peer_commitment,
+    };
+    (revealed.public_nonce.to_bytes(), revealed)
+}
+
+/// Check the public nonce of the other party against its commitment, then compute the
+/// partial signature of the message with the aggregated public key, and return it along
+/// with our public nonce
+pub fn round3(
+    my_secret: &Scalar,
+    my_nonce: RevealedNonce,
+    peer_public_nonce: &[u8; 32],
+    aggregate_public: &[u8; 32],
+    message: &[u8],
+) -> Result<(Scalar, Ge), MultisigError> {
+    if nonce_commitment(peer_public_nonce) != my_nonce.peer_commitment {
+        return Err(MultisigError::CommitmentMismatch);
+    }
+    let peer = ge_decompress(peer_public_nonce).ok_or(MultisigError::InvalidPublicNonce)?;
+    let r = ge_add(&my_nonce.public_nonce, &peer).to_bytes();
+
+    let hash = Sha512::new()
+        .update(&r)
+        .update(aggregate_public)
+        .update(message)
+        .finalize();
+    let k = Scalar::from_uniform_bytes(&hash);
+
+    let s = &my_nonce.r + &(&k * my_secret);
+    Ok((s, my_nonce.public_nonce))
+}
+
+/// Combine the partial signatures and the public nonces of both parties into
+/// an Ed25519 signature `R || S`
+pub fn combine(s1: &Scalar, s2: &Scalar, r1: &Ge, r2: &Ge) -> [u8; 64] {
+    let mut signature = [0u8; 64];
+    signature[0..32].copy_from_slice(&ge_add(r1, r2).to_bytes());
+    signature[32..64].copy_from_slice(&(s1 + s2).to_bytes());
+    signature
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ed25519;
+
+    fn sign(
+        secret1: &Scalar,
+        secret2: &Scalar,
+        random1: &[u8; 32],
+        random2: &[u8; 32],
+        public: &[u8; 32],
+        message: &[u8],
+    ) -> [u8; 64] {
+        let (commitment1, nonce1) = round1(secret1, random1);
+        let (commitment2, nonce2) = round1(secret2, random2);
+        let (public_nonce1, nonce1) = round2(nonce1, &commitment2);
+        let (public_nonce2, nonce2) = round2(nonce2, &commitment1);
+        let (s1, r1) = round3(secret1, nonce1, &public_nonce2, public, message).unwrap();
+        let (s2, r2) = round3(secret2, nonce2, &public_nonce1, public, message).unwrap();
+        combine(&s1, &s2, &r1, &r2)
+    }
+
+    #[test]
+    fn round_trip() {
+        let secret1 = Scalar::from_uniform_bytes(&[1; 64]);
+        let secret2 = Scalar::from_uniform_bytes(&[2; 64]);
+        let public1 = public_key(&secret1);
+        let public2 = public_key(&secret2);
+        let public = aggregate_public_key(&public1, &public2).unwrap();
+        assert_eq!(public, aggregate_public_key(&public2, &public1).unwrap());
+        assert_eq!(public, public_key(&(&secret1 + &secret2)));
+
+        for (i, message) in [&b""[..], b"message", &[0xab; 300]].iter().enumerate() {
+            let random1 = [i as u8; 32];
+            let random2 = [0x80 + i as u8; 32];
+            let signature = sign(&secret1, &secret2, &random1, &random2, &public, message);
+            assert!(ed25519::verify(message, &public, &signature), "{}", i);
+            assert!(!ed25519::verify(b"other", &public, &signature), "{}", i);
+            assert!(!ed25519::verify(message, &public1, &signature), "{}", i);
+            assert!(!ed25519::verify(message, &public2, &signature), "{}", i);
+        }
+    }
+
+    #[test]
+    fn different_messages() {
+        // each party signs a different message, the signature is invalid for both messages
+        let secret1 = Scalar::from_uniform_bytes(&[1; 64]);
+        let secret2 = Scalar::from_uniform_bytes(&[2; 64]);
+        let public = aggregate_public_key(&public_key(&secret1), &public_key(&secret2)).unwrap();
+
+        let (commitment1, nonce1) = round1(&secret1, &[3; 32]);
+        let (commitment2, nonce2) = round1(&secret2, &[4; 32]);
+        let (public_nonce1, nonce1) = round2(nonce1, &commitment2);
+        let (public_nonce2, nonce2) = round2(nonce2, &commitment1);
+        let (s1, r1) = round3(&secret1, nonce1, &public_nonce2, &public, b"message1").unwrap();
+        let (s2, r2) = round3(&secret2, nonce2, &public_nonce1, &public, b"message2").unwrap();
+        let signature = combine(&s1, &s2, &r1, &r2);
+        assert!(!ed25519::verify(b"message1", &public, &signature));
+        assert!(!ed25519::verify(b"message2", &public, &signature));
+    }
+
+    #[test]
+    fn invalid_points() {
+        // y = 2 is not on the curve
+        let mut invalid = [0u8; 32];
+        invalid[0] = 2;
+        let secret = Scalar::from_uniform_bytes(&[1; 64]);
+        let public = public_key(&secret);
+        assert_eq!(
+            aggregate_public_key(&public, &invalid),
+            Err(MultisigError::InvalidPublicKey)
+        );
+        let (_, nonce) = round1(&secret, &[3; 32]);
+        let (_, nonce) = round2(nonce, &nonce_commitment(&invalid));
+        assert!(matches!(
+            round3(&secret, nonce, &invalid, &public, b"message"),
+            Err(MultisigError::InvalidPublicNonce)
+        ));
+    }
+
+    #[test]
+    fn commitment_mismatch() {
+        // the other party reveals a different public nonce than the one it committed to
+        let secret1 = Scalar::from_uniform_bytes(&[1; 64]);
+        let secret2 = Scalar::from_uniform_bytes(&[2; 64]);
+        let public = aggregate_public_key(&public_key(&secret1), &public_key(&secret2)).unwrap();
+
+        let (_, nonce1) = round1(&secret1, &[3; 32]);
+        let (commitment2, _) = round1(&secret2, &[4; 32]);
+        let (_, other_nonce2) = round1(&secret2, &[5; 32]);
+        let (_, nonce1) = round2(nonce1, &commitment2);
+        let (other_public_nonce2, _) = round2(other_nonce2, &[0; 64]);
+        assert!(matches!(
+            round3(&secret1, nonce1, &other_public_nonce2, &public, b"message"),
+            Err(MultisigError::CommitmentMismatch)
+        ));
+    }
+}
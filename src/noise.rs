@@ -0,0 +1,734 @@
+//! Noise Protocol Framework - XX handshake
+//!
+//! Implementation of the `Noise_XX_25519_ChaChaPoly_SHA256` protocol of the
+//! [Noise Protocol Framework][1], made of the building blocks of the specification
+//! (`CipherState`, and `HandshakeState` which includes the symmetric state) and the
+//! XX handshake pattern:
+//!
+//! ```text
+//! XX:
+//!   -> e
+//!   <- e, ee, s, es
+//!   -> s, se
+//! ```
+//!
+//! Both parties learn the static public key of the other party during the handshake,
+//! which needs to be checked by the application with [`HandshakeState::remote_static`].
+//!
+//! [`Initiator`] and [`Responder`] are the state machines of each party, writing and reading
+//! the messages into caller provided buffers, and turning into the transport cipher states
+//! at the end of the handshake.
+//!
+//! As there's no source of randomness in this crate, the ephemeral secret key needs to be
+//! generated by the caller, and should never be reused for another handshake.
+//!
+//! # Examples
+//!
+//! ```
+//! use cryptoxide::noise::HandshakeState;
+//! use cryptoxide::x25519::SecretKey;
+//!
+//! // the keys should come from a high quality random source
+//! let mut initiator = HandshakeState::new_xx(true, b"prologue", SecretKey::from([1; 32]), SecretKey::from([2; 32]));
+//! let mut responder = HandshakeState::new_xx(false, b"prologue", SecretKey::from([3; 32]), SecretKey::from([4; 32]));
+//!
+//! let message = initiator.write_message(b"").unwrap();
+//! responder.read_message(&message).unwrap();
+//! let message = responder.write_message(b"").unwrap();
+//! initiator.read_message(&message).unwrap();
+//! let message = initiator.write_message(b"").unwrap();
+//! responder.read_message(&message).unwrap();
+//!
+//! let (mut send, _) = initiator.split().unwrap();
+//! let (_, mut receive) = responder.split().unwrap();
+//! let ciphertext = send.encrypt_with_ad(b"", b"hello").unwrap();
+//! assert_eq!(receive.decrypt_with_ad(b"", &ciphertext).unwrap(), b"hello");
+//! ```
+//!
+//! [1]: <https://noiseprotocol.org/noise.html>
+
+use alloc::vec::Vec;
+
+use crate::chacha20poly1305::ChaCha20Poly1305;
+use crate::hashing::sha2::Sha256;
+use crate::hkdf::Hkdf;
+use crate::secure_zero::Zeroize;
+use crate::sha2;
+use crate::x25519::{PublicKey, SecretKey};
+
+/// Name of the protocol, mixed in the initial handshake state
+pub const PROTOCOL_NAME: &[u8] = b"Noise_XX_25519_ChaChaPoly_SHA256";
+
+/// Size of the public keys
+pub const DH_LENGTH: usize = 32;
+
+/// Size of the AEAD tag appended to the ciphertexts
+pub const TAG_LENGTH: usize = 16;
+
+/// Maximum size of a Noise message
+pub const MAX_MESSAGE_LENGTH: usize = 65535;
+
+/// Noise errors
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NoiseError {
+    /// The ciphertext cannot be authenticated
+    DecryptError,
+    /// The message is too short to contain the expected public keys and tags
+    InvalidMessage,
+    /// The message is longer than the Noise maximum message length
+    MessageTooLong,
+    /// The Diffie-Hellman output is all zeros, the public key is a low order point
+    LowOrderPoint,
+    /// The nonce of the cipher state is exhausted
+    NonceExhausted,
+    /// The operation is not expected at this step of the handshake
+    InvalidState,
+}
+
+impl core::fmt::Display for NoiseError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            NoiseError::DecryptError => write!(f, "decryption failed"),
+            NoiseError::InvalidMessage => write!(f, "invalid message"),
+            NoiseError::MessageTooLong => write!(f, "message too long"),
+            NoiseError::LowOrderPoint => write!(f, "low order point"),
+            NoiseError::NonceExhausted => write!(f, "nonce exhausted"),
+            NoiseError::InvalidState => write!(f, "invalid handshake state"),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Token {
+    E,
+    S,
+    EE,
+    ES,
+    SE,
+}
+
+// the XX pattern, the initiator sending the even messages and the responder the odd ones
+const XX: [&[Token]; 3] = [
+    &[Token::E],
+    &[Token::E, Token::EE, Token::S, Token::ES],
+    &[Token::S, Token::SE],
+];
+
+// HKDF(chaining_key, input_key_material, 2)
+fn hkdf2(chaining_key: &[u8; 32], input_key_material: &[u8]) -> ([u8; 32], [u8; 32]) {
+    let mut okm = [0u8; 64];
+    Hkdf::new(sha2::Sha256::new(), Some(chaining_key), input_key_material)
+        .expand(b"", &mut okm)
+        .unwrap();
+    let mut output1 = [0u8; 32];
+    let mut output2 = [0u8; 32];
+    output1.copy_from_slice(&okm[0..32]);
+    output2.copy_from_slice(&okm[32..64]);
+    okm.zeroize();
+    (output1, output2)
+}
+
+/// Cipher state: an optional key and a nonce incremented for each message
+pub struct CipherState {
+    k: Option<[u8; 32]>,
+    n: u64,
+}
+
+impl Drop for CipherState {
+    fn drop(&mut self) {
+        if let Some(k) = self.k.as_mut() {
+            k.zeroize();
+        }
+    }
+}
+
+impl CipherState {
+    fn new(k: Option<[u8; 32]>) -> Self {
+        CipherState { k, n: 0 }
+    }
+
+    // 32 bits of zeros followed by the little endian encoding of n
+    fn nonce(&self) -> Result<[u8; 12], NoiseError> {
+        // the nonce 2^64-1 is reserved
+        if self.n == u64::MAX {
+            return Err(NoiseError::NonceExhausted);
+        }
+        let mut nonce = [0u8; 12];
+        nonce[4..12].copy_from_slice(&self.n.to_le_bytes());
+        Ok(nonce)
+    }
+
+    /// Encrypt the plaintext with the associated data, returning the ciphertext followed
+    /// by the tag, or the plaintext if the cipher state has no key
+    pub fn encrypt_with_ad(&mut self, ad: &[u8], plaintext: &[u8]) -> Result<Vec<u8>, NoiseError> {
+        let key = match &self.k {
+            None => return Ok(plaintext.to_vec()),
+            Some(key) => key,
+        };
+        let mut ciphertext = alloc::vec![0u8; plaintext.len() + TAG_LENGTH];
+        let (out, tag) = ciphertext.split_at_mut(plaintext.len());
+        ChaCha20Poly1305::new(key, &self.nonce()?, ad).encrypt(plaintext, out, tag);
+        self.n += 1;
+        Ok(ciphertext)
+    }
+
+    /// Decrypt the ciphertext (followed by the tag) with the associated data, returning the
+    /// plaintext, or the ciphertext if the cipher state has no key
+    pub fn decrypt_with_ad(&mut self, ad: &[u8], ciphertext: &[u8]) -> Result<Vec<u8>, NoiseError> {
+        let key = match &self.k {
+            None => return Ok(ciphertext.to_vec()),
+            Some(key) => key,
+        };
+        if ciphertext.len() < TAG_LENGTH {
+            return Err(NoiseError::DecryptError);
+        }
+        let (input, tag) = ciphertext.split_at(ciphertext.len() - TAG_LENGTH);
+        let mut plaintext = alloc::vec![0u8; input.len()];
+        if !ChaCha20Poly1305::new(key, &self.nonce()?, ad).decrypt(input, &mut plaintext, tag) {
+            plaintext.zeroize();
+            return Err(NoiseError::DecryptError);
+        }
+        self.n += 1;
+        Ok(plaintext)
+    }
+}
+
+/// Handshake state of one party of the XX pattern, including the symmetric state
+///
+/// After an error, the handshake state shouldn't be used anymore and the
+/// handshake needs to be aborted.
+pub struct HandshakeState {
+    ck: [u8; 32],
+    h: [u8; 32],
+    cipher: CipherState,
+    s: SecretKey,
+    e: SecretKey,
+    rs: Option<PublicKey>,
+    re: Option<PublicKey>,
+    initiator: bool,
+    message: usize,
+}
+
+impl Drop for HandshakeState {
+    fn drop(&mut self) {
+        self.ck.zeroize();
+    }
+}
+
+impl HandshakeState {
+    /// Initialize the handshake state of the initiator or the responder, with the
+    /// prologue, the static key pair and the ephemeral key pair of this party
+    pub fn new_xx(
+        initiator: bool,
+        prologue: &[u8],
+        static_secret: SecretKey,
+        ephemeral_secret: SecretKey,
+    ) -> Self {
+        // the protocol name is exactly 32 bytes, so it's used as is
+        let mut h = [0u8; 32];
+        h.copy_from_slice(PROTOCOL_NAME);
+        let mut state = HandshakeState {
+            ck: h,
+            h,
+            cipher: CipherState::new(None),
+            s: static_secret,
+            e: ephemeral_secret,
+            rs: None,
+            re: None,
+            initiator,
+            message: 0,
+        };
+        state.mix_hash(prologue);
+        state
+    }
+
+    /// Mix the data into the handshake hash: `h = HASH(h || data)`
+    pub fn mix_hash(&mut self, data: &[u8]) {
+        self.h = Sha256::new().update(&self.h).update(data).finalize();
+    }
+
+    /// Mix the input key material into the chaining key, and set a new cipher key
+    pub fn mix_key(&mut self, input_key_material: &[u8]) {
+        let (ck, k) = hkdf2(&self.ck, input_key_material);
+        self.ck = ck;
+        self.cipher = CipherState::new(Some(k));
+    }
+
+    /// Encrypt the plaintext with the handshake hash as associated data, and mix the
+    /// ciphertext into the handshake hash
+    ///
+    /// The plaintext is sent as is before the first call to `mix_key`
+    pub fn encrypt_and_hash(&mut self, plaintext: &[u8]) -> Vec<u8> {
+        let ciphertext = self
+            .cipher
+            .encrypt_with_ad(&self.h, plaintext)
+            .expect("handshake nonce never exhausted");
+        self.mix_hash(&ciphertext);
+        ciphertext
+    }
+
+    /// Decrypt the ciphertext with the handshake hash as associated data, and mix the
+    /// ciphertext into the handshake hash
+    pub fn decrypt_and_hash(&mut self, ciphertext: &[u8]) -> Result<Vec<u8>, NoiseError> {
+        let plaintext = self.cipher.decrypt_with_ad(&self.h, ciphertext)?;
+        self.mix_hash(ciphertext);
+        Ok(plaintext)
+    }
+
+    /// Return if the 3 messages of the handshake have been processed
+    pub fn is_handshake_finished(&self) -> bool {
+        self.message == XX.len()
+    }
+
+    /// The handshake hash, which can be used as a unique identifier of the session
+    pub fn handshake_hash(&self) -> &[u8; 32] {
+        &self.h
+    }
+
+    /// The static public key of the other party, once received
+    pub fn remote_static(&self) -> Option<&PublicKey> {
+        self.rs.as_ref()
+    }
+
+    fn dh(secret: &SecretKey, public: Option<&PublicKey>) -> Result<[u8; 32], NoiseError> {
+        let public = public.ok_or(NoiseError::InvalidState)?;
+        let shared = secret
+            .diffie_hellman(public)
+            .map_err(|_| NoiseError::LowOrderPoint)?;
+        let mut out = [0u8; 32];
+        out.copy_from_slice(shared.as_ref());
+        Ok(out)
+    }
+
+    fn mix_dh(&mut self, token: Token) -> Result<(), NoiseError> {
+        let mut shared = match (token, self.initiator) {
+            (Token::EE, _) => Self::dh(&self.e, self.re.as_ref())?,
+            (Token::ES, true) | (Token::SE, false) => Self::dh(&self.e, self.rs.as_ref())?,
+            (Token::ES, false) | (Token::SE, true) => Self::dh(&self.s, self.re.as_ref())?,
+            (Token::E, _) | (Token::S, _) => unreachable!(),
+        };
+        self.mix_key(&shared);
+        shared.zeroize();
+        Ok(())
+    }
+
+    // get the pattern of the next message, checking that it's our turn to write (or read)
+    fn next_pattern(&self, write: bool) -> Result<&'static [Token], NoiseError> {
+        let pattern = XX.get(self.message).ok_or(NoiseError::InvalidState)?;
+        let initiator_turn = self.message % 2 == 0;
+        if initiator_turn != (self.initiator == write) {
+            return Err(NoiseError::InvalidState);
+        }
+        Ok(pattern)
+    }
+
+    /// Write the next handshake message with the payload
+    pub fn write_message(&mut self, payload: &[u8]) -> Result<Vec<u8>, NoiseError> {
+        let pattern = self.next_pattern(true)?;
+        let mut message = Vec::new();
+        for token in pattern {
+            match token {
+                Token::E => {
+                    let public = self.e.public_key();
+                    message.extend_from_slice(public.as_ref());
+                    self.mix_hash(public.as_ref());
+                }
+                Token::S => {
+                    let public = self.s.public_key();
+                    let ciphertext = self.encrypt_and_hash(public.as_ref());
+                    message.extend_from_slice(&ciphertext);
+                }
+                Token::EE | Token::ES | Token::SE => self.mix_dh(*token)?,
+            }
+        }
+        let ciphertext = self.encrypt_and_hash(payload);
+        message.extend_from_slice(&ciphertext);
+        if message.len() > MAX_MESSAGE_LENGTH {
+            return Err(NoiseError::MessageTooLong);
+        }
+        self.message += 1;
+        Ok(message)
+    }
+
+    /// Read the next handshake message, and return its payload
+    pub fn read_message(&mut self, message: &[u8]) -> Result<Vec<u8>, NoiseError> {
+        if message.len() > MAX_MESSAGE_LENGTH {
+            return Err(NoiseError::MessageTooLong);
+        }
+        let pattern = self.next_pattern(false)?;
+        let mut message = message;
+        for token in pattern {
+            match token {
+                Token::E => {
+                    if message.len() < DH_LENGTH {
+                        return Err(NoiseError::InvalidMessage);
+                    }
+                    let (public, rest) = message.split_at(DH_LENGTH);
+                    self.mix_hash(public);
+                    self.re = Some(PublicKey::try_from(public).unwrap());
+                    message = rest;
+                }
+                Token::S => {
+                    let length = DH_LENGTH
+                        + if self.cipher.k.is_some() {
+                            TAG_LENGTH
+                        } else {
+                            0
+                        };
+                    if message.len() < length {
+                        return Err(NoiseError::InvalidMessage);
+                    }
+                    let (ciphertext, rest) = message.split_at(length);
+                    let public = self.decrypt_and_hash(ciphertext)?;
+                    self.rs = Some(PublicKey::try_from(&public[..]).unwrap());
+                    message = rest;
+                }
+                Token::EE | Token::ES | Token::SE => self.mix_dh(*token)?,
+            }
+        }
+        let payload = self.decrypt_and_hash(message)?;
+        self.message += 1;
+        Ok(payload)
+    }
+
+    /// Once the handshake is finished, return the cipher states to send and to receive
+    /// transport messages, from the point of view of this party
+    pub fn split(self) -> Result<(CipherState, CipherState), NoiseError> {
+        if !self.is_handshake_finished() {
+            return Err(NoiseError::InvalidState);
+        }
+        let (k1, k2) = hkdf2(&self.ck, b"");
+        let (c1, c2) = (CipherState::new(Some(k1)), CipherState::new(Some(k2)));
+        if self.initiator {
+            Ok((c1, c2))
+        } else {
+            Ok((c2, c1))
+        }
+    }
+}
+
+macro_rules! handshake_party {
+    ($name:ident, $initiator:literal, $doc:literal) => {
+        #[doc = $doc]
+        ///
+        /// The messages of the handshake are written and read in turn following the XX
+        /// pattern, and once the 3 messages have been processed, the party is turned into
+        /// the cipher states for the transport messages with `into_transport`.
+        pub struct $name(HandshakeState);
+
+        impl $name {
+            /// Create the handshake party with the prologue, the static secret key
+            /// and the ephemeral secret key
+            pub fn new(
+                prologue: &[u8],
+                static_secret: SecretKey,
+                ephemeral_secret: SecretKey,
+            ) -> Self {
+                Self(HandshakeState::new_xx(
+                    $initiator,
+                    prologue,
+                    static_secret,
+                    ephemeral_secret,
+                ))
+            }
+
+            /// Write the next handshake message with the payload, appending it to `output`
+            ///
+            /// On error, nothing is appended to `output`
+            pub fn write_message(
+                &mut self,
+                payload: &[u8],
+                output: &mut Vec<u8>,
+            ) -> Result<(), NoiseError> {
+                let message = self.0.write_message(payload)?;
+                output.extend_from_slice(&message);
+                Ok(())
+            }
+
+            /// Read the next handshake message, appending its payload to `output`
+            ///
+            /// On error, nothing is appended to `output`
+            pub fn read_message(
+                &mut self,
+                ciphertext: &[u8],
+                output: &mut Vec<u8>,
+            ) -> Result<(), NoiseError> {
+                let payload = self.0.read_message(ciphertext)?;
+                output.extend_from_slice(&payload);
+                Ok(())
+            }
+
+            /// Return if the 3 messages of the handshake have been processed
+            pub fn is_handshake_finished(&self) -> bool {
+                self.0.is_handshake_finished()
+            }
+
+            /// The handshake hash, which can be used as a unique identifier of the session
+            pub fn handshake_hash(&self) -> &[u8; 32] {
+                self.0.handshake_hash()
+            }
+
+            /// The static public key of the other party, once received
+            pub fn remote_static(&self) -> Option<&PublicKey> {
+                self.0.remote_static()
+            }
+
+            /// Return the cipher states to send and to receive transport messages
+            ///
+            /// # Panics
+            ///
+            /// Panics if the handshake is not finished
+            pub fn into_transport(self) -> (CipherState, CipherState) {
+                self.0
+                    .split()
+                    .expect("transport before the end of the handshake")
+            }
+        }
+    };
+}
+
+handshake_party!(
+    Initiator,
+    true,
+    "Initiator of the XX handshake, writing the first and the last messages"
+);
+handshake_party!(
+    Responder,
+    false,
+    "Responder of the XX handshake, writing the second message"
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::encode_hex;
+
+    const INITIATOR_STATIC: [u8; 32] = [
+        0xe6, 0x1e, 0xf9, 0x91, 0x9c, 0xde, 0x45, 0xdd, 0x5f, 0x82, 0x16, 0x64, 0x04, 0xbd, 0x08,
+        0xe3, 0x8b, 0xce, 0xb5, 0xdf, 0xdf, 0xde, 0xd0, 0xa3, 0x4c, 0x8d, 0xf7, 0xed, 0x54, 0x22,
+        0x14, 0xd1,
+    ];
+    const INITIATOR_EPHEMERAL: [u8; 32] = [
+        0x89, 0x3e, 0x28, 0xb9, 0xdc, 0x6c, 0xa8, 0xd6, 0x11, 0xab, 0x66, 0x47, 0x54, 0xb8, 0xce,
+        0xb7, 0xba, 0xc5, 0x11, 0x73, 0x49, 0xa4, 0x43, 0x9a, 0x6b, 0x05, 0x69, 0xda, 0x97, 0x7c,
+        0x46, 0x4a,
+    ];
+    const RESPONDER_STATIC: [u8; 32] = [
+        0x4a, 0x3a, 0xcb, 0xfd, 0xb1, 0x63, 0xde, 0xc6, 0x51, 0xdf, 0xa3, 0x19, 0x4d, 0xec, 0xe6,
+        0x76, 0xd4, 0x37, 0x02, 0x9c, 0x62, 0xa4, 0x08, 0xb4, 0xc5, 0xea, 0x91, 0x14, 0x24, 0x6e,
+        0x48, 0x93,
+    ];
+    const RESPONDER_EPHEMERAL: [u8; 32] = [
+        0xbb, 0xdb, 0x4c, 0xdb, 0xd3, 0x09, 0xf1, 0xa1, 0xf2, 0xe1, 0x45, 0x69, 0x67, 0xfe, 0x28,
+        0x8c, 0xad, 0xd6, 0xf7, 0x12, 0xd6, 0x5d, 0xc7, 0xb7, 0x79, 0x3d, 0x5e, 0x63, 0xda, 0x6b,
+        0x37, 0x5b,
+    ];
+
+    fn handshake(
+        prologue_initiator: &[u8],
+        prologue_responder: &[u8],
+    ) -> (HandshakeState, HandshakeState) {
+        let initiator = HandshakeState::new_xx(
+            true,
+            prologue_initiator,
+            SecretKey::from(INITIATOR_STATIC),
+            SecretKey::from(INITIATOR_EPHEMERAL),
+        );
+        let responder = HandshakeState::new_xx(
+            false,
+            prologue_responder,
+            SecretKey::from(RESPONDER_STATIC),
+            SecretKey::from(RESPONDER_EPHEMERAL),
+        );
+        (initiator, responder)
+    }
+
+    #[test]
+    fn vectors() {
+        // keys, prologue and payloads of the cacophony test vectors, and messages
+        // generated with an independent python implementation
+        let payloads: [&[u8]; 5] = [
+            b"Ludwig von Mises",
+            b"Murray Rothbard",
+            b"F. A. Hayek",
+            b"Carl Menger",
+            b"Jean-Baptiste Say",
+        ];
+        let messages = [
+            "ca35def5ae56cec33dc2036731ab14896bc4c75dbb07a61f879f8e3afa4c79444c756477696720766f6e204d69736573",
+            "95ebc60d2b1fa672c1f46a8aa265ef51bfe38e7ccb39ec5be34069f14480884381cbad1f276e038c48378ffce2b65285e08d6b68aaa3629a5a8639392490e5b9bd5269c2f1e4f488ed8831161f19b7815528f8982ffe09be9b5c412f8a0db50f8814c7194e83f23dbd8d162c9326ad",
+            "c7195ffacac1307ff99046f219750fc47693e23c3cb08b89c2af808b444850a80ae475b9df0f169ae80a89be0865b57f58c9fea0d4ec82a286427402f113e4b6ae769a1d95941d49b25030",
+            "3744e25d623542b0576724d2c54efc70916e296af7ecd4fd05336c",
+            "9f722dd57ef7e065a07d2e406c12ad9e274c3bd41bef1f237b430aa839fc1431a4",
+        ];
+        let handshake_hash: [u8; 32] = [
+            0xc8, 0xe5, 0xf6, 0x4e, 0x84, 0x61, 0x93, 0xbe, 0x2a, 0x83, 0x41, 0x04, 0xc2, 0xa0,
+            0x09, 0x86, 0x8d, 0x6c, 0x9f, 0x3b, 0xd3, 0xc1, 0x86, 0x29, 0x98, 0x88, 0xb4, 0x88,
+            0xb2, 0xf1, 0xf5, 0x8e,
+        ];
+
+        let (mut initiator, mut responder) = handshake(b"John Galt", b"John Galt");
+        for i in 0..3 {
+            let (writer, reader) = if i % 2 == 0 {
+                (&mut initiator, &mut responder)
+            } else {
+                (&mut responder, &mut initiator)
+            };
+            let message = writer.write_message(payloads[i]).unwrap();
+            assert_eq!(encode_hex(&message), messages[i], "message {}", i);
+            assert_eq!(
+                reader.read_message(&message).unwrap(),
+                payloads[i],
+                "payload {}",
+                i
+            );
+        }
+        assert!(initiator.is_handshake_finished());
+        assert!(responder.is_handshake_finished());
+        assert_eq!(initiator.handshake_hash(), &handshake_hash);
+        assert_eq!(responder.handshake_hash(), &handshake_hash);
+        assert!(initiator.remote_static() == Some(&SecretKey::from(RESPONDER_STATIC).public_key()));
+        assert!(responder.remote_static() == Some(&SecretKey::from(INITIATOR_STATIC).public_key()));
+
+        let (mut initiator_send, mut initiator_receive) = initiator.split().unwrap();
+        let (mut responder_send, mut responder_receive) = responder.split().unwrap();
+
+        let message = initiator_send.encrypt_with_ad(b"", payloads[3]).unwrap();
+        assert_eq!(encode_hex(&message), messages[3]);
+        assert_eq!(
+            responder_receive.decrypt_with_ad(b"", &message).unwrap(),
+            payloads[3]
+        );
+
+        let message = responder_send.encrypt_with_ad(b"", payloads[4]).unwrap();
+        assert_eq!(encode_hex(&message), messages[4]);
+        assert_eq!(
+            initiator_receive.decrypt_with_ad(b"", &message).unwrap(),
+            payloads[4]
+        );
+    }
+
+    #[test]
+    fn initiator_responder() {
+        // same cacophony vectors as above, through the state machines of each party
+        let payloads: [&[u8]; 3] = [b"Ludwig von Mises", b"Murray Rothbard", b"F. A. Hayek"];
+        let mut initiator = Initiator::new(
+            b"John Galt",
+            SecretKey::from(INITIATOR_STATIC),
+            SecretKey::from(INITIATOR_EPHEMERAL),
+        );
+        let mut responder = Responder::new(
+            b"John Galt",
+            SecretKey::from(RESPONDER_STATIC),
+            SecretKey::from(RESPONDER_EPHEMERAL),
+        );
+
+        let mut message = Vec::new();
+        let mut payload = Vec::new();
+        initiator.write_message(payloads[0], &mut message).unwrap();
+        responder.read_message(&message, &mut payload).unwrap();
+        assert_eq!(payload, payloads[0]);
+
+        message.clear();
+        payload.clear();
+        responder.write_message(payloads[1], &mut message).unwrap();
+        initiator.read_message(&message, &mut payload).unwrap();
+        assert_eq!(payload, payloads[1]);
+
+        message.clear();
+        payload.clear();
+        initiator.write_message(payloads[2], &mut message).unwrap();
+        assert_eq!(
+            encode_hex(&message),
+            "c7195ffacac1307ff99046f219750fc47693e23c3cb08b89c2af808b444850a80ae475b9df0f169ae80a89be0865b57f58c9fea0d4ec82a286427402f113e4b6ae769a1d95941d49b25030"
+        );
+        // a failed read doesn't append to the output
+        assert_eq!(
+            responder.read_message(&message[..40], &mut payload),
+            Err(NoiseError::InvalidMessage)
+        );
+        assert!(payload.is_empty());
+        responder.read_message(&message, &mut payload).unwrap();
+        assert_eq!(payload, payloads[2]);
+
+        assert!(initiator.is_handshake_finished() && responder.is_handshake_finished());
+        assert_eq!(initiator.handshake_hash(), responder.handshake_hash());
+        assert!(initiator.remote_static() == Some(&SecretKey::from(RESPONDER_STATIC).public_key()));
+
+        let (mut initiator_send, _) = initiator.into_transport();
+        let (_, mut responder_receive) = responder.into_transport();
+        let message = initiator_send.encrypt_with_ad(b"", b"Carl Menger").unwrap();
+        assert_eq!(
+            encode_hex(&message),
+            "3744e25d623542b0576724d2c54efc70916e296af7ecd4fd05336c"
+        );
+        assert_eq!(
+            responder_receive.decrypt_with_ad(b"", &message).unwrap(),
+            b"Carl Menger"
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn transport_before_handshake_end() {
+        let initiator = Initiator::new(b"", SecretKey::from([1; 32]), SecretKey::from([2; 32]));
+        initiator.into_transport();
+    }
+
+    #[test]
+    fn errors() {
+        // messages out of order
+        let (mut initiator, mut responder) = handshake(b"", b"");
+        assert_eq!(
+            responder.write_message(b"").unwrap_err(),
+            NoiseError::InvalidState
+        );
+        assert_eq!(
+            initiator.read_message(&[0; 32]).unwrap_err(),
+            NoiseError::InvalidState
+        );
+        let message = initiator.write_message(b"").unwrap();
+        assert_eq!(
+            initiator.write_message(b"").unwrap_err(),
+            NoiseError::InvalidState
+        );
+        assert_eq!(
+            responder.read_message(&message[..31]).unwrap_err(),
+            NoiseError::InvalidMessage
+        );
+
+        // different prologues
+        let (mut initiator, mut responder) = handshake(b"a", b"b");
+        let message = initiator.write_message(b"").unwrap();
+        responder.read_message(&message).unwrap();
+        let message = responder.write_message(b"").unwrap();
+        assert_eq!(
+            initiator.read_message(&message).unwrap_err(),
+            NoiseError::DecryptError
+        );
+
+        // tampered message
+        let (mut initiator, mut responder) = handshake(b"", b"");
+        let message = initiator.write_message(b"").unwrap();
+        responder.read_message(&message).unwrap();
+        let mut message = responder.write_message(b"payload").unwrap();
+        let last = message.len() - 1;
+        message[last] ^= 1;
+        assert_eq!(
+            initiator.read_message(&message).unwrap_err(),
+            NoiseError::DecryptError
+        );
+
+        // low order ephemeral key
+        let (_, mut responder) = handshake(b"", b"");
+        responder.read_message(&[0; 32]).unwrap();
+        assert_eq!(
+            responder.write_message(b"").unwrap_err(),
+            NoiseError::LowOrderPoint
+        );
+
+        // split before the end of the handshake
+        let (initiator, _) = handshake(b"", b"");
+        assert!(initiator.split().is_err());
+    }
+}
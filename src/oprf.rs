@@ -0,0 +1,216 @@
+//! OPRF - Oblivious Pseudorandom Function
+//!
+//! Implementation of the base mode (OPRF) of the ristretto255-SHA512 ciphersuite of [RFC9497][1].
+//!
+//! A server holding a secret key and a client holding an input compute together
+//! the pseudorandom output of the key for the input, without the server learning
+//! anything about the input or the output, and without the client learning the key:
+//!
+//! * the client blinds its input with a random scalar, and sends the blinded element to the server
+//! * the server evaluates the blinded element with its key, and sends the result back
+//! * the client unblinds the evaluated element and finalizes it into the 64 bytes output
+//!
+//! # Examples
+//!
+//! ```
+//! use cryptoxide::{curve25519::Scalar, oprf};
+//!
+//! let server_key = oprf::derive_key(&[1u8; 32], b"key info");
+//! let r = Scalar::from_uniform_bytes(&[2u8; 64]); // should be random
+//!
+//! let blinded = oprf::blind(b"input", &r).expect("valid input");
+//! let evaluated = oprf::evaluate(&server_key, &blinded).expect("valid blinded element");
+//! let output = oprf::finalize(b"input", &oprf::unblind(&evaluated, &r));
+//! ```
+//!
+//! [1]: <https://www.rfc-editor.org/rfc/rfc9497>
+
+use crate::curve25519::{ristretto::RistrettoPoint, Scalar};
+use crate::hashing::sha2::Sha512;
+
+/// Size of the OPRF output
+pub const OUTPUT_LENGTH: usize = 64;
+
+// "OPRFV1-" || I2OSP(mode, 1) || "-" || identifier, with the mode 0x00 (OPRF)
+const CONTEXT_STRING: &[u8] = b"OPRFV1-\x00-ristretto255-SHA512";
+
+// expand_message_xmd with SHA512 (RFC9380 section 5.3.1) for an output of 64 bytes,
+// where the DST is the concatenation of the parts of dst
+fn expand_message_xmd(msg: &[&[u8]], dst: &[&[u8]]) -> [u8; 64] {
+    let dst_len = dst.iter().map(|d| d.len()).sum::<usize>();
+    assert!(dst_len <= 255);
+    let dst_len = [dst_len as u8];
+
+    let mut ctx = Sha512::new().update(&[0; 128]);
+    for m in msg {
+        ctx.update_mut(m);
+    }
+    ctx.update_mut(&[0, 64, 0]);
+    for d in dst {
+        ctx.update_mut(d);
+    }
+    let b0 = ctx.update(&dst_len).finalize();
+
+    let mut ctx = Sha512::new().update(&b0).update(&[1]);
+    for d in dst {
+        ctx.update_mut(d);
+    }
+    ctx.update(&dst_len).finalize()
+}
+
+// HashToGroup: hash_to_ristretto255 with the DST "HashToGroup-" || contextString
+fn hash_to_group(input: &[u8]) -> RistrettoPoint {
+    let uniform = expand_message_xmd(&[input], &[b"HashToGroup-", CONTEXT_STRING]);
+    RistrettoPoint::map_to_point(&uniform)
+}
+
+/// Derive the server secret key from a 32 bytes random seed and some key information
+///
+/// This is the `DeriveKeyPair` function, the public key being `RistrettoPoint::mul_base(&key)`.
+///
+/// # Panics
+///
+/// Panics if the key information is longer than 65535 bytes
+pub fn derive_key(seed: &[u8; 32], info: &[u8]) -> Scalar {
+    let info_len = u16::try_from(info.len()).expect("key info too long");
+    for counter in 0..=255u8 {
+        let uniform = expand_message_xmd(
+            &[seed, &info_len.to_be_bytes(), info, &[counter]],
+            &[b"DeriveKeyPair", CONTEXT_STRING],
+        );
+        let key = Scalar::from_uniform_bytes(&uniform);
+        if key != Scalar::ZERO {
+            return key;
+        }
+    }
+    // a zero scalar has a probability of 2^-252 for each counter
+    unreachable!()
+}
+
+/// Client: blind the input with the scalar `r`, which needs to be uniformly random and not zero
+///
+/// The blinded element is sent to the server, and `r` is kept to unblind the server response.
+///
+/// Returns `None` if the input maps to the identity element, which is the
+/// `InvalidInputError` of the specification
+pub fn blind(input: &[u8], r: &Scalar) -> Option<RistrettoPoint> {
+    let input_element = hash_to_group(input);
+    if input_element == RistrettoPoint::IDENTITY {
+        return None;
+    }
+    Some(&input_element * r)
+}
+
+/// Server: evaluate the blinded element sent by the client with the server secret key
+///
+/// Returns `None` if the blinded element is the identity element, which the
+/// specification rejects when deserializing the client message (RFC9497 section 3.3.1)
+pub fn evaluate(server_key: &Scalar, blinded: &RistrettoPoint) -> Option<RistrettoPoint> {
+    if *blinded == RistrettoPoint::IDENTITY {
+        return None;
+    }
+    Some(blinded * server_key)
+}
+
+/// Client: unblind the element evaluated by the server, using the same `r` as `blind`
+///
+/// # Panics
+///
+/// Panics if `r` is zero
+pub fn unblind(evaluated: &RistrettoPoint, r: &Scalar) -> RistrettoPoint {
+    let r_inv = r.invert().into_option().expect("blind scalar is zero");
+    evaluated * &r_inv
+}
+
+/// Client: compute the OPRF output of the input from the unblinded element
+///
+/// # Panics
+///
+/// Panics if the input is longer than 65535 bytes
+pub fn finalize(input: &[u8], output: &RistrettoPoint) -> [u8; OUTPUT_LENGTH] {
+    let input_len = u16::try_from(input.len()).expect("input too long");
+    Sha512::new()
+        .update(&input_len.to_be_bytes())
+        .update(input)
+        .update(&32u16.to_be_bytes())
+        .update(&output.encode())
+        .update(b"Finalize")
+        .finalize()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::decode_hex;
+
+    fn hex32(s: &str) -> [u8; 32] {
+        decode_hex(s.as_bytes()).unwrap().try_into().unwrap()
+    }
+
+    // RFC9497 Appendix A.1.1: ristretto255-SHA512, OPRF mode
+    #[test]
+    fn rfc9497_vectors() {
+        let seed = [0xa3; 32];
+        let key = derive_key(&seed, &decode_hex(b"74657374206b6579").unwrap());
+        assert_eq!(
+            key.to_bytes(),
+            hex32("5ebcea5ee37023ccb9fc2d2019f9d7737be85591ae8652ffa9ef0f4d37063b0e")
+        );
+
+        let r = Scalar::from_bytes_canonical(&hex32(
+            "64d37aed22a27f5191de1c1d69fadb899d8862b58eb4220029e036ec4c1f6706",
+        ))
+        .unwrap();
+
+        let vectors: [(&[u8], &str, &str, &str); 2] = [
+            (
+                b"\x00",
+                "609a0ae68c15a3cf6903766461307e5c8bb2f95e7e6550e1ffa2dc99e412803c",
+                "7ec6578ae5120958eb2db1745758ff379e77cb64fe77b0b2d8cc917ea0869c7e",
+                "527759c3d9366f277d8c6020418d96bb393ba2afb20ff90df23fb7708264e2f3\
+                 ab9135e3bd69955851de4b1f9fe8a0973396719b7912ba9ee8aa7d0b5e24bcf6",
+            ),
+            (
+                b"\x5a\x5a\x5a\x5a\x5a\x5a\x5a\x5a\x5a\x5a\x5a\x5a\x5a\x5a\x5a\x5a\x5a",
+                "da27ef466870f5f15296299850aa088629945a17d1f5b7f5ff043f76b3c06418",
+                "b4cbf5a4f1eeda5a63ce7b77c7d23f461db3fcab0dd28e4e17cecb5c90d02c25",
+                "f4a74c9c592497375e796aa837e907b1a045d34306a749db9f34221f7e750cb4\
+                 f2a6413a6bf6fa5e19ba6348eb673934a722a7ede2e7621306d18951e7cf2c73",
+            ),
+        ];
+        for (input, blinded_expected, evaluated_expected, output_expected) in vectors.iter() {
+            let blinded = blind(input, &r).unwrap();
+            assert_eq!(blinded.encode(), hex32(blinded_expected));
+            let evaluated = evaluate(&key, &blinded).unwrap();
+            assert_eq!(evaluated.encode(), hex32(evaluated_expected));
+            let output = finalize(input, &unblind(&evaluated, &r));
+            assert_eq!(
+                &output[..],
+                &decode_hex(output_expected.as_bytes()).unwrap()[..]
+            );
+        }
+    }
+
+    #[test]
+    fn blinding_independent() {
+        let key = derive_key(&[7; 32], b"");
+        let r1 = Scalar::from_uniform_bytes(&[1; 64]);
+        let r2 = Scalar::from_uniform_bytes(&[2; 64]);
+        let b1 = blind(b"input", &r1).unwrap();
+        let b2 = blind(b"input", &r2).unwrap();
+        assert_ne!(b1, b2);
+        let o1 = finalize(b"input", &unblind(&evaluate(&key, &b1).unwrap(), &r1));
+        let o2 = finalize(b"input", &unblind(&evaluate(&key, &b2).unwrap(), &r2));
+        assert_eq!(o1, o2);
+
+        let other_key = derive_key(&[8; 32], b"");
+        let o3 = finalize(b"input", &unblind(&evaluate(&other_key, &b1).unwrap(), &r1));
+        assert_ne!(o1, o3);
+    }
+
+    #[test]
+    fn identity_rejected() {
+        let key = derive_key(&[7; 32], b"");
+        assert_eq!(evaluate(&key, &RistrettoPoint::IDENTITY), None);
+    }
+}
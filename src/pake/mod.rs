@@ -0,0 +1,8 @@
+//! Password Authenticated Key Exchange (PAKE) Algorithms
+//!
+//! A PAKE allows two parties sharing a low entropy password to agree on a high
+//! entropy shared key, without exposing the password to offline dictionary attacks
+//! by an eavesdropper or an active attacker.
+
+#[cfg(feature = "spake2")]
+pub mod spake2;
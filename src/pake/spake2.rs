@@ -0,0 +1,459 @@
+//! SPAKE2 - Password Authenticated Key Exchange
+//!
+//! Implementation of [RFC9382][1] SPAKE2 with the ciphersuite
+//! SPAKE2-edwards25519-SHA256-HKDF-HMAC, where:
+//!
+//! * the group is the prime order subgroup of edwards25519, and the shared point is
+//!   multiplied by the cofactor 8
+//! * the transcript hash is SHA256, and the shared key is its first 16 bytes
+//! * the confirmation keys are derived with HKDF-SHA256, and the confirmation
+//!   messages are HMAC-SHA256 of the transcript
+//!
+//! The password scalar `w` is the SHA512 of the password reduced modulo the group order,
+//! so the password should already be the output of a memory hard function (e.g. Argon2
+//! or Scrypt) with a salt agreed by both parties, as recommended by the specification.
+//!
+//! As there's no source of randomness in this crate, each party need to provide
+//! 32 bytes of high quality randomness to create their ephemeral secret.
+//!
+//! # Examples
+//!
+//! ```
+//! use cryptoxide::pake::spake2::Spake2;
+//!
+//! let (client, client_message) = Spake2::new_client(b"password", b"client", b"server", &[1u8; 32]);
+//! let (server, server_message) = Spake2::new_server(b"password", b"client", b"server", &[2u8; 32]);
+//!
+//! let client_keys = Spake2::finish(client, &server_message).unwrap();
+//! let server_keys = Spake2::finish(server, &client_message).unwrap();
+//!
+//! // exchange the confirmation messages before using the shared key
+//! server_keys.verify_confirmation(client_keys.confirmation()).unwrap();
+//! client_keys.verify_confirmation(server_keys.confirmation()).unwrap();
+//! assert_eq!(client_keys.shared_key(), server_keys.shared_key());
+//! ```
+//!
+//! [1]: <https://www.rfc-editor.org/rfc/rfc9382>
+
+use alloc::vec::Vec;
+
+use crate::constant_time::CtEqual;
+use crate::curve25519::{ge_add, ge_decompress, ge_sub, Ge, Scalar};
+use crate::hashing::sha2::{Sha256, Sha512};
+use crate::hkdf::hkdf_expand;
+use crate::hkdf::hkdf_extract;
+use crate::hmac::Hmac;
+use crate::mac::Mac;
+use crate::secure_zero::Zeroize;
+
+/// The M point of RFC9382 for edwards25519, used by the client
+const M: [u8; 32] = [
+    0xd0, 0x48, 0x03, 0x2c, 0x6e, 0xa0, 0xb6, 0xd6, 0x97, 0xdd, 0xc2, 0xe8, 0x6b, 0xda, 0x85, 0xa3,
+    0x3a, 0xda, 0xc9, 0x20, 0xf1, 0xbf, 0x18, 0xe1, 0xb0, 0xc6, 0xd1, 0x66, 0xa5, 0xce, 0xcd, 0xaf,
+];
+
+/// The N point of RFC9382 for edwards25519, used by the server
+const N: [u8; 32] = [
+    0xd3, 0xbf, 0xb5, 0x18, 0xf4, 0x4f, 0x34, 0x30, 0xf2, 0x9d, 0x0c, 0x92, 0xaf, 0x50, 0x38, 0x65,
+    0xa1, 0xed, 0x32, 0x81, 0xdc, 0x69, 0xb3, 0x5d, 0xd8, 0x68, 0xba, 0x85, 0xf8, 0x86, 0xc4, 0xab,
+];
+
+/// Size of the message sent to the other party, a compressed point
+pub const MESSAGE_LENGTH: usize = 32;
+
+/// Size of the shared key
+pub const SHARED_KEY_LENGTH: usize = 16;
+
+/// Size of the confirmation message
+pub const CONFIRMATION_LENGTH: usize = 32;
+
+/// SPAKE2 errors
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpakeError {
+    /// The message of the other party is not a valid point
+    InvalidMessage,
+    /// The shared point is the identity
+    IdentityPoint,
+    /// The confirmation message of the other party doesn't match
+    ConfirmationFailed,
+}
+
+impl core::fmt::Display for SpakeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            SpakeError::InvalidMessage => write!(f, "invalid message"),
+            SpakeError::IdentityPoint => write!(f, "identity point"),
+            SpakeError::ConfirmationFailed => write!(f, "confirmation failed"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Role {
+    Client,
+    Server,
+}
+
+/// State of one party between sending its message and receiving the other party message
+pub struct State {
+    role: Role,
+    w: Scalar,
+    x: Scalar,
+    id_client: Vec<u8>,
+    id_server: Vec<u8>,
+    message: [u8; MESSAGE_LENGTH],
+}
+
+/// Keys resulting of a SPAKE2 exchange
+///
+/// The shared key should only be used once the confirmation message
+/// of the other party has been verified.
+pub struct Keys {
+    shared_key: [u8; SHARED_KEY_LENGTH],
+    confirmation: [u8; CONFIRMATION_LENGTH],
+    peer_confirmation: [u8; CONFIRMATION_LENGTH],
+}
+
+impl Drop for Keys {
+    fn drop(&mut self) {
+        self.shared_key.zeroize();
+        self.confirmation.zeroize();
+        self.peer_confirmation.zeroize();
+    }
+}
+
+impl Keys {
+    /// The shared key
+    pub fn shared_key(&self) -> &[u8; SHARED_KEY_LENGTH] {
+        &self.shared_key
+    }
+
+    /// The confirmation message to send to the other party
+    pub fn confirmation(&self) -> &[u8; CONFIRMATION_LENGTH] {
+        &self.confirmation
+    }
+
+    /// Verify the confirmation message of the other party in constant time
+    pub fn verify_confirmation(&self, peer_confirmation: &[u8]) -> Result<(), SpakeError> {
+        if CtEqual::ct_eq(&self.peer_confirmation[..], peer_confirmation).into() {
+            Ok(())
+        } else {
+            Err(SpakeError::ConfirmationFailed)
+        }
+    }
+}
+
+fn hash_to_scalar(input: &[u8]) -> Scalar {
+    let mut hash = Sha512::new().update(input).finalize();
+    let s = Scalar::from_uniform_bytes(&hash);
+    hash.zeroize();
+    s
+}
+
+// append len(data) as 8 bytes little endian, followed by data
+fn append_with_length(tt: &mut Vec<u8>, data: &[u8]) {
+    tt.extend_from_slice(&(data.len() as u64).to_le_bytes());
+    tt.extend_from_slice(data);
+}
+
+fn hmac_sha256(key: &[u8], data: &[u8]) -> [u8; 32] {
+    let mut mac = Hmac::new(crate::sha2::Sha256::new(), key);
+    mac.input(data);
+    let mut out = [0u8; 32];
+    mac.raw_result(&mut out);
+    out
+}
+
+/// SPAKE2-edwards25519-SHA256-HKDF-HMAC
+pub struct Spake2;
+
+impl Spake2 {
+    fn start(
+        role: Role,
+        password: &[u8],
+        id_client: &[u8],
+        id_server: &[u8],
+        random: &[u8; 32],
+    ) -> (State, [u8; MESSAGE_LENGTH]) {
+        let w = hash_to_scalar(password);
+        let x = hash_to_scalar(random);
+        let blind = match role {
+            Role::Client => M,
+            Role::Server => N,
+        };
+        // pA = x*G + w*M or pB = y*G + w*N
+        let blind = ge_decompress(&blind).unwrap().scalarmult(&w);
+        let message = ge_add(&Ge::scalarmult_base(&x), &blind).to_bytes();
+        let state = State {
+            role,
+            w,
+            x,
+            id_client: id_client.to_vec(),
+            id_server: id_server.to_vec(),
+            message,
+        };
+        (state, message)
+    }
+
+    /// Start the exchange as the client (party A), and return the message to send to the server
+    ///
+    /// `random` need to be 32 bytes from a high quality random source, and never reused.
+    pub fn new_client(
+        password: &[u8],
+        id_client: &[u8],
+        id_server: &[u8],
+        random: &[u8; 32],
+    ) -> (State, [u8; MESSAGE_LENGTH]) {
+        Self::start(Role::Client, password, id_client, id_server, random)
+    }
+
+    /// Start the exchange as the server (party B), and return the message to send to the client
+    ///
+    /// `random` need to be 32 bytes from a high quality random source, and never reused.
+    pub fn new_server(
+        password: &[u8],
+        id_client: &[u8],
+        id_server: &[u8],
+        random: &[u8; 32],
+    ) -> (State, [u8; MESSAGE_LENGTH]) {
+        Self::start(Role::Server, password, id_client, id_server, random)
+    }
+
+    /// Finish the exchange with the message of the other party
+    pub fn finish(state: State, peer_message: &[u8; MESSAGE_LENGTH]) -> Result<Keys, SpakeError> {
+        let peer = ge_decompress(peer_message).ok_or(SpakeError::InvalidMessage)?;
+        let peer_blind = match state.role {
+            Role::Client => N,
+            Role::Server => M,
+        };
+        // K = h*x*(pB - w*N) or K = h*y*(pA - w*M)
+        let peer_blind = ge_decompress(&peer_blind).unwrap().scalarmult(&state.w);
+        let k = ge_sub(&peer, &peer_blind).double().double().double();
+        let mut k = k.scalarmult(&state.x).to_bytes();
+        if k == Ge::ZERO.to_bytes() {
+            return Err(SpakeError::IdentityPoint);
+        }
+
+        let (p_a, p_b) = match state.role {
+            Role::Client => (&state.message, peer_message),
+            Role::Server => (peer_message, &state.message),
+        };
+        let mut tt = Vec::new();
+        append_with_length(&mut tt, &state.id_client);
+        append_with_length(&mut tt, &state.id_server);
+        append_with_length(&mut tt, p_a);
+        append_with_length(&mut tt, p_b);
+        append_with_length(&mut tt, &k);
+        append_with_length(&mut tt, &state.w.to_bytes());
+        k.zeroize();
+
+        // Ke || Ka = Hash(TT)
+        let mut hash = Sha256::new().update(&tt).finalize();
+        let mut shared_key = [0u8; SHARED_KEY_LENGTH];
+        shared_key.copy_from_slice(&hash[0..16]);
+
+        // KcA || KcB = KDF(nil, Ka, "ConfirmationKeys")
+        let mut prk = [0u8; 32];
+        hkdf_extract(crate::sha2::Sha256::new(), &[], &hash[16..32], &mut prk);
+        let mut kc = [0u8; 32];
+        hkdf_expand(
+            crate::sha2::Sha256::new(),
+            &prk,
+            b"ConfirmationKeys",
+            &mut kc,
+        );
+        let c_a = hmac_sha256(&kc[0..16], &tt);
+        let c_b = hmac_sha256(&kc[16..32], &tt);
+        hash.zeroize();
+        prk.zeroize();
+        kc.zeroize();
+        tt[..].zeroize();
+
+        let (confirmation, peer_confirmation) = match state.role {
+            Role::Client => (c_a, c_b),
+            Role::Server => (c_b, c_a),
+        };
+        Ok(Keys {
+            shared_key,
+            confirmation,
+            peer_confirmation,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Test {
+        password: &'static [u8],
+        id_client: &'static [u8],
+        id_server: &'static [u8],
+        random_client: [u8; 32],
+        random_server: [u8; 32],
+        message_client: [u8; 32],
+        message_server: [u8; 32],
+        shared_key: [u8; 16],
+        confirmation_client: [u8; 32],
+        confirmation_server: [u8; 32],
+    }
+
+    #[test]
+    fn points() {
+        // M and N are in the prime order subgroup
+        for p in [M, N] {
+            let p = ge_decompress(&p).unwrap();
+            let order = Scalar::from_bytes(&[
+                0xec, 0xd3, 0xf5, 0x5c, 0x1a, 0x63, 0x12, 0x58, 0xd6, 0x9c, 0xf7, 0xa2, 0xde, 0xf9,
+                0xde, 0x14, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                0x00, 0x00, 0x00, 0x10,
+            ]);
+            // (l - 1) * P = -P
+            assert_eq!(p.scalarmult(&order).to_bytes(), (-&p).to_bytes());
+        }
+    }
+
+    #[test]
+    fn vectors() {
+        // RFC 9382 only has vectors for P-256, these are generated with an independent
+        // implementation of the edwards25519 ciphersuite, using the same derivation of
+        // the scalars from the password and randomness
+        let tests = [
+            Test {
+                password: b"password",
+                id_client: b"client",
+                id_server: b"server",
+                random_client: [
+                    0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01,
+                    0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01,
+                    0x01, 0x01, 0x01, 0x01, 0x01, 0x01,
+                ],
+                random_server: [
+                    0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02,
+                    0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02,
+                    0x02, 0x02, 0x02, 0x02, 0x02, 0x02,
+                ],
+                message_client: [
+                    0x25, 0xee, 0x4c, 0x55, 0x1d, 0x0b, 0xa7, 0x42, 0x93, 0xc4, 0x01, 0x8b, 0xae,
+                    0x2a, 0xc5, 0x85, 0x4b, 0x57, 0xff, 0xdd, 0xc5, 0x4d, 0xb7, 0x7e, 0x77, 0x65,
+                    0xd8, 0x55, 0xab, 0xa9, 0x87, 0x44,
+                ],
+                message_server: [
+                    0x96, 0x8f, 0x86, 0x05, 0x29, 0x5e, 0x8d, 0xfb, 0xd8, 0x84, 0x1f, 0xd9, 0x94,
+                    0x04, 0x77, 0xc0, 0x01, 0x82, 0x37, 0xbd, 0x1c, 0x1a, 0x64, 0xf1, 0x0d, 0x29,
+                    0xa9, 0x3c, 0xc5, 0xde, 0x71, 0x5e,
+                ],
+                shared_key: [
+                    0xd9, 0x9f, 0x3d, 0xc1, 0x2f, 0x2f, 0x16, 0xc7, 0x38, 0x06, 0x23, 0x23, 0x53,
+                    0x28, 0x9b, 0x0b,
+                ],
+                confirmation_client: [
+                    0x5b, 0x89, 0x22, 0x2d, 0xe9, 0xb1, 0x4c, 0x96, 0xda, 0x08, 0xb1, 0xf7, 0xa4,
+                    0xae, 0xf2, 0x8b, 0xe0, 0x79, 0x34, 0x36, 0x83, 0x64, 0xec, 0xd8, 0x44, 0x8b,
+                    0x17, 0x62, 0x64, 0x3a, 0x2a, 0xfa,
+                ],
+                confirmation_server: [
+                    0xa6, 0xf3, 0x58, 0x53, 0xae, 0xe4, 0xd8, 0x29, 0x2f, 0xdf, 0x79, 0xa0, 0x9c,
+                    0xe1, 0xbb, 0xa7, 0xcf, 0x64, 0x5d, 0x04, 0xdd, 0xe7, 0xfe, 0xbd, 0xbc, 0x11,
+                    0x84, 0x2f, 0x17, 0x52, 0x3d, 0x90,
+                ],
+            },
+            Test {
+                password: b"",
+                id_client: b"",
+                id_server: b"",
+                random_client: [
+                    0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c,
+                    0x0d, 0x0e, 0x0f, 0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17, 0x18, 0x19,
+                    0x1a, 0x1b, 0x1c, 0x1d, 0x1e, 0x1f,
+                ],
+                random_server: [
+                    0x20, 0x21, 0x22, 0x23, 0x24, 0x25, 0x26, 0x27, 0x28, 0x29, 0x2a, 0x2b, 0x2c,
+                    0x2d, 0x2e, 0x2f, 0x30, 0x31, 0x32, 0x33, 0x34, 0x35, 0x36, 0x37, 0x38, 0x39,
+                    0x3a, 0x3b, 0x3c, 0x3d, 0x3e, 0x3f,
+                ],
+                message_client: [
+                    0x4b, 0x4d, 0x0f, 0x3a, 0x1f, 0xb1, 0xbb, 0xab, 0x57, 0xbd, 0x76, 0x27, 0xd9,
+                    0x9a, 0xc5, 0x5b, 0x84, 0x1a, 0x32, 0x21, 0x62, 0x56, 0xfa, 0x0a, 0xed, 0x0b,
+                    0x93, 0xa9, 0x9a, 0x5a, 0x14, 0x6c,
+                ],
+                message_server: [
+                    0xcb, 0x7d, 0xd6, 0xc2, 0x17, 0x07, 0x38, 0x06, 0x7a, 0x44, 0x91, 0xad, 0xa1,
+                    0xce, 0x28, 0xe8, 0x15, 0xaa, 0x78, 0x0d, 0x61, 0x7a, 0x04, 0xf8, 0x16, 0xc5,
+                    0x53, 0x1f, 0xfa, 0xe8, 0x24, 0xbb,
+                ],
+                shared_key: [
+                    0x92, 0x90, 0x25, 0xa0, 0x38, 0xa0, 0xfb, 0xf2, 0xe9, 0x5a, 0x78, 0x65, 0x08,
+                    0xa6, 0x1f, 0x90,
+                ],
+                confirmation_client: [
+                    0x18, 0x54, 0x26, 0x19, 0xd8, 0xed, 0xbe, 0x28, 0xd2, 0x58, 0xd7, 0x40, 0x4b,
+                    0x87, 0x38, 0x49, 0xda, 0x21, 0x2d, 0x2c, 0xd0, 0x80, 0xe0, 0x91, 0x80, 0x69,
+                    0x31, 0x42, 0x08, 0x2d, 0x60, 0xe0,
+                ],
+                confirmation_server: [
+                    0x29, 0x5a, 0xca, 0xa8, 0x3f, 0xc1, 0xc6, 0xa5, 0xea, 0xe6, 0xf4, 0x33, 0x21,
+                    0x99, 0x93, 0x24, 0x9d, 0x7f, 0x67, 0xae, 0x09, 0x7d, 0xc1, 0xd9, 0xb7, 0xf3,
+                    0xd5, 0x49, 0x19, 0x61, 0x5c, 0xeb,
+                ],
+            },
+        ];
+        for t in tests.iter() {
+            let (client, message_client) =
+                Spake2::new_client(t.password, t.id_client, t.id_server, &t.random_client);
+            let (server, message_server) =
+                Spake2::new_server(t.password, t.id_client, t.id_server, &t.random_server);
+            assert_eq!(message_client, t.message_client);
+            assert_eq!(message_server, t.message_server);
+
+            let client = Spake2::finish(client, &message_server).unwrap();
+            let server = Spake2::finish(server, &message_client).unwrap();
+            assert_eq!(client.shared_key(), &t.shared_key);
+            assert_eq!(server.shared_key(), &t.shared_key);
+            assert_eq!(client.confirmation(), &t.confirmation_client);
+            assert_eq!(server.confirmation(), &t.confirmation_server);
+            assert_eq!(client.verify_confirmation(server.confirmation()), Ok(()));
+            assert_eq!(server.verify_confirmation(client.confirmation()), Ok(()));
+        }
+    }
+
+    #[test]
+    fn wrong_password() {
+        let (client, message_client) =
+            Spake2::new_client(b"password", b"client", b"server", &[1; 32]);
+        let (server, message_server) =
+            Spake2::new_server(b"passw0rd", b"client", b"server", &[2; 32]);
+        let client = Spake2::finish(client, &message_server).unwrap();
+        let server = Spake2::finish(server, &message_client).unwrap();
+        assert_ne!(client.shared_key(), server.shared_key());
+        assert_eq!(
+            server.verify_confirmation(client.confirmation()),
+            Err(SpakeError::ConfirmationFailed)
+        );
+        assert_eq!(
+            client.verify_confirmation(server.confirmation()),
+            Err(SpakeError::ConfirmationFailed)
+        );
+    }
+
+    #[test]
+    fn invalid_messages() {
+        let (client, _) = Spake2::new_client(b"password", b"client", b"server", &[1; 32]);
+        // y = 2 is not on the curve
+        let mut invalid = [0u8; 32];
+        invalid[0] = 2;
+        assert_eq!(
+            Spake2::finish(client, &invalid).err(),
+            Some(SpakeError::InvalidMessage)
+        );
+
+        // the message w*N makes the shared point the identity
+        let (client, _) = Spake2::new_client(b"password", b"client", b"server", &[1; 32]);
+        let w = hash_to_scalar(b"password");
+        let message = ge_decompress(&N).unwrap().scalarmult(&w).to_bytes();
+        assert_eq!(
+            Spake2::finish(client, &message).err(),
+            Some(SpakeError::IdentityPoint)
+        );
+    }
+}
@@ -0,0 +1,492 @@
+//! PASETO - Platform-Agnostic Security Tokens, version 4
+//!
+//! Implementation of the `v4.local` (symmetric encryption) and `v4.public`
+//! (public key signature) tokens of the [PASETO specification][1].
+//!
+//! * `v4.local` encrypts the payload with XChaCha20 and authenticates the token with
+//!   a keyed BLAKE2b-256, the encryption and authentication keys being derived from
+//!   the secret key and a 32 bytes nonce with BLAKE2b.
+//! * `v4.public` signs the payload with Ed25519, the payload being sent in clear.
+//!
+//! The footer is authenticated and sent in clear, whereas the implicit assertion
+//! is authenticated but not part of the token: the same value needs to be provided
+//! when decrypting or verifying the token.
+//!
+//! As there's no source of randomness in this crate, [`v4_local_encrypt`] derives the
+//! nonce from the key and the inputs with BLAKE2b, which makes the encryption deterministic:
+//! the same inputs always give the same token. When a random source is available,
+//! [`v4_local_encrypt_with_nonce`] should be used with a random nonce instead.
+//!
+//! # Examples
+//!
+//! ```
+//! use cryptoxide::paseto;
+//!
+//! let key = [1u8; 32]; // only for example !
+//! let token = paseto::v4_local_encrypt(&key, b"{\"data\":\"secret\"}", b"footer", b"");
+//! let payload = paseto::v4_local_decrypt(&key, &token, b"footer", b"").unwrap();
+//! assert_eq!(payload, b"{\"data\":\"secret\"}");
+//! ```
+//!
+//! [1]: <https://github.com/paseto-standard/paseto-spec>
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::chacha20::XChaCha;
+use crate::constant_time::CtEqual;
+use crate::ed25519;
+use crate::encoding::{decode_base64_with, encode_base64_with, Base64Alphabet};
+use crate::hashing::blake2b::{Blake2b, ContextDyn};
+use crate::secure_zero::Zeroize;
+
+const LOCAL_HEADER: &str = "v4.local.";
+const PUBLIC_HEADER: &str = "v4.public.";
+
+const NONCE_LENGTH: usize = 32;
+const MAC_LENGTH: usize = 32;
+
+/// PASETO errors
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PasetoError {
+    /// The token doesn't start with the expected version and purpose
+    InvalidHeader,
+    /// The token is not valid base64url
+    InvalidEncoding,
+    /// The token is too short to contain the nonce and tag, or the signature
+    InvalidLength,
+    /// The footer of the token doesn't match the expected footer
+    InvalidFooter,
+    /// The local token cannot be authenticated
+    AuthenticationFailed,
+    /// The signature of the public token is not valid
+    InvalidSignature,
+}
+
+impl core::fmt::Display for PasetoError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            PasetoError::InvalidHeader => write!(f, "invalid token header"),
+            PasetoError::InvalidEncoding => write!(f, "invalid token encoding"),
+            PasetoError::InvalidLength => write!(f, "invalid token length"),
+            PasetoError::InvalidFooter => write!(f, "invalid token footer"),
+            PasetoError::AuthenticationFailed => write!(f, "authentication failed"),
+            PasetoError::InvalidSignature => write!(f, "invalid signature"),
+        }
+    }
+}
+
+// Pre-Authentication Encoding: LE64(count) || (LE64(len(piece)) || piece)*
+fn pae(pieces: &[&[u8]]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(8 + pieces.iter().map(|p| 8 + p.len()).sum::<usize>());
+    out.extend_from_slice(&(pieces.len() as u64).to_le_bytes());
+    for piece in pieces {
+        // the most significant bit is cleared for interoperability with languages without u64
+        out.extend_from_slice(&((piece.len() as u64) & 0x7fff_ffff_ffff_ffff).to_le_bytes());
+        out.extend_from_slice(piece);
+    }
+    out
+}
+
+fn base64url(bytes: &[u8]) -> String {
+    let encoded = encode_base64_with(bytes, Base64Alphabet::UrlSafe, false);
+    // the base64 alphabet is only made of ascii characters
+    String::from_utf8(encoded).unwrap()
+}
+
+// build the token `header || base64url(body) [|| "." || base64url(footer)]`
+fn token(header: &str, body: &[u8], footer: &[u8]) -> String {
+    let mut out = String::from(header);
+    out.push_str(&base64url(body));
+    if !footer.is_empty() {
+        out.push('.');
+        out.push_str(&base64url(footer));
+    }
+    out
+}
+
+// split the token into its decoded body, checking the header and the expected footer
+fn parse(header: &str, token: &str, footer: &[u8]) -> Result<Vec<u8>, PasetoError> {
+    let rest = token
+        .strip_prefix(header)
+        .ok_or(PasetoError::InvalidHeader)?;
+    let (body, token_footer) = match rest.split_once('.') {
+        None => (rest, Vec::new()),
+        Some((body, token_footer)) => {
+            let decoded =
+                decode_base64_with(token_footer.as_bytes(), Base64Alphabet::UrlSafe, false)
+                    .map_err(|_| PasetoError::InvalidEncoding)?;
+            (body, decoded)
+        }
+    };
+    if token_footer.len() != footer.len() || !bool::from(CtEqual::ct_eq(&token_footer[..], footer))
+    {
+        return Err(PasetoError::InvalidFooter);
+    }
+    decode_base64_with(body.as_bytes(), Base64Alphabet::UrlSafe, false)
+        .map_err(|_| PasetoError::InvalidEncoding)
+}
+
+struct LocalKeys {
+    encryption_key: [u8; 32],
+    counter_nonce: [u8; 24],
+    authentication_key: [u8; 32],
+}
+
+impl Drop for LocalKeys {
+    fn drop(&mut self) {
+        self.encryption_key.zeroize();
+        self.counter_nonce.zeroize();
+        self.authentication_key.zeroize();
+    }
+}
+
+impl LocalKeys {
+    fn derive(key: &[u8; 32], nonce: &[u8; NONCE_LENGTH]) -> Self {
+        let mut tmp = [0u8; 56];
+        ContextDyn::new_keyed(56, key)
+            .update(b"paseto-encryption-key")
+            .update(nonce)
+            .finalize_at(&mut tmp);
+        let mut keys = LocalKeys {
+            encryption_key: [0; 32],
+            counter_nonce: [0; 24],
+            authentication_key: [0; 32],
+        };
+        keys.encryption_key.copy_from_slice(&tmp[0..32]);
+        keys.counter_nonce.copy_from_slice(&tmp[32..56]);
+        tmp.zeroize();
+
+        Blake2b::<256>::new_keyed(key)
+            .update(b"paseto-auth-key-for-aead")
+            .update(nonce)
+            .finalize_at(&mut keys.authentication_key);
+        keys
+    }
+
+    fn tag(&self, nonce: &[u8], ciphertext: &[u8], footer: &[u8], implicit: &[u8]) -> [u8; 32] {
+        let pre_auth = pae(&[LOCAL_HEADER.as_bytes(), nonce, ciphertext, footer, implicit]);
+        let mut tag = [0u8; MAC_LENGTH];
+        Blake2b::<256>::new_keyed(&self.authentication_key)
+            .update(&pre_auth)
+            .finalize_at(&mut tag);
+        tag
+    }
+}
+
+/// Encrypt the payload in a `v4.local` token, with a nonce derived from the key and the inputs
+///
+/// The encryption is deterministic, see the module documentation.
+pub fn v4_local_encrypt(
+    key: &[u8; 32],
+    payload: &[u8],
+    footer: &[u8],
+    implicit_assertion: &[u8],
+) -> String {
+    let mut nonce = [0u8; NONCE_LENGTH];
+    Blake2b::<256>::new_keyed(key)
+        .update(&pae(&[
+            b"paseto-v4-local-nonce",
+            payload,
+            footer,
+            implicit_assertion,
+        ]))
+        .finalize_at(&mut nonce);
+    v4_local_encrypt_with_nonce(key, &nonce, payload, footer, implicit_assertion)
+}
+
+/// Encrypt the payload in a `v4.local` token, with the given nonce
+///
+/// The nonce should be 32 bytes from a random source, and should never be reused.
+pub fn v4_local_encrypt_with_nonce(
+    key: &[u8; 32],
+    nonce: &[u8; NONCE_LENGTH],
+    payload: &[u8],
+    footer: &[u8],
+    implicit_assertion: &[u8],
+) -> String {
+    let keys = LocalKeys::derive(key, nonce);
+
+    let mut body = Vec::with_capacity(NONCE_LENGTH + payload.len() + MAC_LENGTH);
+    body.extend_from_slice(nonce);
+    body.extend_from_slice(payload);
+    XChaCha::<20>::new(&keys.encryption_key, &keys.counter_nonce)
+        .process_mut(&mut body[NONCE_LENGTH..]);
+
+    let tag = keys.tag(nonce, &body[NONCE_LENGTH..], footer, implicit_assertion);
+    body.extend_from_slice(&tag);
+
+    token(LOCAL_HEADER, &body, footer)
+}
+
+/// Decrypt a `v4.local` token, checking the expected footer and the implicit assertion
+pub fn v4_local_decrypt(
+    key: &[u8; 32],
+    token: &str,
+    footer: &[u8],
+    implicit_assertion: &[u8],
+) -> Result<Vec<u8>, PasetoError> {
+    let body = parse(LOCAL_HEADER, token, footer)?;
+    if body.len() < NONCE_LENGTH + MAC_LENGTH {
+        return Err(PasetoError::InvalidLength);
+    }
+    let (nonce, rest) = body.split_at(NONCE_LENGTH);
+    let (ciphertext, tag) = rest.split_at(rest.len() - MAC_LENGTH);
+    let mut nonce_array = [0u8; NONCE_LENGTH];
+    nonce_array.copy_from_slice(nonce);
+
+    let keys = LocalKeys::derive(key, &nonce_array);
+    let expected = keys.tag(nonce, ciphertext, footer, implicit_assertion);
+    if !bool::from(CtEqual::ct_eq(&expected[..], tag)) {
+        return Err(PasetoError::AuthenticationFailed);
+    }
+
+    let mut payload = ciphertext.to_vec();
+    XChaCha::<20>::new(&keys.encryption_key, &keys.counter_nonce).process_mut(&mut payload);
+    Ok(payload)
+}
+
+/// Sign the payload in a `v4.public` token with an Ed25519 keypair
+pub fn v4_public_sign(
+    keypair: &[u8; ed25519::KEYPAIR_LENGTH],
+    payload: &[u8],
+    footer: &[u8],
+    implicit_assertion: &[u8],
+) -> String {
+    let pre_auth = pae(&[
+        PUBLIC_HEADER.as_bytes(),
+        payload,
+        footer,
+        implicit_assertion,
+    ]);
+    let signature = ed25519::signature(&pre_auth, keypair);
+
+    let mut body = Vec::with_capacity(payload.len() + ed25519::SIGNATURE_LENGTH);
+    body.extend_from_slice(payload);
+    body.extend_from_slice(&signature);
+    token(PUBLIC_HEADER, &body, footer)
+}
+
+/// Verify a `v4.public` token with an Ed25519 public key, checking the expected footer
+/// and the implicit assertion, and return the payload
+pub fn v4_public_verify(
+    public_key: &[u8; ed25519::PUBLIC_KEY_LENGTH],
+    token: &str,
+    footer: &[u8],
+    implicit_assertion: &[u8],
+) -> Result<Vec<u8>, PasetoError> {
+    let mut body = parse(PUBLIC_HEADER, token, footer)?;
+    if body.len() < ed25519::SIGNATURE_LENGTH {
+        return Err(PasetoError::InvalidLength);
+    }
+    let mut signature = [0u8; ed25519::SIGNATURE_LENGTH];
+    signature.copy_from_slice(&body[body.len() - ed25519::SIGNATURE_LENGTH..]);
+    body.truncate(body.len() - ed25519::SIGNATURE_LENGTH);
+
+    let pre_auth = pae(&[PUBLIC_HEADER.as_bytes(), &body, footer, implicit_assertion]);
+    if !ed25519::verify(&pre_auth, public_key, &signature) {
+        return Err(PasetoError::InvalidSignature);
+    }
+    Ok(body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: [u8; 32] = [
+        0x70, 0x71, 0x72, 0x73, 0x74, 0x75, 0x76, 0x77, 0x78, 0x79, 0x7a, 0x7b, 0x7c, 0x7d, 0x7e,
+        0x7f, 0x80, 0x81, 0x82, 0x83, 0x84, 0x85, 0x86, 0x87, 0x88, 0x89, 0x8a, 0x8b, 0x8c, 0x8d,
+        0x8e, 0x8f,
+    ];
+
+    const SEED: [u8; 32] = [
+        0xb4, 0xcb, 0xfb, 0x43, 0xdf, 0x4c, 0xe2, 0x10, 0x72, 0x7d, 0x95, 0x3e, 0x4a, 0x71, 0x33,
+        0x07, 0xfa, 0x19, 0xbb, 0x7d, 0x9f, 0x85, 0x04, 0x14, 0x38, 0xd9, 0xe1, 0x1b, 0x94, 0x2a,
+        0x37, 0x74,
+    ];
+
+    const PUBLIC: [u8; 32] = [
+        0x1e, 0xb9, 0xdb, 0xbb, 0xbc, 0x04, 0x7c, 0x03, 0xfd, 0x70, 0x60, 0x4e, 0x00, 0x71, 0xf0,
+        0x98, 0x7e, 0x16, 0xb2, 0x8b, 0x75, 0x72, 0x25, 0xc1, 0x1f, 0x00, 0x41, 0x5d, 0x0e, 0x20,
+        0xb1, 0xa2,
+    ];
+
+    const FOOTER: &[u8] = b"{\"kid\":\"zVhMiPBP9fRf2snEcT7gFTioeA9COcNy9DfgL1W60haN\"}";
+
+    struct LocalTest {
+        nonce: [u8; 32],
+        payload: &'static [u8],
+        footer: &'static [u8],
+        implicit_assertion: &'static [u8],
+        token: &'static str,
+    }
+
+    struct PublicTest {
+        payload: &'static [u8],
+        footer: &'static [u8],
+        implicit_assertion: &'static [u8],
+        token: &'static str,
+    }
+
+    #[test]
+    fn local_vectors() {
+        let tests = [
+            // official test vector 4-E-1
+            LocalTest {
+                nonce: [0; 32],
+                payload: b"{\"data\":\"this is a secret message\",\"exp\":\"2022-01-01T00:00:00+00:00\"}",
+                footer: b"",
+                implicit_assertion: b"",
+                token: "v4.local.AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAQAr68PS4AXe7If_ZgesdkUMvSwscFlAl1pk5HC0e8kApeaqMfGo_7OpBnwJOAbY9V7WU6abu74MmcUE8YWAiaArVI8XJ5hOb_4v9RmDkneN0S92dx0OW4pgy7omxgf3S8c3LlQg",
+            },
+            // generated with an independent python implementation
+            LocalTest {
+                nonce: [
+                    0xdf, 0x65, 0x48, 0x12, 0xba, 0xc4, 0x92, 0x66, 0x38, 0x25, 0x52, 0x0b, 0xa2,
+                    0xf6, 0xe6, 0x7c, 0xf5, 0xca, 0x5b, 0xdc, 0x13, 0xd4, 0xe7, 0x50, 0x7a, 0x98,
+                    0xcc, 0x4c, 0x2f, 0xcc, 0x3a, 0xd8,
+                ],
+                payload: b"{\"data\":\"this is a hidden message\",\"exp\":\"2022-01-01T00:00:00+00:00\"}",
+                footer: FOOTER,
+                implicit_assertion: b"{\"test-vector\":\"4-E-9\"}",
+                token: "v4.local.32VIErrEkmY4JVILovbmfPXKW9wT1OdQepjMTC_MOtjA4kiqw7_tcaOM5GNEcnTxl60WiA8rd3wgFSNb_UdJPXjpzm0KW9ojM5f4O2mRvE2IcweP-PRdoHjd5-RHCiExR1IK6t42KE3DGqgqeutC3L4kgxLxZ-UIU2QqSETaUFfPnzM2Mg.eyJraWQiOiJ6VmhNaVBCUDlmUmYyc25FY1Q3Z0ZUaW9lQTlDT2NOeTlEZmdMMVc2MGhhTiJ9",
+            },
+        ];
+
+        for (i, t) in tests.iter().enumerate() {
+            let token = v4_local_encrypt_with_nonce(
+                &KEY,
+                &t.nonce,
+                t.payload,
+                t.footer,
+                t.implicit_assertion,
+            );
+            assert_eq!(token, t.token, "encrypt {}", i);
+            let payload = v4_local_decrypt(&KEY, t.token, t.footer, t.implicit_assertion);
+            assert_eq!(payload.as_deref(), Ok(t.payload), "decrypt {}", i);
+        }
+    }
+
+    #[test]
+    fn public_vectors() {
+        let tests = [
+            // official test vector 4-S-1
+            PublicTest {
+                payload: b"{\"data\":\"this is a signed message\",\"exp\":\"2022-01-01T00:00:00+00:00\"}",
+                footer: b"",
+                implicit_assertion: b"",
+                token: "v4.public.eyJkYXRhIjoidGhpcyBpcyBhIHNpZ25lZCBtZXNzYWdlIiwiZXhwIjoiMjAyMi0wMS0wMVQwMDowMDowMCswMDowMCJ9bg_XBBzds8lTZShVlwwKSgeKpLT3yukTw6JUz3W4h_ExsQV-P0V54zemZDcAxFaSeef1QlXEFtkqxT1ciiQEDA",
+            },
+            // generated with an independent python implementation
+            PublicTest {
+                payload: b"{\"data\":\"this is a signed message\",\"exp\":\"2022-01-01T00:00:00+00:00\"}",
+                footer: FOOTER,
+                implicit_assertion: b"{\"test-vector\":\"4-S-3\"}",
+                token: "v4.public.eyJkYXRhIjoidGhpcyBpcyBhIHNpZ25lZCBtZXNzYWdlIiwiZXhwIjoiMjAyMi0wMS0wMVQwMDowMDowMCswMDowMCJ9NPWciuD3d0o5eXJXG5pJy-DiVEoyPYWs1YSTwWHNJq6DZD3je5gf-0M4JR9ipdUSJbIovzmBECeaWmaqcaP0DQ.eyJraWQiOiJ6VmhNaVBCUDlmUmYyc25FY1Q3Z0ZUaW9lQTlDT2NOeTlEZmdMMVc2MGhhTiJ9",
+            },
+        ];
+
+        let (keypair, public) = ed25519::keypair(&SEED);
+        assert_eq!(public, PUBLIC);
+
+        for (i, t) in tests.iter().enumerate() {
+            let token = v4_public_sign(&keypair, t.payload, t.footer, t.implicit_assertion);
+            assert_eq!(token, t.token, "sign {}", i);
+            let payload = v4_public_verify(&PUBLIC, t.token, t.footer, t.implicit_assertion);
+            assert_eq!(payload.as_deref(), Ok(t.payload), "verify {}", i);
+        }
+    }
+
+    #[test]
+    fn local_invalid() {
+        let token = v4_local_encrypt(&KEY, b"payload", b"footer", b"implicit");
+        assert_eq!(
+            token,
+            v4_local_encrypt(&KEY, b"payload", b"footer", b"implicit")
+        );
+        assert_ne!(
+            token,
+            v4_local_encrypt(&KEY, b"payloae", b"footer", b"implicit")
+        );
+        assert_eq!(
+            v4_local_decrypt(&KEY, &token, b"footer", b"implicit").as_deref(),
+            Ok(&b"payload"[..])
+        );
+
+        let mut other_key = KEY;
+        other_key[0] ^= 1;
+        assert_eq!(
+            v4_local_decrypt(&other_key, &token, b"footer", b"implicit"),
+            Err(PasetoError::AuthenticationFailed)
+        );
+        assert_eq!(
+            v4_local_decrypt(&KEY, &token, b"footer", b"other"),
+            Err(PasetoError::AuthenticationFailed)
+        );
+        assert_eq!(
+            v4_local_decrypt(&KEY, &token, b"other", b"implicit"),
+            Err(PasetoError::InvalidFooter)
+        );
+        assert_eq!(
+            v4_local_decrypt(&KEY, &token, b"", b"implicit"),
+            Err(PasetoError::InvalidFooter)
+        );
+        assert_eq!(
+            v4_local_decrypt(
+                &KEY,
+                &token.replace("v4.local.", "v4.public."),
+                b"footer",
+                b"implicit"
+            ),
+            Err(PasetoError::InvalidHeader)
+        );
+        assert_eq!(
+            v4_local_decrypt(&KEY, "v4.local.AAAA", b"", b""),
+            Err(PasetoError::InvalidLength)
+        );
+        assert_eq!(
+            v4_local_decrypt(&KEY, "v4.local.AA+A", b"", b""),
+            Err(PasetoError::InvalidEncoding)
+        );
+
+        // flip a bit of the ciphertext
+        let (body, footer) = token
+            .split_once("v4.local.")
+            .unwrap()
+            .1
+            .split_once('.')
+            .unwrap();
+        let mut raw = decode_base64_with(body.as_bytes(), Base64Alphabet::UrlSafe, false).unwrap();
+        raw[NONCE_LENGTH] ^= 1;
+        let tampered = format!("v4.local.{}.{}", base64url(&raw), footer);
+        assert_eq!(
+            v4_local_decrypt(&KEY, &tampered, b"footer", b"implicit"),
+            Err(PasetoError::AuthenticationFailed)
+        );
+    }
+
+    #[test]
+    fn public_invalid() {
+        let (keypair, public) = ed25519::keypair(&SEED);
+        let token = v4_public_sign(&keypair, b"payload", b"footer", b"implicit");
+
+        let (_, other) = ed25519::keypair(&KEY);
+        assert_eq!(
+            v4_public_verify(&other, &token, b"footer", b"implicit"),
+            Err(PasetoError::InvalidSignature)
+        );
+        assert_eq!(
+            v4_public_verify(&public, &token, b"footer", b"other"),
+            Err(PasetoError::InvalidSignature)
+        );
+        assert_eq!(
+            v4_public_verify(&public, &token, b"other", b"implicit"),
+            Err(PasetoError::InvalidFooter)
+        );
+        assert_eq!(
+            v4_public_verify(&public, "v4.public.AAAA", b"", b""),
+            Err(PasetoError::InvalidLength)
+        );
+    }
+}
@@ -20,7 +20,13 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+#[cfg(feature = "blake2")]
+use crate::hashing::blake2b;
 use crate::mac::Mac;
+#[cfg(feature = "blake2")]
+use crate::mac::MacResult;
+#[cfg(all(feature = "hmac", feature = "sha1"))]
+use crate::{hmac::Hmac, sha1::Sha1};
 use alloc::vec::Vec;
 use core::iter::repeat;
 
@@ -31,19 +37,22 @@ use core::iter::repeat;
 // `idx` - the 1 based index of the block
 // `scratch` - a temporary variable the same length as the block
 // `block` - the block of the output to calculate
-fn calculate_block<M: Mac>(
+// `iteration` - called after each of the `c` iterations
+fn calculate_block<M: Mac, F: FnMut()>(
     mac: &mut M,
     salt: &[u8],
     c: u32,
     idx: u32,
     scratch: &mut [u8],
     block: &mut [u8],
+    iteration: &mut F,
 ) {
     // Perform the 1st iteration. The output goes directly into block
     mac.input(salt);
     mac.input(&idx.to_be_bytes());
     mac.raw_result(block);
     mac.reset();
+    iteration();
 
     // Perform the 2nd iteration. The input comes from block and is output into scratch. scratch is
     // then exclusive-or added into block. After all this, the input to the next step is now in
@@ -55,6 +64,7 @@ fn calculate_block<M: Mac>(
         for (output, &input) in block.iter_mut().zip(scratch.iter()) {
             *output ^= input;
         }
+        iteration();
     }
 
     // Perform all remaining iterations
@@ -65,6 +75,7 @@ fn calculate_block<M: Mac>(
         for (output, &input) in block.iter_mut().zip(scratch.iter()) {
             *output ^= input;
         }
+        iteration();
     }
 }
 
@@ -80,8 +91,42 @@ fn calculate_block<M: Mac>(
  *       factor in determining the security of the derived key.
  * * `output` - The output buffer to fill with the derived key value.
  *
+ * The output can be of any length: it is made of the successive blocks `T(1) || T(2) || ...`
+ * of the size of the Mac output, the last block being truncated. An empty output is a no-op.
+ * The output is limited to `(2^32 - 1)` blocks, and this function panics if the output is longer.
+ *
  */
 pub fn pbkdf2<M: Mac>(mac: &mut M, salt: &[u8], c: u32, output: &mut [u8]) {
+    pbkdf2_with_progress(mac, salt, c, output, |_, _| {})
+}
+
+/**
+ * Execute the PBKDF2 Key Derivation Function, reporting the progress of the computation.
+ *
+ * This is the same as `pbkdf2`, except that `progress` is called after each iteration with
+ * `(current_iteration, total_iterations)`, where `current_iteration` goes from 1 to
+ * `total_iterations`. The callback is called synchronously, so it should return quickly
+ * (e.g. update a progress bar or feed a watchdog) to not slow down the derivation.
+ *
+ * Each block of the output takes `c` iterations, so `total_iterations` is `c` times the
+ * number of blocks of the output, which is `c` when the output is no longer than the Mac output.
+ * An empty output is a no-op and the callback is never called.
+ *
+ * # Arguments
+ * * `mac` - The Pseudo Random Function to use.
+ * * `salt` - The salt value to use.
+ * * `c` - The iteration count.
+ * * `output` - The output buffer to fill with the derived key value.
+ * * `progress` - The callback receiving `(current_iteration, total_iterations)`.
+ *
+ */
+pub fn pbkdf2_with_progress<M: Mac, F: Fn(u32, u32)>(
+    mac: &mut M,
+    salt: &[u8],
+    c: u32,
+    output: &mut [u8],
+    progress: F,
+) {
     assert!(c > 0);
 
     let os = mac.output_bytes();
@@ -92,6 +137,14 @@ pub fn pbkdf2<M: Mac>(mac: &mut M, salt: &[u8], c: u32, output: &mut [u8]) {
     // the bottleneck in Scrypt performance.
     let mut scratch: Vec<u8> = repeat(0).take(os).collect();
 
+    let blocks = u32::try_from(output.len().div_ceil(os)).expect("PBKDF2 size limit exceeded.");
+    let total = blocks.saturating_mul(c);
+    let mut current: u32 = 0;
+    let mut iteration = || {
+        current = current.saturating_add(1);
+        progress(current, total)
+    };
+
     let mut idx: u32 = 0;
 
     for chunk in output.chunks_mut(os) {
@@ -99,16 +152,175 @@ pub fn pbkdf2<M: Mac>(mac: &mut M, salt: &[u8], c: u32, output: &mut [u8]) {
         idx = idx.checked_add(1).expect("PBKDF2 size limit exceeded.");
 
         if chunk.len() == os {
-            calculate_block(mac, salt, c, idx, &mut scratch, chunk);
+            calculate_block(mac, salt, c, idx, &mut scratch, chunk, &mut iteration);
         } else {
             let mut tmp: Vec<u8> = repeat(0).take(os).collect();
-            calculate_block(mac, salt, c, idx, &mut scratch[..], &mut tmp[..]);
+            calculate_block(
+                mac,
+                salt,
+                c,
+                idx,
+                &mut scratch[..],
+                &mut tmp[..],
+                &mut iteration,
+            );
             let chunk_len = chunk.len();
             chunk[0..chunk_len].copy_from_slice(&tmp[..chunk_len]);
         }
     }
 }
 
+/**
+ * Execute the PBKDF2 Key Derivation Function without any heap allocation.
+ *
+ * This is the same as `pbkdf2`, except that the intermediate block is stored in `work_buf`,
+ * and the last truncated block of the output (if any) in an array of `HLEN` bytes on the stack,
+ * instead of vectors allocated for each call. `HLEN` needs to be the output size of the Mac.
+ *
+ * Besides the Mac context, the stack usage of the function is `work_buf` and `HLEN` bytes:
+ *
+ * | Mac         | HLEN | buffers   | `Hmac` context (64 bits targets) |
+ * |-------------|------|-----------|----------------------------------|
+ * | HMAC-SHA1   | 20   | 40 bytes  | 168 bytes                        |
+ * | HMAC-SHA256 | 32   | 64 bytes  | 184 bytes                        |
+ * | HMAC-SHA512 | 64   | 128 bytes | 304 bytes                        |
+ *
+ * Note that `Hmac::new` itself allocates its inner and outer padded keys (2 blocks of
+ * the digest) when created, so the Mac should be created once and outside of any
+ * allocation sensitive section.
+ *
+ * # Arguments
+ * * `mac` - The Pseudo Random Function to use, keyed with the password.
+ * * `salt` - The salt value to use.
+ * * `c` - The iteration count.
+ * * `output` - The output buffer to fill with the derived key value.
+ * * `work_buf` - The buffer used for the intermediate block.
+ *
+ * # Panics
+ *
+ * Panics if `HLEN` is not the output size of the Mac.
+ *
+ */
+pub fn pbkdf2_no_alloc<M: Mac, const HLEN: usize>(
+    mac: &mut M,
+    salt: &[u8],
+    c: u32,
+    output: &mut [u8],
+    work_buf: &mut [u8; HLEN],
+) {
+    assert!(c > 0);
+    assert_eq!(mac.output_bytes(), HLEN);
+
+    let mut idx: u32 = 0;
+
+    for chunk in output.chunks_mut(HLEN) {
+        // The block index starts at 1. So, this is supposed to run on the first execution.
+        idx = idx.checked_add(1).expect("PBKDF2 size limit exceeded.");
+
+        if chunk.len() == HLEN {
+            calculate_block(mac, salt, c, idx, work_buf, chunk, &mut || {});
+        } else {
+            let mut tmp = [0u8; HLEN];
+            calculate_block(mac, salt, c, idx, work_buf, &mut tmp, &mut || {});
+            let chunk_len = chunk.len();
+            chunk.copy_from_slice(&tmp[..chunk_len]);
+        }
+    }
+}
+
+/**
+ * Execute the PBKDF2 Key Derivation Function using HMAC-SHA1 as the Pseudo Random Function.
+ *
+ * This is only provided for compatibility with existing systems, like the WPA2 pre-shared key
+ * (derived from the passphrase with the SSID as salt, 4096 iterations and 32 bytes of output),
+ * PKCS#12 or some SSH key formats. SHA1 is deprecated, and new designs should use PBKDF2 with
+ * HMAC-SHA256 or HMAC-SHA512, or a memory hard function like Argon2 or Scrypt.
+ *
+ * # Arguments
+ * * `password` - The password used as the key of HMAC.
+ * * `salt` - The salt value to use.
+ * * `iterations` - The iteration count.
+ * * `output` - The output buffer to fill with the derived key value.
+ *
+ */
+#[cfg(all(feature = "hmac", feature = "sha1"))]
+pub fn pbkdf2_hmac_sha1(password: &[u8], salt: &[u8], iterations: u32, output: &mut [u8]) {
+    pbkdf2(
+        &mut Hmac::new(Sha1::new(), password),
+        salt,
+        iterations,
+        output,
+    )
+}
+
+/**
+ * Execute the PBKDF2 Key Derivation Function using keyed Blake2b-512 as the Pseudo Random
+ * Function, instead of HMAC: `PRF(K, data) = Blake2b(key=K, input=data)`.
+ *
+ * Blake2b accepts keys of at most 64 bytes, so passwords longer than 64 bytes are first
+ * hashed with Blake2b-512 to make the key.
+ *
+ * # Arguments
+ * * `password` - The password used as the key of the Pseudo Random Function.
+ * * `salt` - The salt value to use.
+ * * `iterations` - The iteration count.
+ * * `output` - The output buffer to fill with the derived key value.
+ *
+ */
+#[cfg(feature = "blake2")]
+pub fn pbkdf2_blake2b(password: &[u8], salt: &[u8], iterations: u32, output: &mut [u8]) {
+    let mut mac = if password.len() > 64 {
+        let key = blake2b::Context::<512>::new().update(password).finalize();
+        Blake2bPrf::new(&key)
+    } else {
+        Blake2bPrf::new(password)
+    };
+    pbkdf2(&mut mac, salt, iterations, output)
+}
+
+// Keyed Blake2b-512 as a Mac, where reset goes back to the keyed state
+#[cfg(feature = "blake2")]
+struct Blake2bPrf {
+    initial: blake2b::ContextDyn,
+    ctx: blake2b::ContextDyn,
+}
+
+#[cfg(feature = "blake2")]
+impl Blake2bPrf {
+    fn new(key: &[u8]) -> Self {
+        let initial = blake2b::ContextDyn::new_keyed(64, key);
+        Self {
+            ctx: initial.clone(),
+            initial,
+        }
+    }
+}
+
+#[cfg(feature = "blake2")]
+impl Mac for Blake2bPrf {
+    fn input(&mut self, data: &[u8]) {
+        self.ctx.update_mut(data)
+    }
+
+    fn reset(&mut self) {
+        self.ctx = self.initial.clone();
+    }
+
+    fn result(&mut self) -> MacResult {
+        let mut code = [0u8; 64];
+        self.raw_result(&mut code);
+        MacResult::new(&code)
+    }
+
+    fn raw_result(&mut self, output: &mut [u8]) {
+        self.ctx.clone().finalize_at(output)
+    }
+
+    fn output_bytes(&self) -> usize {
+        64
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::pbkdf2;
@@ -130,4 +342,295 @@ mod test {
             ]
         )
     }
+
+    #[cfg(feature = "sha2")]
+    #[test]
+    fn multiple_blocks() {
+        use super::calculate_block;
+        use crate::sha2::Sha256;
+
+        // generated with python's hashlib.pbkdf2_hmac
+        let expected = [
+            0xad, 0x35, 0x24, 0x0a, 0xc6, 0x83, 0xfe, 0xbf, 0xaf, 0x3c, 0xd4, 0x9d, 0x84, 0x54,
+            0x73, 0xfb, 0xbb, 0xaa, 0x24, 0x37, 0xf5, 0xf8, 0x2d, 0x5a, 0x41, 0x5a, 0xe0, 0x0a,
+            0xc7, 0x6c, 0x6b, 0xfc, 0xcf, 0x9a, 0x9b, 0x8d, 0x6d, 0x2f, 0xe4, 0xa1, 0xe7, 0x00,
+            0xc4, 0x46, 0x0b, 0x04, 0x0d, 0xbe, 0xd6, 0x92, 0xc1, 0xcb, 0x85, 0xa7, 0x47, 0xf3,
+            0x55, 0x88, 0xc0, 0x89, 0x30, 0xfc, 0xfc, 0x41,
+        ];
+
+        let mut mac = Hmac::new(Sha256::new(), b"password");
+        let mut out = [0u8; 64];
+        pbkdf2(&mut mac, b"salt", 3, &mut out);
+        assert_eq!(out, expected);
+
+        // the output is T(1) || T(2)
+        let mut scratch = [0u8; 32];
+        let mut block = [0u8; 32];
+        calculate_block(
+            &mut mac,
+            b"salt",
+            3,
+            1,
+            &mut scratch,
+            &mut block,
+            &mut || {},
+        );
+        assert_eq!(block, out[0..32]);
+        calculate_block(
+            &mut mac,
+            b"salt",
+            3,
+            2,
+            &mut scratch,
+            &mut block,
+            &mut || {},
+        );
+        assert_eq!(block, out[32..64]);
+
+        // a partial last block is the prefix of the full block
+        let mut partial = [0u8; 40];
+        pbkdf2(&mut mac, b"salt", 3, &mut partial);
+        assert_eq!(partial, out[0..40]);
+
+        // an empty output is a no-op
+        pbkdf2(&mut mac, b"salt", 3, &mut []);
+    }
+
+    #[cfg(any(feature = "blake2", all(feature = "hmac", feature = "sha1")))]
+    struct Test {
+        password: &'static [u8],
+        salt: &'static [u8],
+        c: u32,
+        expected: &'static [u8],
+    }
+
+    #[cfg(all(feature = "hmac", feature = "sha1"))]
+    #[test]
+    fn pbkdf2_hmac_sha1() {
+        // RFC6070 section 2, and the WPA2 pre-shared key of IEEE 802.11i annex H.4
+        let tests = [
+            Test {
+                password: b"password",
+                salt: b"salt",
+                c: 1,
+                expected: &[
+                    0x0c, 0x60, 0xc8, 0x0f, 0x96, 0x1f, 0x0e, 0x71, 0xf3, 0xa9, 0xb5, 0x24, 0xaf,
+                    0x60, 0x12, 0x06, 0x2f, 0xe0, 0x37, 0xa6,
+                ],
+            },
+            Test {
+                password: b"password",
+                salt: b"salt",
+                c: 2,
+                expected: &[
+                    0xea, 0x6c, 0x01, 0x4d, 0xc7, 0x2d, 0x6f, 0x8c, 0xcd, 0x1e, 0xd9, 0x2a, 0xce,
+                    0x1d, 0x41, 0xf0, 0xd8, 0xde, 0x89, 0x57,
+                ],
+            },
+            Test {
+                password: b"password",
+                salt: b"salt",
+                c: 4096,
+                expected: &[
+                    0x4b, 0x00, 0x79, 0x01, 0xb7, 0x65, 0x48, 0x9a, 0xbe, 0xad, 0x49, 0xd9, 0x26,
+                    0xf7, 0x21, 0xd0, 0x65, 0xa4, 0x29, 0xc1,
+                ],
+            },
+            Test {
+                password: b"passwordPASSWORDpassword",
+                salt: b"saltSALTsaltSALTsaltSALTsaltSALTsalt",
+                c: 4096,
+                expected: &[
+                    0x3d, 0x2e, 0xec, 0x4f, 0xe4, 0x1c, 0x84, 0x9b, 0x80, 0xc8, 0xd8, 0x36, 0x62,
+                    0xc0, 0xe4, 0x4a, 0x8b, 0x29, 0x1a, 0x96, 0x4c, 0xf2, 0xf0, 0x70, 0x38,
+                ],
+            },
+            Test {
+                password: b"pass\0word",
+                salt: b"sa\0lt",
+                c: 4096,
+                expected: &[
+                    0x56, 0xfa, 0x6a, 0xa7, 0x55, 0x48, 0x09, 0x9d, 0xcc, 0x37, 0xd7, 0xf0, 0x34,
+                    0x25, 0xe0, 0xc3,
+                ],
+            },
+            Test {
+                password: b"password",
+                salt: b"IEEE",
+                c: 4096,
+                expected: &[
+                    0xf4, 0x2c, 0x6f, 0xc5, 0x2d, 0xf0, 0xeb, 0xef, 0x9e, 0xbb, 0x4b, 0x90, 0xb3,
+                    0x8a, 0x5f, 0x90, 0x2e, 0x83, 0xfe, 0x1b, 0x13, 0x5a, 0x70, 0xe2, 0x3a, 0xed,
+                    0x76, 0x2e, 0x97, 0x10, 0xa1, 0x2e,
+                ],
+            },
+        ];
+        for t in tests.iter() {
+            let mut out = [0u8; 32];
+            let out = &mut out[..t.expected.len()];
+            super::pbkdf2_hmac_sha1(t.password, t.salt, t.c, out);
+            assert_eq!(out, t.expected);
+        }
+    }
+
+    #[cfg(feature = "sha2")]
+    #[test]
+    fn progress() {
+        use super::pbkdf2_with_progress;
+        use crate::sha2::Sha256;
+        use core::cell::RefCell;
+
+        let calls = RefCell::new(alloc::vec::Vec::new());
+        let mut expected = [0u8; 32];
+        let mut out = [0u8; 32];
+        pbkdf2(
+            &mut Hmac::new(Sha256::new(), b"password"),
+            b"salt",
+            100,
+            &mut expected,
+        );
+        pbkdf2_with_progress(
+            &mut Hmac::new(Sha256::new(), b"password"),
+            b"salt",
+            100,
+            &mut out,
+            |current, total| calls.borrow_mut().push((current, total)),
+        );
+        assert_eq!(out, expected);
+        let calls = calls.into_inner();
+        assert_eq!(calls.len(), 100);
+        for (i, &(current, total)) in calls.iter().enumerate() {
+            assert_eq!(current, i as u32 + 1);
+            assert_eq!(total, 100);
+        }
+
+        // 2 blocks and a half of output
+        let calls = RefCell::new(alloc::vec::Vec::new());
+        let mut out = [0u8; 80];
+        pbkdf2_with_progress(
+            &mut Hmac::new(Sha256::new(), b"password"),
+            b"salt",
+            3,
+            &mut out,
+            |current, total| calls.borrow_mut().push((current, total)),
+        );
+        assert_eq!(
+            calls.into_inner(),
+            (1..=9).map(|i| (i, 9)).collect::<alloc::vec::Vec<_>>()
+        );
+    }
+
+    #[cfg(feature = "sha2")]
+    #[test]
+    fn no_alloc() {
+        use super::pbkdf2_no_alloc;
+        use crate::sha2::{Sha256, Sha512};
+
+        for len in [0, 1, 31, 32, 33, 64, 100] {
+            let mut expected = [0u8; 100];
+            let mut out = [0u8; 100];
+            let mut mac = Hmac::new(Sha256::new(), b"password");
+            pbkdf2(&mut mac, b"salt", 5, &mut expected[..len]);
+            pbkdf2_no_alloc(&mut mac, b"salt", 5, &mut out[..len], &mut [0u8; 32]);
+            assert_eq!(out, expected, "sha256 length {}", len);
+
+            let mut mac = Hmac::new(Sha512::new(), b"password");
+            pbkdf2(&mut mac, b"salt", 3, &mut expected[..len]);
+            pbkdf2_no_alloc(&mut mac, b"salt", 3, &mut out[..len], &mut [0u8; 64]);
+            assert_eq!(out, expected, "sha512 length {}", len);
+        }
+    }
+
+    #[cfg(feature = "sha2")]
+    #[test]
+    #[should_panic]
+    fn no_alloc_wrong_hlen() {
+        use crate::sha2::Sha256;
+        let mut out = [0u8; 32];
+        super::pbkdf2_no_alloc(
+            &mut Hmac::new(Sha256::new(), b"password"),
+            b"salt",
+            1,
+            &mut out,
+            &mut [0u8; 64],
+        );
+    }
+
+    // RFC6070 test vector with 2^24 iterations
+    #[cfg(all(feature = "hmac", feature = "sha1"))]
+    #[test]
+    #[ignore]
+    fn pbkdf2_hmac_sha1_large() {
+        let mut out = [0u8; 20];
+        super::pbkdf2_hmac_sha1(b"password", b"salt", 16777216, &mut out);
+        assert_eq!(
+            out,
+            [
+                0xee, 0xfe, 0x3d, 0x61, 0xcd, 0x4d, 0xa4, 0xe4, 0xe9, 0x94, 0x5b, 0x3d, 0x6b, 0xa2,
+                0x15, 0x8c, 0x26, 0x34, 0xe9, 0x84
+            ]
+        );
+    }
+
+    #[cfg(feature = "blake2")]
+    #[test]
+    fn pbkdf2_blake2b() {
+        // generated with a python implementation using hashlib's keyed blake2b
+        let tests: [Test; 4] = [
+            Test {
+                password: b"password",
+                salt: b"salt",
+                c: 1,
+                expected: &[
+                    0xae, 0xd4, 0x22, 0x67, 0xea, 0xef, 0x4a, 0x8f, 0x8f, 0xda, 0xf3, 0x97, 0xe6,
+                    0x77, 0x06, 0x27, 0x24, 0xc6, 0xfc, 0xed, 0x7d, 0x9c, 0xfd, 0xb6, 0x95, 0x4d,
+                    0xe4, 0x4d, 0xf5, 0x86, 0xb2, 0xa4, 0x10, 0xef, 0x08, 0xb3, 0xf4, 0x97, 0x81,
+                    0xa5, 0x83, 0x29, 0x3b, 0xb3, 0x01, 0xf8, 0x36, 0x5f, 0x76, 0x85, 0x30, 0x6f,
+                    0x4f, 0x2a, 0x75, 0x3a, 0x5e, 0x1e, 0x3a, 0x92, 0x29, 0xd1, 0xd3, 0x90,
+                ],
+            },
+            Test {
+                password: b"password",
+                salt: b"salt",
+                c: 2,
+                expected: &[
+                    0x2e, 0xd6, 0xd6, 0x47, 0xcf, 0x18, 0x99, 0x6f, 0xe4, 0xac, 0xb6, 0xb1, 0x4a,
+                    0x33, 0x7b, 0xa3, 0xbe, 0xc7, 0xe8, 0x84, 0xa0, 0x44, 0x68, 0xe1, 0xb0, 0xc8,
+                    0xd4, 0xc9, 0x6c, 0x3a, 0x01, 0xf3,
+                ],
+            },
+            Test {
+                password: b"password",
+                salt: b"salt",
+                c: 4096,
+                expected: &[
+                    0x08, 0xf7, 0x33, 0x5a, 0x8a, 0x54, 0x16, 0xbd, 0x05, 0x44, 0x16, 0x1c, 0x11,
+                    0x7e, 0xee, 0x7c, 0xdb, 0xfa, 0xe7, 0x55, 0xa1, 0x2e, 0x7b, 0x2a, 0x12, 0xd6,
+                    0xb0, 0xd0, 0xac, 0xa3, 0xe7, 0x51, 0xba, 0xa2, 0x78, 0xfb, 0xeb, 0x13, 0xe4,
+                    0x79, 0x16, 0x06, 0x7c, 0xf9, 0xe1, 0xbf, 0xe8, 0x77, 0x55, 0xda, 0xd8, 0x44,
+                    0x16, 0x61, 0x23, 0xf9, 0xdc, 0xbd, 0xed, 0xf0, 0xd4, 0xc9, 0x29, 0x2b, 0x72,
+                    0xa3, 0x39, 0x46, 0xbb, 0xdb, 0x89, 0x19, 0x3f, 0xc1, 0x30, 0x31, 0x40, 0xfa,
+                    0xd0, 0x9d,
+                ],
+            },
+            Test {
+                password: &[b'p'; 100],
+                salt: b"saltSALTsaltSALT",
+                c: 3,
+                expected: &[
+                    0xd7, 0x6a, 0xe9, 0x45, 0x86, 0x77, 0xb8, 0x20, 0xbd, 0xcf, 0x2b, 0xe4, 0x71,
+                    0x72, 0x63, 0x87, 0x72, 0x3a, 0xfa, 0x18, 0xad, 0x9c, 0xb6, 0xf9, 0x48, 0x97,
+                    0x05, 0x1c, 0x50, 0xf2, 0xdd, 0xe2, 0xb5, 0x0a, 0x7b, 0xe1, 0xbb, 0xac, 0x1d,
+                    0xc5, 0xd0, 0x63, 0x9f, 0x4e, 0x2a, 0x55, 0xdc, 0x91, 0xc2, 0x10, 0x42, 0xdd,
+                    0x41, 0x27, 0x09, 0xc8, 0x16, 0xf3, 0x7b, 0x97, 0xb9, 0xdb, 0x4d, 0x7a,
+                ],
+            },
+        ];
+        for t in tests.iter() {
+            let mut out = [0u8; 80];
+            let out = &mut out[..t.expected.len()];
+            super::pbkdf2_blake2b(t.password, t.salt, t.c, out);
+            assert_eq!(out, t.expected);
+        }
+    }
 }
@@ -10,6 +10,13 @@
 //! let mac = context.result();
 //! ```
 //!
+//! The implementation has 2 different backends:
+//! * a 32bits architecture 26 bits limbs using u32 / u64 for multiplication
+//! * a 64bits architecture 44 bits limbs using u64 / u128 for multiplication
+//!
+//! The 32 bits backend is enabled when selecting the "force-32bits"
+//! feature and also for known 32 bits architecture (currently just arm32).
+//!
 //! [1]: <https://cr.yp.to/mac/poly1305-20050329.pdf>
 
 // This is a port of Andrew Moons poly1305-donna
@@ -17,173 +24,60 @@
 
 use core::cmp::min;
 
-use crate::cryptoutil::{read_u32_le, write_u32_le};
+use crate::constant_time::CtEqual;
 use crate::mac::{Mac, MacResult};
 
+#[cfg(any(target_arch = "arm", feature = "force-32bits"))]
+mod poly1305_32;
+
+#[cfg(not(any(target_arch = "arm", feature = "force-32bits")))]
+mod poly1305_64;
+
+#[cfg(any(target_arch = "arm", feature = "force-32bits"))]
+use poly1305_32::State;
+
+#[cfg(not(any(target_arch = "arm", feature = "force-32bits")))]
+use poly1305_64::State;
+
 /// `Poly1305` Context
 ///
 /// Use the `Mac` traits for interaction
 #[derive(Clone)]
 pub struct Poly1305 {
-    r: [u32; 5],
-    h: [u32; 5],
-    pad: [u32; 4],
+    state: State,
     leftover: usize,
     buffer: [u8; 16],
     finalized: bool,
 }
 
-#[inline(always)]
-fn mul64(a: u32, b: u32) -> u64 {
-    a as u64 * b as u64
-}
-
 impl Poly1305 {
     /// Create a new `Poly1305` context using the key (32 bytes)
     pub fn new(key: &[u8; 32]) -> Self {
-        // r &= 0xffffffc0ffffffc0ffffffc0fffffff
-        let r = [
-            (read_u32_le(&key[0..4])) & 0x3ffffff,
-            (read_u32_le(&key[3..7]) >> 2) & 0x3ffff03,
-            (read_u32_le(&key[6..10]) >> 4) & 0x3ffc0ff,
-            (read_u32_le(&key[9..13]) >> 6) & 0x3f03fff,
-            (read_u32_le(&key[12..16]) >> 8) & 0x00fffff,
-        ];
-
-        let pad = [
-            read_u32_le(&key[16..20]),
-            read_u32_le(&key[20..24]),
-            read_u32_le(&key[24..28]),
-            read_u32_le(&key[28..32]),
-        ];
-
         Poly1305 {
-            r,
-            h: [0u32; 5],
-            pad,
+            state: State::new(key),
             leftover: 0,
             buffer: [0u8; 16],
             finalized: false,
         }
     }
 
-    #[rustfmt::skip]
-    fn block(&mut self, m: &[u8]) {
-        let hibit : u32 = if self.finalized { 0 } else { 1 << 24 };
-
-        let r0 = self.r[0];
-        let r1 = self.r[1];
-        let r2 = self.r[2];
-        let r3 = self.r[3];
-        let r4 = self.r[4];
-
-        let s1 = r1 * 5;
-        let s2 = r2 * 5;
-        let s3 = r3 * 5;
-        let s4 = r4 * 5;
-
-        let mut h0 = self.h[0];
-        let mut h1 = self.h[1];
-        let mut h2 = self.h[2];
-        let mut h3 = self.h[3];
-        let mut h4 = self.h[4];
-
-        // h += m
-        h0 += (read_u32_le(&m[0..4])     ) & 0x3ffffff;
-        h1 += (read_u32_le(&m[3..7]) >> 2) & 0x3ffffff;
-        h2 += (read_u32_le(&m[6..10]) >> 4) & 0x3ffffff;
-        h3 += (read_u32_le(&m[9..13]) >> 6) & 0x3ffffff;
-        h4 += (read_u32_le(&m[12..16]) >> 8) | hibit;
-
-        // h *= r
-        let     d0 = mul64(h0, r0) + mul64(h1, s4) + mul64(h2, s3) + mul64(h3, s2) + mul64(h4, s1);
-        let mut d1 = mul64(h0, r1) + mul64(h1, r0) + mul64(h2, s4) + mul64(h3, s3) + mul64(h4, s2);
-        let mut d2 = mul64(h0, r2) + mul64(h1, r1) + mul64(h2, r0) + mul64(h3, s4) + mul64(h4, s3);
-        let mut d3 = mul64(h0, r3) + mul64(h1, r2) + mul64(h2, r1) + mul64(h3, r0) + mul64(h4, s4);
-        let mut d4 = mul64(h0, r4) + mul64(h1, r3) + mul64(h2, r2) + mul64(h3, r1) + mul64(h4, r0);
-
-        // (partial) h %= p
-        let mut c : u32;
-                        c = (d0 >> 26) as u32; h0 = d0 as u32 & 0x3ffffff;
-        d1 += c as u64; c = (d1 >> 26) as u32; h1 = d1 as u32 & 0x3ffffff;
-        d2 += c as u64; c = (d2 >> 26) as u32; h2 = d2 as u32 & 0x3ffffff;
-        d3 += c as u64; c = (d3 >> 26) as u32; h3 = d3 as u32 & 0x3ffffff;
-        d4 += c as u64; c = (d4 >> 26) as u32; h4 = d4 as u32 & 0x3ffffff;
-        h0 += c * 5;    c = h0 >> 26; h0 &= 0x3ffffff;
-        h1 += c;
-
-        self.h[0] = h0;
-        self.h[1] = h1;
-        self.h[2] = h2;
-        self.h[3] = h3;
-        self.h[4] = h4;
+    /// Verify in constant time that the tag of the data is equal to `expected_tag`
+    pub fn verify(&mut self, expected_tag: &[u8; 16]) -> bool {
+        let mut tag = [0u8; 16];
+        self.raw_result(&mut tag);
+        CtEqual::ct_eq(&tag, expected_tag).into()
     }
 
-    #[rustfmt::skip]
-    fn finish(&mut self) {
+    // compute the tag from a copy of the state, processing the leftover bytes as the last partial block
+    fn tag(&self) -> [u8; 16] {
+        let mut state = self.state.clone();
         if self.leftover > 0 {
-            self.buffer[self.leftover] = 1;
-            for i in self.leftover+1..16 {
-                self.buffer[i] = 0;
-            }
-            self.finalized = true;
-            let tmp = self.buffer;
-            self.block(&tmp);
+            let mut block = [0u8; 16];
+            block[..self.leftover].copy_from_slice(&self.buffer[..self.leftover]);
+            block[self.leftover] = 1;
+            state.block(&block, true);
         }
-
-        // fully carry h
-        let mut h0 = self.h[0];
-        let mut h1 = self.h[1];
-        let mut h2 = self.h[2];
-        let mut h3 = self.h[3];
-        let mut h4 = self.h[4];
-
-        let mut c : u32;
-                     c = h1 >> 26; h1 &= 0x3ffffff;
-        h2 +=     c; c = h2 >> 26; h2 &= 0x3ffffff;
-        h3 +=     c; c = h3 >> 26; h3 &= 0x3ffffff;
-        h4 +=     c; c = h4 >> 26; h4 &= 0x3ffffff;
-        h0 += c * 5; c = h0 >> 26; h0 &= 0x3ffffff;
-        h1 +=     c;
-
-        // compute h + -p
-        let mut g0 = h0.wrapping_add(5); c = g0 >> 26; g0 &= 0x3ffffff;
-        let mut g1 = h1.wrapping_add(c); c = g1 >> 26; g1 &= 0x3ffffff;
-        let mut g2 = h2.wrapping_add(c); c = g2 >> 26; g2 &= 0x3ffffff;
-        let mut g3 = h3.wrapping_add(c); c = g3 >> 26; g3 &= 0x3ffffff;
-        let mut g4 = h4.wrapping_add(c).wrapping_sub(1 << 26);
-
-        // select h if h < p, or h + -p if h >= p
-        let mut mask = (g4 >> (32 - 1)).wrapping_sub(1);
-        g0 &= mask;
-        g1 &= mask;
-        g2 &= mask;
-        g3 &= mask;
-        g4 &= mask;
-        mask = !mask;
-        h0 = (h0 & mask) | g0;
-        h1 = (h1 & mask) | g1;
-        h2 = (h2 & mask) | g2;
-        h3 = (h3 & mask) | g3;
-        h4 = (h4 & mask) | g4;
-
-        // h = h % (2^128)
-        h0 = ((h0      ) | (h1 << 26)) & 0xffffffff;
-        h1 = ((h1 >>  6) | (h2 << 20)) & 0xffffffff;
-        h2 = ((h2 >> 12) | (h3 << 14)) & 0xffffffff;
-        h3 = ((h3 >> 18) | (h4 <<  8)) & 0xffffffff;
-
-        // h = mac = (h + pad) % (2^128)
-        let mut f : u64;
-        f = h0 as u64 + self.pad[0] as u64            ; h0 = f as u32;
-        f = h1 as u64 + self.pad[1] as u64 + (f >> 32); h1 = f as u32;
-        f = h2 as u64 + self.pad[2] as u64 + (f >> 32); h2 = f as u32;
-        f = h3 as u64 + self.pad[3] as u64 + (f >> 32); h3 = f as u32;
-
-        self.h[0] = h0;
-        self.h[1] = h1;
-        self.h[2] = h2;
-        self.h[3] = h3;
+        state.finalize()
     }
 }
 
@@ -195,10 +89,7 @@ impl Mac for Poly1305 {
         if self.leftover > 0 {
             let want = min(16 - self.leftover, m.len());
 
-            #[allow(clippy::needless_range_loop)]
-            for i in 0..want {
-                self.buffer[self.leftover + i] = m[i];
-            }
+            self.buffer[self.leftover..self.leftover + want].copy_from_slice(&m[..want]);
             m = &m[want..];
             self.leftover += want;
 
@@ -206,25 +97,25 @@ impl Mac for Poly1305 {
                 return;
             }
 
-            // self.block(self.buffer[..]);
             let tmp = self.buffer;
-            self.block(&tmp);
+            self.state.block(&tmp, false);
 
             self.leftover = 0;
         }
 
         while m.len() >= 16 {
-            self.block(&m[0..16]);
-            m = &m[16..];
+            let (block, rest) = m.split_at(16);
+            self.state.block(block.try_into().unwrap(), false);
+            m = rest;
         }
 
-        self.buffer[..m.len()].copy_from_slice(&m[..]);
+        self.buffer[..m.len()].copy_from_slice(m);
 
         self.leftover = m.len();
     }
 
     fn reset(&mut self) {
-        self.h = [0u32; 5];
+        self.state.reset();
         self.leftover = 0;
         self.finalized = false;
     }
@@ -237,13 +128,8 @@ impl Mac for Poly1305 {
 
     fn raw_result(&mut self, output: &mut [u8]) {
         assert!(output.len() >= 16);
-        if !self.finalized {
-            self.finish();
-        }
-        write_u32_le(&mut output[0..4], self.h[0]);
-        write_u32_le(&mut output[4..8], self.h[1]);
-        write_u32_le(&mut output[8..12], self.h[2]);
-        write_u32_le(&mut output[12..16], self.h[3]);
+        self.finalized = true;
+        output[0..16].copy_from_slice(&self.tag());
     }
 
     fn output_bytes(&self) -> usize {
@@ -374,6 +260,31 @@ mod test {
         poly1305(key, msg, &mut mac);
         assert_eq!(&mac[..], &expected[..]);
     }
+    #[test]
+    fn verify() {
+        let key = [7u8; 32];
+        let msg = b"data to authenticate";
+        let mut tag = [0u8; 16];
+        poly1305(&key, msg, &mut tag);
+
+        let mut poly = Poly1305::new(&key);
+        poly.input(msg);
+        assert!(poly.verify(&tag));
+
+        // tags at different hamming distances from the computed tag are rejected
+        for i in 0..16 {
+            let mut one_bit = tag;
+            one_bit[i] ^= 0x01;
+            assert!(!poly.verify(&one_bit));
+        }
+        let all_bits = tag.map(|b| !b);
+        assert!(!poly.verify(&all_bits));
+
+        // through the Mac trait
+        assert!(Mac::verify(&mut poly, &tag[..]));
+        assert!(!Mac::verify(&mut poly, &all_bits[..]));
+        assert!(!Mac::verify(&mut poly, &tag[..15]));
+    }
 }
 
 #[cfg(all(test, feature = "with-bench"))]
@@ -0,0 +1,135 @@
+//! Poly1305 64 bits backend, with the accumulator in 3 limbs of 44, 44 and 42 bits
+
+use crate::cryptoutil::{read_u64_le, write_u64_le};
+
+const MASK44: u64 = 0xfffffffffff;
+const MASK42: u64 = 0x3ffffffffff;
+
+#[inline(always)]
+fn mul128(a: u64, b: u64) -> u128 {
+    a as u128 * b as u128
+}
+
+#[derive(Clone)]
+pub(super) struct State {
+    r: [u64; 3],
+    h: [u64; 3],
+    pad: [u64; 2],
+}
+
+impl State {
+    pub fn new(key: &[u8; 32]) -> Self {
+        // r &= 0xffffffc0ffffffc0ffffffc0fffffff
+        let t0 = read_u64_le(&key[0..8]);
+        let t1 = read_u64_le(&key[8..16]);
+        let r = [
+            t0 & 0xffc0fffffff,
+            ((t0 >> 44) | (t1 << 20)) & 0xfffffc0ffff,
+            (t1 >> 24) & 0x00ffffffc0f,
+        ];
+
+        let pad = [read_u64_le(&key[16..24]), read_u64_le(&key[24..32])];
+
+        State {
+            r,
+            h: [0u64; 3],
+            pad,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.h = [0u64; 3];
+    }
+
+    /// Process a block of 16 bytes, when `last_partial` is set the block is
+    /// already padded and the 2^128 bit is not added
+    #[rustfmt::skip]
+    pub fn block(&mut self, m: &[u8; 16], last_partial: bool) {
+        let hibit : u64 = if last_partial { 0 } else { 1 << 40 };
+
+        let r0 = self.r[0];
+        let r1 = self.r[1];
+        let r2 = self.r[2];
+
+        let s1 = r1 * (5 << 2);
+        let s2 = r2 * (5 << 2);
+
+        let mut h0 = self.h[0];
+        let mut h1 = self.h[1];
+        let mut h2 = self.h[2];
+
+        // h += m
+        let t0 = read_u64_le(&m[0..8]);
+        let t1 = read_u64_le(&m[8..16]);
+        h0 += t0 & MASK44;
+        h1 += ((t0 >> 44) | (t1 << 20)) & MASK44;
+        h2 += ((t1 >> 24) & MASK42) | hibit;
+
+        // h *= r
+        let     d0 = mul128(h0, r0) + mul128(h1, s2) + mul128(h2, s1);
+        let mut d1 = mul128(h0, r1) + mul128(h1, r0) + mul128(h2, s2);
+        let mut d2 = mul128(h0, r2) + mul128(h1, r1) + mul128(h2, r0);
+
+        // (partial) h %= p
+        let mut c : u64;
+                         c = (d0 >> 44) as u64; h0 = d0 as u64 & MASK44;
+        d1 += c as u128; c = (d1 >> 44) as u64; h1 = d1 as u64 & MASK44;
+        d2 += c as u128; c = (d2 >> 42) as u64; h2 = d2 as u64 & MASK42;
+        h0 += c * 5;     c = h0 >> 44; h0 &= MASK44;
+        h1 += c;
+
+        self.h[0] = h0;
+        self.h[1] = h1;
+        self.h[2] = h2;
+    }
+
+    /// Compute the tag from the accumulator
+    #[rustfmt::skip]
+    pub fn finalize(&self) -> [u8; 16] {
+        // fully carry h
+        let mut h0 = self.h[0];
+        let mut h1 = self.h[1];
+        let mut h2 = self.h[2];
+
+        let mut c : u64;
+                     c = h1 >> 44; h1 &= MASK44;
+        h2 +=     c; c = h2 >> 42; h2 &= MASK42;
+        h0 += c * 5; c = h0 >> 44; h0 &= MASK44;
+        h1 +=     c; c = h1 >> 44; h1 &= MASK44;
+        h2 +=     c; c = h2 >> 42; h2 &= MASK42;
+        h0 += c * 5; c = h0 >> 44; h0 &= MASK44;
+        h1 +=     c;
+
+        // compute h + -p
+        let mut g0 = h0.wrapping_add(5); c = g0 >> 44; g0 &= MASK44;
+        let mut g1 = h1.wrapping_add(c); c = g1 >> 44; g1 &= MASK44;
+        let mut g2 = h2.wrapping_add(c).wrapping_sub(1 << 42);
+
+        // select h if h < p, or h + -p if h >= p
+        let mut mask = (g2 >> (64 - 1)).wrapping_sub(1);
+        g0 &= mask;
+        g1 &= mask;
+        g2 &= mask;
+        mask = !mask;
+        h0 = (h0 & mask) | g0;
+        h1 = (h1 & mask) | g1;
+        h2 = (h2 & mask) | g2;
+
+        // h = (h + pad)
+        let t0 = self.pad[0];
+        let t1 = self.pad[1];
+
+        h0 += t0 & MASK44;                                  c = h0 >> 44; h0 &= MASK44;
+        h1 += (((t0 >> 44) | (t1 << 20)) & MASK44) + c;     c = h1 >> 44; h1 &= MASK44;
+        h2 += ((t1 >> 24) & MASK42) + c;                    h2 &= MASK42;
+
+        // mac = h % (2^128)
+        let h0 = h0 | (h1 << 44);
+        let h1 = (h1 >> 20) | (h2 << 24);
+
+        let mut tag = [0u8; 16];
+        write_u64_le(&mut tag[0..8], h0);
+        write_u64_le(&mut tag[8..16], h1);
+        tag
+    }
+}
@@ -0,0 +1,203 @@
+//! Schnorr signatures over the Ed25519 curve
+//!
+//! A basic Schnorr signature scheme using the Ed25519 prime order group, meant as
+//! a building block for threshold and multi-signature protocols, where the keys and
+//! nonces are computed by the protocol instead of derived from a seed as in
+//! [`ed25519`](crate::ed25519).
+//!
+//! With the secret scalar `x`, the public point `A = x*B`, and a nonce `k`:
+//!
+//! * `R = k*B`
+//! * `e = H(R || A || message)`, where H is SHA256 interpreted as a little endian
+//!   integer and reduced modulo the group order, as in RFC8032
+//! * `s = k + e*x`
+//!
+//! The signature is the pair of scalars `(e, s)`, and is verified by recomputing
+//! `R = s*B - e*A` and checking that it hashes to `e`.
+//!
+//! The nonce must be uniformly random and never reused: two signatures with the same
+//! nonce reveal the secret key.
+//!
+//! # Examples
+//!
+//! ```
+//! use cryptoxide::curve25519::{Ge, Scalar};
+//! use cryptoxide::schnorr;
+//!
+//! let secret = Scalar::from_uniform_bytes(&[1u8; 64]); // only for example !
+//! let nonce = Scalar::from_uniform_bytes(&[2u8; 64]); // only for example !
+//! let public = Ge::scalarmult_base(&secret);
+//!
+//! let (e, s) = schnorr::sign(b"message", &nonce, &secret, &public);
+//! assert!(schnorr::verify(b"message", &public, (&e, &s)));
+//! ```
+
+use crate::curve25519::{scalar, Ge, GePartial, Scalar};
+use crate::hashing::sha2::Sha256;
+
+// e = H(R || A || message) mod l
+fn challenge(r: &[u8; 32], public: &[u8; 32], message: &[u8]) -> Scalar {
+    let hash = Sha256::new()
+        .update(r)
+        .update(public)
+        .update(message)
+        .finalize();
+    let mut wide = [0u8; 64];
+    wide[0..32].copy_from_slice(&hash);
+    Scalar::from_uniform_bytes(&wide)
+}
+
+/// Sign the message with the secret scalar and the associated public point,
+/// using the given nonce, and return the signature `(e, s)`
+pub fn sign(message: &[u8], nonce: &Scalar, secret: &Scalar, public: &Ge) -> (Scalar, Scalar) {
+    let r = Ge::scalarmult_base(nonce).to_bytes();
+    let e = challenge(&r, &public.to_bytes(), message);
+    let s = scalar::muladd(&e, secret, nonce);
+    (e, s)
+}
+
+/// Verify the signature `(e, s)` of the message with the public point
+pub fn verify(message: &[u8], public: &Ge, sig: (&Scalar, &Scalar)) -> bool {
+    let (e, s) = sig;
+    // R = s*B - e*A
+    let r = GePartial::double_scalarmult_vartime(e, -public, s).to_bytes();
+    challenge(&r, &public.to_bytes(), message) == *e
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curve25519::ge_decompress;
+
+    struct Test {
+        secret: [u8; 32],
+        nonce: [u8; 32],
+        message: &'static [u8],
+        public: [u8; 32],
+        challenge: [u8; 32],
+        response: [u8; 32],
+    }
+
+    #[test]
+    fn vectors() {
+        // generated with an independent python implementation
+        let tests = [
+            Test {
+                secret: [
+                    0x7a, 0x5f, 0x67, 0x0e, 0x01, 0x5c, 0x35, 0x2c, 0x7a, 0xd2, 0x2c, 0x6c, 0x65,
+                    0x97, 0x7c, 0x58, 0x39, 0xbf, 0xab, 0xb6, 0x48, 0x63, 0x7a, 0xcb, 0xc1, 0xe4,
+                    0x4e, 0x12, 0x8e, 0x92, 0x6a, 0x09,
+                ],
+                nonce: [
+                    0x70, 0x2d, 0x0c, 0xe6, 0xf6, 0xf3, 0x85, 0xc2, 0xf7, 0x4c, 0x1e, 0x03, 0xa8,
+                    0x95, 0x71, 0x60, 0xe8, 0x55, 0x5c, 0xa4, 0x1b, 0x22, 0x11, 0x52, 0x0a, 0x5e,
+                    0x1c, 0x14, 0x65, 0xbb, 0x8e, 0x0d,
+                ],
+                message: b"",
+                public: [
+                    0x01, 0x01, 0x47, 0x02, 0x29, 0xb7, 0xac, 0xaa, 0x6f, 0xd4, 0x78, 0x88, 0x61,
+                    0xe3, 0xd9, 0x93, 0xd6, 0x10, 0x0a, 0xf5, 0x59, 0xd9, 0x3b, 0x6d, 0x22, 0x39,
+                    0x66, 0x11, 0x6e, 0x05, 0x73, 0x21,
+                ],
+                challenge: [
+                    0xd9, 0x93, 0x4d, 0x68, 0x39, 0x3f, 0x7b, 0x95, 0x6a, 0x8f, 0xb1, 0x44, 0xf8,
+                    0xc3, 0x5e, 0xd8, 0x74, 0xda, 0x8e, 0xac, 0x09, 0xa7, 0xfa, 0xc3, 0xee, 0x43,
+                    0x60, 0x99, 0xf6, 0x17, 0x2a, 0x02,
+                ],
+                response: [
+                    0xa9, 0xc8, 0x53, 0x92, 0x33, 0x3d, 0x9e, 0x6a, 0x57, 0xa2, 0x5c, 0x92, 0x3f,
+                    0xc1, 0xb0, 0xf1, 0xae, 0x73, 0xf9, 0x74, 0x83, 0xdc, 0x9e, 0xee, 0x9e, 0x02,
+                    0xaf, 0xa9, 0x46, 0xe5, 0xa9, 0x01,
+                ],
+            },
+            Test {
+                secret: [
+                    0x82, 0x37, 0xc2, 0xb3, 0xc3, 0xe9, 0x64, 0x68, 0xe4, 0xf8, 0xb6, 0x81, 0xf5,
+                    0xee, 0xea, 0x04, 0x05, 0xb2, 0x79, 0xb7, 0x47, 0x2e, 0x97, 0xf8, 0x12, 0x17,
+                    0x1f, 0x6e, 0xea, 0xc0, 0x36, 0x0b,
+                ],
+                nonce: [
+                    0x8a, 0x9a, 0xec, 0x60, 0xe2, 0x80, 0x5e, 0x85, 0x5f, 0x57, 0x77, 0xde, 0x75,
+                    0x5b, 0xa7, 0x6e, 0x8e, 0x87, 0xef, 0x36, 0x7a, 0x2d, 0xa2, 0x2e, 0xa1, 0x8d,
+                    0xe9, 0x84, 0x01, 0x97, 0xbc, 0x04,
+                ],
+                message: b"abc",
+                public: [
+                    0xbe, 0x7f, 0x86, 0xf4, 0xb1, 0x0c, 0xe8, 0x82, 0xd2, 0x30, 0xd1, 0xc7, 0x47,
+                    0xd5, 0x94, 0xa5, 0x9a, 0x52, 0x73, 0x52, 0xc7, 0xe3, 0xa2, 0x43, 0xb6, 0x15,
+                    0x26, 0x0d, 0x8a, 0x59, 0x64, 0xf4,
+                ],
+                challenge: [
+                    0x67, 0x05, 0xb9, 0x72, 0xb6, 0xb0, 0x80, 0xd6, 0xcd, 0x97, 0x60, 0xf7, 0x75,
+                    0x73, 0xff, 0x9e, 0xdf, 0x92, 0x96, 0x60, 0x8e, 0xe0, 0x46, 0x15, 0xe3, 0x29,
+                    0x53, 0xda, 0x0d, 0x9a, 0x18, 0x0e,
+                ],
+                response: [
+                    0x9d, 0x02, 0x0c, 0xae, 0x27, 0xb5, 0x2c, 0x92, 0x47, 0x52, 0xcf, 0xe8, 0xd7,
+                    0xd5, 0x88, 0xaa, 0x31, 0xd1, 0xfc, 0x96, 0x37, 0xff, 0x3a, 0x92, 0x8f, 0x1d,
+                    0xb8, 0x4a, 0xa6, 0xe4, 0x63, 0x02,
+                ],
+            },
+            Test {
+                secret: [
+                    0xc3, 0x92, 0x4f, 0x38, 0xb6, 0x5b, 0xed, 0x46, 0xf1, 0x02, 0x2e, 0x68, 0x21,
+                    0x19, 0xaf, 0xb3, 0xfb, 0x6b, 0x9b, 0x65, 0x73, 0xc9, 0xd8, 0x4f, 0x7e, 0x26,
+                    0xc9, 0xfb, 0x7f, 0xd4, 0x0e, 0x07,
+                ],
+                nonce: [
+                    0x45, 0xba, 0x69, 0x17, 0xdc, 0x8d, 0x54, 0xf0, 0x73, 0xf8, 0xda, 0x2e, 0xf5,
+                    0x10, 0x2a, 0x06, 0xb0, 0xe2, 0xad, 0xed, 0x42, 0xb4, 0x24, 0x91, 0x79, 0xf9,
+                    0x51, 0x2f, 0x88, 0x6b, 0x46, 0x06,
+                ],
+                message: b"The quick brown fox jumps over the lazy dog",
+                public: [
+                    0x2c, 0x2a, 0xcb, 0xa1, 0xe3, 0x95, 0xe7, 0xb6, 0xa3, 0xc5, 0x6a, 0x51, 0x61,
+                    0x35, 0xde, 0x38, 0xdb, 0x81, 0xa0, 0x09, 0x2d, 0x77, 0x69, 0xf7, 0x90, 0x79,
+                    0xb1, 0x4a, 0x77, 0xdc, 0x2a, 0x69,
+                ],
+                challenge: [
+                    0x4f, 0x92, 0x62, 0xfd, 0xfa, 0x49, 0x23, 0xbf, 0xa9, 0xe3, 0xc9, 0x3d, 0x3b,
+                    0x79, 0x58, 0xb3, 0xd8, 0x02, 0xa7, 0xaf, 0xf6, 0x9c, 0x63, 0xa1, 0x40, 0x8f,
+                    0x2d, 0xed, 0x69, 0x96, 0x45, 0x0e,
+                ],
+                response: [
+                    0x63, 0xff, 0xb9, 0x1f, 0x25, 0x95, 0x74, 0x95, 0xc6, 0x61, 0x3f, 0x5a, 0x01,
+                    0x44, 0x1c, 0xd6, 0x2b, 0x97, 0xad, 0x15, 0xaa, 0xf0, 0x80, 0x12, 0xc7, 0x63,
+                    0x45, 0xaf, 0xce, 0xb8, 0xec, 0x09,
+                ],
+            },
+        ];
+
+        for (i, t) in tests.iter().enumerate() {
+            let secret = Scalar::from_bytes(&t.secret);
+            let nonce = Scalar::from_bytes(&t.nonce);
+            let public = Ge::scalarmult_base(&secret);
+            assert_eq!(public.to_bytes(), t.public, "public {}", i);
+
+            let (e, s) = sign(t.message, &nonce, &secret, &public);
+            assert_eq!(e.to_bytes(), t.challenge, "challenge {}", i);
+            assert_eq!(s.to_bytes(), t.response, "response {}", i);
+
+            let public = ge_decompress(&t.public).unwrap();
+            assert!(verify(t.message, &public, (&e, &s)), "verify {}", i);
+        }
+    }
+
+    #[test]
+    fn invalid() {
+        let secret = Scalar::from_uniform_bytes(&[1u8; 64]);
+        let nonce = Scalar::from_uniform_bytes(&[2u8; 64]);
+        let public = Ge::scalarmult_base(&secret);
+        let (e, s) = sign(b"message", &nonce, &secret, &public);
+        assert!(verify(b"message", &public, (&e, &s)));
+
+        assert!(!verify(b"messagf", &public, (&e, &s)));
+        assert!(!verify(b"message", &public, (&s, &e)));
+
+        let other = Ge::scalarmult_base(&Scalar::from_uniform_bytes(&[3u8; 64]));
+        assert!(!verify(b"message", &other, (&e, &s)));
+
+        let s_plus_one = scalar::muladd(&Scalar::ONE, &Scalar::ONE, &s);
+        assert!(!verify(b"message", &public, (&e, &s_plus_one)));
+    }
+}
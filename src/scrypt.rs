@@ -3,27 +3,23 @@
 //! # Examples
 //!
 //! ```
-//! use cryptoxide::scrypt::{scrypt, ScryptParams};
+//! use cryptoxide::scrypt::ScryptParams;
 //!
 //! let password = b"password";
 //! let salt = b"salt";
-//! let params = ScryptParams::new(4, 1, 1);
+//! let params = ScryptParams::builder().log_n(4).r(1).p(1).build().unwrap();
 //! let mut out = [0u8; 64];
-//! scrypt(password, salt, &params, &mut out);
+//! params.run(password, salt, &mut out);
 //! ```
 //!
 //! # References
 //! [1]: <http://www.tarsnap.com/scrypt/scrypt.pdf>
 //!
 
-use alloc::vec::Vec;
-use core::iter::repeat;
-use core::mem::size_of;
+use alloc::vec;
 
 use crate::cryptoutil::{read_u32_le, read_u32v_le, write_u32_le};
-use crate::hmac::Hmac;
-use crate::pbkdf2::pbkdf2;
-use crate::sha2::Sha256;
+use crate::hashing::sha2::Context256;
 
 // The salsa20/8 core function.
 fn salsa20_8(input: &[u8], output: &mut [u8]) {
@@ -148,13 +144,130 @@ fn scrypt_ro_mix(b: &mut [u8], v: &mut [u8], t: &mut [u8], n: usize) {
 /**
  * The Scrypt parameter values.
  */
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct ScryptParams {
     log_n: u8,
     r: u32,
     p: u32,
 }
 
+/// Possible errors when building the Scrypt parameters
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScryptError {
+    /// log_n should be at least 1 (N >= 2)
+    LogNZero,
+    /// log_n should be less than the number of bits of usize, and less than r * 16
+    LogNTooHigh,
+    /// r should be at least 1
+    RZero,
+    /// p should be at least 1
+    PZero,
+    /// r * p should be less than 2^30
+    RPTooHigh,
+    /// The memory needed (N * r * 128 or p * r * 128 bytes) doesn't fit in usize
+    MemoryOverflow,
+    /// The caller provided buffers are smaller than `scrypt_memory_requirement`
+    BufferTooSmall,
+    /// The output should be between 1 and (2^32 - 1) * 32 bytes
+    InvalidOutputLength,
+}
+
+impl core::fmt::Display for ScryptError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ScryptError::LogNZero => write!(f, "log_n is zero"),
+            ScryptError::LogNTooHigh => write!(f, "log_n is too high"),
+            ScryptError::RZero => write!(f, "r is zero"),
+            ScryptError::PZero => write!(f, "p is zero"),
+            ScryptError::RPTooHigh => write!(f, "r * p is too high"),
+            ScryptError::MemoryOverflow => write!(f, "memory requirement overflow"),
+            ScryptError::BufferTooSmall => write!(f, "buffer too small"),
+            ScryptError::InvalidOutputLength => write!(f, "invalid output length"),
+        }
+    }
+}
+
+/// Builder for [`ScryptParams`], validating the parameters on `build`
+///
+/// ```
+/// use cryptoxide::scrypt::ScryptParams;
+///
+/// let params = ScryptParams::builder().log_n(14).r(8).p(1).build().unwrap();
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct ScryptParamsBuilder {
+    log_n: u8,
+    r: u32,
+    p: u32,
+}
+
+impl ScryptParamsBuilder {
+    /// Set the log2 of the Scrypt parameter N
+    pub fn log_n(mut self, log_n: u8) -> Self {
+        self.log_n = log_n;
+        self
+    }
+
+    /// Set the Scrypt parameter r (block size)
+    pub fn r(mut self, r: u32) -> Self {
+        self.r = r;
+        self
+    }
+
+    /// Set the Scrypt parameter p (parallelization)
+    pub fn p(mut self, p: u32) -> Self {
+        self.p = p;
+        self
+    }
+
+    /// Validate the parameters and create the [`ScryptParams`]
+    pub fn build(self) -> Result<ScryptParams, ScryptError> {
+        let ScryptParamsBuilder { log_n, r, p } = self;
+        if log_n == 0 {
+            return Err(ScryptError::LogNZero);
+        }
+        if r == 0 {
+            return Err(ScryptError::RZero);
+        }
+        if p == 0 {
+            return Err(ScryptError::PZero);
+        }
+        if u32::from(log_n) >= usize::BITS {
+            return Err(ScryptError::LogNTooHigh);
+        }
+
+        let r = usize::try_from(r).map_err(|_| ScryptError::MemoryOverflow)?;
+        let p = usize::try_from(p).map_err(|_| ScryptError::MemoryOverflow)?;
+        let n: usize = 1 << log_n;
+
+        // check that r * 128, n * r * 128 and p * r * 128 don't overflow
+        let r128 = r.checked_mul(128).ok_or(ScryptError::MemoryOverflow)?;
+        r128.checked_mul(n).ok_or(ScryptError::MemoryOverflow)?;
+        r128.checked_mul(p).ok_or(ScryptError::MemoryOverflow)?;
+
+        // This check required by Scrypt:
+        // check: n < 2^(128 * r / 8)
+        // r * 16 won't overflow since r128 didn't
+        if (log_n as usize) >= r * 16 {
+            return Err(ScryptError::LogNTooHigh);
+        }
+
+        // This check required by Scrypt:
+        // check: p <= ((2^32-1) * 32) / (128 * r)
+        // It takes a bit of re-arranging to get the check above into this form, but, it is indeed
+        // the same.
+        if r * p >= 0x40000000 {
+            return Err(ScryptError::RPTooHigh);
+        }
+
+        Ok(ScryptParams {
+            log_n,
+            r: r as u32,
+            p: p as u32,
+        })
+    }
+}
+
 impl ScryptParams {
     /**
      * Create a new instance of ScryptParams.
@@ -165,95 +278,199 @@ impl ScryptParams {
      * * r - The Scrypt parameter r
      * * p - The Scrypt parameter p
      *
+     * # Panics
+     *
+     * Panics if the parameters are invalid, use `ScryptParams::builder` to
+     * handle invalid parameters as errors
      */
     pub fn new(log_n: u8, r: u32, p: u32) -> ScryptParams {
-        assert!(r > 0);
-        assert!(p > 0);
-        assert!(log_n > 0);
-        assert!((log_n as usize) < size_of::<usize>() * 8);
-        assert!(
-            size_of::<usize>() >= size_of::<u32>()
-                || (r <= core::usize::MAX as u32 && p < core::usize::MAX as u32)
-        );
+        match Self::builder().log_n(log_n).r(r).p(p).build() {
+            Ok(params) => params,
+            Err(e) => panic!("Invalid Scrypt parameters: {}", e),
+        }
+    }
 
-        let r = r as usize;
-        let p = p as usize;
+    /// Create a builder for the Scrypt parameters, initialized with the
+    /// interactive recommended parameters
+    pub fn builder() -> ScryptParamsBuilder {
+        ScryptParamsBuilder {
+            log_n: 14,
+            r: 8,
+            p: 1,
+        }
+    }
 
-        let n: usize = 1 << log_n;
+    /// Recommended parameters for interactive logins: N=16384 (2^14), r=8, p=1
+    pub fn recommended_interactive() -> ScryptParams {
+        ScryptParams::new(14, 8, 1)
+    }
 
-        // check that r * 128 doesn't overflow
-        let r128 = match r.checked_mul(128) {
-            Some(x) => x,
-            None => panic!("Invalid Scrypt parameters."),
-        };
+    /// Recommended parameters for sensitive storage: N=1048576 (2^20), r=8, p=1
+    ///
+    /// Note that this requires 1 GiB of memory
+    pub fn recommended_sensitive() -> ScryptParams {
+        ScryptParams::new(20, 8, 1)
+    }
 
-        // check that n * r * 128 doesn't overflow
-        match r128.checked_mul(n) {
-            Some(_) => {}
-            None => panic!("Invalid Scrypt parameters."),
-        };
+    /**
+     * Run the scrypt key derivation function with those parameters.
+     *
+     * # Arguments
+     *
+     * * password - The password to process as a byte vector
+     * * salt - The salt value to use as a byte vector
+     * * output - The resulting derived key is returned in this byte vector.
+     *
+     * # Panics
+     *
+     * Panics if the output is empty or longer than (2^32 - 1) * 32 bytes
+     */
+    pub fn run(&self, password: &[u8], salt: &[u8], output: &mut [u8]) {
+        let (v_len, xy_len) = scrypt_memory_requirement(self);
+        let mut v_block = vec![0u8; v_len];
+        let mut xy_block = vec![0u8; xy_len];
+        if let Err(e) = scrypt_no_alloc(self, password, salt, output, &mut v_block, &mut xy_block) {
+            panic!("scrypt failed: {}", e)
+        }
+    }
+}
 
-        // check that p * r * 128 doesn't overflow
-        match r128.checked_mul(p) {
-            Some(_) => {}
-            None => panic!("Invalid Scrypt parameters."),
+// HMAC-SHA256 that doesn't allocate, with a state that can be cloned after
+// some data has been processed, to derive multiple outputs from a common prefix.
+#[derive(Clone)]
+struct HmacSha256 {
+    inner: Context256,
+    outer: Context256,
+}
+
+impl HmacSha256 {
+    fn new(key: &[u8]) -> Self {
+        let mut ipad = [0x36u8; 64];
+        let mut opad = [0x5cu8; 64];
+        let hashed_key;
+        let key = if key.len() > 64 {
+            hashed_key = Context256::new().update(key).finalize();
+            &hashed_key[..]
+        } else {
+            key
         };
+        for (i, k) in key.iter().enumerate() {
+            ipad[i] ^= k;
+            opad[i] ^= k;
+        }
+        Self {
+            inner: Context256::new().update(&ipad),
+            outer: Context256::new().update(&opad),
+        }
+    }
 
-        // This check required by Scrypt:
-        // check: n < 2^(128 * r / 8)
-        // r * 16 won't overflow since r128 didn't
-        assert!((log_n as usize) < r * 16);
+    fn update(&mut self, data: &[u8]) {
+        self.inner.update_mut(data)
+    }
 
-        // This check required by Scrypt:
-        // check: p <= ((2^32-1) * 32) / (128 * r)
-        // It takes a bit of re-arranging to get the check above into this form, but, it is indeed
-        // the same.
-        assert!(r * p < 0x40000000);
+    fn finalize(self) -> [u8; 32] {
+        let h = self.inner.finalize();
+        self.outer.update(&h).finalize()
+    }
+}
 
-        ScryptParams {
-            log_n: log_n,
-            r: r as u32,
-            p: p as u32,
-        }
+// PBKDF2-HMAC-SHA256 with 1 iteration, where `mac` already processed the salt,
+// generating the output blocks starting from the 1-based block index `idx`
+fn pbkdf2_1_at(mac: &HmacSha256, mut idx: u32, output: &mut [u8]) {
+    for chunk in output.chunks_mut(32) {
+        let mut h = mac.clone();
+        h.update(&idx.to_be_bytes());
+        let block = h.finalize();
+        chunk.copy_from_slice(&block[..chunk.len()]);
+        idx += 1;
     }
 }
 
+/// Return the size in bytes of the buffers needed by `scrypt_no_alloc`
+///
+/// The first value is the size of the V block (N * r * 128 bytes), and the
+/// second value is the size of the XY block (r * 256 bytes)
+pub fn scrypt_memory_requirement(params: &ScryptParams) -> (usize, usize) {
+    // The checks in the ScryptParams builder guarantee that the following is safe:
+    let n = 1 << params.log_n;
+    let r128 = (params.r as usize) * 128;
+    (n * r128, 2 * r128)
+}
+
 /**
- * The scrypt key derivation function.
+ * The scrypt key derivation function, using caller provided memory.
+ *
+ * Contrary to `ScryptParams::run`, no memory is allocated, which makes it
+ * suitable for environments without dynamic memory.
  *
  * # Arguments
  *
+ * * params - The ScryptParams to use
  * * password - The password to process as a byte vector
  * * salt - The salt value to use as a byte vector
- * * params - The ScryptParams to use
  * * output - The resulting derived key is returned in this byte vector.
+ * * v_block - Scratch memory of at least N * r * 128 bytes
+ * * xy_block - Scratch memory of at least r * 256 bytes
  *
+ * The scratch buffers sizes can be computed with `scrypt_memory_requirement`
  */
-pub fn scrypt(password: &[u8], salt: &[u8], params: &ScryptParams, output: &mut [u8]) {
+pub fn scrypt_no_alloc(
+    params: &ScryptParams,
+    password: &[u8],
+    salt: &[u8],
+    output: &mut [u8],
+    v_block: &mut [u8],
+    xy_block: &mut [u8],
+) -> Result<(), ScryptError> {
     // This check required by Scrypt:
     // check output.len() > 0 && output.len() <= (2^32 - 1) * 32
-    assert!(output.len() > 0);
-    assert!(output.len() / 32 <= 0xffffffff);
+    if output.is_empty() || output.len() / 32 > 0xffffffff {
+        return Err(ScryptError::InvalidOutputLength);
+    }
+
+    let (v_len, xy_len) = scrypt_memory_requirement(params);
+    if v_block.len() < v_len || xy_block.len() < xy_len {
+        return Err(ScryptError::BufferTooSmall);
+    }
 
-    // The checks in the ScryptParams constructor guarantee that the following is safe:
     let n = 1 << params.log_n;
     let r128 = (params.r as usize) * 128;
-    let pr128 = (params.p as usize) * r128;
-    let nr128 = n * r128;
-
-    let mut mac = Hmac::new(Sha256::new(), password);
-
-    let mut b: Vec<u8> = repeat(0).take(pr128).collect();
-    pbkdf2(&mut mac, salt, 1, &mut b);
-
-    let mut v: Vec<u8> = repeat(0).take(nr128).collect();
-    let mut t: Vec<u8> = repeat(0).take(r128).collect();
-
-    for chunk in &mut b.chunks_mut(r128) {
-        scrypt_ro_mix(chunk, &mut v, &mut t, n);
+    let v = &mut v_block[..v_len];
+    let (b, t) = xy_block[..xy_len].split_at_mut(r128);
+
+    let mac = HmacSha256::new(password);
+    let mut salted = mac.clone();
+    salted.update(salt);
+
+    // B is PBKDF2(password, salt, 1, p * r * 128), and each of the p chunks of B
+    // is processed independently with ROMix before being fed to the final PBKDF2
+    // as the salt, so only one chunk is needed at a time
+    let mut final_mac = mac;
+    for i in 0..params.p {
+        // r * 128 is a multiple of the 32 bytes of output of HMAC-SHA256
+        pbkdf2_1_at(&salted, i * params.r * 4 + 1, b);
+        scrypt_ro_mix(b, v, t, n);
+        final_mac.update(b);
     }
 
-    pbkdf2(&mut mac, &*b, 1, output);
+    pbkdf2_1_at(&final_mac, 1, output);
+    Ok(())
+}
+
+/**
+ * The scrypt key derivation function.
+ *
+ * # Arguments
+ *
+ * * password - The password to process as a byte vector
+ * * salt - The salt value to use as a byte vector
+ * * params - The ScryptParams to use
+ * * output - The resulting derived key is returned in this byte vector.
+ *
+ */
+#[deprecated(since = "0.6.0", note = "use `ScryptParams::run`")]
+pub fn scrypt(password: &[u8], salt: &[u8], params: &ScryptParams, output: &mut [u8]) {
+    params.run(password, salt, output)
 }
 
 #[cfg(test)]
@@ -261,7 +478,7 @@ mod test {
     use alloc::vec::Vec;
     use core::iter::repeat;
 
-    use super::{scrypt, ScryptParams};
+    use super::{scrypt_memory_requirement, scrypt_no_alloc, ScryptError, ScryptParams};
 
     struct Test {
         password: &'static str,
@@ -272,7 +489,10 @@ mod test {
         expected: &'static [u8],
     }
 
-    // Test vectors from [1]. The last test vector is omitted because it takes too long to run.
+    // Test vectors from [1]. The last test vector needs 1GB of memory and is in the
+    // ignored test `test_scrypt_rfc7914_large`.
+    // The vectors with p = 2 and p = 3 are generated with the reference implementation, checking
+    // that the independent ROMix blocks are concatenated in order before the final PBKDF2.
 
     fn tests() -> &'static [Test] {
         &[
@@ -318,22 +538,176 @@ mod test {
                     0x0d, 0x65, 0x1e, 0x40, 0xdf, 0xcf, 0x01, 0x7b, 0x45, 0x57, 0x58, 0x87,
                 ],
             },
+            Test {
+                password: "password",
+                salt: "NaCl",
+                log_n: 4,
+                r: 1,
+                p: 2,
+                expected: &[
+                    0xc2, 0x47, 0xea, 0xbb, 0x7c, 0x5d, 0x38, 0x94, 0xe0, 0x45, 0xed, 0xf2, 0xa4,
+                    0xe9, 0x92, 0x73, 0x7d, 0x95, 0x3a, 0x3b, 0x18, 0x1f, 0x2e, 0x48, 0x2c, 0x2b,
+                    0x7c, 0x3a, 0x70, 0x60, 0xa1, 0x1c, 0xa0, 0xd5, 0xe4, 0x5a, 0x98, 0x74, 0x5b,
+                    0xfb, 0xa7, 0xfa, 0x54, 0x9c, 0x0d, 0xb5, 0x59, 0x59, 0x1b, 0x81, 0x74, 0x7c,
+                    0x9b, 0x37, 0xe8, 0x42, 0xeb, 0xac, 0x53, 0xeb, 0xdc, 0xfd, 0xee, 0x6d,
+                ],
+            },
+            Test {
+                password: "pleaseletmein",
+                salt: "SodiumChloride",
+                log_n: 10,
+                r: 2,
+                p: 3,
+                expected: &[
+                    0xab, 0x1a, 0x1b, 0x0c, 0x2d, 0xb9, 0x1c, 0xd8, 0x58, 0xb5, 0x56, 0x37, 0x32,
+                    0xec, 0x1e, 0x5e, 0x85, 0x06, 0xc9, 0xa6, 0x30, 0x6a, 0x25, 0xd4, 0x09, 0x65,
+                    0x2e, 0x6c, 0x8f, 0x39, 0xb2, 0x16,
+                ],
+            },
         ]
     }
 
+    // RFC7914 section 8
+    #[test]
+    fn test_salsa20_8() {
+        let input = crate::encoding::decode_hex(
+            b"7e879a214f3ec9867ca940e641718f26baee555b8c61c1b50df846116dcd3b1d\
+              ee24f319df9b3d8514121e4b5ac5aa3276021d2909c74829edebc68db8b8c25e",
+        )
+        .unwrap();
+        let expected = crate::encoding::decode_hex(
+            b"a41f859c6608cc993b81cacb020cef05044b2181a2fd337dfd7b1c6396682f29\
+              b4393168e3c9e6bcfe6bc5b7a06d96bae424cc102c91745c24ad673dc7618f81",
+        )
+        .unwrap();
+        let mut output = [0u8; 64];
+        super::salsa20_8(&input, &mut output);
+        assert_eq!(&output[..], &expected[..]);
+    }
+
     #[test]
     fn test_scrypt() {
         let tests = tests();
         for t in tests.iter() {
             let mut result: Vec<u8> = repeat(0).take(t.expected.len()).collect();
             let params = ScryptParams::new(t.log_n, t.r, t.p);
-            scrypt(
+            params.run(t.password.as_bytes(), t.salt.as_bytes(), &mut result);
+            assert_eq!(result, t.expected);
+        }
+    }
+
+    // too slow and memory hungry for the default test run, the elapsed time is printed
+    // so that performance regressions are visible in the CI logs
+    #[test]
+    #[ignore]
+    fn test_scrypt_rfc7914_large() {
+        use std::time::Instant;
+
+        let expected = [
+            0x21, 0x01, 0xcb, 0x9b, 0x6a, 0x51, 0x1a, 0xae, 0xad, 0xdb, 0xbe, 0x09, 0xcf, 0x70,
+            0xf8, 0x81, 0xec, 0x56, 0x8d, 0x57, 0x4a, 0x2f, 0xfd, 0x4d, 0xab, 0xe5, 0xee, 0x98,
+            0x20, 0xad, 0xaa, 0x47, 0x8e, 0x56, 0xfd, 0x8f, 0x4b, 0xa5, 0xd0, 0x9f, 0xfa, 0x1c,
+            0x6d, 0x92, 0x7c, 0x40, 0xf4, 0xc3, 0x37, 0x30, 0x40, 0x49, 0xe8, 0xa9, 0x52, 0xfb,
+            0xcb, 0xf4, 0x5c, 0x6f, 0xa7, 0x7a, 0x41, 0xa4,
+        ];
+        let mut result = [0u8; 64];
+        let params = ScryptParams::new(20, 8, 1);
+        let start = Instant::now();
+        params.run(b"pleaseletmein", b"SodiumChloride", &mut result);
+        std::println!("scrypt N=2^20 r=8 p=1: {:?}", start.elapsed());
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_builder() {
+        let build = |log_n, r, p| ScryptParams::builder().log_n(log_n).r(r).p(p).build();
+        assert_eq!(build(0, 8, 1), Err(ScryptError::LogNZero));
+        assert_eq!(build(14, 0, 1), Err(ScryptError::RZero));
+        assert_eq!(build(14, 8, 0), Err(ScryptError::PZero));
+        assert_eq!(build(16, 1, 1), Err(ScryptError::LogNTooHigh));
+        assert_eq!(build(255, 8, 1), Err(ScryptError::LogNTooHigh));
+        assert_eq!(build(14, 0x8000, 0x8000), Err(ScryptError::RPTooHigh));
+        assert_eq!(build(14, 8, 1), Ok(ScryptParams::recommended_interactive()));
+        assert_eq!(
+            ScryptParams::builder().build(),
+            Ok(ScryptParams::recommended_interactive())
+        );
+        assert_eq!(build(20, 8, 1), Ok(ScryptParams::recommended_sensitive()));
+    }
+
+    #[test]
+    fn test_scrypt_no_alloc() {
+        let mut v_block = [0u8; 16 * 2 * 128];
+        let mut xy_block = [0u8; 2 * 256];
+        for t in tests().iter() {
+            let params = ScryptParams::new(t.log_n, t.r, t.p);
+            let mut result = [0u8; 64];
+            let result = &mut result[..t.expected.len()];
+            let (v_len, xy_len) = scrypt_memory_requirement(&params);
+            if v_len > v_block.len() || xy_len > xy_block.len() {
+                continue;
+            }
+            scrypt_no_alloc(
+                &params,
                 t.password.as_bytes(),
                 t.salt.as_bytes(),
-                &params,
-                &mut result,
-            );
+                result,
+                &mut v_block,
+                &mut xy_block,
+            )
+            .unwrap();
             assert_eq!(result, t.expected);
         }
+
+        let params = ScryptParams::new(4, 2, 1);
+        assert_eq!(scrypt_memory_requirement(&params), (16 * 2 * 128, 2 * 256));
+        let mut out = [0u8; 32];
+        assert_eq!(
+            scrypt_no_alloc(
+                &params,
+                b"",
+                b"",
+                &mut out,
+                &mut v_block[1..],
+                &mut xy_block
+            ),
+            Err(ScryptError::BufferTooSmall)
+        );
+        assert_eq!(
+            scrypt_no_alloc(
+                &params,
+                b"",
+                b"",
+                &mut out,
+                &mut v_block,
+                &mut xy_block[1..]
+            ),
+            Err(ScryptError::BufferTooSmall)
+        );
+        assert_eq!(
+            scrypt_no_alloc(&params, b"", b"", &mut [], &mut v_block, &mut xy_block),
+            Err(ScryptError::InvalidOutputLength)
+        );
+    }
+}
+
+#[cfg(all(test, feature = "with-bench"))]
+mod bench {
+    use super::{salsa20_8, ScryptParams};
+    use test::Bencher;
+
+    #[bench]
+    pub fn salsa20_8_core(bh: &mut Bencher) {
+        let input = [7u8; 64];
+        let mut output = [0u8; 64];
+        bh.iter(|| salsa20_8(&input, &mut output));
+        bh.bytes = 64;
+    }
+
+    #[bench]
+    pub fn scrypt_n16384_r8_p1(bh: &mut Bencher) {
+        let params = ScryptParams::new(14, 8, 1);
+        let mut output = [0u8; 64];
+        bh.iter(|| params.run(b"password", b"saltsalt", &mut output));
     }
 }
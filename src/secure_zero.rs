@@ -0,0 +1,71 @@
+//! Secure zeroing of sensitive memory
+//!
+//! The `Zeroize` trait clears a value using volatile writes followed by a
+//! compiler fence, so that the compiler cannot elide the clearing as a
+//! dead store, for example just before the value is dropped.
+//!
+//! The crate uses it to clear secret material (keys, ciphers state, scalars)
+//! on drop, and it is exposed so that users can clear the plain byte arrays
+//! returned by the API (e.g. ed25519 keypairs and extended secret keys).
+//!
+//! # Examples
+//!
+//! ```
+//! use cryptoxide::secure_zero::Zeroize;
+//!
+//! let mut secret = [0x42u8; 32];
+//! secret.zeroize();
+//! assert_eq!(secret, [0u8; 32]);
+//! ```
+
+use core::ptr;
+use core::sync::atomic::{compiler_fence, Ordering};
+
+/// Securely set a value to zero
+pub trait Zeroize {
+    /// Overwrite the value with zeros, in a way that is not optimised away by the compiler
+    fn zeroize(&mut self);
+}
+
+macro_rules! zeroize_impl {
+    ($t:ty) => {
+        impl Zeroize for [$t] {
+            fn zeroize(&mut self) {
+                for v in self.iter_mut() {
+                    unsafe { ptr::write_volatile(v, 0) }
+                }
+                compiler_fence(Ordering::SeqCst);
+            }
+        }
+
+        impl<const N: usize> Zeroize for [$t; N] {
+            fn zeroize(&mut self) {
+                self[..].zeroize()
+            }
+        }
+    };
+}
+
+zeroize_impl!(u8);
+zeroize_impl!(u32);
+zeroize_impl!(u64);
+
+#[cfg(test)]
+mod tests {
+    use super::Zeroize;
+
+    #[test]
+    fn zeroize() {
+        let mut a = [0xffu8; 33];
+        a.zeroize();
+        assert_eq!(a, [0u8; 33]);
+
+        let mut b = [0xffff_ffffu32; 5];
+        b[1..3].zeroize();
+        assert_eq!(b, [0xffff_ffff, 0, 0, 0xffff_ffff, 0xffff_ffff]);
+
+        let mut c = [u64::MAX; 4];
+        c.zeroize();
+        assert_eq!(c, [0u64; 4]);
+    }
+}
@@ -0,0 +1,314 @@
+//! SipHash-2-4 keyed hash function as defined in [Specification][1].
+//!
+//! SipHash is a fast keyed pseudo-random function optimized for short
+//! inputs, typically used for hash table randomization and short message
+//! authentication. It takes a 16 bytes key and produce a 8 bytes tag,
+//! or a 16 bytes tag using the SipHash-2-4-128 variant.
+//!
+//! # Examples
+//!
+//! ```
+//! use cryptoxide::{mac::Mac, siphash::SipHash24};
+//!
+//! let mut context = SipHash24::new(&[0u8; 16]);
+//! context.input(b"data to authenticate");
+//! let mac = context.result();
+//! ```
+//!
+//! [1]: <https://cr.yp.to/siphash/siphash-20120918.pdf>
+
+use core::cmp::min;
+
+use crate::cryptoutil::read_u64_le;
+use crate::mac::{Mac, MacResult};
+
+/// `SipHash24` Context
+///
+/// Use the `Mac` traits for interaction
+#[derive(Clone)]
+pub struct SipHash24 {
+    k0: u64,
+    k1: u64,
+    v: [u64; 4],
+    buffer: [u8; 8],
+    leftover: usize,
+    length: usize,
+    output_bytes: usize,
+    finalized: bool,
+}
+
+#[inline(always)]
+fn sipround(v: &mut [u64; 4]) {
+    v[0] = v[0].wrapping_add(v[1]);
+    v[1] = v[1].rotate_left(13);
+    v[1] ^= v[0];
+    v[0] = v[0].rotate_left(32);
+    v[2] = v[2].wrapping_add(v[3]);
+    v[3] = v[3].rotate_left(16);
+    v[3] ^= v[2];
+    v[0] = v[0].wrapping_add(v[3]);
+    v[3] = v[3].rotate_left(21);
+    v[3] ^= v[0];
+    v[2] = v[2].wrapping_add(v[1]);
+    v[1] = v[1].rotate_left(17);
+    v[1] ^= v[2];
+    v[2] = v[2].rotate_left(32);
+}
+
+impl SipHash24 {
+    /// Create a new `SipHash24` context using the key (16 bytes), producing a 8 bytes tag
+    pub fn new(key: &[u8; 16]) -> Self {
+        Self::with_output(key, 8)
+    }
+
+    /// Create a new SipHash-2-4-128 context using the key (16 bytes), producing a 16 bytes tag
+    pub fn new_128(key: &[u8; 16]) -> Self {
+        Self::with_output(key, 16)
+    }
+
+    fn with_output(key: &[u8; 16], output_bytes: usize) -> Self {
+        let mut ctx = SipHash24 {
+            k0: read_u64_le(&key[0..8]),
+            k1: read_u64_le(&key[8..16]),
+            v: [0; 4],
+            buffer: [0; 8],
+            leftover: 0,
+            length: 0,
+            output_bytes,
+            finalized: false,
+        };
+        ctx.reset();
+        ctx
+    }
+
+    fn compress(&mut self, m: u64) {
+        self.v[3] ^= m;
+        sipround(&mut self.v);
+        sipround(&mut self.v);
+        self.v[0] ^= m;
+    }
+
+    fn tag(&self) -> [u8; 16] {
+        let mut ctx = self.clone();
+        let mut last = [0u8; 8];
+        last[..ctx.leftover].copy_from_slice(&ctx.buffer[..ctx.leftover]);
+        last[7] = ctx.length as u8;
+        ctx.compress(u64::from_le_bytes(last));
+
+        let mut out = [0u8; 16];
+        ctx.v[2] ^= if ctx.output_bytes == 16 { 0xee } else { 0xff };
+        for _ in 0..4 {
+            sipround(&mut ctx.v);
+        }
+        let h = ctx.v[0] ^ ctx.v[1] ^ ctx.v[2] ^ ctx.v[3];
+        out[0..8].copy_from_slice(&h.to_le_bytes());
+
+        if ctx.output_bytes == 16 {
+            ctx.v[1] ^= 0xdd;
+            for _ in 0..4 {
+                sipround(&mut ctx.v);
+            }
+            let h = ctx.v[0] ^ ctx.v[1] ^ ctx.v[2] ^ ctx.v[3];
+            out[8..16].copy_from_slice(&h.to_le_bytes());
+        }
+        out
+    }
+}
+
+impl Mac for SipHash24 {
+    fn input(&mut self, data: &[u8]) {
+        assert!(!self.finalized);
+        let mut m = data;
+        self.length = self.length.wrapping_add(data.len());
+
+        if self.leftover > 0 {
+            let want = min(8 - self.leftover, m.len());
+            self.buffer[self.leftover..self.leftover + want].copy_from_slice(&m[..want]);
+            m = &m[want..];
+            self.leftover += want;
+
+            if self.leftover < 8 {
+                return;
+            }
+
+            let b = u64::from_le_bytes(self.buffer);
+            self.compress(b);
+            self.leftover = 0;
+        }
+
+        while m.len() >= 8 {
+            let (block, rest) = m.split_at(8);
+            self.compress(read_u64_le(block));
+            m = rest;
+        }
+
+        self.buffer[..m.len()].copy_from_slice(m);
+        self.leftover = m.len();
+    }
+
+    fn reset(&mut self) {
+        self.v = [
+            self.k0 ^ 0x736f6d6570736575,
+            self.k1 ^ 0x646f72616e646f6d,
+            self.k0 ^ 0x6c7967656e657261,
+            self.k1 ^ 0x7465646279746573,
+        ];
+        if self.output_bytes == 16 {
+            self.v[1] ^= 0xee;
+        }
+        self.leftover = 0;
+        self.length = 0;
+        self.finalized = false;
+    }
+
+    fn result(&mut self) -> MacResult {
+        let mut mac = [0u8; 16];
+        let n = self.output_bytes;
+        self.raw_result(&mut mac[..n]);
+        MacResult::new(&mac[..n])
+    }
+
+    fn raw_result(&mut self, output: &mut [u8]) {
+        assert!(output.len() >= self.output_bytes);
+        self.finalized = true;
+        output[0..self.output_bytes].copy_from_slice(&self.tag()[0..self.output_bytes]);
+    }
+
+    fn output_bytes(&self) -> usize {
+        self.output_bytes
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::SipHash24;
+    use crate::mac::Mac;
+
+    const KEY: [u8; 16] = [
+        0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e,
+        0x0f,
+    ];
+
+    fn message(len: usize) -> [u8; 64] {
+        let mut msg = [0u8; 64];
+        for (i, b) in msg.iter_mut().enumerate().take(len) {
+            *b = i as u8;
+        }
+        msg
+    }
+
+    #[test]
+    fn paper_vector() {
+        // SipHash paper, appendix A
+        let msg = message(15);
+        let mut ctx = SipHash24::new(&KEY);
+        ctx.input(&msg[..15]);
+        let mut mac = [0u8; 8];
+        ctx.raw_result(&mut mac);
+        assert_eq!(u64::from_le_bytes(mac), 0xa129ca6149be45e5);
+    }
+
+    #[test]
+    fn reference_vectors_64() {
+        // from the reference implementation vectors, message = 00 01 02 ... (len - 1)
+        let expected: [u64; 16] = [
+            0x726fdb47dd0e0e31,
+            0x74f839c593dc67fd,
+            0x0d6c8009d9a94f5a,
+            0x85676696d7fb7e2d,
+            0xcf2794e0277187b7,
+            0x18765564cd99a68d,
+            0xcbc9466e58fee3ce,
+            0xab0200f58b01d137,
+            0x93f5f5799a932462,
+            0x9e0082df0ba9e4b0,
+            0x7a5dbbc594ddb9f3,
+            0xf4b32f46226bada7,
+            0x751e8fbc860ee5fb,
+            0x14ea5627c0843d90,
+            0xf723ca908e7af2ee,
+            0xa129ca6149be45e5,
+        ];
+        for (len, e) in expected.iter().enumerate() {
+            let msg = message(len);
+            let mut ctx = SipHash24::new(&KEY);
+            ctx.input(&msg[..len]);
+            let mut mac = [0u8; 8];
+            ctx.raw_result(&mut mac);
+            assert_eq!(u64::from_le_bytes(mac), *e, "length {}", len);
+
+            // byte by byte input
+            let mut ctx = SipHash24::new(&KEY);
+            for b in &msg[..len] {
+                ctx.input(core::slice::from_ref(b));
+            }
+            assert!(ctx.verify(&e.to_le_bytes()));
+        }
+    }
+
+    #[test]
+    fn reference_vectors_128() {
+        let tests: [(usize, [u8; 16]); 7] = [
+            (
+                0,
+                [
+                    0xa3, 0x81, 0x7f, 0x04, 0xba, 0x25, 0xa8, 0xe6, 0x6d, 0xf6, 0x72, 0x14, 0xc7,
+                    0x55, 0x02, 0x93,
+                ],
+            ),
+            (
+                1,
+                [
+                    0xda, 0x87, 0xc1, 0xd8, 0x6b, 0x99, 0xaf, 0x44, 0x34, 0x76, 0x59, 0x11, 0x9b,
+                    0x22, 0xfc, 0x45,
+                ],
+            ),
+            (
+                7,
+                [
+                    0xa1, 0xf1, 0xeb, 0xbe, 0xd8, 0xdb, 0xc1, 0x53, 0xc0, 0xb8, 0x4a, 0xa6, 0x1f,
+                    0xf0, 0x82, 0x39,
+                ],
+            ),
+            (
+                8,
+                [
+                    0x3b, 0x62, 0xa9, 0xba, 0x62, 0x58, 0xf5, 0x61, 0x0f, 0x83, 0xe2, 0x64, 0xf3,
+                    0x14, 0x97, 0xb4,
+                ],
+            ),
+            (
+                15,
+                [
+                    0x54, 0x93, 0xe9, 0x99, 0x33, 0xb0, 0xa8, 0x11, 0x7e, 0x08, 0xec, 0x0f, 0x97,
+                    0xcf, 0xc3, 0xd9,
+                ],
+            ),
+            (
+                16,
+                [
+                    0x6e, 0xe2, 0xa4, 0xca, 0x67, 0xb0, 0x54, 0xbb, 0xfd, 0x33, 0x15, 0xbf, 0x85,
+                    0x23, 0x05, 0x77,
+                ],
+            ),
+            (
+                63,
+                [
+                    0x51, 0x50, 0xd1, 0x77, 0x2f, 0x50, 0x83, 0x4a, 0x50, 0x3e, 0x06, 0x9a, 0x97,
+                    0x3f, 0xbd, 0x7c,
+                ],
+            ),
+        ];
+        for (len, expected) in tests.iter() {
+            let msg = message(*len);
+            let mut ctx = SipHash24::new_128(&KEY);
+            assert_eq!(ctx.output_bytes(), 16);
+            ctx.input(&msg[..*len]);
+            assert_eq!(ctx.result().code(), &expected[..], "length {}", len);
+
+            ctx.reset();
+            ctx.input(&msg[..*len]);
+            assert!(ctx.verify(&expected[..]));
+        }
+    }
+}
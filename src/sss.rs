@@ -0,0 +1,194 @@
+//! Shamir's Secret Sharing over the Ed25519 scalar field
+//!
+//! The secret is split into `n` shares such that any `t` shares (the threshold) are
+//! enough to reconstruct the secret, whereas `t - 1` shares reveal nothing about it.
+//!
+//! The secret is the constant term of a random polynomial of degree `t - 1` over the
+//! field of integers modulo the Ed25519 group order l, and each share is the evaluation
+//! of the polynomial at a distinct non-zero point `x`. The secret is reconstructed
+//! with Lagrange interpolation at 0.
+//!
+//! As there's no source of randomness in this crate, the `t - 1` random coefficients of the
+//! polynomial need to be provided by the caller, and should come from a CSPRNG: the security
+//! of the scheme relies on those coefficients being uniformly random and secret.
+//!
+//! # Examples
+//!
+//! ```
+//! use cryptoxide::curve25519::Scalar;
+//! use cryptoxide::sss::{sss_reconstruct, sss_split};
+//!
+//! let secret = Scalar::from_uniform_bytes(&[1u8; 64]);
+//! // should be random scalars, one per coefficient
+//! let coefficients = [Scalar::from_uniform_bytes(&[2u8; 64]), Scalar::from_uniform_bytes(&[3u8; 64])];
+//!
+//! let shares = sss_split(&secret, 3, 5, &coefficients);
+//! assert_eq!(sss_reconstruct(&shares[1..4]), Some(secret));
+//! ```
+
+use alloc::vec::Vec;
+
+use crate::curve25519::Scalar;
+
+fn scalar_from_u8(x: u8) -> Scalar {
+    let mut bytes = [0u8; 32];
+    bytes[0] = x;
+    Scalar::from_bytes(&bytes)
+}
+
+/// Split the secret in `num_shares` shares, any `threshold` of them being needed to
+/// reconstruct the secret
+///
+/// The shares are `(x, y)` with x from 1 to `num_shares`, and `random_scalars` are the
+/// `threshold - 1` random coefficients of the polynomial
+///
+/// # Panics
+///
+/// Panics if the threshold is 0, if the threshold is bigger than the number of shares,
+/// or if the number of random scalars is not `threshold - 1`
+pub fn sss_split(
+    secret: &Scalar,
+    threshold: u8,
+    num_shares: u8,
+    random_scalars: &[Scalar],
+) -> Vec<(u8, Scalar)> {
+    assert!(threshold > 0);
+    assert!(threshold <= num_shares);
+    assert_eq!(random_scalars.len(), threshold as usize - 1);
+
+    (1..=num_shares)
+        .map(|x| {
+            // evaluate the polynomial at x with Horner's method
+            let xs = scalar_from_u8(x);
+            let mut y = Scalar::ZERO;
+            for coefficient in random_scalars.iter().rev() {
+                y = &(&y * &xs) + coefficient;
+            }
+            y = &(&y * &xs) + secret;
+            (x, y)
+        })
+        .collect()
+}
+
+// evaluate at `x` the polynomial going through the points, with Lagrange interpolation
+fn interpolate(points: &[(Scalar, &Scalar)], x: &Scalar) -> Option<Scalar> {
+    let mut result = Scalar::ZERO;
+    for (i, (xi, yi)) in points.iter().enumerate() {
+        let mut numerator = Scalar::ONE;
+        let mut denominator = Scalar::ONE;
+        for (j, (xj, _)) in points.iter().enumerate() {
+            if i != j {
+                numerator = &numerator * &(x - xj);
+                denominator = &denominator * &(xi - xj);
+            }
+        }
+        // the denominator is zero only if two points have the same x
+        let lagrange = &numerator * &denominator.invert().into_option()?;
+        result = &result + &(&lagrange * yi);
+    }
+    Some(result)
+}
+
+/// Reconstruct the secret from the shares, which need to be at least the threshold used
+/// to split the secret, otherwise the result is unrelated to the secret
+///
+/// Returns `None` if there's no shares, if a share has `x = 0`, or if two shares have the same x
+pub fn sss_reconstruct(shares: &[(u8, Scalar)]) -> Option<Scalar> {
+    if shares.is_empty() || shares.iter().any(|(x, _)| *x == 0) {
+        return None;
+    }
+    let points: Vec<(Scalar, &Scalar)> = shares
+        .iter()
+        .map(|(x, y)| (scalar_from_u8(*x), y))
+        .collect();
+    interpolate(&points, &Scalar::ZERO)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scalars(seed: u8, n: usize) -> Vec<Scalar> {
+        (0..n)
+            .map(|i| Scalar::from_uniform_bytes(&[seed.wrapping_add(i as u8); 64]))
+            .collect()
+    }
+
+    // all the subsets of size k of the shares
+    fn subsets(shares: &[(u8, Scalar)], k: usize) -> Vec<Vec<(u8, Scalar)>> {
+        if k == 0 {
+            return vec![Vec::new()];
+        }
+        let mut out = Vec::new();
+        for i in 0..shares.len() {
+            for mut rest in subsets(&shares[i + 1..], k - 1) {
+                rest.insert(0, shares[i].clone());
+                out.push(rest);
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn split_reconstruct() {
+        let secret = Scalar::from_uniform_bytes(&[0x42; 64]);
+        for (threshold, num_shares) in [(1, 1), (1, 3), (2, 2), (2, 5), (3, 5), (5, 5), (4, 7)] {
+            let coefficients = scalars(threshold, threshold as usize - 1);
+            let shares = sss_split(&secret, threshold, num_shares, &coefficients);
+            assert_eq!(shares.len(), num_shares as usize);
+            for subset in subsets(&shares, threshold as usize) {
+                assert_eq!(sss_reconstruct(&subset), Some(secret.clone()));
+            }
+            // more shares than the threshold also works
+            assert_eq!(sss_reconstruct(&shares), Some(secret.clone()));
+        }
+    }
+
+    #[test]
+    fn below_threshold() {
+        let secret = Scalar::from_uniform_bytes(&[0x42; 64]);
+        let shares = sss_split(&secret, 3, 5, &scalars(1, 2));
+        let known = &shares[0..2];
+        assert_ne!(sss_reconstruct(known), Some(secret));
+
+        // whatever the secret, there's a share at x = 5 consistent with the 2 known shares,
+        // so the 2 known shares reveal nothing of the secret
+        for candidate in scalars(100, 10) {
+            let mut points: Vec<(Scalar, &Scalar)> =
+                known.iter().map(|(x, y)| (scalar_from_u8(*x), y)).collect();
+            points.push((Scalar::ZERO, &candidate));
+            let y = interpolate(&points, &scalar_from_u8(5)).unwrap();
+
+            let mut forged = known.to_vec();
+            forged.push((5, y));
+            assert_eq!(sss_reconstruct(&forged), Some(candidate.clone()));
+        }
+    }
+
+    #[test]
+    fn invalid_shares() {
+        let secret = Scalar::from_uniform_bytes(&[0x42; 64]);
+        let shares = sss_split(&secret, 2, 3, &scalars(1, 1));
+        assert_eq!(sss_reconstruct(&[]), None);
+        assert_eq!(
+            sss_reconstruct(&[shares[0].clone(), shares[0].clone()]),
+            None
+        );
+        assert_eq!(
+            sss_reconstruct(&[(0, Scalar::ONE), shares[1].clone()]),
+            None
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn threshold_too_big() {
+        sss_split(&Scalar::ONE, 4, 3, &scalars(1, 3));
+    }
+
+    #[test]
+    #[should_panic]
+    fn wrong_coefficients() {
+        sss_split(&Scalar::ONE, 3, 5, &scalars(1, 3));
+    }
+}
@@ -0,0 +1,344 @@
+//! VRF - Verifiable Random Function
+//!
+//! Implementation of the ECVRF-EDWARDS25519-SHA512-TAI ciphersuite of [RFC9381][1].
+//!
+//! The owner of a secret key can compute, for any input `alpha`, a pseudorandom output
+//! `beta` along with a proof `pi`, which allows anyone with the public key to verify
+//! that `beta` is the correct output for `alpha`.
+//!
+//! The keys are the same as Ed25519: a 32 bytes secret key, and the associated 32 bytes
+//! public key as returned by [`ed25519::keypair`](crate::ed25519::keypair).
+//!
+//! # Examples
+//!
+//! ```
+//! use cryptoxide::{ed25519, vrf};
+//!
+//! let secret = [1u8; 32];
+//! let (_, public) = ed25519::keypair(&secret);
+//!
+//! let proof = vrf::vrf_prove(&secret, b"input");
+//! let output = vrf::vrf_verify(&public, b"input", &proof).unwrap();
+//! assert_eq!(Some(output), vrf::vrf_proof_to_hash(&proof));
+//! ```
+//!
+//! [1]: <https://www.rfc-editor.org/rfc/rfc9381>
+
+use core::convert::TryFrom;
+
+use crate::curve25519::{ge_decompress, ge_multiscalar_mul, scalar, Ge, GePartial, Scalar};
+use crate::ed25519::extended_secret;
+use crate::hashing::sha2::Sha512;
+use crate::secure_zero::Zeroize;
+
+/// Size of the encoded VRF proof
+pub const PROOF_LENGTH: usize = 80;
+
+/// Size of the VRF output
+pub const OUTPUT_LENGTH: usize = 64;
+
+// ECVRF-EDWARDS25519-SHA512-TAI
+const SUITE_STRING: u8 = 0x03;
+
+/// VRF proof: the point Gamma, the challenge c (16 bytes) and the scalar s
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VrfProof {
+    gamma: [u8; 32],
+    c: [u8; 16],
+    s: [u8; 32],
+}
+
+impl VrfProof {
+    /// Encode the proof as `Gamma || c || s`
+    pub fn to_bytes(&self) -> [u8; PROOF_LENGTH] {
+        let mut out = [0u8; PROOF_LENGTH];
+        out[0..32].copy_from_slice(&self.gamma);
+        out[32..48].copy_from_slice(&self.c);
+        out[48..80].copy_from_slice(&self.s);
+        out
+    }
+
+    /// Decode the proof from `Gamma || c || s`
+    ///
+    /// The components are only validated when verifying the proof
+    pub fn from_bytes(bytes: &[u8; PROOF_LENGTH]) -> Self {
+        VrfProof {
+            gamma: <[u8; 32]>::try_from(&bytes[0..32]).unwrap(),
+            c: <[u8; 16]>::try_from(&bytes[32..48]).unwrap(),
+            s: <[u8; 32]>::try_from(&bytes[48..80]).unwrap(),
+        }
+    }
+}
+
+// multiply the point by the cofactor 8
+fn mul_by_cofactor(p: &Ge) -> Ge {
+    p.double().double().double()
+}
+
+fn is_identity(p: &Ge) -> bool {
+    p.to_bytes() == Ge::ZERO.to_bytes()
+}
+
+// ECVRF_encode_to_curve_try_and_increment
+fn encode_to_curve(public: &[u8; 32], alpha: &[u8]) -> Ge {
+    for ctr in 0..=255u8 {
+        let hash = Sha512::new()
+            .update(&[SUITE_STRING, 0x01])
+            .update(public)
+            .update(alpha)
+            .update(&[ctr, 0x00])
+            .finalize();
+        let candidate = <&[u8; 32]>::try_from(&hash[0..32]).unwrap();
+        if let Some(h) = ge_decompress(candidate) {
+            return mul_by_cofactor(&h);
+        }
+    }
+    // each candidate is a valid point with a probability of about 1/2
+    unreachable!()
+}
+
+// ECVRF_challenge_generation, truncated to 16 bytes
+fn challenge(points: &[&[u8; 32]; 5]) -> [u8; 16] {
+    let mut ctx = Sha512::new().update(&[SUITE_STRING, 0x02]);
+    for p in points.iter() {
+        ctx.update_mut(*p);
+    }
+    let hash = ctx.update(&[0x00]).finalize();
+    <[u8; 16]>::try_from(&hash[0..16]).unwrap()
+}
+
+fn challenge_scalar(c: &[u8; 16]) -> Scalar {
+    let mut bytes = [0u8; 32];
+    bytes[0..16].copy_from_slice(c);
+    Scalar::from_bytes(&bytes)
+}
+
+/// Compute the VRF proof for the input `alpha` with the secret key
+pub fn vrf_prove(secret: &[u8; 32], alpha: &[u8]) -> VrfProof {
+    let mut extended = extended_secret(secret);
+    let x = Scalar::from_bytes(<&[u8; 32]>::try_from(&extended[0..32]).unwrap());
+    let public = Ge::scalarmult_base(&x).to_bytes();
+
+    let h = encode_to_curve(&public, alpha);
+    let h_string = h.to_bytes();
+    let gamma = h.scalarmult(&x).to_bytes();
+
+    // ECVRF_nonce_generation_RFC8032
+    let mut nonce_hash = Sha512::new()
+        .update(&extended[32..64])
+        .update(&h_string)
+        .finalize();
+    let k = Scalar::from_uniform_bytes(&nonce_hash);
+    nonce_hash.zeroize();
+    extended.zeroize();
+
+    let u = Ge::scalarmult_base(&k).to_bytes();
+    let v = h.scalarmult(&k).to_bytes();
+    let c = challenge(&[&public, &h_string, &gamma, &u, &v]);
+    let s = scalar::muladd(&challenge_scalar(&c), &x, &k);
+
+    VrfProof {
+        gamma,
+        c,
+        s: s.to_bytes(),
+    }
+}
+
+/// Compute the VRF output `beta` of a proof, without verifying it
+///
+/// Returns `None` if Gamma is not a valid point
+pub fn vrf_proof_to_hash(proof: &VrfProof) -> Option<[u8; OUTPUT_LENGTH]> {
+    let gamma = ge_decompress(&proof.gamma)?;
+    let beta = Sha512::new()
+        .update(&[SUITE_STRING, 0x03])
+        .update(&mul_by_cofactor(&gamma).to_bytes())
+        .update(&[0x00])
+        .finalize();
+    Some(beta)
+}
+
+/// Verify the VRF proof for the input `alpha` with the public key, and return the
+/// VRF output `beta` if the proof is valid
+///
+/// Public keys of low order are rejected
+pub fn vrf_verify(
+    public: &[u8; 32],
+    alpha: &[u8],
+    proof: &VrfProof,
+) -> Option<[u8; OUTPUT_LENGTH]> {
+    let y = ge_decompress(public)?;
+    if is_identity(&mul_by_cofactor(&y)) {
+        return None;
+    }
+    let gamma = ge_decompress(&proof.gamma)?;
+    let s = Scalar::from_bytes_canonical(&proof.s)?;
+    let c = challenge_scalar(&proof.c);
+
+    let h = encode_to_curve(public, alpha);
+    // U = s*B - c*Y and V = s*H - c*Gamma
+    let u = GePartial::double_scalarmult_vartime(&c, -&y, &s).to_bytes();
+    let v = ge_multiscalar_mul(&[s, c], &[h.clone(), -&gamma]).to_bytes();
+
+    let expected = challenge(&[public, &h.to_bytes(), &proof.gamma, &u, &v]);
+    if expected == proof.c {
+        vrf_proof_to_hash(proof)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ed25519;
+
+    struct Test {
+        secret: [u8; 32],
+        alpha: &'static [u8],
+        proof: [u8; PROOF_LENGTH],
+        output: [u8; OUTPUT_LENGTH],
+    }
+
+    #[test]
+    fn vectors() {
+        // RFC 9381 Appendix B.3, examples 16 to 18
+        let tests = [
+            Test {
+                secret: [
+                    0x9d, 0x61, 0xb1, 0x9d, 0xef, 0xfd, 0x5a, 0x60, 0xba, 0x84, 0x4a, 0xf4, 0x92,
+                    0xec, 0x2c, 0xc4, 0x44, 0x49, 0xc5, 0x69, 0x7b, 0x32, 0x69, 0x19, 0x70, 0x3b,
+                    0xac, 0x03, 0x1c, 0xae, 0x7f, 0x60,
+                ],
+                alpha: &[],
+                proof: [
+                    0x86, 0x57, 0x10, 0x66, 0x90, 0xb5, 0x52, 0x62, 0x45, 0xa9, 0x2b, 0x00, 0x3b,
+                    0xb0, 0x79, 0xcc, 0xd1, 0xa9, 0x21, 0x30, 0x47, 0x76, 0x71, 0xf6, 0xfc, 0x01,
+                    0xad, 0x16, 0xf2, 0x6f, 0x72, 0x3f, 0x26, 0xf8, 0xa5, 0x7c, 0xca, 0xed, 0x74,
+                    0xee, 0x1b, 0x19, 0x0b, 0xed, 0x1f, 0x47, 0x9d, 0x97, 0x27, 0xd2, 0xd0, 0xf9,
+                    0xb0, 0x05, 0xa6, 0xe4, 0x56, 0xa3, 0x5d, 0x4f, 0xb0, 0xda, 0xab, 0x12, 0x68,
+                    0xa1, 0xb0, 0xdb, 0x10, 0x83, 0x6d, 0x98, 0x26, 0xa5, 0x28, 0xca, 0x76, 0x56,
+                    0x78, 0x05,
+                ],
+                output: [
+                    0x90, 0xcf, 0x1d, 0xf3, 0xb7, 0x03, 0xcc, 0xe5, 0x9e, 0x2a, 0x35, 0xb9, 0x25,
+                    0xd4, 0x11, 0x16, 0x40, 0x68, 0x26, 0x9d, 0x7b, 0x2d, 0x29, 0xf3, 0x30, 0x1c,
+                    0x03, 0xdd, 0x75, 0x78, 0x76, 0xff, 0x66, 0xb7, 0x1d, 0xda, 0x49, 0xd2, 0xde,
+                    0x59, 0xd0, 0x34, 0x50, 0x45, 0x1a, 0xf0, 0x26, 0x79, 0x8e, 0x8f, 0x81, 0xcd,
+                    0x2e, 0x33, 0x3d, 0xe5, 0xcd, 0xf4, 0xf3, 0xe1, 0x40, 0xfd, 0xd8, 0xae,
+                ],
+            },
+            Test {
+                secret: [
+                    0x4c, 0xcd, 0x08, 0x9b, 0x28, 0xff, 0x96, 0xda, 0x9d, 0xb6, 0xc3, 0x46, 0xec,
+                    0x11, 0x4e, 0x0f, 0x5b, 0x8a, 0x31, 0x9f, 0x35, 0xab, 0xa6, 0x24, 0xda, 0x8c,
+                    0xf6, 0xed, 0x4f, 0xb8, 0xa6, 0xfb,
+                ],
+                alpha: &[0x72],
+                proof: [
+                    0xf3, 0x14, 0x1c, 0xd3, 0x82, 0xdc, 0x42, 0x90, 0x9d, 0x19, 0xec, 0x51, 0x10,
+                    0x46, 0x9e, 0x4f, 0xea, 0xe1, 0x83, 0x00, 0xe9, 0x4f, 0x30, 0x45, 0x90, 0xab,
+                    0xdc, 0xed, 0x48, 0xae, 0xd5, 0x93, 0x3b, 0xf0, 0x86, 0x4a, 0x62, 0x55, 0x8b,
+                    0x3e, 0xd7, 0xf2, 0xfe, 0xa4, 0x5c, 0x92, 0xa4, 0x65, 0x30, 0x1b, 0x3b, 0xbf,
+                    0x5e, 0x3e, 0x54, 0xdd, 0xf2, 0xd9, 0x35, 0xbe, 0x3b, 0x67, 0x92, 0x6d, 0xa3,
+                    0xef, 0x39, 0x22, 0x6b, 0xbc, 0x35, 0x5b, 0xdc, 0x98, 0x50, 0x11, 0x2c, 0x8f,
+                    0x4b, 0x02,
+                ],
+                output: [
+                    0xeb, 0x44, 0x40, 0x66, 0x5d, 0x38, 0x91, 0xd6, 0x68, 0xe7, 0xe0, 0xfc, 0xaf,
+                    0x58, 0x7f, 0x1b, 0x4b, 0xd7, 0xfb, 0xfe, 0x99, 0xd0, 0xeb, 0x22, 0x11, 0xcc,
+                    0xec, 0x90, 0x49, 0x63, 0x10, 0xeb, 0x5e, 0x33, 0x82, 0x1b, 0xc6, 0x13, 0xef,
+                    0xb9, 0x4d, 0xb5, 0xe5, 0xb5, 0x4c, 0x70, 0xa8, 0x48, 0xa0, 0xbe, 0xf4, 0x55,
+                    0x3a, 0x41, 0xbe, 0xfc, 0x57, 0x66, 0x3b, 0x56, 0x37, 0x3a, 0x50, 0x31,
+                ],
+            },
+            Test {
+                secret: [
+                    0xc5, 0xaa, 0x8d, 0xf4, 0x3f, 0x9f, 0x83, 0x7b, 0xed, 0xb7, 0x44, 0x2f, 0x31,
+                    0xdc, 0xb7, 0xb1, 0x66, 0xd3, 0x85, 0x35, 0x07, 0x6f, 0x09, 0x4b, 0x85, 0xce,
+                    0x3a, 0x2e, 0x0b, 0x44, 0x58, 0xf7,
+                ],
+                alpha: &[0xaf, 0x82],
+                proof: [
+                    0x9b, 0xc0, 0xf7, 0x91, 0x19, 0xcc, 0x56, 0x04, 0xbf, 0x02, 0xd2, 0x3b, 0x4c,
+                    0xae, 0xde, 0x71, 0x39, 0x3c, 0xed, 0xfb, 0xb1, 0x91, 0x43, 0x4d, 0xd0, 0x16,
+                    0xd3, 0x01, 0x77, 0xcc, 0xbf, 0x80, 0x96, 0xbb, 0x47, 0x4e, 0x53, 0x89, 0x5c,
+                    0x36, 0x2d, 0x86, 0x28, 0xee, 0x9f, 0x9e, 0xa3, 0xc0, 0xe5, 0x2c, 0x7a, 0x5c,
+                    0x69, 0x1b, 0x6c, 0x18, 0xc9, 0x97, 0x98, 0x66, 0x56, 0x8a, 0xdd, 0x7a, 0x2d,
+                    0x41, 0xb0, 0x0b, 0x05, 0x08, 0x1e, 0xd0, 0xf5, 0x8e, 0xe5, 0xe3, 0x1b, 0x3a,
+                    0x97, 0x0e,
+                ],
+                output: [
+                    0x64, 0x54, 0x27, 0xe5, 0xd0, 0x0c, 0x62, 0xa2, 0x3f, 0xb7, 0x03, 0x73, 0x2f,
+                    0xa5, 0xd8, 0x92, 0x94, 0x09, 0x35, 0x94, 0x21, 0x01, 0xe4, 0x56, 0xec, 0xca,
+                    0x7b, 0xb2, 0x17, 0xc6, 0x1c, 0x45, 0x21, 0x18, 0xfe, 0xc1, 0x21, 0x92, 0x02,
+                    0xa0, 0xed, 0xcf, 0x03, 0x8b, 0xb6, 0x37, 0x32, 0x41, 0x57, 0x8b, 0xe7, 0x21,
+                    0x7b, 0xa8, 0x5a, 0x26, 0x87, 0xf7, 0xa0, 0x31, 0x0b, 0x2d, 0xf1, 0x9f,
+                ],
+            },
+        ];
+
+        for (i, t) in tests.iter().enumerate() {
+            let (_, public) = ed25519::keypair(&t.secret);
+            let proof = vrf_prove(&t.secret, t.alpha);
+            assert_eq!(proof.to_bytes(), t.proof, "proof {}", i);
+            assert_eq!(VrfProof::from_bytes(&t.proof), proof, "decoding {}", i);
+            assert_eq!(vrf_proof_to_hash(&proof), Some(t.output), "hash {}", i);
+            assert_eq!(
+                vrf_verify(&public, t.alpha, &proof),
+                Some(t.output),
+                "verify {}",
+                i
+            );
+        }
+    }
+
+    #[test]
+    fn invalid() {
+        let secret = [0x42u8; 32];
+        let (_, public) = ed25519::keypair(&secret);
+        let proof = vrf_prove(&secret, b"alpha");
+        assert!(vrf_verify(&public, b"alpha", &proof).is_some());
+
+        // wrong input
+        assert!(vrf_verify(&public, b"beta", &proof).is_none());
+
+        // wrong public key
+        let (_, other) = ed25519::keypair(&[0x43u8; 32]);
+        assert!(vrf_verify(&other, b"alpha", &proof).is_none());
+
+        // tampered proof components
+        let bytes = proof.to_bytes();
+        for i in [0, 32, 48, 79] {
+            let mut tampered = bytes;
+            tampered[i] ^= 0x01;
+            let tampered = VrfProof::from_bytes(&tampered);
+            assert!(
+                vrf_verify(&public, b"alpha", &tampered).is_none(),
+                "byte {}",
+                i
+            );
+        }
+
+        // non canonical s (s + l)
+        let mut bytes = proof.to_bytes();
+        let s = Scalar::from_bytes(<&[u8; 32]>::try_from(&bytes[48..80]).unwrap());
+        let mut carry = 0u16;
+        let l = [
+            0xed, 0xd3, 0xf5, 0x5c, 0x1a, 0x63, 0x12, 0x58, 0xd6, 0x9c, 0xf7, 0xa2, 0xde, 0xf9,
+            0xde, 0x14, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x10,
+        ];
+        let s_bytes = s.to_bytes();
+        for i in 0..32 {
+            let v = s_bytes[i] as u16 + l[i] as u16 + carry;
+            bytes[48 + i] = v as u8;
+            carry = v >> 8;
+        }
+        assert!(vrf_verify(&public, b"alpha", &VrfProof::from_bytes(&bytes)).is_none());
+
+        // low order public key (identity)
+        let mut identity = [0u8; 32];
+        identity[0] = 1;
+        assert!(vrf_verify(&identity, b"alpha", &proof).is_none());
+    }
+}
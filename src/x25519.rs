@@ -26,7 +26,9 @@
 //! [1]: <https://cr.yp.to/ecdh/curve25519-20060209.pdf>
 //! [2]: <https://en.wikipedia.org/wiki/Curve25519>
 
+use crate::constant_time::CtZero;
 use crate::curve25519::{curve25519, curve25519_base};
+use crate::secure_zero::Zeroize;
 
 macro_rules! bytes_impl {
     ($t:ident, $n:literal) => {
@@ -60,10 +62,35 @@ macro_rules! bytes_impl {
 }
 
 /// X25519 Secret Key
+///
+/// The bytes are kept as given, so converting the key back to bytes returns
+/// the original value. The key is clamped as per RFC7748 section 5 when it is
+/// used by [`dh`] and [`base`]: the 3 lowest bits are cleared, the highest bit
+/// is cleared and the second highest bit is set.
 pub struct SecretKey([u8; 32]);
 
 bytes_impl!(SecretKey, 32);
 
+impl SecretKey {
+    /// Derive the public key associated with this secret key
+    pub fn public_key(&self) -> PublicKey {
+        base(self)
+    }
+
+    /// Compute the shared secret between this secret key and the other party public key
+    ///
+    /// Returns an error if the shared secret is all zeros, which happens when
+    /// the public key is a low order point (RFC7748 section 6.1)
+    pub fn diffie_hellman(&self, other: &PublicKey) -> Result<SharedSecret, X25519Error> {
+        let shared = dh(self, other);
+        if (&shared.0).ct_zero().is_true() {
+            Err(X25519Error::LowOrderPoint)
+        } else {
+            Ok(shared)
+        }
+    }
+}
+
 /// X25519 Public Key
 #[derive(PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct PublicKey([u8; 32]);
@@ -75,6 +102,33 @@ pub struct SharedSecret([u8; 32]);
 
 bytes_impl!(SharedSecret, 32);
 
+/// X25519 errors
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum X25519Error {
+    /// The public key is a low order point, resulting in an all-zero shared secret
+    LowOrderPoint,
+}
+
+impl core::fmt::Display for X25519Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            X25519Error::LowOrderPoint => write!(f, "low order point"),
+        }
+    }
+}
+
+impl Drop for SecretKey {
+    fn drop(&mut self) {
+        self.0.zeroize()
+    }
+}
+
+impl Drop for SharedSecret {
+    fn drop(&mut self) {
+        self.0.zeroize()
+    }
+}
+
 /// Computes a shared secret from the curve25519 private key (n) and public
 /// key (p)
 pub fn dh(n: &SecretKey, p: &PublicKey) -> SharedSecret {
@@ -85,3 +139,56 @@ pub fn dh(n: &SecretKey, p: &PublicKey) -> SharedSecret {
 pub fn base(x: &SecretKey) -> PublicKey {
     PublicKey(curve25519_base(&x.0))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn secret_key_clamped() {
+        // the bytes round-trip unchanged
+        let sk = SecretKey::from([0xff; 32]);
+        assert_eq!(sk.as_ref(), &[0xff; 32][..]);
+        let bytes: [u8; 32] = sk.into();
+        assert_eq!(bytes, [0xff; 32]);
+
+        // and the clamping happens when the key is used
+        let mut clamped = [0xff; 32];
+        clamped[0] = 0xf8;
+        clamped[31] = 0x7f;
+        let other = base(&SecretKey::from([3; 32]));
+        assert_eq!(
+            base(&SecretKey::from([0xff; 32])).as_ref(),
+            base(&SecretKey::from(clamped)).as_ref()
+        );
+        assert_eq!(
+            dh(&SecretKey::from([0xff; 32]), &other).as_ref(),
+            dh(&SecretKey::from(clamped), &other).as_ref()
+        );
+    }
+
+    #[test]
+    fn diffie_hellman() {
+        let alice = SecretKey::from([1; 32]);
+        let bob = SecretKey::from([2; 32]);
+        let s1 = alice.diffie_hellman(&bob.public_key()).unwrap();
+        let s2 = bob.diffie_hellman(&alice.public_key()).unwrap();
+        assert_eq!(s1.as_ref(), s2.as_ref());
+    }
+
+    #[test]
+    fn low_order_point() {
+        // 0 and 1 are low order points on curve25519
+        let mut one = [0; 32];
+        one[0] = 1;
+        let sk = SecretKey::from([7; 32]);
+        assert_eq!(
+            sk.diffie_hellman(&PublicKey::from([0; 32])).err(),
+            Some(X25519Error::LowOrderPoint)
+        );
+        assert_eq!(
+            sk.diffie_hellman(&PublicKey::from(one)).err(),
+            Some(X25519Error::LowOrderPoint)
+        );
+    }
+}
@@ -22,6 +22,10 @@ fi
 # native testing
 cargo test
 
+# optional algorithms, not part of the default features
+OPTIONAL_FEATURES="aes aegis ascon siphash balloon hpke spake2 vrf oprf schnorr paseto jws commitment sss noise multisig"
+cargo test --features "$OPTIONAL_FEATURES"
+
 # 32bits x86 testing
 for flag in force-32bits
 do
@@ -47,3 +51,9 @@ done
 
 # native optimisation testing
 RUSTFLAGS="-C target_cpu=native" cargo test
+
+# wasm32 SIMD128 testing, running the tests with wasmtime if available
+if command -v wasmtime > /dev/null; then
+	echo "#### testing wasm32 simd128"
+	CARGO_TARGET_WASM32_WASIP1_RUNNER=wasmtime RUSTFLAGS="-C target-feature=+simd128" cargo test --target wasm32-wasip1
+fi
@@ -0,0 +1,231 @@
+//! Ed25519 verification against the Project Wycheproof test vectors
+//!
+//! The vectors are read from `tests/wycheproof/ed25519_test.json`, in the format of the
+//! upstream `testvectors/ed25519_test.json`, and the result of `ed25519::verify` is
+//! checked against the "result" field of each test case.
+#![cfg(feature = "ed25519")]
+
+use cryptoxide::ed25519;
+use cryptoxide::encoding::decode_hex;
+
+const VECTORS: &str = include_str!("wycheproof/ed25519_test.json");
+
+// Wycheproof marks the cases that a verifier may either accept or reject as "acceptable".
+// The verification is cofactorless, so the expected result of each of those cases is
+// listed explicitly here by tcId, and an acceptable case missing from the list fails.
+const ACCEPTABLE: [(u64, bool); 0] = [];
+
+#[derive(Debug)]
+enum Json {
+    Null,
+    Bool,
+    Number(u64),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    fn get(&self, key: &str) -> Option<&Json> {
+        match self {
+            Json::Object(fields) => fields.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    fn field(&self, key: &str) -> &Json {
+        self.get(key)
+            .unwrap_or_else(|| panic!("missing field {}", key))
+    }
+
+    fn as_str(&self) -> &str {
+        match self {
+            Json::String(s) => s,
+            v => panic!("expected a string, got {:?}", v),
+        }
+    }
+
+    fn as_u64(&self) -> u64 {
+        match self {
+            Json::Number(n) => *n,
+            v => panic!("expected a number, got {:?}", v),
+        }
+    }
+
+    fn as_array(&self) -> &[Json] {
+        match self {
+            Json::Array(a) => a,
+            v => panic!("expected an array, got {:?}", v),
+        }
+    }
+}
+
+// a minimal JSON parser, sufficient for the Wycheproof files: no escapes other than
+// the simple ones, and only non-negative integer numbers
+struct Parser<'a> {
+    input: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn skip_whitespace(&mut self) {
+        while self.pos < self.input.len() && self.input[self.pos].is_ascii_whitespace() {
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, c: u8) {
+        self.skip_whitespace();
+        assert_eq!(self.input[self.pos], c, "at offset {}", self.pos);
+        self.pos += 1;
+    }
+
+    fn peek(&mut self) -> u8 {
+        self.skip_whitespace();
+        self.input[self.pos]
+    }
+
+    fn keyword(&mut self, word: &str, value: Json) -> Json {
+        assert!(self.input[self.pos..].starts_with(word.as_bytes()));
+        self.pos += word.len();
+        value
+    }
+
+    fn string(&mut self) -> String {
+        self.expect(b'"');
+        let mut s = String::new();
+        loop {
+            let c = self.input[self.pos];
+            self.pos += 1;
+            match c {
+                b'"' => return s,
+                b'\\' => {
+                    let e = self.input[self.pos];
+                    self.pos += 1;
+                    s.push(match e {
+                        b'"' => '"',
+                        b'\\' => '\\',
+                        b'/' => '/',
+                        b'n' => '\n',
+                        b't' => '\t',
+                        _ => panic!("unsupported escape at offset {}", self.pos),
+                    })
+                }
+                _ => s.push(c as char),
+            }
+        }
+    }
+
+    fn value(&mut self) -> Json {
+        match self.peek() {
+            b'{' => {
+                self.pos += 1;
+                let mut fields = Vec::new();
+                if self.peek() == b'}' {
+                    self.pos += 1;
+                    return Json::Object(fields);
+                }
+                loop {
+                    let key = self.string();
+                    self.expect(b':');
+                    fields.push((key, self.value()));
+                    match self.peek() {
+                        b',' => self.pos += 1,
+                        _ => break,
+                    }
+                }
+                self.expect(b'}');
+                Json::Object(fields)
+            }
+            b'[' => {
+                self.pos += 1;
+                let mut items = Vec::new();
+                if self.peek() == b']' {
+                    self.pos += 1;
+                    return Json::Array(items);
+                }
+                loop {
+                    items.push(self.value());
+                    match self.peek() {
+                        b',' => self.pos += 1,
+                        _ => break,
+                    }
+                }
+                self.expect(b']');
+                Json::Array(items)
+            }
+            b'"' => Json::String(self.string()),
+            b't' => self.keyword("true", Json::Bool),
+            b'f' => self.keyword("false", Json::Bool),
+            b'n' => self.keyword("null", Json::Null),
+            _ => {
+                let start = self.pos;
+                while self.pos < self.input.len() && self.input[self.pos].is_ascii_digit() {
+                    self.pos += 1;
+                }
+                let digits = core::str::from_utf8(&self.input[start..self.pos]).unwrap();
+                Json::Number(digits.parse().expect("number"))
+            }
+        }
+    }
+}
+
+fn hex(s: &str) -> Vec<u8> {
+    decode_hex(s.as_bytes()).unwrap()
+}
+
+#[test]
+fn wycheproof_ed25519() {
+    let mut parser = Parser {
+        input: VECTORS.as_bytes(),
+        pos: 0,
+    };
+    let vectors = parser.value();
+    assert_eq!(vectors.field("algorithm").as_str(), "EDDSA");
+
+    let mut count = 0;
+    for group in vectors.field("testGroups").as_array() {
+        // the key is named "publicKey" in the newer versions of the files
+        let key = group.get("publicKey").unwrap_or_else(|| group.field("key"));
+        let public_key = hex(key.field("pk").as_str());
+
+        for test in group.field("tests").as_array() {
+            let tc_id = test.field("tcId").as_u64();
+            let message = hex(test.field("msg").as_str());
+            let signature = hex(test.field("sig").as_str());
+
+            let expected = match test.field("result").as_str() {
+                "valid" => true,
+                "invalid" => false,
+                "acceptable" => {
+                    ACCEPTABLE
+                        .iter()
+                        .find(|(id, _)| *id == tc_id)
+                        .unwrap_or_else(|| panic!("tcId {}: no expected result", tc_id))
+                        .1
+                }
+                r => panic!("tcId {}: unknown result {}", tc_id, r),
+            };
+
+            // the signature and the public key have a fixed size: any other size is invalid
+            let got = match (
+                <[u8; 64]>::try_from(&signature[..]),
+                <[u8; 32]>::try_from(&public_key[..]),
+            ) {
+                (Ok(signature), Ok(public_key)) => {
+                    ed25519::verify(&message, &public_key, &signature)
+                }
+                _ => false,
+            };
+            assert_eq!(
+                got,
+                expected,
+                "tcId {}: {}",
+                tc_id,
+                test.field("comment").as_str()
+            );
+            count += 1;
+        }
+    }
+    assert_eq!(count, vectors.field("numberOfTests").as_u64());
+}